@@ -0,0 +1,59 @@
+//! Benchmarks of tokenization, stemming, and TF-IDF on synthetic corpora from `rnltk::bench_fixtures`.
+//!
+//! Run with `cargo bench --features bench_fixtures`.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use rnltk::bench_fixtures::{self, CorpusConfig};
+use rnltk::document::DocumentTermFrequencies;
+use rnltk::{stem, token};
+
+const CORPUS_SIZES: [usize; 2] = [10, 100];
+
+fn corpus(num_documents: usize) -> Vec<String> {
+    bench_fixtures::generate_corpus(&CorpusConfig { num_documents, ..CorpusConfig::default() })
+}
+
+fn tokenization(c: &mut Criterion) {
+    let mut group = c.benchmark_group("tokenization");
+    for num_documents in CORPUS_SIZES {
+        let documents = corpus(num_documents);
+        group.throughput(Throughput::Bytes(documents.iter().map(|document| document.len() as u64).sum()));
+        group.bench_with_input(BenchmarkId::new("tokenize_sentence", num_documents), &documents, |b, documents| {
+            b.iter(|| documents.iter().map(|document| token::tokenize_sentence(document).len()).sum::<usize>());
+        });
+        group.bench_with_input(BenchmarkId::new("tokenize_stemmed_sentence", num_documents), &documents, |b, documents| {
+            b.iter(|| documents.iter().map(|document| token::tokenize_stemmed_sentence(document).len()).sum::<usize>());
+        });
+    }
+    group.finish();
+}
+
+fn stemming(c: &mut Criterion) {
+    let words = bench_fixtures::generate_vocabulary(10_000, 0);
+    let mut group = c.benchmark_group("stemming");
+    group.throughput(Throughput::Elements(words.len() as u64));
+    group.bench_function("stem::get", |b| {
+        b.iter(|| words.iter().filter_map(|word| stem::get(word).ok()).count());
+    });
+    group.finish();
+}
+
+fn tfidf(c: &mut Criterion) {
+    let mut group = c.benchmark_group("tfidf");
+    for num_documents in CORPUS_SIZES {
+        let documents = corpus(num_documents);
+        let documents: Vec<&str> = documents.iter().map(String::as_str).collect();
+        let term_counts = token::get_term_counts_from_sentences(&documents);
+        let term_frequencies = DocumentTermFrequencies::from_term_counts(&term_counts).expect("Corpus has terms");
+        group.bench_with_input(BenchmarkId::new("term_counts", num_documents), &documents, |b, documents| {
+            b.iter(|| token::get_term_counts_from_sentences(documents));
+        });
+        group.bench_with_input(BenchmarkId::new("get_tfidf_from_term_frequencies", num_documents), &term_frequencies, |b, term_frequencies| {
+            b.iter(|| term_frequencies.get_tfidf_from_term_frequencies());
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, tokenization, stemming, tfidf);
+criterion_main!(benches);
@@ -17,7 +17,8 @@ fn main() {
     let token_config = token::TokenConfig {
         remove_stop_words: true,
         stem: true,
-        stop_words
+        stop_words: stop_words.into(),
+        ..token::TokenConfig::default()
     };
 
     let documents_term_frequencies = token::get_term_frequencies_from_sentences_configurable(&documents, token_config);
@@ -25,7 +26,7 @@ fn main() {
     let mut all_term_frequencies: Vec<f64> = vec![];
 
     documents_term_frequencies.iter().for_each(|term_frequencies| {
-        all_term_frequencies.extend(term_frequencies.values().into_iter());
+        all_term_frequencies.extend(term_frequencies.values());
     });
 
     let nrows = documents_term_frequencies[0].values().len();
@@ -33,40 +34,18 @@ fn main() {
 
     let document_term_frequencies = DMatrix::from_vec(nrows, ncols, all_term_frequencies);
 
-    let document_term_frequency_matrix = document::DocumentTermFrequencies::new(document_term_frequencies);
+    let document_term_frequency_matrix = document::DocumentTermFrequencies::new(document_term_frequencies).unwrap();
     let tfidf_matrix = document_term_frequency_matrix.get_tfidf_from_term_frequencies();
 
-    let cosine_similarity = tfidf_matrix.get_cosine_similarity_from_tfidf();
-    let cosine_similarity_matrix = cosine_similarity.get_cosine_similarity_matrix();
+    let document_labels = vec!["Document 1", "Document 2", "Document 3", "Document 4"];
 
+    let cosine_similarity = tfidf_matrix.get_cosine_similarity_from_tfidf();
     println!("COSINE SIMILARITY MATRIX");
-    for row_index in 0..ncols {
-        println!(
-            "Document {}          {:.2}          {:.2}          {:.2}          {:.2}",
-            row_index + 1,
-            &cosine_similarity_matrix[(row_index, 0)],
-            &cosine_similarity_matrix[(row_index, 1)],
-            &cosine_similarity_matrix[(row_index, 2)],
-            &cosine_similarity_matrix[(row_index, 3)]
-        )
-    }
-    println!("              Document 1    Document 2    Document 3    Document 4");
-
-    println!("\n-----------------------------\n");
+    println!("{}", cosine_similarity.with_labels(document_labels.clone()).unwrap());
 
-    let lsa_cosine_similarity = tfidf_matrix.get_lsa_cosine_similarity_from_tfidf(2).unwrap();
-    let lsa_cosine_similarity_matrix = lsa_cosine_similarity.get_lsa_cosine_similarity_matrix();
+    println!("-----------------------------\n");
 
+    let lsa_cosine_similarity = tfidf_matrix.get_lsa_cosine_similarity_from_tfidf(2).unwrap();
     println!("LSA COSINE SIMILARITY MATRIX");
-    for row_index in 0..ncols {
-        println!(
-            "Document {}          {:.2}          {:.2}          {:.2}          {:.2}",
-            row_index + 1,
-            &lsa_cosine_similarity_matrix[(row_index, 0)],
-            &lsa_cosine_similarity_matrix[(row_index, 1)],
-            &lsa_cosine_similarity_matrix[(row_index, 2)],
-            &lsa_cosine_similarity_matrix[(row_index, 3)]
-        )
-    }
-    println!("              Document 1    Document 2    Document 3    Document 4");
+    println!("{}", lsa_cosine_similarity.with_labels(document_labels).unwrap());
 }
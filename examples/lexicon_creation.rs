@@ -5,7 +5,7 @@
 //! \* The data referenced here is only permitted to be used non-commercially
 
 use std::collections::HashMap;
-use rnltk::sentiment::{SentimentModel, CustomWords, SentimentDictValue};
+use rnltk::sentiment::{SentimentModel, CustomWords, Score, SentimentDictValue};
 use rnltk::stem;
 
 
@@ -19,10 +19,10 @@ fn main() {
 
         let stemmed_word = stem::get(&word).unwrap();
 
-        let avg = vec![record[2].parse::<f64>().unwrap(), record[5].parse::<f64>().unwrap()];
-        let std = vec![record[3].parse::<f64>().unwrap(), record[6].parse::<f64>().unwrap()];
+        let valence = Score::new(record[2].parse::<f64>().unwrap(), record[3].parse::<f64>().unwrap());
+        let arousal = Score::new(record[5].parse::<f64>().unwrap(), record[6].parse::<f64>().unwrap());
         
-        let sentiment_dict = SentimentDictValue::new(word, stemmed_word, avg, std);
+        let sentiment_dict = SentimentDictValue::from_scores_with_stem(word, stemmed_word, valence, arousal);
         custom_words.insert(record[1].to_owned(), sentiment_dict);
     }
     
@@ -0,0 +1,321 @@
+//! Module containing a one-call document report combining tokenization, keywords,
+//! sentiment, readability, and TF-IDF term weights, and a bulk analysis of many documents
+//! returning its results as columns.
+
+use std::collections::BTreeMap;
+
+use serde::{Serialize, Deserialize};
+
+use crate::sentiment::SentimentModel;
+use crate::similarity;
+use crate::token::{self, StopWords};
+use crate::trace::stage_span;
+
+/// Number of keywords and TF-IDF terms included in a [`DocumentReport`].
+const NUM_REPORT_TERMS: usize = 10;
+
+/// Struct for holding a term and its weight in a [`DocumentReport`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct WeightedTerm {
+    pub term: String,
+    pub weight: f64,
+}
+
+/// Struct for holding the aggregate sentiment of a document.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SentimentSummary {
+    pub valence: f64,
+    pub arousal: f64,
+    /// The Russel-like description from [`SentimentModel::get_sentiment_description`]
+    pub description: String,
+}
+
+/// Struct for holding Flesch readability scores and the counts used to compute them.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Readability {
+    pub num_sentences: usize,
+    pub num_words: usize,
+    pub num_syllables: usize,
+    /// Flesch reading ease, where higher scores are easier to read
+    pub flesch_reading_ease: f64,
+    /// Flesch–Kincaid grade level, the approximate U.S. school grade needed to understand the text
+    pub flesch_kincaid_grade: f64,
+}
+
+/// Struct for holding the full report returned from [`analyze`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DocumentReport {
+    pub sentences: Vec<String>,
+    pub tokens: Vec<String>,
+    /// Most frequent non-stop-word tokens, weighted by count
+    pub keywords: Vec<WeightedTerm>,
+    /// Aggregate sentiment, only present if a [`SentimentModel`] was provided
+    pub sentiment: Option<SentimentSummary>,
+    pub readability: Readability,
+    /// Terms with the highest TF-IDF weight when each sentence is treated as a document
+    pub top_tfidf_terms: Vec<WeightedTerm>,
+}
+
+/// Struct for holding the per-document results of [`analyze_bulk`] as parallel columns, where index `i` of
+/// every column belongs to document `i`.
+///
+/// Columns match the layout of Arrow and polars tables, so they can be handed off without building a struct per document.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct ColumnarResults {
+    pub num_sentences: Vec<u64>,
+    pub num_tokens: Vec<u64>,
+    /// Valence of each document, `None` without a [`SentimentModel`] or if the document has no lexicon terms
+    pub valence: Vec<Option<f64>>,
+    /// Arousal of each document, `None` without a [`SentimentModel`] or if the document has no lexicon terms
+    pub arousal: Vec<Option<f64>>,
+}
+
+impl ColumnarResults {
+    /// Gets the number of documents.
+    pub fn len(&self) -> usize {
+        self.num_tokens.len()
+    }
+
+    /// Checks if there are no documents.
+    pub fn is_empty(&self) -> bool {
+        self.num_tokens.is_empty()
+    }
+}
+
+/// Analyzes every document of `documents`, returning sentence counts, token counts, and sentiment as
+/// [`ColumnarResults`]. Documents are split and tokenized as in [`analyze`], reusing one token buffer
+/// across documents, and sentiment is only computed if a `sentiment_model` is provided.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::analysis;
+/// use rnltk::sentiment::SentimentModel;
+/// use rnltk::sample_data;
+///
+/// let sentiment = SentimentModel::new(sample_data::get_sample_custom_word_dict());
+/// let results = analysis::analyze_bulk(&["The bees were betrayed. They left!", "Why hello there"], Some(&sentiment));
+///
+/// assert_eq!(results.num_sentences, vec![2, 1]);
+/// assert_eq!(results.num_tokens, vec![6, 3]);
+/// assert!(results.valence[0].is_some());
+/// assert_eq!(results.arousal[1], None);
+/// ```
+pub fn analyze_bulk<S: AsRef<str>>(documents: &[S], sentiment_model: Option<&SentimentModel>) -> ColumnarResults {
+    stage_span!("analyze_bulk", documents = documents.len());
+    let mut results = ColumnarResults {
+        num_sentences: Vec::with_capacity(documents.len()),
+        num_tokens: Vec::with_capacity(documents.len()),
+        valence: Vec::with_capacity(documents.len()),
+        arousal: Vec::with_capacity(documents.len()),
+    };
+    let mut tokens: Vec<String> = vec![];
+    for document in documents {
+        let sentences = token::tokenize_into_sentences(document.as_ref());
+        tokens.clear();
+        tokens.extend(sentences.iter().flat_map(|sentence| token::tokenize_sentence(sentence)));
+
+        let sentiment = sentiment_model
+            .filter(|model| tokens.iter().any(|token| model.does_term_exist(token)))
+            .map(|model| (model.get_valence_for_term_vector(&tokens), model.get_arousal_for_term_vector(&tokens)));
+        results.num_sentences.push(sentences.len() as u64);
+        results.num_tokens.push(tokens.len() as u64);
+        results.valence.push(sentiment.map(|(valence, _)| valence));
+        results.arousal.push(sentiment.map(|(_, arousal)| arousal));
+    }
+    results
+}
+
+/// Analyzes a `document` in one call, returning a serializable [`DocumentReport`].
+///
+/// Sentiment is only computed if a `sentiment_model` is provided, since RNLTK requires
+/// a user-provided lexicon.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::analysis;
+/// use rnltk::sentiment::SentimentModel;
+/// use rnltk::sample_data;
+///
+/// let sentiment = SentimentModel::new(sample_data::get_sample_custom_word_dict());
+/// let report = analysis::analyze("The bees were betrayed. The bees were angry!", Some(&sentiment));
+///
+/// assert_eq!(report.sentences, vec!["The bees were betrayed", "The bees were angry"]);
+/// assert_eq!(report.keywords[0].term, "bees");
+/// assert_eq!(report.sentiment.unwrap().description, "stressed");
+/// ```
+pub fn analyze(document: &str, sentiment_model: Option<&SentimentModel>) -> DocumentReport {
+    let sentences = token::tokenize_into_sentences(document);
+    let tokens: Vec<String> = sentences.iter().flat_map(|sentence| token::tokenize_sentence(sentence)).collect();
+
+    let stop_words = StopWords::from(token::get_stop_words());
+    let token_refs: Vec<&str> = tokens.iter().map(|token| token.as_str()).collect();
+    let keyword_counts = token::get_term_frequencies_from_word_vector_without_stop_words(token_refs.clone(), stop_words.clone());
+    let keywords = top_terms(keyword_counts);
+
+    let sentiment = sentiment_model.map(|model| {
+        let valence = model.get_valence_for_term_vector(&token_refs);
+        let arousal = model.get_arousal_for_term_vector(&token_refs);
+        let description = if arousal == 0. {
+            "unknown".to_string()
+        } else {
+            model.get_sentiment_description(&valence, &arousal).into_owned()
+        };
+        SentimentSummary {
+            valence,
+            arousal,
+            description,
+        }
+    });
+
+    let sentence_refs: Vec<&str> = sentences.iter().map(|sentence| sentence.as_str()).collect();
+    let top_tfidf_terms = top_terms(get_tfidf_term_weights(&sentence_refs, stop_words));
+
+    DocumentReport {
+        readability: get_readability(&sentences, &tokens),
+        sentences,
+        tokens,
+        keywords,
+        sentiment,
+        top_tfidf_terms,
+    }
+}
+
+/// Gets the Flesch readability scores of a `document`.
+///
+/// Syllables are estimated by counting groups of vowels, ignoring a silent trailing "e".
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::analysis;
+///
+/// let readability = analysis::readability("The cat sat on the mat.");
+///
+/// assert_eq!(readability.num_words, 6);
+/// assert_eq!(readability.num_syllables, 6);
+/// assert!(readability.flesch_reading_ease > 100.);
+/// ```
+pub fn readability(document: &str) -> Readability {
+    let sentences = token::tokenize_into_sentences(document);
+    let tokens: Vec<String> = sentences.iter().flat_map(|sentence| token::tokenize_sentence(sentence)).collect();
+    get_readability(&sentences, &tokens)
+}
+
+fn get_readability(sentences: &[String], tokens: &[String]) -> Readability {
+    let num_sentences = sentences.len();
+    let num_words = tokens.len();
+    let num_syllables: usize = tokens.iter().map(|token| count_syllables(token)).sum();
+
+    let (flesch_reading_ease, flesch_kincaid_grade) = if num_sentences == 0 || num_words == 0 {
+        (0., 0.)
+    } else {
+        let words_per_sentence = num_words as f64 / num_sentences as f64;
+        let syllables_per_word = num_syllables as f64 / num_words as f64;
+        (
+            206.835 - 1.015 * words_per_sentence - 84.6 * syllables_per_word,
+            0.39 * words_per_sentence + 11.8 * syllables_per_word - 15.59,
+        )
+    };
+
+    Readability {
+        num_sentences,
+        num_words,
+        num_syllables,
+        flesch_reading_ease,
+        flesch_kincaid_grade,
+    }
+}
+
+fn count_syllables(word: &str) -> usize {
+    let is_vowel = |character: char| matches!(character, 'a' | 'e' | 'i' | 'o' | 'u' | 'y');
+    let characters: Vec<char> = word.to_lowercase().chars().collect();
+    let mut count = 0;
+    let mut previous_was_vowel = false;
+    for character in &characters {
+        let vowel = is_vowel(*character);
+        if vowel && !previous_was_vowel {
+            count += 1;
+        }
+        previous_was_vowel = vowel;
+    }
+    if characters.len() > 2 && characters.ends_with(&['e']) && !characters.ends_with(&['l', 'e']) && count > 1 {
+        count -= 1;
+    }
+    count.max(1)
+}
+
+fn get_tfidf_term_weights(sentences: &[&str], stop_words: StopWords) -> BTreeMap<String, f64> {
+    let term_frequencies = token::get_term_frequencies_from_sentences_without_stop_words(sentences, stop_words);
+    let weighted = similarity::smoothed_tfidf(&term_frequencies);
+    // frequencies are aligned, so every sentence holds the terms of the first in the same order
+    term_frequencies.first().map_or_else(BTreeMap::new, |first| {
+        first.keys().enumerate()
+            .map(|(index, term)| (term.to_string(), weighted.iter().map(|weights| weights[index]).sum()))
+            .collect()
+    })
+}
+
+fn top_terms(weights: BTreeMap<String, f64>) -> Vec<WeightedTerm> {
+    let mut terms: Vec<WeightedTerm> = weights.into_iter().map(|(term, weight)| WeightedTerm { term, weight }).collect();
+    // stable sort keeps ties in alphabetical order
+    terms.sort_by(|first, second| second.weight.total_cmp(&first.weight));
+    terms.truncate(NUM_REPORT_TERMS);
+    terms
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn syllable_counts() {
+        assert_eq!(count_syllables("cat"), 1);
+        assert_eq!(count_syllables("table"), 2);
+        assert_eq!(count_syllables("make"), 1);
+        assert_eq!(count_syllables("beautiful"), 3);
+        assert_eq!(count_syllables("the"), 1);
+    }
+
+    #[test]
+    fn report_without_sentiment() {
+        let report = analyze("Fear leads to anger. Anger leads to hatred.", None);
+        assert_eq!(report.sentiment, None);
+        assert_eq!(report.tokens.len(), 8);
+        let keywords: Vec<&str> = report.keywords.iter().map(|keyword| keyword.term.as_str()).collect();
+        assert_eq!(keywords, vec!["anger", "leads", "fear", "hatred"]);
+    }
+
+    #[test]
+    fn report_serializes_to_json() {
+        let report = analyze("Why hello there. General Kenobi!", None);
+        let json = serde_json::to_string(&report).unwrap();
+        let deserialized: DocumentReport = serde_json::from_str(&json).unwrap();
+        assert_eq!(report, deserialized);
+    }
+
+    #[test]
+    fn bulk_matches_reports() {
+        let sentiment = SentimentModel::new(crate::sample_data::get_sample_custom_word_dict());
+        let documents = ["Fear leads to anger. Anger leads to hatred.", "", "The bees were betrayed!"];
+        let results = analyze_bulk(&documents, Some(&sentiment));
+        assert_eq!(results.len(), 3);
+        for (index, document) in documents.iter().enumerate() {
+            let report = analyze(document, Some(&sentiment));
+            assert_eq!(results.num_sentences[index], report.readability.num_sentences as u64);
+            assert_eq!(results.num_tokens[index], report.tokens.len() as u64);
+        }
+        assert_eq!(results.valence[..2], [None, None]);
+        assert_eq!(results.valence[2], analyze(documents[2], Some(&sentiment)).sentiment.map(|summary| summary.valence));
+        assert_eq!(analyze_bulk(&documents, None).arousal, vec![None; 3]);
+        assert!(analyze_bulk::<&str>(&[], None).is_empty());
+    }
+
+    #[test]
+    fn empty_document() {
+        let report = analyze("", None);
+        assert!(report.tokens.is_empty());
+        assert_eq!(report.readability.flesch_reading_ease, 0.);
+    }
+}
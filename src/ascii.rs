@@ -0,0 +1,145 @@
+//! Crate-internal ASCII fast paths for the tokenizer and stemmer.
+//!
+//! Bytes are classified eight at a time as lanes of a `u64` (SIMD within a register), which works on
+//! every target without `unsafe` or platform intrinsics. Leftover bytes at the end of the input, and
+//! chunks holding non-ASCII bytes, fall back to scalar classification with the same results.
+
+const LANES: usize = 8;
+const ONES: u64 = 0x0101_0101_0101_0101;
+const HIGH_BITS: u64 = 0x8080_8080_8080_8080;
+const LOW_BITS: u64 = 0x7F7F_7F7F_7F7F_7F7F;
+
+// loads up to eight bytes into lanes, with the first byte in the lowest lane
+fn load(bytes: &[u8]) -> u64 {
+    let mut lanes = [0; LANES];
+    lanes[..bytes.len()].copy_from_slice(bytes);
+    u64::from_le_bytes(lanes)
+}
+
+// high bit of each lane that is zero
+fn zero_lanes(lanes: u64) -> u64 {
+    !(((lanes & LOW_BITS) + LOW_BITS) | lanes | LOW_BITS)
+}
+
+// high bit of each lane equal to `byte`
+fn eq_lanes(lanes: u64, byte: u8) -> u64 {
+    zero_lanes(lanes ^ (ONES * byte as u64))
+}
+
+// high bit of each lane of at least `byte`, for lanes holding ASCII bytes
+fn ge_lanes(lanes: u64, byte: u8) -> u64 {
+    (lanes + ONES * (0x80 - byte as u64)) & HIGH_BITS
+}
+
+// high bit of each lane within `low..=high`, for lanes holding ASCII bytes
+fn range_lanes(lanes: u64, low: u8, high: u8) -> u64 {
+    ge_lanes(lanes, low) & !ge_lanes(lanes, high + 1)
+}
+
+// the characters removed by the tokenizer, all ASCII punctuation except the backslash
+fn is_removed_punctuation(byte: u8) -> bool {
+    byte.is_ascii_punctuation() && byte != b'\\'
+}
+
+// high bit of each lane holding punctuation the tokenizer removes, for lanes holding ASCII bytes
+fn punctuation_lanes(lanes: u64) -> u64 {
+    let punctuation = range_lanes(lanes, b'!', b'/') | range_lanes(lanes, b':', b'@') | range_lanes(lanes, b'[', b'`') | range_lanes(lanes, b'{', b'~');
+    punctuation & !eq_lanes(lanes, b'\\')
+}
+
+// high bit of each lane of `chunk` holding punctuation the tokenizer removes
+fn punctuation_mask(chunk: &[u8]) -> u64 {
+    let lanes = load(chunk);
+    if lanes & HIGH_BITS == 0 {
+        return punctuation_lanes(lanes);
+    }
+    // scalar fallback for chunks with non-ASCII bytes, whose lanes can't be compared as ASCII
+    chunk.iter().enumerate()
+        .filter(|(_, byte)| is_removed_punctuation(**byte))
+        .fold(0, |mask, (position, _)| mask | 0x80 << (position * LANES))
+}
+
+/// Appends `text` to `out` without ASCII punctuation other than backslashes, as the tokenizer strips it.
+/// Non-ASCII characters are always kept.
+pub(crate) fn strip_punctuation(text: &str, out: &mut String) {
+    let bytes = text.as_bytes();
+    let mut run_start = 0;
+    for (chunk_index, chunk) in bytes.chunks(LANES).enumerate() {
+        let mut mask = punctuation_mask(chunk);
+        while mask != 0 {
+            // punctuation is a single ASCII byte, so both sides of it are character boundaries
+            let index = chunk_index * LANES + mask.trailing_zeros() as usize / LANES;
+            out.push_str(&text[run_start..index]);
+            run_start = index + 1;
+            mask &= mask - 1;
+        }
+    }
+    out.push_str(&text[run_start..]);
+}
+
+/// Classifies `bytes[from..]` of a lowercase ASCII word as consonants, as the Porter stemmer defines them:
+/// every letter other than a, e, i, o, and u, except that a "y" following a consonant is a vowel.
+/// `consonants` is truncated to `from` first, so it must already hold the classification of `bytes[..from]`.
+pub(crate) fn classify_consonants(bytes: &[u8], consonants: &mut Vec<bool>, from: usize) {
+    consonants.truncate(from);
+    consonants.reserve(bytes.len().saturating_sub(from));
+    for chunk in bytes[from.min(bytes.len())..].chunks(LANES) {
+        let lanes = load(chunk);
+        let vowels = eq_lanes(lanes, b'a') | eq_lanes(lanes, b'e') | eq_lanes(lanes, b'i') | eq_lanes(lanes, b'o') | eq_lanes(lanes, b'u');
+        let ys = eq_lanes(lanes, b'y');
+        for position in 0..chunk.len() {
+            let lane = 0x80 << (position * LANES);
+            let is_consonant = if vowels & lane != 0 {
+                false
+            } else if ys & lane != 0 {
+                consonants.last().is_none_or(|previous| !previous)
+            } else {
+                true
+            };
+            consonants.push(is_consonant);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strips_punctuation_like_the_scalar_filter() {
+        for text in ["", "hello", "Hello, there! General Kenobi...", r"a\b-c_d{e}~f", "naïve café, «quoted» — 1,000!", "!!!!!!!!!!!!!!!!!!!"] {
+            let mut stripped = String::new();
+            strip_punctuation(text, &mut stripped);
+            let expected: String = text.chars().filter(|c| !(c.is_ascii() && is_removed_punctuation(*c as u8))).collect();
+            assert_eq!(stripped, expected, "{text}");
+        }
+    }
+
+    // the recursive classification of the original stemmer
+    fn is_consonant(bytes: &[u8], index: usize) -> bool {
+        match bytes[index] {
+            b'a' | b'e' | b'i' | b'o' | b'u' => false,
+            b'y' => index == 0 || !is_consonant(bytes, index - 1),
+            _ => true,
+        }
+    }
+
+    #[test]
+    fn classifies_consonants_like_porter() {
+        let mut consonants = vec![];
+        for word in ["", "y", "yesterday's", "syzygy", "yyyyyyyyyyyyyyyyy", "rhythmically", "queueing"] {
+            let bytes = word.as_bytes();
+            classify_consonants(bytes, &mut consonants, 0);
+            let expected: Vec<bool> = (0..bytes.len()).map(|index| is_consonant(bytes, index)).collect();
+            assert_eq!(consonants, expected, "{word}");
+        }
+
+        // reclassifying a changed tail keeps the head
+        let mut bytes = b"rhythmy".to_vec();
+        classify_consonants(&bytes, &mut consonants, 0);
+        assert_eq!(consonants, vec![true, true, false, true, true, true, false]);
+        bytes[6] = b's';
+        classify_consonants(&bytes, &mut consonants, 6);
+        assert_eq!(consonants, vec![true, true, false, true, true, true, true]);
+    }
+}
@@ -0,0 +1,170 @@
+//! Module containing reproducible synthetic corpora and timing helpers for benchmarking RNLTK.
+//!
+//! Enabled with the `bench_fixtures` feature. The crate's own `benches/` suite uses these corpora to
+//! validate performance work on tokenization, stemming, and TF-IDF, and they can be used the same way to
+//! benchmark other configurations. Corpora are generated from a seed, so the same [`CorpusConfig`] always
+//! gives the same text on every platform.
+//!
+//! Words are drawn from a made-up vocabulary with English-like suffixes, so the stemmer has real work
+//! to do, and follow a Zipfian distribution, so a few words are very common as in natural text.
+
+use std::collections::HashSet;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Serialize, Deserialize};
+
+const ONSETS: [&str; 16] = ["b", "c", "d", "f", "g", "h", "l", "m", "n", "p", "r", "s", "t", "v", "st", "tr"];
+const VOWELS: [&str; 6] = ["a", "e", "i", "o", "u", "ea"];
+const SUFFIXES: [&str; 10] = ["", "", "", "s", "ed", "ing", "ly", "ness", "ation", "ful"];
+
+/// Struct for configuring a corpus generated by [`generate_corpus`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CorpusConfig {
+    pub num_documents: usize,
+    pub sentences_per_document: usize,
+    pub words_per_sentence: usize,
+    /// Number of distinct words the corpus is drawn from
+    pub vocabulary_size: usize,
+    /// Seed of the random number generator, so the same seed generates the same corpus
+    pub seed: u64,
+}
+
+impl Default for CorpusConfig {
+    fn default() -> Self {
+        CorpusConfig {
+            num_documents: 100,
+            sentences_per_document: 10,
+            words_per_sentence: 12,
+            vocabulary_size: 5000,
+            seed: 0,
+        }
+    }
+}
+
+/// Gets `size` distinct made-up lowercase words, generated from `seed`.
+pub fn generate_vocabulary(size: usize, seed: u64) -> Vec<String> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut seen = HashSet::new();
+    let mut vocabulary = Vec::with_capacity(size);
+    while vocabulary.len() < size {
+        let syllables = rng.gen_range(1..=3);
+        let mut word: String = (0..syllables)
+            .map(|_| format!("{}{}", ONSETS[rng.gen_range(0..ONSETS.len())], VOWELS[rng.gen_range(0..VOWELS.len())]))
+            .collect();
+        word.push_str(SUFFIXES[rng.gen_range(0..SUFFIXES.len())]);
+        if seen.insert(word.clone()) {
+            vocabulary.push(word);
+        }
+    }
+    vocabulary
+}
+
+/// Gets the documents of a synthetic corpus generated from `config`. Sentences start with a capital letter,
+/// end with a period, and sometimes contain a comma.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::bench_fixtures::{self, CorpusConfig};
+/// use rnltk::token;
+///
+/// let config = CorpusConfig { num_documents: 3, sentences_per_document: 2, words_per_sentence: 5, ..CorpusConfig::default() };
+/// let corpus = bench_fixtures::generate_corpus(&config);
+///
+/// assert_eq!(corpus.len(), 3);
+/// assert_eq!(token::tokenize_into_sentences(&corpus[0]).len(), 2);
+/// assert_eq!(corpus, bench_fixtures::generate_corpus(&config));
+/// ```
+pub fn generate_corpus(config: &CorpusConfig) -> Vec<String> {
+    let vocabulary = generate_vocabulary(config.vocabulary_size, config.seed);
+    if vocabulary.is_empty() {
+        return vec![String::new(); config.num_documents];
+    }
+    let zipf = WeightedIndex::new((1..=vocabulary.len()).map(|rank| 1. / rank as f64)).expect("Vocabulary is not empty");
+    let mut rng = StdRng::seed_from_u64(config.seed.wrapping_add(1));
+    (0..config.num_documents).map(|_| {
+        let sentences: Vec<String> = (0..config.sentences_per_document).map(|_| {
+            let mut sentence = String::new();
+            for index in 0..config.words_per_sentence {
+                let word = &vocabulary[zipf.sample(&mut rng)];
+                if index == 0 {
+                    let mut chars = word.chars();
+                    sentence.extend(chars.next().map(|c| c.to_ascii_uppercase()));
+                    sentence.push_str(chars.as_str());
+                } else {
+                    sentence.push_str(if rng.gen_bool(0.1) { ", " } else { " " });
+                    sentence.push_str(word);
+                }
+            }
+            sentence.push('.');
+            sentence
+        }).collect();
+        sentences.join(" ")
+    }).collect()
+}
+
+/// Gets the median time of running `f` `iterations` times, for quick checks outside of a benchmark harness.
+/// Results of `f` are passed through [`black_box`] so the work isn't optimized away.
+pub fn measure<T>(iterations: usize, mut f: impl FnMut() -> T) -> Duration {
+    let mut timings: Vec<Duration> = (0..iterations.max(1)).map(|_| {
+        let start = Instant::now();
+        black_box(f());
+        start.elapsed()
+    }).collect();
+    timings.sort_unstable();
+    timings[timings.len() / 2]
+}
+
+/// Checks if `current` is more than `tolerance` (as a fraction, such as 0.1 for 10%) slower than `baseline`.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use rnltk::bench_fixtures;
+///
+/// let baseline = Duration::from_millis(100);
+///
+/// assert!(!bench_fixtures::is_regression(baseline, Duration::from_millis(105), 0.1));
+/// assert!(bench_fixtures::is_regression(baseline, Duration::from_millis(120), 0.1));
+/// ```
+pub fn is_regression(baseline: Duration, current: Duration, tolerance: f64) -> bool {
+    current.as_secs_f64() > baseline.as_secs_f64() * (1. + tolerance)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::token;
+
+    #[test]
+    fn corpora_are_reproducible() {
+        let config = CorpusConfig { num_documents: 4, sentences_per_document: 3, words_per_sentence: 6, vocabulary_size: 50, seed: 7 };
+        let corpus = generate_corpus(&config);
+        assert_eq!(corpus, generate_corpus(&config));
+        assert_ne!(corpus, generate_corpus(&CorpusConfig { seed: 8, ..config.clone() }));
+
+        let vocabulary: HashSet<String> = generate_vocabulary(50, 7).into_iter().collect();
+        assert_eq!(vocabulary.len(), 50);
+        for document in &corpus {
+            let sentences = token::tokenize_into_sentences(document);
+            assert_eq!(sentences.len(), 3);
+            for sentence in sentences {
+                let tokens = token::tokenize_sentence(&sentence);
+                assert_eq!(tokens.len(), 6);
+                assert!(tokens.iter().all(|token| vocabulary.contains(token)));
+            }
+        }
+    }
+
+    #[test]
+    fn empty_vocabulary() {
+        let config = CorpusConfig { num_documents: 2, vocabulary_size: 0, ..CorpusConfig::default() };
+        assert_eq!(generate_corpus(&config), vec!["", ""]);
+        assert!(measure(3, || 1 + 1) < Duration::from_secs(1));
+    }
+}
@@ -0,0 +1,72 @@
+//! Module containing cooperative cancellation for long-running operations.
+//!
+//! A [`CancellationToken`] is cloned into an operation and cancelled from another thread.
+//! Long-running loops check it between units of work and return [`RnltkError::Cancelled`],
+//! so applications can abort a computation cleanly instead of killing threads.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use crate::error::RnltkError;
+
+/// Shared flag used to request that an operation stop early. Clones share the same flag.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    /// Creates new, uncancelled instance of CancellationToken.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requests cancellation of every operation holding a clone of this token.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rnltk::cancel::CancellationToken;
+    ///
+    /// let token = CancellationToken::new();
+    /// let worker_token = token.clone();
+    /// token.cancel();
+    ///
+    /// assert!(worker_token.is_cancelled());
+    /// ```
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Checks if cancellation has been requested.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// Checks the token between units of work.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RnltkError::Cancelled`] if cancellation has been requested.
+    pub fn check(&self) -> Result<(), RnltkError> {
+        if self.is_cancelled() {
+            Err(RnltkError::Cancelled)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cancel_from_another_thread() {
+        let token = CancellationToken::new();
+        assert_eq!(token.check(), Ok(()));
+        let worker_token = token.clone();
+        std::thread::spawn(move || worker_token.cancel()).join().unwrap();
+        assert_eq!(token.check(), Err(RnltkError::Cancelled));
+    }
+}
@@ -0,0 +1,573 @@
+//! Module containing text classifiers that can be trained incrementally.
+//!
+//! Documents are token vectors, such as the output of [`token::tokenize_sentence_configurable`](crate::token::tokenize_sentence_configurable),
+//! and labels are strings. Both classifiers implement [`Classifier`], whose [`partial_fit`](Classifier::partial_fit)
+//! updates a model with a batch of labeled documents without retraining from scratch, so models can follow
+//! streaming data. Labels not seen before are added as new classes.
+
+use std::collections::BTreeMap;
+
+use serde::{Serialize, Deserialize};
+
+use crate::error::RnltkError;
+
+/// Text classifier that can be trained incrementally.
+pub trait Classifier {
+    /// Updates the model with a `batch` of documents and their labels, keeping what was learned before.
+    fn partial_fit<T: AsRef<str>, L: ToString>(&mut self, batch: &[(Vec<T>, L)]);
+
+    /// Forgets everything learned so far, then trains the model on `batch`.
+    fn fit<T: AsRef<str>, L: ToString>(&mut self, batch: &[(Vec<T>, L)]);
+
+    /// Gets the probability of every class for `document`. Empty if the model hasn't been trained.
+    fn predict_proba<T: AsRef<str>>(&self, document: &[T]) -> BTreeMap<String, f64>;
+
+    /// Gets the most probable class for `document`, or `None` if the model hasn't been trained.
+    /// Ties go to the class that sorts first.
+    fn predict<T: AsRef<str>>(&self, document: &[T]) -> Option<String> {
+        self.predict_proba(document)
+            .into_iter()
+            .fold(None, |best: Option<(String, f64)>, (class, probability)| match best {
+                Some((_, best_probability)) if best_probability >= probability => best,
+                _ => Some((class, probability)),
+            })
+            .map(|(class, _)| class)
+    }
+
+    /// Gets the share of training documents in every class.
+    fn class_priors(&self) -> BTreeMap<String, f64>;
+
+    /// Gets the `n` highest-weight terms of every class, the terms that most favor the class,
+    /// in descending order of weight.
+    fn top_features_per_class(&self, n: usize) -> BTreeMap<String, Vec<FeatureWeight>>;
+
+    /// Explains the prediction for `document` with how much each term counted toward the predicted class,
+    /// or `None` if the model hasn't been trained.
+    fn explain<T: AsRef<str>>(&self, document: &[T]) -> Option<Explanation>;
+}
+
+/// Weight of a term toward a class, from [`Classifier::top_features_per_class`] and [`Classifier::explain`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct FeatureWeight {
+    pub term: String,
+    pub weight: f64,
+}
+
+/// Breakdown of a prediction from [`Classifier::explain`].
+///
+/// The score of `class` is `intercept` plus the sum of the `contributions`, which hold every distinct term
+/// of the document the model knows, in descending order of absolute weight. Positive contributions
+/// argue for the class and negative ones against it.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Explanation {
+    pub class: String,
+    pub probability: f64,
+    pub intercept: f64,
+    pub contributions: Vec<FeatureWeight>,
+}
+
+fn top_weights<'a>(weights: impl Iterator<Item = (&'a String, f64)>, n: usize) -> Vec<FeatureWeight> {
+    let mut weights: Vec<FeatureWeight> = weights.map(|(term, weight)| FeatureWeight { term: term.to_string(), weight }).collect();
+    weights.sort_by(|a, b| b.weight.total_cmp(&a.weight).then_with(|| a.term.cmp(&b.term)));
+    weights.truncate(n);
+    weights
+}
+
+fn explanation(class: String, probability: f64, intercept: f64, mut contributions: Vec<FeatureWeight>) -> Explanation {
+    contributions.sort_by(|a, b| b.weight.abs().total_cmp(&a.weight.abs()).then_with(|| a.term.cmp(&b.term)));
+    Explanation {
+        class,
+        probability,
+        intercept,
+        contributions,
+    }
+}
+
+fn priors(class_counts: &BTreeMap<String, u64>) -> BTreeMap<String, f64> {
+    let total: u64 = class_counts.values().sum();
+    class_counts.iter().map(|(class, count)| (class.to_string(), *count as f64 / total as f64)).collect()
+}
+
+// converts scores to probabilities without overflowing
+fn softmax(scores: BTreeMap<String, f64>) -> BTreeMap<String, f64> {
+    let max_score = scores.values().copied().fold(f64::NEG_INFINITY, f64::max);
+    let exponentials: BTreeMap<String, f64> = scores.into_iter().map(|(class, score)| (class, (score - max_score).exp())).collect();
+    let total: f64 = exponentials.values().sum();
+    exponentials.into_iter().map(|(class, exponential)| (class, exponential / total)).collect()
+}
+
+// whether gradient descent with these settings keeps weights finite
+fn valid_training_config(learning_rate: f64, l2_penalty: f64) -> bool {
+    learning_rate.is_finite() && learning_rate > 0. && l2_penalty.is_finite() && l2_penalty >= 0.
+}
+
+// whether both maps have the same keys
+fn same_keys<A, B>(first: &BTreeMap<String, A>, second: &BTreeMap<String, B>) -> bool {
+    first.len() == second.len() && first.keys().eq(second.keys())
+}
+
+fn count_tokens<T: AsRef<str>>(document: &[T]) -> BTreeMap<&str, f64> {
+    let mut counts: BTreeMap<&str, f64> = BTreeMap::new();
+    for token in document {
+        *counts.entry(token.as_ref()).or_insert(0.) += 1.;
+    }
+    counts
+}
+
+/// Multinomial Naive Bayes classifier with additive smoothing.
+///
+/// Training only adds to token and document counts, so [`partial_fit`](Classifier::partial_fit) on several
+/// batches gives the same model as [`fit`](Classifier::fit) on all of them at once.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::classify::{Classifier, NaiveBayesClassifier};
+///
+/// let mut classifier = NaiveBayesClassifier::new(1.).unwrap();
+/// classifier.partial_fit(&[(vec!["great", "fun", "movie"], "positive"), (vec!["boring", "movie"], "negative")]);
+/// classifier.partial_fit(&[(vec!["fun", "great", "acting"], "positive")]);
+///
+/// assert_eq!(classifier.predict(&["great", "fun"]).as_deref(), Some("positive"));
+/// assert_eq!(classifier.class_priors()["positive"], 2. / 3.);
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct NaiveBayesClassifier {
+    smoothing: f64,
+    class_counts: BTreeMap<String, u64>,
+    // occurrences of every token in the documents of every class
+    token_counts: BTreeMap<String, BTreeMap<String, f64>>,
+    class_token_totals: BTreeMap<String, f64>,
+    vocabulary: BTreeMap<String, u64>,
+}
+
+impl Default for NaiveBayesClassifier {
+    fn default() -> Self {
+        Self::untrained(1.)
+    }
+}
+
+impl NaiveBayesClassifier {
+    /// Creates new, untrained instance of NaiveBayesClassifier, adding `smoothing` to every token count
+    /// so unseen tokens don't rule out a class. 1 is Laplace smoothing.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RnltkError::InvalidSmoothing`] if `smoothing` isn't positive and finite, since the
+    /// probability of a token never seen with a class would be 0, or undefined for a class without tokens.
+    pub fn new(smoothing: f64) -> Result<Self, RnltkError> {
+        if !smoothing.is_finite() || smoothing <= 0. {
+            return Err(RnltkError::InvalidSmoothing);
+        }
+        Ok(Self::untrained(smoothing))
+    }
+
+    fn untrained(smoothing: f64) -> Self {
+        NaiveBayesClassifier {
+            smoothing,
+            class_counts: BTreeMap::new(),
+            token_counts: BTreeMap::new(),
+            class_token_totals: BTreeMap::new(),
+            vocabulary: BTreeMap::new(),
+        }
+    }
+
+    /// Gets the classes seen in training, in sorted order.
+    pub fn classes(&self) -> Vec<&str> {
+        self.class_counts.keys().map(String::as_str).collect()
+    }
+
+    // checks that the smoothing of a loaded model is one `new` accepts, and that every class has token counts
+    // and a token total, and nothing else does
+    pub(crate) fn check_consistency(&self) -> Result<(), RnltkError> {
+        if !self.smoothing.is_finite() || self.smoothing <= 0. {
+            return Err(RnltkError::ModelFormat(format!("smoothing {} isn't positive and finite", self.smoothing)));
+        }
+        if !(same_keys(&self.class_counts, &self.token_counts) && same_keys(&self.class_counts, &self.class_token_totals)) {
+            return Err(RnltkError::ModelFormat("token counts don't match the classes".to_string()));
+        }
+        Ok(())
+    }
+
+    fn log_likelihood(&self, class: &str, token: &str) -> f64 {
+        let count = self.token_counts[class].get(token).copied().unwrap_or_default();
+        let denominator = self.class_token_totals[class] + self.smoothing * self.vocabulary.len() as f64;
+        ((count + self.smoothing) / denominator).ln()
+    }
+
+    // average log-likelihood of `token` over all classes
+    fn average_log_likelihood(&self, token: &str) -> f64 {
+        self.class_counts.keys().map(|class| self.log_likelihood(class, token)).sum::<f64>() / self.class_counts.len() as f64
+    }
+
+    // log-likelihood of `token` in `class` relative to its average over all classes
+    fn term_weight(&self, class: &str, token: &str) -> f64 {
+        self.log_likelihood(class, token) - self.average_log_likelihood(token)
+    }
+
+    // log of the prior of every class plus the log-likelihoods of the tokens of `document`
+    fn scores<T: AsRef<str>>(&self, document: &[T]) -> BTreeMap<String, f64> {
+        priors(&self.class_counts).into_iter().map(|(class, prior)| {
+            let log_likelihood: f64 = document.iter()
+                // tokens never seen in training carry no information about the class
+                .filter(|token| self.vocabulary.contains_key(token.as_ref()))
+                .map(|token| self.log_likelihood(&class, token.as_ref()))
+                .sum();
+            (class, prior.ln() + log_likelihood)
+        }).collect()
+    }
+}
+
+impl Classifier for NaiveBayesClassifier {
+    fn partial_fit<T: AsRef<str>, L: ToString>(&mut self, batch: &[(Vec<T>, L)]) {
+        for (document, label) in batch {
+            let label = label.to_string();
+            *self.class_counts.entry(label.clone()).or_insert(0) += 1;
+            let class_token_counts = self.token_counts.entry(label.clone()).or_default();
+            for token in document {
+                *class_token_counts.entry(token.as_ref().to_string()).or_insert(0.) += 1.;
+                *self.vocabulary.entry(token.as_ref().to_string()).or_insert(0) += 1;
+            }
+            *self.class_token_totals.entry(label).or_insert(0.) += document.len() as f64;
+        }
+    }
+
+    fn fit<T: AsRef<str>, L: ToString>(&mut self, batch: &[(Vec<T>, L)]) {
+        *self = Self::untrained(self.smoothing);
+        self.partial_fit(batch);
+    }
+
+    fn predict_proba<T: AsRef<str>>(&self, document: &[T]) -> BTreeMap<String, f64> {
+        softmax(self.scores(document))
+    }
+
+    fn class_priors(&self) -> BTreeMap<String, f64> {
+        priors(&self.class_counts)
+    }
+
+    /// Term weights are the log-likelihood of the term in the class minus its average log-likelihood
+    /// over all classes, so terms common to every class weigh close to 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rnltk::classify::{Classifier, NaiveBayesClassifier};
+    ///
+    /// let mut classifier = NaiveBayesClassifier::default();
+    /// classifier.fit(&[(vec!["great", "fun", "movie"], "positive"), (vec!["boring", "movie"], "negative")]);
+    /// let top_features = classifier.top_features_per_class(1);
+    ///
+    /// assert_eq!(top_features["negative"][0].term, "boring");
+    /// ```
+    fn top_features_per_class(&self, n: usize) -> BTreeMap<String, Vec<FeatureWeight>> {
+        self.class_counts.keys().map(|class| {
+            let weights = self.vocabulary.keys().map(|token| (token, self.term_weight(class, token)));
+            (class.to_string(), top_weights(weights, n))
+        }).collect()
+    }
+
+    /// Every term contributes its weight from [`top_features_per_class`](Classifier::top_features_per_class)
+    /// times its count. The intercept is the log of the class prior plus the average log-likelihood over all
+    /// classes of every known term times its count, the part of the score that doesn't favor any class.
+    fn explain<T: AsRef<str>>(&self, document: &[T]) -> Option<Explanation> {
+        let probabilities = self.predict_proba(document);
+        let class = self.predict(document)?;
+        let counts: Vec<(&str, f64)> = count_tokens(document).into_iter()
+            .filter(|(token, _)| self.vocabulary.contains_key(*token))
+            .collect();
+        let intercept = self.class_priors()[&class].ln()
+            + counts.iter().map(|(token, count)| count * self.average_log_likelihood(token)).sum::<f64>();
+        let contributions = counts.into_iter()
+            .map(|(token, count)| FeatureWeight { term: token.to_string(), weight: count * self.term_weight(&class, token) })
+            .collect();
+        Some(explanation(class.clone(), probabilities[&class], intercept, contributions))
+    }
+}
+
+/// Multinomial logistic regression classifier trained with stochastic gradient descent.
+///
+/// Features are token counts divided by the document length. Every call to
+/// [`partial_fit`](Classifier::partial_fit) runs `epochs` passes of gradient descent over its batch,
+/// in batch order, so training is reproducible. Classes seen for the first time start with a bias of
+/// the log of their prior, so a new class isn't predicted more often than it has been seen.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::classify::{Classifier, LogisticRegressionClassifier};
+///
+/// let mut classifier = LogisticRegressionClassifier::new(0.5, 0., 20).unwrap();
+/// classifier.fit(&[(vec!["great", "fun"], "positive"), (vec!["boring", "dull"], "negative")]);
+/// classifier.partial_fit(&[(vec!["awful", "dull"], "negative")]);
+///
+/// assert_eq!(classifier.predict(&["fun"]).as_deref(), Some("positive"));
+/// assert_eq!(classifier.predict(&["awful"]).as_deref(), Some("negative"));
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct LogisticRegressionClassifier {
+    learning_rate: f64,
+    l2_penalty: f64,
+    epochs: usize,
+    class_counts: BTreeMap<String, u64>,
+    biases: BTreeMap<String, f64>,
+    // weight of every token for every class, with missing tokens weighing 0
+    weights: BTreeMap<String, BTreeMap<String, f64>>,
+}
+
+impl Default for LogisticRegressionClassifier {
+    fn default() -> Self {
+        Self::untrained(0.1, 0.0001, 5)
+    }
+}
+
+impl LogisticRegressionClassifier {
+    /// Creates new, untrained instance of LogisticRegressionClassifier with the given gradient descent
+    /// `learning_rate`, `l2_penalty` on token weights, and number of `epochs` per training batch.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RnltkError::InvalidTrainingConfig`] if `learning_rate` isn't positive and finite or `l2_penalty`
+    /// isn't non-negative and finite, since training would then diverge to infinite or NaN weights.
+    pub fn new(learning_rate: f64, l2_penalty: f64, epochs: usize) -> Result<Self, RnltkError> {
+        if !valid_training_config(learning_rate, l2_penalty) {
+            return Err(RnltkError::InvalidTrainingConfig);
+        }
+        Ok(Self::untrained(learning_rate, l2_penalty, epochs))
+    }
+
+    fn untrained(learning_rate: f64, l2_penalty: f64, epochs: usize) -> Self {
+        LogisticRegressionClassifier {
+            learning_rate,
+            l2_penalty,
+            epochs,
+            class_counts: BTreeMap::new(),
+            biases: BTreeMap::new(),
+            weights: BTreeMap::new(),
+        }
+    }
+
+    /// Gets the classes seen in training, in sorted order.
+    pub fn classes(&self) -> Vec<&str> {
+        self.class_counts.keys().map(String::as_str).collect()
+    }
+
+    // checks that the learning rate and penalty of a loaded model are ones `new` accepts, and that every class
+    // has a bias and weights, and nothing else does
+    pub(crate) fn check_consistency(&self) -> Result<(), RnltkError> {
+        if !valid_training_config(self.learning_rate, self.l2_penalty) {
+            return Err(RnltkError::ModelFormat(format!(
+                "learning rate {} must be positive and finite, and L2 penalty {} non-negative and finite", self.learning_rate, self.l2_penalty
+            )));
+        }
+        if !(same_keys(&self.class_counts, &self.biases) && same_keys(&self.class_counts, &self.weights)) {
+            return Err(RnltkError::ModelFormat("biases and weights don't match the classes".to_string()));
+        }
+        Ok(())
+    }
+
+    fn features<T: AsRef<str>>(document: &[T]) -> BTreeMap<&str, f64> {
+        let mut features = count_tokens(document);
+        for value in features.values_mut() {
+            *value /= document.len() as f64;
+        }
+        features
+    }
+
+    fn scores(&self, features: &BTreeMap<&str, f64>) -> BTreeMap<String, f64> {
+        self.biases.iter().map(|(class, bias)| {
+            let class_weights = &self.weights[class];
+            let score: f64 = features.iter()
+                .map(|(token, value)| class_weights.get(*token).copied().unwrap_or_default() * value)
+                .sum();
+            (class.to_string(), bias + score)
+        }).collect()
+    }
+}
+
+impl Classifier for LogisticRegressionClassifier {
+    fn partial_fit<T: AsRef<str>, L: ToString>(&mut self, batch: &[(Vec<T>, L)]) {
+        let labels: Vec<String> = batch.iter().map(|(_, label)| label.to_string()).collect();
+        for label in &labels {
+            *self.class_counts.entry(label.to_string()).or_insert(0) += 1;
+            self.weights.entry(label.to_string()).or_default();
+        }
+        let class_priors = priors(&self.class_counts);
+        for (class, prior) in &class_priors {
+            self.biases.entry(class.to_string()).or_insert(prior.ln());
+        }
+
+        for _ in 0..self.epochs {
+            for ((document, _), label) in batch.iter().zip(&labels) {
+                let features = Self::features(document);
+                let probabilities = softmax(self.scores(&features));
+                for (class, probability) in probabilities {
+                    let error = probability - if class == *label { 1. } else { 0. };
+                    *self.biases.get_mut(&class).expect("Every class has a bias") -= self.learning_rate * error;
+                    let class_weights = self.weights.get_mut(&class).expect("Every class has weights");
+                    for (token, value) in &features {
+                        let weight = class_weights.entry(token.to_string()).or_insert(0.);
+                        *weight -= self.learning_rate * (error * value + self.l2_penalty * *weight);
+                    }
+                }
+            }
+        }
+    }
+
+    fn fit<T: AsRef<str>, L: ToString>(&mut self, batch: &[(Vec<T>, L)]) {
+        *self = Self::untrained(self.learning_rate, self.l2_penalty, self.epochs);
+        self.partial_fit(batch);
+    }
+
+    fn predict_proba<T: AsRef<str>>(&self, document: &[T]) -> BTreeMap<String, f64> {
+        if document.is_empty() {
+            return softmax(self.biases.clone());
+        }
+        softmax(self.scores(&Self::features(document)))
+    }
+
+    fn class_priors(&self) -> BTreeMap<String, f64> {
+        priors(&self.class_counts)
+    }
+
+    /// Term weights are the learned coefficients of the class.
+    fn top_features_per_class(&self, n: usize) -> BTreeMap<String, Vec<FeatureWeight>> {
+        self.weights.iter()
+            .map(|(class, class_weights)| (class.to_string(), top_weights(class_weights.iter().map(|(token, weight)| (token, *weight)), n)))
+            .collect()
+    }
+
+    /// The intercept is the bias of the class, and every term contributes its coefficient times
+    /// its count divided by the document length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rnltk::classify::{Classifier, LogisticRegressionClassifier};
+    ///
+    /// let mut classifier = LogisticRegressionClassifier::new(0.5, 0., 20).unwrap();
+    /// classifier.fit(&[(vec!["great", "fun"], "positive"), (vec!["boring", "dull"], "negative")]);
+    /// let explanation = classifier.explain(&["great", "plot", "but", "dull"]).unwrap();
+    /// let contributions: Vec<&str> = explanation.contributions.iter().map(|contribution| contribution.term.as_str()).collect();
+    ///
+    /// assert_eq!(contributions, vec!["dull", "great"]);
+    /// ```
+    fn explain<T: AsRef<str>>(&self, document: &[T]) -> Option<Explanation> {
+        let probabilities = self.predict_proba(document);
+        let class = self.predict(document)?;
+        let class_weights = &self.weights[&class];
+        let contributions = Self::features(document).into_iter()
+            .filter_map(|(token, value)| class_weights.get(token).map(|weight| FeatureWeight { term: token.to_string(), weight: weight * value }))
+            .collect();
+        Some(explanation(class.clone(), probabilities[&class], self.biases[&class], contributions))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn batches() -> Vec<Vec<(Vec<&'static str>, &'static str)>> {
+        vec![
+            vec![(vec!["great", "fun", "movie"], "positive"), (vec!["boring", "slow", "movie"], "negative")],
+            vec![(vec!["loved", "fun"], "positive")],
+            vec![(vec!["news", "report"], "neutral"), (vec!["dull", "slow"], "negative")],
+        ]
+    }
+
+    #[test]
+    fn naive_bayes_partial_fit_matches_fit() {
+        let mut incremental = NaiveBayesClassifier::default();
+        for batch in batches() {
+            incremental.partial_fit(&batch);
+        }
+        let mut full = NaiveBayesClassifier::default();
+        full.fit(&batches().concat());
+        assert_eq!(incremental, full);
+        assert_eq!(incremental.classes(), vec!["negative", "neutral", "positive"]);
+        assert_eq!(incremental.class_priors()["neutral"], 0.2);
+    }
+
+    #[test]
+    fn probabilities_sum_to_one() {
+        let mut naive_bayes = NaiveBayesClassifier::default();
+        let mut logistic_regression = LogisticRegressionClassifier::default();
+        for batch in batches() {
+            naive_bayes.partial_fit(&batch);
+            logistic_regression.partial_fit(&batch);
+        }
+        for probabilities in [naive_bayes.predict_proba(&["fun", "unseen"]), logistic_regression.predict_proba(&["fun", "unseen"])] {
+            assert_eq!(probabilities.len(), 3);
+            assert!((probabilities.values().sum::<f64>() - 1.).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn invalid_naive_bayes_smoothing() {
+        for smoothing in [0., -1., f64::NAN, f64::INFINITY] {
+            assert!(matches!(NaiveBayesClassifier::new(smoothing), Err(RnltkError::InvalidSmoothing)));
+        }
+        assert_eq!(NaiveBayesClassifier::new(1.).unwrap(), NaiveBayesClassifier::default());
+    }
+
+    #[test]
+    fn invalid_logistic_regression_config() {
+        for (learning_rate, l2_penalty) in [(0., 0.), (-0.1, 0.), (f64::NAN, 0.), (f64::INFINITY, 0.), (0.1, -1.), (0.1, f64::NAN)] {
+            assert!(matches!(LogisticRegressionClassifier::new(learning_rate, l2_penalty, 5), Err(RnltkError::InvalidTrainingConfig)));
+        }
+        assert_eq!(LogisticRegressionClassifier::new(0.1, 0.0001, 5).unwrap(), LogisticRegressionClassifier::default());
+    }
+
+    #[test]
+    fn untrained_classifiers_predict_nothing() {
+        assert!(NaiveBayesClassifier::default().predict(&["fun"]).is_none());
+        assert!(LogisticRegressionClassifier::default().predict(&["fun"]).is_none());
+    }
+
+    #[test]
+    fn logistic_regression_learns_new_classes() {
+        let mut classifier = LogisticRegressionClassifier::new(0.5, 0., 30).unwrap();
+        classifier.partial_fit(&[(vec!["great", "fun"], "positive"), (vec!["boring", "dull"], "negative")]);
+        assert_eq!(classifier.classes(), vec!["negative", "positive"]);
+        classifier.partial_fit(&[(vec!["news", "report"], "neutral"), (vec!["great", "movie"], "positive"), (vec!["report", "weather"], "neutral")]);
+        assert_eq!(classifier.classes(), vec!["negative", "neutral", "positive"]);
+        assert_eq!(classifier.predict(&["report"]).as_deref(), Some("neutral"));
+        assert_eq!(classifier.predict(&["great"]).as_deref(), Some("positive"));
+        assert_eq!(classifier.class_priors()["neutral"], 0.4);
+    }
+
+    #[test]
+    fn explanations_add_up_to_scores() {
+        let mut classifier = LogisticRegressionClassifier::new(0.5, 0.001, 10).unwrap();
+        for batch in batches() {
+            classifier.partial_fit(&batch);
+        }
+        let document = ["fun", "fun", "slow", "unseen"];
+        let explanation = classifier.explain(&document).unwrap();
+        let score = explanation.intercept + explanation.contributions.iter().map(|contribution| contribution.weight).sum::<f64>();
+        let scores = classifier.scores(&LogisticRegressionClassifier::features(&document));
+        assert!((score - scores[&explanation.class]).abs() < 1e-12);
+        assert_eq!(explanation.contributions.len(), 2);
+        assert!(explanation.contributions[0].weight.abs() >= explanation.contributions[1].weight.abs());
+
+        let mut naive_bayes = NaiveBayesClassifier::default();
+        naive_bayes.fit(&batches().concat());
+        let explanation = naive_bayes.explain(&document).unwrap();
+        let score = explanation.intercept + explanation.contributions.iter().map(|contribution| contribution.weight).sum::<f64>();
+        assert!((score - naive_bayes.scores(&document)[&explanation.class]).abs() < 1e-12);
+        assert_eq!(explanation.contributions.len(), 2);
+    }
+
+    #[test]
+    fn naive_bayes_weights_favor_their_class() {
+        let mut classifier = NaiveBayesClassifier::default();
+        classifier.fit(&batches().concat());
+        let top_features = classifier.top_features_per_class(2);
+        assert_eq!(top_features["positive"][0].term, "fun");
+        assert!(top_features.values().all(|features| features.len() == 2));
+        let explanation = classifier.explain(&["fun", "movie"]).unwrap();
+        assert_eq!(explanation.class, "positive");
+        assert_eq!(explanation.contributions[0].term, "fun");
+        assert!(explanation.contributions[0].weight > 0.);
+        assert!(NaiveBayesClassifier::default().explain(&["fun"]).is_none());
+    }
+}
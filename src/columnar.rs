@@ -0,0 +1,259 @@
+//! Module containing conversions of term frequencies, TF-IDF matrices, and sentiment scores to
+//! Arrow [`RecordBatch`]es and Parquet files, so results feed straight into polars or DataFusion.
+//!
+//! Requires the `arrow` feature. Writing Parquet files additionally requires the `parquet` feature.
+
+use std::collections::BTreeMap;
+#[cfg(feature = "parquet")]
+use std::fs::File;
+#[cfg(feature = "parquet")]
+use std::path::Path;
+use std::sync::Arc;
+
+use arrow::array::{ArrayRef, Float64Array, StringArray, UInt64Array};
+use arrow::datatypes::{DataType, Field, Schema};
+use arrow::record_batch::RecordBatch;
+
+use crate::analysis::{ColumnarResults, SentimentSummary};
+use crate::document::TfidfMatrix;
+use crate::error::RnltkError;
+
+/// Name of the index column holding the position of each document.
+pub const DOCUMENT_COLUMN: &str = "document";
+
+fn interop_error(error: impl ToString) -> RnltkError {
+    RnltkError::Interop(error.to_string())
+}
+
+fn document_column(num_documents: usize) -> ArrayRef {
+    Arc::new(UInt64Array::from_iter_values(0..num_documents as u64))
+}
+
+/// Converts aligned `term_frequencies`, like those from [`token::get_term_frequencies_from_sentences`](crate::token::get_term_frequencies_from_sentences),
+/// to a wide [`RecordBatch`] with a `document` index column followed by one `Float64` column per term.
+/// Terms missing from a document are filled with 0.
+///
+/// # Errors
+///
+/// Returns [`RnltkError::Interop`] if a term is named [`DOCUMENT_COLUMN`], since its column would clash with
+/// the index column, or if the record batch could not be built.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::{columnar, token};
+///
+/// let term_frequencies = token::get_term_frequencies_from_sentences(&["fear leads to anger", "anger leads to hatred"]);
+/// let batch = columnar::term_frequencies_to_record_batch(&term_frequencies).unwrap();
+///
+/// assert_eq!(batch.num_rows(), 2);
+/// assert_eq!(batch.schema().field(0).name(), "document");
+/// assert_eq!(batch.num_columns(), 6);
+/// ```
+pub fn term_frequencies_to_record_batch(term_frequencies: &[BTreeMap<String, f64>]) -> Result<RecordBatch, RnltkError> {
+    let mut terms: Vec<&String> = term_frequencies.iter().flat_map(|frequencies| frequencies.keys()).collect();
+    terms.sort_unstable();
+    terms.dedup();
+    if terms.binary_search_by(|term| term.as_str().cmp(DOCUMENT_COLUMN)).is_ok() {
+        return Err(RnltkError::Interop(format!("term '{DOCUMENT_COLUMN}' clashes with the document index column")));
+    }
+
+    let mut fields = vec![Field::new(DOCUMENT_COLUMN, DataType::UInt64, false)];
+    let mut columns = vec![document_column(term_frequencies.len())];
+    for term in terms {
+        fields.push(Field::new(term.as_str(), DataType::Float64, false));
+        columns.push(Arc::new(Float64Array::from_iter_values(
+            term_frequencies.iter().map(|frequencies| frequencies.get(term).copied().unwrap_or(0.))
+        )));
+    }
+    RecordBatch::try_new(Arc::new(Schema::new(fields)), columns).map_err(interop_error)
+}
+
+/// Converts a [`TfidfMatrix`] to a long-format [`RecordBatch`] with `document`, `term`, and `tfidf`
+/// columns, where `terms` labels the rows of the matrix. Only non-zero weights are included.
+///
+/// # Errors
+///
+/// Returns [`RnltkError::LabelCountMismatch`] if the number of `terms` doesn't match the matrix.
+///
+/// Returns [`RnltkError::Interop`] if the record batch could not be built.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::columnar;
+/// use rnltk::document::DocumentTermFrequencies;
+/// use nalgebra::DMatrix;
+///
+/// let term_frequencies = DMatrix::from_row_slice(3, 2, &[1., 0.,
+///     0., 1.,
+///     1., 1.,]);
+/// let tfidf_matrix = DocumentTermFrequencies::new(term_frequencies).unwrap().get_tfidf_from_term_frequencies();
+/// let batch = columnar::tfidf_to_record_batch(&tfidf_matrix, &["fear", "hatred", "anger"]).unwrap();
+///
+/// // "anger" appears in every document, so it has a weight of 0 and is left out
+/// assert_eq!(batch.num_rows(), 2);
+/// ```
+pub fn tfidf_to_record_batch<T: ToString>(tfidf_matrix: &TfidfMatrix, terms: &[T]) -> Result<RecordBatch, RnltkError> {
+    let matrix = tfidf_matrix.get_tfidf_matrix();
+    if terms.len() != matrix.nrows() {
+        return Err(RnltkError::LabelCountMismatch);
+    }
+    let terms: Vec<String> = terms.iter().map(|term| term.to_string()).collect();
+
+    let mut documents: Vec<u64> = vec![];
+    let mut term_column: Vec<&str> = vec![];
+    let mut weights: Vec<f64> = vec![];
+    for (document, column) in matrix.column_iter().enumerate() {
+        for (term, weight) in terms.iter().zip(column.iter()) {
+            if *weight != 0. {
+                documents.push(document as u64);
+                term_column.push(term);
+                weights.push(*weight);
+            }
+        }
+    }
+
+    let schema = Schema::new(vec![
+        Field::new(DOCUMENT_COLUMN, DataType::UInt64, false),
+        Field::new("term", DataType::Utf8, false),
+        Field::new("tfidf", DataType::Float64, false),
+    ]);
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(UInt64Array::from(documents)),
+        Arc::new(StringArray::from(term_column)),
+        Arc::new(Float64Array::from(weights)),
+    ];
+    RecordBatch::try_new(Arc::new(schema), columns).map_err(interop_error)
+}
+
+/// Converts per-document sentiment `scores`, like those in an [`analysis::DocumentReport`](crate::analysis::DocumentReport),
+/// to a [`RecordBatch`] with `document`, `valence`, `arousal`, and `description` columns.
+///
+/// # Errors
+///
+/// Returns [`RnltkError::Interop`] if the record batch could not be built.
+pub fn sentiment_to_record_batch(scores: &[SentimentSummary]) -> Result<RecordBatch, RnltkError> {
+    let schema = Schema::new(vec![
+        Field::new(DOCUMENT_COLUMN, DataType::UInt64, false),
+        Field::new("valence", DataType::Float64, false),
+        Field::new("arousal", DataType::Float64, false),
+        Field::new("description", DataType::Utf8, false),
+    ]);
+    let columns: Vec<ArrayRef> = vec![
+        document_column(scores.len()),
+        Arc::new(Float64Array::from_iter_values(scores.iter().map(|score| score.valence))),
+        Arc::new(Float64Array::from_iter_values(scores.iter().map(|score| score.arousal))),
+        Arc::new(StringArray::from_iter_values(scores.iter().map(|score| score.description.as_str()))),
+    ];
+    RecordBatch::try_new(Arc::new(schema), columns).map_err(interop_error)
+}
+
+/// Converts bulk analysis `results` from [`analysis::analyze_bulk`](crate::analysis::analyze_bulk) to a [`RecordBatch`]
+/// with `document`, `num_sentences`, `num_tokens`, `valence`, and `arousal` columns. The sentiment columns are
+/// null where a document has no sentiment.
+///
+/// # Errors
+///
+/// Returns [`RnltkError::Interop`] if the record batch could not be built.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::{analysis, columnar};
+///
+/// let results = analysis::analyze_bulk(&["Why hello there.", "General Kenobi!"], None);
+/// let batch = columnar::bulk_results_to_record_batch(&results).unwrap();
+///
+/// assert_eq!(batch.num_rows(), 2);
+/// assert_eq!(batch.column(4).null_count(), 2);
+/// ```
+pub fn bulk_results_to_record_batch(results: &ColumnarResults) -> Result<RecordBatch, RnltkError> {
+    let schema = Schema::new(vec![
+        Field::new(DOCUMENT_COLUMN, DataType::UInt64, false),
+        Field::new("num_sentences", DataType::UInt64, false),
+        Field::new("num_tokens", DataType::UInt64, false),
+        Field::new("valence", DataType::Float64, true),
+        Field::new("arousal", DataType::Float64, true),
+    ]);
+    let columns: Vec<ArrayRef> = vec![
+        document_column(results.len()),
+        Arc::new(UInt64Array::from(results.num_sentences.clone())),
+        Arc::new(UInt64Array::from(results.num_tokens.clone())),
+        Arc::new(Float64Array::from(results.valence.clone())),
+        Arc::new(Float64Array::from(results.arousal.clone())),
+    ];
+    RecordBatch::try_new(Arc::new(schema), columns).map_err(interop_error)
+}
+
+/// Writes `batch` to a Parquet file at `path`, replacing any existing file.
+///
+/// Requires the `parquet` feature.
+///
+/// # Errors
+///
+/// Returns [`RnltkError::Interop`] if the file could not be created or written.
+#[cfg(feature = "parquet")]
+pub fn write_parquet(batch: &RecordBatch, path: impl AsRef<Path>) -> Result<(), RnltkError> {
+    let file = File::create(path).map_err(interop_error)?;
+    let mut writer = parquet::arrow::ArrowWriter::try_new(file, batch.schema(), None).map_err(interop_error)?;
+    writer.write(batch).map_err(interop_error)?;
+    writer.close().map_err(interop_error)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::Array;
+    use crate::token;
+
+    #[test]
+    fn wide_term_frequencies() {
+        let term_frequencies = token::get_term_frequencies_from_sentences(&["fear leads to anger", "anger leads to hatred"]);
+        let batch = term_frequencies_to_record_batch(&term_frequencies).unwrap();
+        let schema = batch.schema();
+        let names: Vec<&str> = schema.fields().iter().map(|field| field.name().as_str()).collect();
+        assert_eq!(names, vec!["document", "anger", "fear", "hatred", "leads", "to"]);
+        let fear = batch.column(2).as_any().downcast_ref::<Float64Array>().unwrap();
+        assert_eq!(fear.values().to_vec(), vec![1., 0.]);
+    }
+
+    #[test]
+    fn term_named_like_the_index_column() {
+        let term_frequencies = token::get_term_frequencies_from_sentences(&["the document is long"]);
+        assert!(matches!(term_frequencies_to_record_batch(&term_frequencies), Err(RnltkError::Interop(message)) if message.contains("document")));
+    }
+
+    #[test]
+    fn tfidf_label_mismatch() {
+        let tfidf_matrix = crate::document::DocumentTermFrequencies::new(crate::sample_data::get_term_frequencies()).unwrap().get_tfidf_from_term_frequencies();
+        assert_eq!(tfidf_to_record_batch(&tfidf_matrix, &["only one"]).unwrap_err(), RnltkError::LabelCountMismatch);
+    }
+
+    #[test]
+    fn sentiment_columns() {
+        let scores = vec![SentimentSummary { valence: 2.5, arousal: 6., description: "upset".to_string() }];
+        let batch = sentiment_to_record_batch(&scores).unwrap();
+        assert_eq!(batch.num_rows(), 1);
+        let description = batch.column(3).as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(description.value(0), "upset");
+        assert!(!description.is_null(0));
+    }
+
+    #[cfg(feature = "parquet")]
+    #[test]
+    fn parquet_round_trip() {
+        use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+        let scores = vec![SentimentSummary { valence: 7., arousal: 3., description: "calm".to_string() }];
+        let batch = sentiment_to_record_batch(&scores).unwrap();
+        let path = std::env::temp_dir().join("rnltk_sentiment_test.parquet");
+        write_parquet(&batch, &path).unwrap();
+
+        let reader = ParquetRecordBatchReaderBuilder::try_new(File::open(&path).unwrap()).unwrap().build().unwrap();
+        let batches: Vec<RecordBatch> = reader.map(|batch| batch.unwrap()).collect();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(batches, vec![batch]);
+    }
+}
@@ -0,0 +1,177 @@
+//! Module containing utilities for diffing RNLTK outputs against reference outputs, such as
+//! fixtures produced by NLTK or scikit-learn, to check compatibility when migrating from Python.
+
+use nalgebra::DMatrix;
+use serde::{Serialize, Deserialize};
+
+use crate::error::RnltkError;
+use crate::stem;
+
+/// An input whose actual output differs from the expected reference output.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Mismatch<T> {
+    pub input: String,
+    pub expected: T,
+    pub actual: T,
+}
+
+/// A matrix cell whose actual value differs from the expected reference value by more than the tolerance.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct CellMismatch {
+    pub row: usize,
+    pub column: usize,
+    pub expected: f64,
+    pub actual: f64,
+}
+
+/// Gets every case in `cases`, pairs of input and expected output, where `function` gives a different output.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::compat;
+///
+/// let cases = vec![("Hello", "hello".to_string()), ("WORLD", "World".to_string())];
+/// let mismatches = compat::diff_outputs(&cases, |input| input.to_lowercase());
+///
+/// assert_eq!(mismatches.len(), 1);
+/// assert_eq!(mismatches[0].actual, "world");
+/// ```
+pub fn diff_outputs<S: AsRef<str>, T: PartialEq + Clone>(cases: &[(S, T)], function: impl Fn(&str) -> T) -> Vec<Mismatch<T>> {
+    cases.iter()
+        .filter_map(|(input, expected)| {
+            let actual = function(input.as_ref());
+            (actual != *expected).then(|| Mismatch {
+                input: input.as_ref().to_string(),
+                expected: expected.clone(),
+                actual,
+            })
+        })
+        .collect()
+}
+
+/// Gets every case in `cases`, pairs of word and expected stem, where [`stem::get`] gives a different stem.
+/// Words that can't be stemmed are compared unchanged.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::compat;
+///
+/// let cases = vec![("caresses", "caress"), ("ponies", "poni"), ("relational", "relat")];
+///
+/// assert!(compat::diff_stems(&cases).is_empty());
+/// ```
+pub fn diff_stems<S: AsRef<str>, T: AsRef<str>>(cases: &[(S, T)]) -> Vec<Mismatch<String>> {
+    let cases: Vec<(&str, String)> = cases.iter().map(|(word, stem)| (word.as_ref(), stem.as_ref().to_string())).collect();
+    diff_outputs(&cases, |word| stem::get(word).unwrap_or_else(|_| word.to_string()))
+}
+
+/// Gets every case in `cases`, pairs of sentence and expected tokens, where `tokenizer`, such as
+/// [`treebank::tokenize`](crate::treebank::tokenize) or [`token::tokenize_sentence`](crate::token::tokenize_sentence), gives different tokens.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::{compat, token, treebank};
+///
+/// let cases = vec![("They don't know.", vec!["They".to_string(), "do".to_string(), "n't".to_string(), "know".to_string(), ".".to_string()])];
+///
+/// assert!(compat::diff_tokens(&cases, treebank::tokenize).is_empty());
+/// assert_eq!(compat::diff_tokens(&cases, token::tokenize_sentence)[0].actual, vec!["they", "dont", "know"]);
+/// ```
+pub fn diff_tokens<S: AsRef<str>>(cases: &[(S, Vec<String>)], tokenizer: impl Fn(&str) -> Vec<String>) -> Vec<Mismatch<Vec<String>>> {
+    diff_outputs(cases, tokenizer)
+}
+
+/// Gets every cell where `actual` differs from `expected` by more than `tolerance`.
+///
+/// # Errors
+///
+/// Returns [`RnltkError::MatrixShapeMismatch`] if the matrices don't have the same shape.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::compat;
+/// use nalgebra::DMatrix;
+///
+/// let expected = DMatrix::from_row_slice(1, 2, &[0.5, 0.25]);
+/// let actual = DMatrix::from_row_slice(1, 2, &[0.5000001, 0.3]);
+/// let mismatches = compat::diff_matrices(&expected, &actual, 1e-6).unwrap();
+///
+/// assert_eq!((mismatches[0].row, mismatches[0].column), (0, 1));
+/// ```
+pub fn diff_matrices(expected: &DMatrix<f64>, actual: &DMatrix<f64>, tolerance: f64) -> Result<Vec<CellMismatch>, RnltkError> {
+    if expected.shape() != actual.shape() {
+        return Err(RnltkError::MatrixShapeMismatch);
+    }
+    let mut mismatches = vec![];
+    for row in 0..expected.nrows() {
+        for column in 0..expected.ncols() {
+            let (expected, actual) = (expected[(row, column)], actual[(row, column)]);
+            if !((expected - actual).abs() <= tolerance || expected == actual) {
+                mismatches.push(CellMismatch { row, column, expected, actual });
+            }
+        }
+    }
+    Ok(mismatches)
+}
+
+/// Parses reference `contents` with one tab-separated input and expected output per line,
+/// as exported from Python with `"\n".join(f"{word}\t{stem}" for ...)`. Empty lines and lines starting with `#` are skipped.
+///
+/// # Errors
+///
+/// Returns [`RnltkError::DictionaryFormat`] naming the first line without a tab.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::compat;
+///
+/// let cases = compat::parse_tab_separated_cases("# word\tstem\nponies\tponi\n").unwrap();
+///
+/// assert_eq!(cases, vec![("ponies".to_string(), "poni".to_string())]);
+/// ```
+pub fn parse_tab_separated_cases(contents: &str) -> Result<Vec<(String, String)>, RnltkError> {
+    contents.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.starts_with('#'))
+        .map(|(index, line)| {
+            line.split_once('\t')
+                .map(|(input, expected)| (input.to_string(), expected.to_string()))
+                .ok_or_else(|| RnltkError::DictionaryFormat(format!("line {} has no tab separator", index + 1)))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stem_mismatches_are_reported() {
+        let mismatches = diff_stems(&[("ponies", "pony"), ("cats", "cat")]);
+        assert_eq!(mismatches, vec![Mismatch { input: "ponies".to_string(), expected: "pony".to_string(), actual: "poni".to_string() }]);
+    }
+
+    #[test]
+    fn matrix_shape_mismatch() {
+        let expected = DMatrix::<f64>::zeros(2, 2);
+        let actual = DMatrix::<f64>::zeros(2, 3);
+        assert_eq!(diff_matrices(&expected, &actual, 0.).unwrap_err(), RnltkError::MatrixShapeMismatch);
+    }
+
+    #[test]
+    fn nan_cells_mismatch() {
+        let expected = DMatrix::from_row_slice(1, 1, &[0.]);
+        let actual = DMatrix::from_row_slice(1, 1, &[f64::NAN]);
+        assert_eq!(diff_matrices(&expected, &actual, 1.).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn line_without_tab() {
+        assert_eq!(parse_tab_separated_cases("a\tb\nc").unwrap_err(), RnltkError::DictionaryFormat("line 2 has no tab separator".to_string()));
+    }
+}
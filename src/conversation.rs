@@ -0,0 +1,212 @@
+//! Module containing sentiment rollups of conversation transcripts: per-speaker and per-conversation
+//! summaries, escalation detection, and the most negative utterances.
+//!
+//! Transcripts are `(speaker, utterance, timestamp)` triples, with timestamps in any `i64` unit such as Unix seconds.
+//! Utterances are scored with [`SentimentModel::get_valence_for_term_vector`] and
+//! [`SentimentModel::get_arousal_for_term_vector`], and utterances without lexicon terms are counted but not scored.
+
+use std::collections::BTreeMap;
+
+use serde::{Serialize, Deserialize};
+
+use crate::sentiment::SentimentModel;
+use crate::token;
+use crate::trends::Estimate;
+
+/// Least-squares increase in arousal per scored utterance at which a conversation or speaker is escalating.
+pub const ESCALATION_SLOPE: f64 = 0.2;
+
+/// Number of scored utterances needed before an arousal trend is fitted.
+const MIN_TREND_UTTERANCES: usize = 3;
+
+/// Struct for holding the sentiment of one utterance of a transcript.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct UtteranceSentiment {
+    /// Position of the utterance in the transcript as given
+    pub index: usize,
+    pub speaker: String,
+    pub utterance: String,
+    pub timestamp: i64,
+    /// Valence of the utterance, `None` if it has no terms from the sentiment lexicons
+    pub valence: Option<f64>,
+    /// Arousal of the utterance, `None` if it has no terms from the sentiment lexicons
+    pub arousal: Option<f64>,
+}
+
+/// Struct for holding the aggregate sentiment of a speaker or of a whole conversation.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SentimentRollup {
+    pub num_utterances: usize,
+    /// Number of utterances containing at least one term from the sentiment lexicons
+    pub num_scored: usize,
+    /// Mean utterance valence, `None` if no utterance could be scored
+    pub valence: Option<Estimate>,
+    /// Mean utterance arousal, `None` if no utterance could be scored
+    pub arousal: Option<Estimate>,
+    /// Least-squares slope of arousal per scored utterance in time order, `None` if fewer than three utterances could be scored
+    pub arousal_trend: Option<f64>,
+    /// Whether `arousal_trend` is at least [`ESCALATION_SLOPE`]
+    pub is_escalating: bool,
+}
+
+/// Struct for holding the sentiment rollups of a transcript returned from [`get_conversation_sentiment`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ConversationSentiment {
+    /// Every utterance in time order, with utterances at the same timestamp kept in transcript order
+    pub utterances: Vec<UtteranceSentiment>,
+    pub conversation: SentimentRollup,
+    pub speakers: BTreeMap<String, SentimentRollup>,
+}
+
+impl SentimentRollup {
+    fn from_utterances<'a>(utterances: impl Iterator<Item = &'a UtteranceSentiment>) -> Self {
+        let mut num_utterances = 0;
+        let mut valences = vec![];
+        let mut arousals = vec![];
+        for utterance in utterances {
+            num_utterances += 1;
+            if let (Some(valence), Some(arousal)) = (utterance.valence, utterance.arousal) {
+                valences.push(valence);
+                arousals.push(arousal);
+            }
+        }
+        let arousal_trend = get_trend(&arousals);
+        SentimentRollup {
+            num_utterances,
+            num_scored: valences.len(),
+            valence: Estimate::from_values(&valences),
+            arousal: Estimate::from_values(&arousals),
+            arousal_trend,
+            is_escalating: arousal_trend.is_some_and(|slope| slope >= ESCALATION_SLOPE),
+        }
+    }
+}
+
+/// Gets the least-squares slope of `values` against their positions.
+fn get_trend(values: &[f64]) -> Option<f64> {
+    if values.len() < MIN_TREND_UTTERANCES {
+        return None;
+    }
+    let count = values.len() as f64;
+    let mean_position = (count - 1.) / 2.;
+    let mean_value = values.iter().sum::<f64>() / count;
+    let covariance: f64 = values.iter().enumerate().map(|(position, value)| (position as f64 - mean_position) * (value - mean_value)).sum();
+    let variance: f64 = (0..values.len()).map(|position| (position as f64 - mean_position).powi(2)).sum();
+    Some(covariance / variance)
+}
+
+/// Gets the sentiment of every utterance of a `(speaker, utterance, timestamp)` `transcript`, rolled up per speaker
+/// and for the whole conversation.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::conversation;
+/// use rnltk::sentiment::SentimentModel;
+/// use rnltk::sample_data;
+///
+/// let sentiment = SentimentModel::new(sample_data::get_sample_custom_word_dict());
+/// let transcript = vec![
+///     ("customer", "It felt like an abduction of my account", 0),
+///     ("agent", "Let me take a look", 5),
+///     ("customer", "Now there are bees in the package", 9),
+///     ("customer", "I feel betrayed", 12),
+/// ];
+/// let conversation_sentiment = conversation::get_conversation_sentiment(&transcript, &sentiment);
+///
+/// assert_eq!(conversation_sentiment.speakers["customer"].num_scored, 3);
+/// assert_eq!(conversation_sentiment.speakers["agent"].valence, None);
+/// assert!(conversation_sentiment.conversation.is_escalating);
+/// assert_eq!(conversation_sentiment.get_most_negative_utterances(1)[0].utterance, "I feel betrayed");
+/// ```
+pub fn get_conversation_sentiment<S: AsRef<str>, U: AsRef<str>>(transcript: &[(S, U, i64)], sentiment_model: &SentimentModel) -> ConversationSentiment {
+    let mut utterances: Vec<UtteranceSentiment> = transcript.iter().enumerate().map(|(index, (speaker, utterance, timestamp))| {
+        let tokens: Vec<String> = token::tokenize_into_sentences(utterance.as_ref()).iter().flat_map(|sentence| token::tokenize_sentence(sentence)).collect();
+        let is_scored = tokens.iter().any(|token| sentiment_model.does_term_exist(token));
+        UtteranceSentiment {
+            index,
+            speaker: speaker.as_ref().to_string(),
+            utterance: utterance.as_ref().to_string(),
+            timestamp: *timestamp,
+            valence: is_scored.then(|| sentiment_model.get_valence_for_term_vector(&tokens)),
+            arousal: is_scored.then(|| sentiment_model.get_arousal_for_term_vector(&tokens)),
+        }
+    }).collect();
+    // stable sort keeps utterances at the same timestamp in transcript order
+    utterances.sort_by_key(|utterance| utterance.timestamp);
+
+    let mut speaker_names: Vec<&str> = utterances.iter().map(|utterance| utterance.speaker.as_str()).collect();
+    speaker_names.sort_unstable();
+    speaker_names.dedup();
+    let speakers = speaker_names.into_iter()
+        .map(|speaker| (speaker.to_string(), SentimentRollup::from_utterances(utterances.iter().filter(|utterance| utterance.speaker == speaker))))
+        .collect();
+
+    ConversationSentiment {
+        conversation: SentimentRollup::from_utterances(utterances.iter()),
+        speakers,
+        utterances,
+    }
+}
+
+impl ConversationSentiment {
+    /// Gets the `n` scored utterances with the lowest valence, most negative first.
+    /// Utterances with the same valence are in time order.
+    pub fn get_most_negative_utterances(&self, n: usize) -> Vec<&UtteranceSentiment> {
+        let mut scored: Vec<&UtteranceSentiment> = self.utterances.iter().filter(|utterance| utterance.valence.is_some()).collect();
+        // stable sort keeps ties in time order
+        scored.sort_by(|first, second| first.valence.unwrap_or_default().total_cmp(&second.valence.unwrap_or_default()));
+        scored.truncate(n);
+        scored
+    }
+
+    /// Gets the speakers whose arousal is rising, in sorted order.
+    pub fn get_escalating_speakers(&self) -> Vec<&str> {
+        self.speakers.iter().filter(|(_, rollup)| rollup.is_escalating).map(|(speaker, _)| speaker.as_str()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sample_data;
+
+    #[test]
+    fn rollups_follow_time_order() {
+        let sentiment = SentimentModel::new(sample_data::get_sample_custom_word_dict());
+        // out of order in the transcript, arousal rises 5.53, 6.51, 7.24 by timestamp
+        let transcript = [
+            ("b", "betrayed", 30),
+            ("a", "abduction", 10),
+            ("a", "hello", 10),
+            ("b", "bees", 20),
+        ];
+        let conversation_sentiment = get_conversation_sentiment(&transcript, &sentiment);
+
+        let indices: Vec<usize> = conversation_sentiment.utterances.iter().map(|utterance| utterance.index).collect();
+        assert_eq!(indices, vec![1, 2, 3, 0]);
+        assert_eq!(conversation_sentiment.conversation.num_utterances, 4);
+        assert_eq!(conversation_sentiment.conversation.num_scored, 3);
+        let trend = conversation_sentiment.conversation.arousal_trend.unwrap();
+        assert!((trend - (7.24 - 5.53) / 2.).abs() < 1e-9);
+        assert!(conversation_sentiment.conversation.is_escalating);
+
+        // two scored utterances are too few for a trend
+        assert_eq!(conversation_sentiment.speakers["b"].arousal_trend, None);
+        assert!(conversation_sentiment.get_escalating_speakers().is_empty());
+        assert_eq!(conversation_sentiment.speakers["a"].arousal.unwrap().mean, 5.53);
+
+        let most_negative: Vec<&str> = conversation_sentiment.get_most_negative_utterances(5).iter().map(|utterance| utterance.utterance.as_str()).collect();
+        assert_eq!(most_negative, vec!["betrayed", "abduction", "bees"]);
+    }
+
+    #[test]
+    fn empty_transcript() {
+        let sentiment = SentimentModel::new(sample_data::get_sample_custom_word_dict());
+        let conversation_sentiment = get_conversation_sentiment::<&str, &str>(&[], &sentiment);
+        assert!(conversation_sentiment.speakers.is_empty());
+        assert_eq!(conversation_sentiment.conversation.valence, None);
+        assert!(!conversation_sentiment.conversation.is_escalating);
+        assert!(conversation_sentiment.get_most_negative_utterances(3).is_empty());
+    }
+}
@@ -0,0 +1,329 @@
+//! Module containing heuristic pronoun resolution, linking pronouns to the entities they most likely
+//! refer to so entity-level analysis (such as sentiment about a company) also counts pronoun mentions.
+//!
+//! Entities are registered by the caller and/or detected as runs of capitalized words. Each pronoun is
+//! resolved to the most recent earlier mention that agrees with it in gender and number, within a
+//! limited number of sentences. This is deliberately simple and will make mistakes, especially with
+//! several people of unknown gender in the same passage.
+
+use std::collections::BTreeMap;
+
+use serde::{Serialize, Deserialize};
+
+use crate::token::{self, StopWords, TokenSpan};
+
+/// Titles that continue a capitalized name across the period that follows them.
+const TITLES: [&str; 6] = ["mr", "mrs", "ms", "miss", "dr", "prof"];
+/// Final words that mark a detected entity as an organization.
+const ORGANIZATION_SUFFIXES: [&str; 9] = ["inc", "corp", "corporation", "ltd", "llc", "co", "company", "group", "plc"];
+
+/// Grammatical gender of an entity or pronoun.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Gender {
+    Masculine,
+    Feminine,
+    /// Things and organizations
+    Neuter,
+    Unknown,
+}
+
+/// Grammatical number of an entity or pronoun.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Number {
+    Singular,
+    Plural,
+}
+
+/// Struct for holding an entity name and its agreement features.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Entity {
+    pub name: String,
+    pub gender: Gender,
+    pub number: Number,
+}
+
+/// Struct for holding a mention of an entity found by [`PronounResolver::resolve`], either by name or by a pronoun.
+///
+/// `start` and `end` are byte offsets of the mention in the resolved text.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Mention {
+    /// The mention as it appears in the text
+    pub text: String,
+    /// Name of the entity the mention refers to
+    pub entity: String,
+    pub is_pronoun: bool,
+    /// Index of the sentence containing the mention
+    pub sentence: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Heuristic pronoun resolver based on recency and gender/number agreement.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::coref::{Gender, Number, PronounResolver};
+///
+/// let resolver = PronounResolver::new().entity("Acme", Gender::Neuter, Number::Singular);
+/// let text = "Mrs. Dalloway reviewed Acme. She said its service was slow.";
+/// let mentions = resolver.resolve(text);
+/// let resolved: Vec<(&str, &str)> = mentions.iter().map(|mention| (mention.text.as_str(), mention.entity.as_str())).collect();
+///
+/// assert_eq!(resolved, vec![("Mrs. Dalloway", "Mrs. Dalloway"), ("Acme", "Acme"), ("She", "Mrs. Dalloway"), ("its", "Acme")]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct PronounResolver {
+    entities: Vec<(Vec<String>, Entity)>,
+    detect_entities: bool,
+    max_sentence_distance: usize,
+}
+
+impl Default for PronounResolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PronounResolver {
+    /// Creates new instance of PronounResolver that detects entities and resolves pronouns up to 2 sentences back.
+    pub fn new() -> Self {
+        PronounResolver {
+            entities: vec![],
+            detect_entities: true,
+            max_sentence_distance: 2,
+        }
+    }
+
+    /// Registers an entity to be matched by `name`, case-insensitively on whole tokens.
+    /// Registered entities take precedence over detected ones.
+    pub fn entity(mut self, name: impl ToString, gender: Gender, number: Number) -> Self {
+        let name = name.to_string();
+        let tokens = token::tokenize_sentence(&name);
+        if !tokens.is_empty() {
+            self.entities.push((tokens, Entity { name, gender, number }));
+        }
+        self
+    }
+
+    /// Sets whether runs of capitalized words are detected as entities. Titles such as "Mrs." mark detected
+    /// entities as masculine or feminine, and suffixes such as "Inc" mark them as organizations.
+    pub fn detect_entities(mut self, detect_entities: bool) -> Self {
+        self.detect_entities = detect_entities;
+        self
+    }
+
+    /// Sets how many sentences back a pronoun's antecedent may be, where 0 only allows the same sentence.
+    pub fn max_sentence_distance(mut self, max_sentence_distance: usize) -> Self {
+        self.max_sentence_distance = max_sentence_distance;
+        self
+    }
+
+    /// Gets every entity mention in `text` in order, with pronouns resolved to their antecedent where one
+    /// agrees with them. Unresolved pronouns are left out.
+    pub fn resolve(&self, text: &str) -> Vec<Mention> {
+        let spans = token::tokenize_sentence_with_spans(text);
+        let sentences = sentence_indices(text, &spans);
+        let stop_words = StopWords::from(token::get_stop_words());
+        let mut mentions: Vec<Mention> = vec![];
+        let mut detected: Vec<Entity> = vec![];
+        let mut known: Vec<Entity> = vec![];
+        // (entity index into `known`, sentence) of every mention so far, for resolving pronouns
+        let mut history: Vec<(usize, usize)> = vec![];
+
+        let mut index = 0;
+        while index < spans.len() {
+            if let Some((gender, number)) = pronoun_features(&spans[index].token) {
+                let antecedent = history.iter().rev()
+                    .take_while(|(_, sentence)| sentences[index] - sentence <= self.max_sentence_distance)
+                    .find(|(entity_index, _)| agrees(&known[*entity_index], gender, number));
+                if let Some(&(entity_index, _)) = antecedent {
+                    mentions.push(self.mention(text, &spans[index..=index], &known[entity_index].name, true, sentences[index]));
+                    history.push((entity_index, sentences[index]));
+                }
+                index += 1;
+                continue;
+            }
+
+            let matched = self.match_registered(&spans[index..])
+                .or_else(|| if self.detect_entities { detect(text, &spans[index..], &sentences[index..], &detected, &stop_words) } else { None });
+            let Some((length, entity)) = matched else {
+                index += 1;
+                continue;
+            };
+            if !self.entities.iter().any(|(_, registered)| registered.name == entity.name) && !detected.contains(&entity) {
+                detected.push(entity.clone());
+            }
+            let entity_index = known.iter().position(|other| *other == entity).unwrap_or_else(|| {
+                known.push(entity.clone());
+                known.len() - 1
+            });
+            mentions.push(self.mention(text, &spans[index..index + length], &entity.name, false, sentences[index]));
+            history.push((entity_index, sentences[index]));
+            index += length;
+        }
+        mentions
+    }
+
+    /// Gets the sentences of `text` that mention each entity by name or by a resolved pronoun, for
+    /// entity-level analysis such as scoring them with a [`SentimentModel`](crate::sentiment::SentimentModel).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rnltk::coref::PronounResolver;
+    ///
+    /// let text = "Globex Inc posted record losses. It blamed the weather. Analysts were unconvinced.";
+    /// let entity_sentences = PronounResolver::new().get_entity_sentences(text);
+    ///
+    /// assert_eq!(entity_sentences["Globex Inc"], vec!["Globex Inc posted record losses.", "It blamed the weather."]);
+    /// ```
+    pub fn get_entity_sentences(&self, text: &str) -> BTreeMap<String, Vec<String>> {
+        let bounds = token::sentence_bounds(text);
+        let mut entity_sentences: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for mention in self.resolve(text) {
+            let Some(&(start, end)) = bounds.iter().find(|(start, end)| *start <= mention.start && mention.start < *end) else {
+                continue;
+            };
+            let sentences = entity_sentences.entry(mention.entity).or_default();
+            if sentences.last().map(|sentence| sentence.as_str()) != Some(&text[start..end]) {
+                sentences.push(text[start..end].to_string());
+            }
+        }
+        entity_sentences
+    }
+
+    fn mention(&self, text: &str, spans: &[TokenSpan], entity: &str, is_pronoun: bool, sentence: usize) -> Mention {
+        let (start, end) = (spans[0].start, spans[spans.len() - 1].end);
+        Mention {
+            text: text[start..end].to_string(),
+            entity: entity.to_string(),
+            is_pronoun,
+            sentence,
+            start,
+            end,
+        }
+    }
+
+    fn match_registered(&self, spans: &[TokenSpan]) -> Option<(usize, Entity)> {
+        self.entities.iter()
+            .filter(|(tokens, _)| tokens.len() <= spans.len() && tokens.iter().zip(spans).all(|(token, span)| *token == span.token))
+            .max_by_key(|(tokens, _)| tokens.len())
+            .map(|(tokens, entity)| (tokens.len(), entity.clone()))
+    }
+}
+
+// sentence index of every span, where a sentence ending in a title such as "Mr." continues into the next one
+fn sentence_indices(text: &str, spans: &[TokenSpan]) -> Vec<usize> {
+    let bounds = token::sentence_bounds(text);
+    let mut sentence = 0;
+    let mut bound_index = 0;
+    spans.iter().enumerate().map(|(index, span)| {
+        while bound_index + 1 < bounds.len() && span.start >= bounds[bound_index].1 {
+            bound_index += 1;
+            let follows_title = index > 0 && TITLES.contains(&spans[index - 1].token.as_str());
+            if !follows_title {
+                sentence += 1;
+            }
+        }
+        sentence
+    }).collect()
+}
+
+fn pronoun_features(token: &str) -> Option<(Gender, Number)> {
+    match token {
+        "he" | "him" | "his" | "himself" => Some((Gender::Masculine, Number::Singular)),
+        "she" | "her" | "hers" | "herself" => Some((Gender::Feminine, Number::Singular)),
+        "it" | "its" | "itself" => Some((Gender::Neuter, Number::Singular)),
+        "they" | "them" | "their" | "theirs" | "themselves" => Some((Gender::Unknown, Number::Plural)),
+        _ => None,
+    }
+}
+
+fn agrees(entity: &Entity, gender: Gender, number: Number) -> bool {
+    match (number, entity.number) {
+        // organizations are often referred to as "they"
+        (Number::Plural, Number::Singular) => entity.gender == Gender::Neuter,
+        (Number::Plural, Number::Plural) => true,
+        (Number::Singular, Number::Plural) => false,
+        (Number::Singular, Number::Singular) => entity.gender == gender || entity.gender == Gender::Unknown,
+    }
+}
+
+fn is_capitalized(text: &str, span: &TokenSpan) -> bool {
+    text[span.start..span.end].chars().next().is_some_and(char::is_uppercase)
+}
+
+// detects a run of capitalized words in the same sentence at the start of `spans`, skipping leading stop words
+fn detect(text: &str, spans: &[TokenSpan], sentences: &[usize], detected: &[Entity], stop_words: &StopWords) -> Option<(usize, Entity)> {
+    let length = spans.iter().zip(sentences)
+        .take_while(|(span, sentence)| is_capitalized(text, span) && **sentence == sentences[0])
+        .count();
+    if length == 0 || stop_words.contains(&spans[0].token) {
+        return None;
+    }
+    let run = &spans[..length];
+    let name = text[run[0].start..run[length - 1].end].to_string();
+
+    // a later single word like "Smith" refers back to an earlier "Mrs. Smith" or "Smith Corp"
+    if length == 1 {
+        let word = &run[0].token;
+        let earlier = detected.iter().find(|entity| {
+            let tokens = token::tokenize_sentence(&entity.name);
+            tokens.len() > 1 && (tokens.last() == Some(word) || tokens.iter().find(|token| !TITLES.contains(&token.as_str())) == Some(word))
+        });
+        if let Some(entity) = earlier {
+            return Some((length, entity.clone()));
+        }
+    }
+
+    let gender = match run[0].token.as_str() {
+        "mr" => Gender::Masculine,
+        "mrs" | "ms" | "miss" => Gender::Feminine,
+        _ if length > 1 && ORGANIZATION_SUFFIXES.contains(&run[length - 1].token.as_str()) => Gender::Neuter,
+        _ => Gender::Unknown,
+    };
+    Some((length, Entity { name, gender, number: Number::Singular }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resolved(mentions: &[Mention]) -> Vec<(&str, &str)> {
+        mentions.iter().filter(|mention| mention.is_pronoun).map(|mention| (mention.text.as_str(), mention.entity.as_str())).collect()
+    }
+
+    #[test]
+    fn gender_agreement_skips_recent_mismatch() {
+        let mentions = PronounResolver::new().resolve("Mr. Darcy met Miss Bennet. He bowed.");
+        assert_eq!(resolved(&mentions), vec![("He", "Mr. Darcy")]);
+    }
+
+    #[test]
+    fn surname_refers_back() {
+        let mentions = PronounResolver::new().resolve("Mrs. Smith arrived. Smith sat down.");
+        assert_eq!(mentions[1].entity, "Mrs. Smith");
+        assert_eq!(mentions[1].text, "Smith");
+    }
+
+    #[test]
+    fn distant_antecedents_are_ignored() {
+        let text = "Initech Corp filed. The market fell. The shares fell. The bonds fell. It waited.";
+        assert!(resolved(&PronounResolver::new().resolve(text)).is_empty());
+        let resolver = PronounResolver::new().max_sentence_distance(4);
+        assert_eq!(resolved(&resolver.resolve(text)), vec![("It", "Initech Corp")]);
+    }
+
+    #[test]
+    fn organizations_can_be_they() {
+        let resolver = PronounResolver::new().detect_entities(false).entity("Acme", Gender::Neuter, Number::Singular);
+        let mentions = resolver.resolve("Acme announced layoffs and they apologized.");
+        assert_eq!(resolved(&mentions), vec![("they", "Acme")]);
+    }
+
+    #[test]
+    fn sentence_initial_stop_words_are_not_entities() {
+        assert!(PronounResolver::new().resolve("The weather was nice. It rained later.").is_empty());
+    }
+}
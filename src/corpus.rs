@@ -0,0 +1,455 @@
+//! Module containing corpus ingestion, reading and tokenizing documents on multiple threads
+//! while keeping them in their original order.
+//!
+//! Document order is deterministic regardless of the level of parallelism, so the column
+//! indices of matrices built from a [`Corpus`] are reproducible between runs.
+//!
+//! Every document is assigned a [`DocId`] at ingestion. Unlike positional indices, ids are kept
+//! when documents are filtered out, so results can always be traced back to their source document.
+
+use std::collections::BTreeSet;
+use std::fmt;
+use std::fs;
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::thread;
+
+use nalgebra::DMatrix;
+use serde::{Serialize, Deserialize};
+
+use crate::cancel::CancellationToken;
+use crate::document::DocumentTermFrequencies;
+use crate::error::RnltkError;
+use crate::token;
+use crate::trace::stage_span;
+
+/// Stable identifier of a document, assigned in ingestion order when a [`Corpus`] is created.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DocId(pub u64);
+
+impl fmt::Display for DocId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Struct for holding a single ingested document.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CorpusDocument {
+    pub id: DocId,
+    /// The file the document was read from, if it was read from disk
+    pub path: Option<PathBuf>,
+    pub text: String,
+    pub sentences: Vec<String>,
+    pub tokens: Vec<String>,
+}
+
+impl CorpusDocument {
+    fn from_text(path: Option<PathBuf>, text: String) -> Self {
+        let sentences = token::tokenize_into_sentences(&text);
+        let tokens = sentences.iter().flat_map(|sentence| token::tokenize_sentence(sentence)).collect();
+        CorpusDocument {
+            id: DocId(0),
+            path,
+            text,
+            sentences,
+            tokens,
+        }
+    }
+}
+
+/// Struct for holding tokenized documents in ingestion order.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct Corpus {
+    documents: Vec<CorpusDocument>,
+}
+
+impl Corpus {
+    fn with_sequential_ids(mut documents: Vec<CorpusDocument>) -> Self {
+        for (index, document) in documents.iter_mut().enumerate() {
+            document.id = DocId(index as u64);
+        }
+        Corpus {
+            documents,
+        }
+    }
+
+    /// Creates new instance of Corpus from already tokenized `documents`, skipping internal
+    /// tokenization so custom tokenizers and external taggers can be used with the matrix and
+    /// similarity layers. Each document's text is its tokens joined by spaces, kept as a single sentence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rnltk::corpus::Corpus;
+    ///
+    /// let corpus = Corpus::from_tokens(vec![
+    ///     vec!["New York".to_string(), "is".to_string(), "big".to_string()],
+    ///     vec!["York".to_string(), "is".to_string(), "old".to_string()],
+    /// ]);
+    /// let term_frequencies = corpus.get_document_term_frequencies().unwrap();
+    ///
+    /// assert_eq!(corpus.get_terms(), vec!["New York", "York", "big", "is", "old"]);
+    /// assert_eq!(term_frequencies.num_documents(), 2);
+    /// ```
+    pub fn from_tokens(documents: Vec<Vec<String>>) -> Self {
+        let documents = documents.into_iter().map(|tokens| {
+            let text = tokens.join(" ");
+            let sentences = if text.is_empty() { vec![] } else { vec![text.clone()] };
+            CorpusDocument {
+                id: DocId(0),
+                path: None,
+                text,
+                sentences,
+                tokens,
+            }
+        }).collect();
+        Corpus::with_sequential_ids(documents)
+    }
+
+    /// Gets the documents in ingestion order.
+    pub fn get_documents(&self) -> &[CorpusDocument] {
+        &self.documents
+    }
+
+    /// Gets the ids of the documents in order. These are the column labels of
+    /// [`Corpus::get_document_term_frequencies`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rnltk::corpus::{CorpusReader, DocId};
+    ///
+    /// let corpus = CorpusReader::new().read_texts(vec!["fear leads to anger", "anger leads to hatred"]).unwrap();
+    /// let term_frequencies = corpus.get_document_term_frequencies().unwrap();
+    /// let labeled_matrix = term_frequencies.with_labels(corpus.get_terms(), corpus.get_doc_ids()).unwrap();
+    ///
+    /// assert_eq!(corpus.get_doc_ids(), vec![DocId(0), DocId(1)]);
+    /// assert_eq!(labeled_matrix.get_column_labels(), ["0", "1"]);
+    /// ```
+    pub fn get_doc_ids(&self) -> Vec<DocId> {
+        self.documents.iter().map(|document| document.id).collect()
+    }
+
+    /// Gets the document with the given `id`, if it's still in the corpus.
+    pub fn get_document(&self, id: DocId) -> Option<&CorpusDocument> {
+        self.documents.binary_search_by_key(&id, |document| document.id).ok().map(|index| &self.documents[index])
+    }
+
+    /// Creates a new Corpus with only the documents matching `predicate`, keeping their ids.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rnltk::corpus::{CorpusReader, DocId};
+    ///
+    /// let corpus = CorpusReader::new().read_texts(vec!["Why hello there.", "Hi!", "General Kenobi!"]).unwrap();
+    /// let filtered = corpus.filter(|document| document.tokens.len() > 1);
+    ///
+    /// assert_eq!(filtered.get_doc_ids(), vec![DocId(0), DocId(2)]);
+    /// assert_eq!(filtered.get_document(DocId(2)).unwrap().tokens, vec!["general", "kenobi"]);
+    /// assert!(filtered.get_document(DocId(1)).is_none());
+    /// ```
+    pub fn filter(&self, predicate: impl Fn(&CorpusDocument) -> bool) -> Corpus {
+        Corpus {
+            documents: self.documents.iter().filter(|document| predicate(document)).cloned().collect(),
+        }
+    }
+
+    /// Gets the number of documents in the corpus.
+    pub fn len(&self) -> usize {
+        self.documents.len()
+    }
+
+    /// Checks if the corpus has no documents.
+    pub fn is_empty(&self) -> bool {
+        self.documents.is_empty()
+    }
+
+    /// Gets every distinct token in the corpus, in sorted order. These are the row labels
+    /// of [`Corpus::get_document_term_frequencies`].
+    pub fn get_terms(&self) -> Vec<String> {
+        self.documents.iter()
+            .flat_map(|document| document.tokens.iter().cloned())
+            .collect::<BTreeSet<String>>()
+            .into_iter()
+            .collect()
+    }
+
+    /// Gets the term frequencies of the corpus as a terms × documents [`DocumentTermFrequencies`],
+    /// where row `i` is term `i` of [`Corpus::get_terms`] and column `j` is document `j` of [`Corpus::get_doc_ids`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RnltkError::TermFrequenciesEmpty`] if the corpus has no documents or no tokens.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rnltk::corpus::CorpusReader;
+    ///
+    /// let corpus = CorpusReader::new().read_texts(vec!["fear leads to anger", "anger leads to hatred"]).unwrap();
+    /// let term_frequencies = corpus.get_document_term_frequencies().unwrap();
+    ///
+    /// assert_eq!(corpus.get_terms(), vec!["anger", "fear", "hatred", "leads", "to"]);
+    /// assert_eq!(term_frequencies.num_terms(), 5);
+    /// assert_eq!(term_frequencies.num_documents(), 2);
+    /// ```
+    pub fn get_document_term_frequencies(&self) -> Result<DocumentTermFrequencies, RnltkError> {
+        let terms = self.get_terms();
+        let mut term_frequencies = DMatrix::zeros(terms.len(), self.documents.len());
+        for (column, document) in self.documents.iter().enumerate() {
+            for token in &document.tokens {
+                if let Ok(row) = terms.binary_search(token) {
+                    term_frequencies[(row, column)] += 1.;
+                }
+            }
+        }
+        DocumentTermFrequencies::from_term_rows(term_frequencies)
+    }
+}
+
+/// Borrowed selection of the documents in a [`Corpus`], such as a balanced subsample from
+/// [`sampling::balance_classes`](crate::sampling::balance_classes), without copying any text.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CorpusView<'a> {
+    corpus: &'a Corpus,
+    ids: Vec<DocId>,
+}
+
+impl<'a> CorpusView<'a> {
+    /// Creates new instance of CorpusView with the documents of `corpus` with the given `ids`.
+    /// Ids not in the corpus are skipped, and documents stay in corpus order.
+    pub fn new(corpus: &'a Corpus, ids: impl IntoIterator<Item = DocId>) -> Self {
+        let mut ids: Vec<DocId> = ids.into_iter().filter(|id| corpus.get_document(*id).is_some()).collect();
+        ids.sort_unstable();
+        ids.dedup();
+        CorpusView {
+            corpus,
+            ids,
+        }
+    }
+
+    /// Gets the documents in the view, in corpus order.
+    pub fn get_documents(&self) -> Vec<&'a CorpusDocument> {
+        self.ids.iter().filter_map(|id| self.corpus.get_document(*id)).collect()
+    }
+
+    /// Gets the ids of the documents in the view, in corpus order.
+    pub fn get_doc_ids(&self) -> &[DocId] {
+        &self.ids
+    }
+
+    /// Gets the number of documents in the view.
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    /// Checks if the view has no documents.
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    /// Creates a new Corpus with copies of the documents in the view, keeping their ids.
+    pub fn to_corpus(&self) -> Corpus {
+        Corpus {
+            documents: self.get_documents().into_iter().cloned().collect(),
+        }
+    }
+}
+
+/// Reads and tokenizes documents on a pool of scoped threads.
+#[derive(Debug, Clone)]
+pub struct CorpusReader {
+    parallelism: usize,
+    cancellation_token: Option<CancellationToken>,
+}
+
+impl Default for CorpusReader {
+    fn default() -> Self {
+        CorpusReader {
+            parallelism: thread::available_parallelism().map(NonZeroUsize::get).unwrap_or(1),
+            cancellation_token: None,
+        }
+    }
+}
+
+impl CorpusReader {
+    /// Creates new instance of CorpusReader using one thread per available CPU.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum number of threads used for reading and tokenizing. Values below 1 are treated as 1.
+    pub fn parallelism(mut self, parallelism: usize) -> Self {
+        self.parallelism = parallelism.max(1);
+        self
+    }
+
+    /// Sets a [`CancellationToken`] that is checked before every document is read and tokenized.
+    pub fn cancellation_token(mut self, cancellation_token: CancellationToken) -> Self {
+        self.cancellation_token = Some(cancellation_token);
+        self
+    }
+
+    fn check_cancelled(&self) -> Result<(), RnltkError> {
+        match &self.cancellation_token {
+            Some(cancellation_token) => cancellation_token.check(),
+            None => Ok(()),
+        }
+    }
+
+    /// Tokenizes already loaded `texts` into a [`Corpus`], keeping their order.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RnltkError::Cancelled`] if the [`CancellationToken`] was cancelled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rnltk::corpus::CorpusReader;
+    ///
+    /// let corpus = CorpusReader::new()
+    ///     .parallelism(2)
+    ///     .read_texts(vec!["Why hello there.", "General Kenobi!", "You are a bold one."])
+    ///     .unwrap();
+    ///
+    /// assert_eq!(corpus.len(), 3);
+    /// assert_eq!(corpus.get_documents()[1].tokens, vec!["general", "kenobi"]);
+    /// ```
+    pub fn read_texts<S: ToString + Sync>(&self, texts: Vec<S>) -> Result<Corpus, RnltkError> {
+        stage_span!("corpus_ingest", documents = texts.len(), parallelism = self.parallelism);
+        let documents = parallel_map(&texts, self.parallelism, |text| {
+            self.check_cancelled()?;
+            Ok(CorpusDocument::from_text(None, text.to_string()))
+        }).into_iter().collect::<Result<Vec<CorpusDocument>, RnltkError>>()?;
+        Ok(Corpus::with_sequential_ids(documents))
+    }
+
+    /// Reads and tokenizes the files at `paths` into a [`Corpus`], keeping the order of `paths`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RnltkError::CorpusIo`] for the first path, in `paths` order, that could not be read.
+    /// 
+    /// Returns [`RnltkError::Cancelled`] if the [`CancellationToken`] was cancelled.
+    pub fn read_paths<P: AsRef<Path> + Sync>(&self, paths: &[P]) -> Result<Corpus, RnltkError> {
+        stage_span!("corpus_ingest", documents = paths.len(), parallelism = self.parallelism);
+        let documents = parallel_map(paths, self.parallelism, |path| {
+            self.check_cancelled()?;
+            let path = path.as_ref();
+            fs::read_to_string(path)
+                .map(|text| CorpusDocument::from_text(Some(path.to_path_buf()), text))
+                .map_err(|error| RnltkError::CorpusIo(format!("{}: {}", path.display(), error)))
+        }).into_iter().collect::<Result<Vec<CorpusDocument>, RnltkError>>()?;
+        Ok(Corpus::with_sequential_ids(documents))
+    }
+
+    /// Reads and tokenizes every file directly inside `directory` into a [`Corpus`].
+    /// Files are sorted by path so the document order does not depend on the file system.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RnltkError::CorpusIo`] if the directory or any of its files could not be read.
+    /// 
+    /// Returns [`RnltkError::Cancelled`] if the [`CancellationToken`] was cancelled.
+    pub fn read_dir(&self, directory: impl AsRef<Path>) -> Result<Corpus, RnltkError> {
+        let directory = directory.as_ref();
+        let io_error = |error: std::io::Error| RnltkError::CorpusIo(format!("{}: {}", directory.display(), error));
+        let mut paths: Vec<PathBuf> = vec![];
+        for entry in fs::read_dir(directory).map_err(io_error)? {
+            let path = entry.map_err(io_error)?.path();
+            if path.is_file() {
+                paths.push(path);
+            }
+        }
+        paths.sort();
+        self.read_paths(&paths)
+    }
+}
+
+/// Applies `function` to every item on up to `parallelism` scoped threads, returning the
+/// results in the same order as `items`.
+pub(crate) fn parallel_map<T, R, F>(items: &[T], parallelism: usize, function: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let parallelism = parallelism.max(1).min(items.len());
+    if parallelism <= 1 {
+        return items.iter().map(&function).collect();
+    }
+
+    let chunk_size = items.len().div_ceil(parallelism);
+    let function = &function;
+    thread::scope(|scope| {
+        let handles: Vec<_> = items.chunks(chunk_size)
+            .map(|chunk| scope.spawn(move || chunk.iter().map(function).collect::<Vec<R>>()))
+            .collect();
+        handles.into_iter()
+            .flat_map(|handle| handle.join().expect("Corpus worker thread panicked"))
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn order_is_independent_of_parallelism() {
+        let texts: Vec<String> = (0..50).map(|index| format!("document number {}", index)).collect();
+        let sequential = CorpusReader::new().parallelism(1).read_texts(texts.clone()).unwrap();
+        let parallel = CorpusReader::new().parallelism(7).read_texts(texts).unwrap();
+        assert_eq!(sequential, parallel);
+        assert_eq!(parallel.get_documents()[42].tokens, vec!["document", "number", "42"]);
+    }
+
+    #[test]
+    fn read_paths_in_given_order() {
+        let corpus = CorpusReader::new().read_paths(&["test_data/test.json", "Cargo.toml"]).unwrap();
+        assert_eq!(corpus.get_documents()[0].path, Some(PathBuf::from("test_data/test.json")));
+        assert_eq!(corpus.get_documents()[1].path, Some(PathBuf::from("Cargo.toml")));
+    }
+
+    #[test]
+    fn ids_follow_ingestion_order() {
+        let corpus = CorpusReader::new().parallelism(3).read_texts(vec!["a b", "c", "d e", "f"]).unwrap();
+        assert_eq!(corpus.get_doc_ids(), (0..4).map(DocId).collect::<Vec<DocId>>());
+        let filtered = corpus.filter(|document| document.tokens.len() == 1);
+        assert_eq!(filtered.get_doc_ids(), vec![DocId(1), DocId(3)]);
+        assert_eq!(filtered.get_document(DocId(3)).unwrap().text, "f");
+    }
+
+    #[test]
+    fn read_missing_path() {
+        let error = CorpusReader::new().read_paths(&["does/not/exist.txt"]).unwrap_err();
+        assert!(matches!(error, RnltkError::CorpusIo(message) if message.starts_with("does/not/exist.txt")));
+    }
+
+    #[test]
+    fn cancelled_ingestion() {
+        let token = CancellationToken::new();
+        token.cancel();
+        let reader = CorpusReader::new().parallelism(2).cancellation_token(token);
+        assert_eq!(reader.read_texts(vec!["a", "b", "c"]).unwrap_err(), RnltkError::Cancelled);
+        assert_eq!(reader.read_paths(&["Cargo.toml"]).unwrap_err(), RnltkError::Cancelled);
+    }
+
+    #[test]
+    fn pre_tokenized_tokens_are_kept_verbatim() {
+        let corpus = Corpus::from_tokens(vec![vec!["Hello".to_string(), "WORLD".to_string()], vec![]]);
+        assert_eq!(corpus.get_documents()[0].tokens, vec!["Hello", "WORLD"]);
+        assert_eq!(corpus.get_documents()[0].text, "Hello WORLD");
+        assert!(corpus.get_documents()[1].sentences.is_empty());
+    }
+
+    #[test]
+    fn empty_corpus_term_frequencies() {
+        let corpus = CorpusReader::new().read_texts(Vec::<String>::new()).unwrap();
+        assert!(corpus.is_empty());
+        assert_eq!(corpus.get_document_term_frequencies().unwrap_err(), RnltkError::TermFrequenciesEmpty);
+    }
+}
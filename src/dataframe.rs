@@ -0,0 +1,264 @@
+//! Module containing conversions between RNLTK results and polars [`DataFrame`]s.
+//!
+//! Requires the `polars` feature. Frequency tables become wide frames with a `document` index
+//! column followed by one column per term, and sentiment scores and document reports become one
+//! row per document.
+
+use std::collections::BTreeMap;
+
+use polars::prelude::{Column, DataFrame, DataType};
+
+use crate::analysis::{ColumnarResults, DocumentReport, SentimentSummary};
+use crate::error::RnltkError;
+
+/// Name of the index column holding the position of each document.
+pub const DOCUMENT_COLUMN: &str = "document";
+
+/// Conversion of RNLTK results to a polars [`DataFrame`].
+pub trait ToPolars {
+    /// Converts the results to a [`DataFrame`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RnltkError::Interop`] if the frame could not be built.
+    fn to_polars(&self) -> Result<DataFrame, RnltkError>;
+}
+
+/// Conversion of a polars [`DataFrame`] back to RNLTK results.
+pub trait FromPolars: Sized {
+    /// Converts a [`DataFrame`] in the layout produced by [`ToPolars::to_polars`] back to results.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RnltkError::Interop`] if a required column is missing, has the wrong type, or contains nulls.
+    fn from_polars(data_frame: &DataFrame) -> Result<Self, RnltkError>;
+}
+
+fn interop_error(error: impl ToString) -> RnltkError {
+    RnltkError::Interop(error.to_string())
+}
+
+fn document_column(num_documents: usize) -> Column {
+    Column::new(DOCUMENT_COLUMN.into(), (0..num_documents as u64).collect::<Vec<u64>>())
+}
+
+fn f64_values(data_frame: &DataFrame, name: &str) -> Result<Vec<f64>, RnltkError> {
+    let column = data_frame.column(name).map_err(interop_error)?;
+    column_f64_values(column)
+}
+
+fn column_f64_values(column: &Column) -> Result<Vec<f64>, RnltkError> {
+    let column = column.cast(&DataType::Float64).map_err(interop_error)?;
+    column.f64().map_err(interop_error)?
+        .iter()
+        .map(|value| value.ok_or_else(|| RnltkError::Interop(format!("null value in column '{}'", column.name()))))
+        .collect()
+}
+
+/// Aligned term frequencies, like those from [`token::get_term_frequencies_from_sentences`](crate::token::get_term_frequencies_from_sentences).
+/// Terms missing from a document are filled with 0. Converting fails with [`RnltkError::Interop`] if a term is
+/// named [`DOCUMENT_COLUMN`], since its column would clash with the index column.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::BTreeMap;
+/// use rnltk::dataframe::{FromPolars, ToPolars};
+/// use rnltk::token;
+///
+/// let term_frequencies = token::get_term_frequencies_from_sentences(&["fear leads to anger", "anger leads to hatred"]);
+/// let data_frame = term_frequencies.to_polars().unwrap();
+///
+/// assert_eq!(data_frame.shape(), (2, 6));
+/// assert_eq!(Vec::<BTreeMap<String, f64>>::from_polars(&data_frame).unwrap(), term_frequencies);
+/// ```
+impl ToPolars for [BTreeMap<String, f64>] {
+    fn to_polars(&self) -> Result<DataFrame, RnltkError> {
+        let mut terms: Vec<&String> = self.iter().flat_map(|frequencies| frequencies.keys()).collect();
+        terms.sort_unstable();
+        terms.dedup();
+        if terms.binary_search_by(|term| term.as_str().cmp(DOCUMENT_COLUMN)).is_ok() {
+            return Err(RnltkError::Interop(format!("term '{DOCUMENT_COLUMN}' clashes with the document index column")));
+        }
+
+        let mut columns = vec![document_column(self.len())];
+        for term in terms {
+            let values: Vec<f64> = self.iter().map(|frequencies| frequencies.get(term).copied().unwrap_or(0.)).collect();
+            columns.push(Column::new(term.as_str().into(), values));
+        }
+        DataFrame::new(self.len(), columns).map_err(interop_error)
+    }
+}
+
+impl ToPolars for Vec<BTreeMap<String, f64>> {
+    fn to_polars(&self) -> Result<DataFrame, RnltkError> {
+        self.as_slice().to_polars()
+    }
+}
+
+/// Every column except [`DOCUMENT_COLUMN`] is read as a term, with its values cast to `f64`.
+impl FromPolars for Vec<BTreeMap<String, f64>> {
+    fn from_polars(data_frame: &DataFrame) -> Result<Self, RnltkError> {
+        let mut term_frequencies = vec![BTreeMap::new(); data_frame.height()];
+        for column in data_frame.columns() {
+            if column.name().as_str() == DOCUMENT_COLUMN {
+                continue;
+            }
+            for (frequencies, value) in term_frequencies.iter_mut().zip(column_f64_values(column)?) {
+                frequencies.insert(column.name().to_string(), value);
+            }
+        }
+        Ok(term_frequencies)
+    }
+}
+
+/// Per-document sentiment scores, with `document`, `valence`, `arousal`, and `description` columns.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::analysis::SentimentSummary;
+/// use rnltk::dataframe::{FromPolars, ToPolars};
+///
+/// let scores = vec![SentimentSummary { valence: 2.5, arousal: 6., description: "upset".to_string() }];
+/// let data_frame = scores.to_polars().unwrap();
+///
+/// assert_eq!(Vec::<SentimentSummary>::from_polars(&data_frame).unwrap(), scores);
+/// ```
+impl ToPolars for [SentimentSummary] {
+    fn to_polars(&self) -> Result<DataFrame, RnltkError> {
+        let columns = vec![
+            document_column(self.len()),
+            Column::new("valence".into(), self.iter().map(|score| score.valence).collect::<Vec<f64>>()),
+            Column::new("arousal".into(), self.iter().map(|score| score.arousal).collect::<Vec<f64>>()),
+            Column::new("description".into(), self.iter().map(|score| score.description.as_str()).collect::<Vec<&str>>()),
+        ];
+        DataFrame::new(self.len(), columns).map_err(interop_error)
+    }
+}
+
+impl ToPolars for Vec<SentimentSummary> {
+    fn to_polars(&self) -> Result<DataFrame, RnltkError> {
+        self.as_slice().to_polars()
+    }
+}
+
+impl FromPolars for Vec<SentimentSummary> {
+    fn from_polars(data_frame: &DataFrame) -> Result<Self, RnltkError> {
+        let valences = f64_values(data_frame, "valence")?;
+        let arousals = f64_values(data_frame, "arousal")?;
+        let descriptions = data_frame.column("description").map_err(interop_error)?.str().map_err(interop_error)?;
+
+        valences.into_iter().zip(arousals).zip(descriptions.iter()).map(|((valence, arousal), description)| {
+            let description = description.ok_or_else(|| RnltkError::Interop("null value in column 'description'".to_string()))?;
+            Ok(SentimentSummary {
+                valence,
+                arousal,
+                description: description.to_string(),
+            })
+        }).collect()
+    }
+}
+
+/// Flattened [`DocumentReport`] scores, one row per report. Sentiment columns are null for
+/// reports without sentiment.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::analysis;
+/// use rnltk::dataframe::ToPolars;
+///
+/// let reports = vec![analysis::analyze("Why hello there.", None), analysis::analyze("General Kenobi!", None)];
+/// let data_frame = reports.to_polars().unwrap();
+///
+/// assert_eq!(data_frame.height(), 2);
+/// assert_eq!(data_frame.column("num_tokens").unwrap().u64().unwrap().get(1), Some(2));
+/// ```
+impl ToPolars for [DocumentReport] {
+    fn to_polars(&self) -> Result<DataFrame, RnltkError> {
+        let sentiment = |field: fn(&SentimentSummary) -> f64| -> Vec<Option<f64>> {
+            self.iter().map(|report| report.sentiment.as_ref().map(field)).collect()
+        };
+        let columns = vec![
+            document_column(self.len()),
+            Column::new("num_sentences".into(), self.iter().map(|report| report.readability.num_sentences as u64).collect::<Vec<u64>>()),
+            Column::new("num_tokens".into(), self.iter().map(|report| report.tokens.len() as u64).collect::<Vec<u64>>()),
+            Column::new("valence".into(), sentiment(|score| score.valence)),
+            Column::new("arousal".into(), sentiment(|score| score.arousal)),
+            Column::new("description".into(), self.iter().map(|report| report.sentiment.as_ref().map(|score| score.description.as_str())).collect::<Vec<Option<&str>>>()),
+            Column::new("flesch_reading_ease".into(), self.iter().map(|report| report.readability.flesch_reading_ease).collect::<Vec<f64>>()),
+            Column::new("flesch_kincaid_grade".into(), self.iter().map(|report| report.readability.flesch_kincaid_grade).collect::<Vec<f64>>()),
+        ];
+        DataFrame::new(self.len(), columns).map_err(interop_error)
+    }
+}
+
+impl ToPolars for Vec<DocumentReport> {
+    fn to_polars(&self) -> Result<DataFrame, RnltkError> {
+        self.as_slice().to_polars()
+    }
+}
+
+/// Bulk analysis results from [`analysis::analyze_bulk`](crate::analysis::analyze_bulk), one row per document.
+/// Sentiment columns are null for documents without sentiment.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::analysis;
+/// use rnltk::dataframe::ToPolars;
+///
+/// let results = analysis::analyze_bulk(&["Why hello there.", "General Kenobi!"], None);
+/// let data_frame = results.to_polars().unwrap();
+///
+/// assert_eq!(data_frame.shape(), (2, 5));
+/// assert_eq!(data_frame.column("num_tokens").unwrap().u64().unwrap().get(1), Some(2));
+/// ```
+impl ToPolars for ColumnarResults {
+    fn to_polars(&self) -> Result<DataFrame, RnltkError> {
+        let columns = vec![
+            document_column(self.len()),
+            Column::new("num_sentences".into(), self.num_sentences.clone()),
+            Column::new("num_tokens".into(), self.num_tokens.clone()),
+            Column::new("valence".into(), self.valence.clone()),
+            Column::new("arousal".into(), self.arousal.clone()),
+        ];
+        DataFrame::new(self.len(), columns).map_err(interop_error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_column() {
+        let data_frame = DataFrame::new(1, vec![Column::new("valence".into(), vec![1.])]).unwrap();
+        assert!(matches!(Vec::<SentimentSummary>::from_polars(&data_frame), Err(RnltkError::Interop(_))));
+    }
+
+    #[test]
+    fn integer_term_columns_are_cast() {
+        let data_frame = DataFrame::new(2, vec![
+            Column::new(DOCUMENT_COLUMN.into(), vec![0u64, 1]),
+            Column::new("bees".into(), vec![2i64, 0]),
+        ]).unwrap();
+        let term_frequencies = Vec::<BTreeMap<String, f64>>::from_polars(&data_frame).unwrap();
+        assert_eq!(term_frequencies[0]["bees"], 2.);
+        assert_eq!(term_frequencies[1]["bees"], 0.);
+    }
+
+    #[test]
+    fn term_named_like_the_index_column() {
+        let term_frequencies = crate::token::get_term_frequencies_from_sentences(&["the document is long"]);
+        assert!(matches!(term_frequencies.to_polars(), Err(RnltkError::Interop(message)) if message.contains("document")));
+    }
+
+    #[test]
+    fn report_without_sentiment_has_nulls() {
+        let reports = vec![crate::analysis::analyze("Fear leads to anger.", None)];
+        let data_frame = reports.to_polars().unwrap();
+        assert_eq!(data_frame.column("valence").unwrap().null_count(), 1);
+    }
+}
@@ -0,0 +1,419 @@
+//! Module containing exact and near-duplicate detection for a [`Corpus`].
+//!
+//! Duplicates inflate the document frequency of the terms they share and so distort IDF,
+//! which makes deduplication a common step before building TF-IDF matrices.
+//!
+//! Exact duplicates are documents with identical tokens. Near duplicates are found over token shingles,
+//! either with MinHash signatures, using locality-sensitive hashing to find candidate pairs that are then
+//! checked against the exact Jaccard similarity of their shingles, or with SimHash fingerprints, whose
+//! candidate pairs share a block of bits and are then checked against their Hamming distance.
+//! All hashing is seeded with constants, so reports are reproducible between runs and platforms.
+
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+use serde::{Serialize, Deserialize};
+
+use crate::corpus::{Corpus, CorpusDocument, DocId};
+use crate::error::RnltkError;
+
+/// How [`dedup_report_configurable`] finds near-duplicate documents.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::corpus::{CorpusReader, DocId};
+/// use rnltk::dedup::{self, DedupConfig, NearDuplicateMethod};
+///
+/// let corpus = CorpusReader::new().read_texts(vec![
+///     "The quick brown fox jumps over the lazy dog near the old river bank today.",
+///     "The quick brown fox jumps over the lazy dog near the old river bank today and tomorrow.",
+///     "An entirely different document about sentiment lexicons.",
+/// ]).unwrap();
+/// let config = DedupConfig { method: NearDuplicateMethod::SimHash, max_hamming_distance: 10, ..DedupConfig::default() };
+/// let report = dedup::dedup_report_configurable(&corpus, config).unwrap();
+///
+/// assert_eq!(report.near_duplicates[0].canonical, DocId(1));
+/// assert_eq!(report.near_duplicates[0].duplicates, vec![DocId(0)]);
+/// assert!(report.near_duplicates[0].similarity > 0.8);
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NearDuplicateMethod {
+    /// MinHash signatures, linking documents whose shingles have a Jaccard similarity of at least `threshold`
+    #[default]
+    MinHash,
+    /// 64-bit SimHash fingerprints of the shingles, linking documents whose fingerprints differ in at most
+    /// `max_hamming_distance` bits. Fingerprints are smaller and faster to compare than MinHash signatures,
+    /// but only find documents that are very similar
+    SimHash,
+}
+
+/// Settings for [`dedup_report_configurable`].
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct DedupConfig {
+    /// Number of consecutive tokens in each shingle
+    pub shingle_size: usize,
+    /// How near duplicates are found
+    #[serde(default)]
+    pub method: NearDuplicateMethod,
+    /// Number of MinHash functions in each document signature
+    pub num_hashes: usize,
+    /// Number of locality-sensitive hashing bands the signature is split into. Must divide `num_hashes`,
+    /// and more bands find more candidate pairs at lower similarities
+    pub bands: usize,
+    /// Minimum Jaccard similarity of two documents' shingles for them to be near duplicates with MinHash
+    pub threshold: f64,
+    /// Maximum number of bits in which the fingerprints of two documents differ for them to be near duplicates
+    /// with SimHash. Must be below 64
+    #[serde(default = "default_max_hamming_distance")]
+    pub max_hamming_distance: u32,
+}
+
+fn default_max_hamming_distance() -> u32 {
+    3
+}
+
+impl Default for DedupConfig {
+    fn default() -> Self {
+        Self {
+            shingle_size: 3,
+            method: NearDuplicateMethod::MinHash,
+            num_hashes: 128,
+            bands: 32,
+            threshold: 0.8,
+            max_hamming_distance: default_max_hamming_distance(),
+        }
+    }
+}
+
+/// Group of duplicate documents, with the document suggested to keep.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DuplicateCluster {
+    /// The document suggested to keep
+    pub canonical: DocId,
+    /// The other documents in the group, in ascending order
+    pub duplicates: Vec<DocId>,
+    /// The lowest similarity among the pairs that joined the group, or 1 for exact duplicates. This is the
+    /// Jaccard similarity of their shingles with MinHash, and the share of matching fingerprint bits with SimHash
+    pub similarity: f64,
+}
+
+/// Duplicates found by [`dedup_report`].
+///
+/// Every document in an exact duplicate group is represented in near-duplicate detection
+/// by the group's canonical document only, so no document is in more than one near-duplicate cluster.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct DedupReport {
+    /// Groups of documents with identical tokens. The canonical document is the one ingested first
+    pub exact_duplicates: Vec<DuplicateCluster>,
+    /// Groups of similar documents. The canonical document is the one with the most tokens,
+    /// or the one ingested first if there's a tie
+    pub near_duplicates: Vec<DuplicateCluster>,
+}
+
+impl DedupReport {
+    /// Gets the ids of every document that isn't canonical, which can be dropped
+    /// to keep one document of every duplicate group.
+    pub fn get_redundant_doc_ids(&self) -> BTreeSet<DocId> {
+        self.exact_duplicates.iter()
+            .chain(&self.near_duplicates)
+            .flat_map(|cluster| cluster.duplicates.iter().copied())
+            .collect()
+    }
+}
+
+/// Finds exact and near-duplicate documents in `corpus` with the default [`DedupConfig`].
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::corpus::{CorpusReader, DocId};
+/// use rnltk::dedup;
+///
+/// let corpus = CorpusReader::new().read_texts(vec![
+///     "The quick brown fox jumps over the lazy dog near the old river bank today.",
+///     "the quick brown fox jumps over the lazy dog near the old river bank today",
+///     "The quick brown fox jumps over the lazy dog near the old river bank today and tomorrow.",
+///     "An entirely different document about sentiment lexicons.",
+/// ]).unwrap();
+/// let report = dedup::dedup_report(&corpus);
+///
+/// assert_eq!(report.exact_duplicates[0].canonical, DocId(0));
+/// assert_eq!(report.exact_duplicates[0].duplicates, vec![DocId(1)]);
+/// assert_eq!(report.near_duplicates[0].canonical, DocId(2));
+/// assert_eq!(report.near_duplicates[0].duplicates, vec![DocId(0)]);
+///
+/// let redundant = report.get_redundant_doc_ids();
+/// let deduplicated = corpus.filter(|document| !redundant.contains(&document.id));
+/// assert_eq!(deduplicated.get_doc_ids(), vec![DocId(2), DocId(3)]);
+/// ```
+pub fn dedup_report(corpus: &Corpus) -> DedupReport {
+    dedup_report_configurable(corpus, DedupConfig::default()).expect("Default dedup configuration is valid")
+}
+
+/// Finds exact and near-duplicate documents in `corpus` based on a given configuration.
+///
+/// # Errors
+///
+/// Returns [`RnltkError::InvalidDedupConfig`] if `shingle_size`, `num_hashes`, or `bands` is 0,
+/// if `bands` doesn't divide `num_hashes`, if `threshold` isn't between 0 and 1, or if
+/// `max_hamming_distance` is 64 or more.
+pub fn dedup_report_configurable(corpus: &Corpus, config: DedupConfig) -> Result<DedupReport, RnltkError> {
+    if config.shingle_size == 0 || config.num_hashes == 0 || config.bands == 0
+        || !config.num_hashes.is_multiple_of(config.bands) || !(0. ..=1.).contains(&config.threshold)
+        || config.max_hamming_distance >= u64::BITS {
+        return Err(RnltkError::InvalidDedupConfig);
+    }
+
+    let mut token_groups: BTreeMap<&[String], Vec<&CorpusDocument>> = BTreeMap::new();
+    for document in corpus.get_documents() {
+        token_groups.entry(&document.tokens).or_default().push(document);
+    }
+    let mut exact_duplicates = vec![];
+    let mut representatives: Vec<&CorpusDocument> = vec![];
+    for group in token_groups.into_values() {
+        representatives.push(group[0]);
+        if group.len() > 1 {
+            exact_duplicates.push(DuplicateCluster {
+                canonical: group[0].id,
+                duplicates: group[1..].iter().map(|document| document.id).collect(),
+                similarity: 1.,
+            });
+        }
+    }
+    representatives.sort_by_key(|document| document.id);
+    exact_duplicates.sort_by_key(|cluster| cluster.canonical);
+
+    Ok(DedupReport {
+        exact_duplicates,
+        near_duplicates: near_duplicate_clusters(&representatives, config),
+    })
+}
+
+fn near_duplicate_clusters(documents: &[&CorpusDocument], config: DedupConfig) -> Vec<DuplicateCluster> {
+    let shingles: Vec<HashSet<u64>> = documents.iter().map(|document| shingle_hashes(&document.tokens, config.shingle_size)).collect();
+    let linked = match config.method {
+        NearDuplicateMethod::MinHash => minhash_links(&shingles, config),
+        NearDuplicateMethod::SimHash => simhash_links(&shingles, config.max_hamming_distance),
+    };
+
+    let mut parents: Vec<usize> = (0..documents.len()).collect();
+    let mut similarities: BTreeMap<usize, f64> = BTreeMap::new();
+    for (first, second, _) in &linked {
+        let (first_root, second_root) = (find_root(&mut parents, *first), find_root(&mut parents, *second));
+        parents[first_root.max(second_root)] = first_root.min(second_root);
+    }
+    for (first, _, similarity) in linked {
+        let root = find_root(&mut parents, first);
+        let lowest = similarities.entry(root).or_insert(1.);
+        *lowest = lowest.min(similarity);
+    }
+
+    let mut clusters: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+    for index in 0..documents.len() {
+        let root = find_root(&mut parents, index);
+        clusters.entry(root).or_default().push(index);
+    }
+    clusters.into_iter()
+        .filter(|(_, members)| members.len() > 1)
+        .map(|(root, members)| {
+            let canonical = *members.iter()
+                .max_by(|first, second| documents[**first].tokens.len().cmp(&documents[**second].tokens.len()).then(second.cmp(first)))
+                .expect("Clusters have members");
+            DuplicateCluster {
+                canonical: documents[canonical].id,
+                duplicates: members.into_iter().filter(|member| *member != canonical).map(|member| documents[member].id).collect(),
+                similarity: similarities[&root],
+            }
+        })
+        .collect()
+}
+
+// pairs of documents whose shingles are similar enough, with their Jaccard similarity
+fn minhash_links(shingles: &[HashSet<u64>], config: DedupConfig) -> Vec<(usize, usize, f64)> {
+    let rows = config.num_hashes / config.bands;
+    let signatures: Vec<Vec<u64>> = shingles.iter().map(|shingles| minhash_signature(shingles, config.num_hashes)).collect();
+    let mut candidates: BTreeSet<(usize, usize)> = BTreeSet::new();
+    for band in 0..config.bands {
+        let mut buckets: BTreeMap<&[u64], Vec<usize>> = BTreeMap::new();
+        for (index, signature) in signatures.iter().enumerate().filter(|(index, _)| !shingles[*index].is_empty()) {
+            buckets.entry(&signature[band * rows..(band + 1) * rows]).or_default().push(index);
+        }
+        insert_bucket_pairs(&mut candidates, buckets.values());
+    }
+    candidates.into_iter()
+        .map(|(first, second)| (first, second, jaccard_similarity(&shingles[first], &shingles[second])))
+        .filter(|(_, _, similarity)| *similarity >= config.threshold)
+        .collect()
+}
+
+// pairs of documents whose fingerprints differ in at most `max_hamming_distance` bits, with the share of matching bits.
+// Splitting fingerprints into `max_hamming_distance + 1` blocks, such pairs have at least one identical block
+fn simhash_links(shingles: &[HashSet<u64>], max_hamming_distance: u32) -> Vec<(usize, usize, f64)> {
+    let fingerprints: Vec<u64> = shingles.iter().map(simhash_fingerprint).collect();
+    let blocks = max_hamming_distance + 1;
+    let mut candidates: BTreeSet<(usize, usize)> = BTreeSet::new();
+    for block in 0..blocks {
+        let (start, end) = (block * u64::BITS / blocks, (block + 1) * u64::BITS / blocks);
+        let mask = (u64::MAX >> (u64::BITS - (end - start))) << start;
+        let mut buckets: BTreeMap<u64, Vec<usize>> = BTreeMap::new();
+        for (index, fingerprint) in fingerprints.iter().enumerate().filter(|(index, _)| !shingles[*index].is_empty()) {
+            buckets.entry(fingerprint & mask).or_default().push(index);
+        }
+        insert_bucket_pairs(&mut candidates, buckets.values());
+    }
+    candidates.into_iter()
+        .map(|(first, second)| (first, second, (fingerprints[first] ^ fingerprints[second]).count_ones()))
+        .filter(|(_, _, distance)| *distance <= max_hamming_distance)
+        .map(|(first, second, distance)| (first, second, 1. - distance as f64 / u64::BITS as f64))
+        .collect()
+}
+
+// inserts every pair of documents sharing a bucket, lower index first
+fn insert_bucket_pairs<'a>(candidates: &mut BTreeSet<(usize, usize)>, buckets: impl Iterator<Item = &'a Vec<usize>>) {
+    for bucket in buckets {
+        for (position, first) in bucket.iter().enumerate() {
+            for second in &bucket[position + 1..] {
+                candidates.insert((*first, *second));
+            }
+        }
+    }
+}
+
+fn find_root(parents: &mut [usize], mut index: usize) -> usize {
+    while parents[index] != index {
+        parents[index] = parents[parents[index]];
+        index = parents[index];
+    }
+    index
+}
+
+// hashes of every run of `size` consecutive tokens, or of all tokens if there are fewer than `size`
+fn shingle_hashes(tokens: &[String], size: usize) -> HashSet<u64> {
+    if tokens.is_empty() {
+        return HashSet::new();
+    }
+    tokens.windows(size.min(tokens.len()))
+        .map(|shingle| {
+            shingle.iter().fold(FNV_OFFSET_BASIS, |hash, token| {
+                // separate tokens so ["ab", "c"] and ["a", "bc"] hash differently
+                token.bytes().chain([0xff]).fold(hash, |hash, byte| (hash ^ byte as u64).wrapping_mul(FNV_PRIME))
+            })
+        })
+        .collect()
+}
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+fn minhash_signature(shingles: &HashSet<u64>, num_hashes: usize) -> Vec<u64> {
+    (0..num_hashes as u64)
+        .map(|seed| shingles.iter().map(|shingle| splitmix64(shingle ^ splitmix64(seed))).min().unwrap_or(u64::MAX))
+        .collect()
+}
+
+// sets every bit that is set in most of the mixed shingle hashes
+fn simhash_fingerprint(shingles: &HashSet<u64>) -> u64 {
+    let mut votes = [0i64; u64::BITS as usize];
+    for hash in shingles.iter().map(|shingle| splitmix64(*shingle)) {
+        for (bit, vote) in votes.iter_mut().enumerate() {
+            *vote += if hash >> bit & 1 == 1 { 1 } else { -1 };
+        }
+    }
+    votes.iter().enumerate().filter(|(_, vote)| **vote > 0).fold(0, |fingerprint, (bit, _)| fingerprint | 1 << bit)
+}
+
+fn splitmix64(value: u64) -> u64 {
+    let mut value = value.wrapping_add(0x9e3779b97f4a7c15);
+    value = (value ^ (value >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    value = (value ^ (value >> 27)).wrapping_mul(0x94d049bb133111eb);
+    value ^ (value >> 31)
+}
+
+fn jaccard_similarity(first: &HashSet<u64>, second: &HashSet<u64>) -> f64 {
+    let intersection = first.intersection(second).count();
+    intersection as f64 / (first.len() + second.len() - intersection) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::corpus::CorpusReader;
+
+    #[test]
+    fn test_unrelated_documents_have_no_duplicates() {
+        let corpus = CorpusReader::new().read_texts(vec!["fear leads to anger", "anger leads to hatred", "hatred leads to suffering"]).unwrap();
+        let report = dedup_report(&corpus);
+        assert_eq!(report, DedupReport::default());
+        assert!(report.get_redundant_doc_ids().is_empty());
+    }
+
+    #[test]
+    fn test_near_duplicates_are_transitive() {
+        let base: Vec<String> = (0..40).map(|index| format!("word{index}")).collect();
+        let mut first_edit = base.clone();
+        first_edit[39] = "changed".to_string();
+        let mut second_edit = first_edit.clone();
+        second_edit[0] = "changed".to_string();
+        let corpus = Corpus::from_tokens(vec![vec!["unrelated".to_string()], first_edit, second_edit, base]);
+        let report = dedup_report(&corpus);
+        assert!(report.exact_duplicates.is_empty());
+        assert_eq!(report.near_duplicates.len(), 1);
+        assert_eq!(report.near_duplicates[0].canonical, DocId(1));
+        assert_eq!(report.near_duplicates[0].duplicates, vec![DocId(2), DocId(3)]);
+        assert!(report.near_duplicates[0].similarity >= 0.8 && report.near_duplicates[0].similarity < 1.);
+    }
+
+    #[test]
+    fn test_simhash_near_duplicates() {
+        let base: Vec<String> = (0..40).map(|index| format!("word{index}")).collect();
+        let mut edit = base.clone();
+        edit[20] = "changed".to_string();
+        let unrelated: Vec<String> = (0..40).map(|index| format!("other{index}")).collect();
+        let corpus = Corpus::from_tokens(vec![base.clone(), unrelated, edit.clone()]);
+
+        let config = |max_hamming_distance| DedupConfig { method: NearDuplicateMethod::SimHash, max_hamming_distance, ..DedupConfig::default() };
+        let distance = (simhash_fingerprint(&shingle_hashes(&base, 3)) ^ simhash_fingerprint(&shingle_hashes(&edit, 3))).count_ones();
+        assert!(distance > 0 && distance < 16);
+        let report = dedup_report_configurable(&corpus, config(distance)).unwrap();
+        assert_eq!(report.near_duplicates, vec![DuplicateCluster {
+            canonical: DocId(0),
+            duplicates: vec![DocId(2)],
+            similarity: 1. - distance as f64 / 64.,
+        }]);
+        assert!(dedup_report_configurable(&corpus, config(distance - 1)).unwrap().near_duplicates.is_empty());
+    }
+
+    #[test]
+    fn test_simhash_fingerprints_differ_in_few_bits_for_similar_shingles() {
+        let shingles: HashSet<u64> = (0..200).collect();
+        let mut similar = shingles.clone();
+        similar.remove(&0);
+        let different: HashSet<u64> = (1000..1200).collect();
+        let fingerprint = simhash_fingerprint(&shingles);
+        assert!((fingerprint ^ simhash_fingerprint(&similar)).count_ones() < 8);
+        assert!((fingerprint ^ simhash_fingerprint(&different)).count_ones() > 16);
+        assert_eq!(simhash_fingerprint(&HashSet::new()), 0);
+    }
+
+    #[test]
+    fn test_empty_documents_are_only_exact_duplicates() {
+        let corpus = Corpus::from_tokens(vec![vec![], vec!["hello".to_string()], vec![]]);
+        let report = dedup_report(&corpus);
+        assert_eq!(report.exact_duplicates, vec![DuplicateCluster { canonical: DocId(0), duplicates: vec![DocId(2)], similarity: 1. }]);
+        assert!(report.near_duplicates.is_empty());
+    }
+
+    #[test]
+    fn test_invalid_config() {
+        let corpus = Corpus::default();
+        for config in [
+            DedupConfig { shingle_size: 0, ..DedupConfig::default() },
+            DedupConfig { bands: 0, ..DedupConfig::default() },
+            DedupConfig { bands: 3, ..DedupConfig::default() },
+            DedupConfig { threshold: 1.5, ..DedupConfig::default() },
+            DedupConfig { method: NearDuplicateMethod::SimHash, max_hamming_distance: 64, ..DedupConfig::default() },
+        ] {
+            assert_eq!(dedup_report_configurable(&corpus, config).unwrap_err(), RnltkError::InvalidDedupConfig);
+        }
+    }
+}
@@ -0,0 +1,179 @@
+//! Module containing a dialogue act tagger for conversation transcripts, labeling utterances as
+//! greetings, questions, answers, thanks, and so on.
+//!
+//! Utterances are turned into features such as their leading words, terminal punctuation, sentence type,
+//! and length, and tagged with a [`NaiveBayesClassifier`]. [`DialogueActTagger::pretrained`] is trained on a
+//! small built-in set of customer service utterances, and any tagger can be trained further on labeled transcripts.
+
+use std::collections::BTreeMap;
+
+use serde::{Serialize, Deserialize};
+
+use crate::classify::{Classifier, NaiveBayesClassifier};
+use crate::error::RnltkError;
+use crate::sentence::{self, SentenceType};
+
+// (utterance, dialogue act) pairs the pretrained tagger learns from
+const TRAINING_UTTERANCES: [(&str, &str); 64] = [
+    ("Hi there!", "greeting"), ("Hello, how can I help you today?", "greeting"), ("Good morning.", "greeting"),
+    ("Hey, thanks for calling.", "greeting"), ("Hello!", "greeting"), ("Hi, this is Sam from support.", "greeting"),
+    ("Good afternoon, welcome.", "greeting"), ("Hey there", "greeting"),
+    ("Goodbye.", "closing"), ("Have a great day!", "closing"), ("Bye for now.", "closing"),
+    ("Talk to you later.", "closing"), ("Take care, goodbye!", "closing"), ("Have a nice evening.", "closing"),
+    ("See you soon.", "closing"), ("That's all for today, bye.", "closing"),
+    ("Where is my order?", "question"), ("Can you check the status of my refund?", "question"), ("How do I reset my password?", "question"),
+    ("What is your account number?", "question"), ("When will it arrive?", "question"), ("Is there a fee for that?", "question"),
+    ("Why was I charged twice?", "question"), ("Could you tell me the delivery date?", "question"),
+    ("Yes.", "answer"), ("No, it isn't.", "answer"), ("Yes, that's correct.", "answer"),
+    ("It's 4 5 6 7.", "answer"), ("Nope.", "answer"), ("Yeah, it is.", "answer"),
+    ("My account number is 12345.", "answer"), ("No, I haven't.", "answer"),
+    ("Thank you!", "thanks"), ("Thanks so much.", "thanks"), ("Thanks for your help.", "thanks"),
+    ("I really appreciate it.", "thanks"), ("Thank you very much for your patience.", "thanks"), ("Thanks a lot!", "thanks"),
+    ("Much appreciated.", "thanks"), ("Great, thank you.", "thanks"),
+    ("Okay.", "acknowledgement"), ("Sure.", "acknowledgement"), ("Got it.", "acknowledgement"),
+    ("I see.", "acknowledgement"), ("Alright, understood.", "acknowledgement"), ("Okay, sounds good.", "acknowledgement"),
+    ("Right.", "acknowledgement"), ("Mm-hmm.", "acknowledgement"),
+    ("Please send me a new one.", "request"), ("I'd like to cancel my subscription.", "request"), ("Please update my address.", "request"),
+    ("I want a refund.", "request"), ("Can you please escalate this?", "request"), ("Send me the invoice, please.", "request"),
+    ("I need to change my plan.", "request"), ("Cancel the order please.", "request"),
+    ("I'm sorry for the inconvenience.", "apology"), ("Sorry about that.", "apology"), ("My apologies for the delay.", "apology"),
+    ("I apologize for the confusion.", "apology"), ("Sorry, my mistake.", "apology"), ("We're sorry this happened.", "apology"),
+    ("I'm so sorry to hear that.", "apology"), ("Apologies for the wait.", "apology"),
+];
+
+/// Gets the features [`DialogueActTagger`] tags `utterance` by: its first word and first two words,
+/// terminal punctuation, [`SentenceType`], length in words, and every word.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::dialogue;
+///
+/// let features = dialogue::get_dialogue_act_features("Where is it?");
+///
+/// assert_eq!(features, vec!["first=where", "first2=where is", "end=?", "type=question", "length=short", "word=where", "word=is", "word=it"]);
+/// ```
+pub fn get_dialogue_act_features(utterance: &str) -> Vec<String> {
+    let words: Vec<String> = utterance.split_whitespace()
+        .map(|word| word.replace('\u{2019}', "'").trim_matches(|c: char| !c.is_alphanumeric()).to_lowercase())
+        .filter(|word| !word.is_empty())
+        .collect();
+    let trimmed = utterance.trim_end();
+    let end = match trimmed.chars().last() {
+        Some(c @ ('?' | '!' | '.')) => c.to_string(),
+        Some(_) => "none".to_string(),
+        None => "empty".to_string(),
+    };
+    let sentence_type = match sentence::get_sentence_type(trimmed) {
+        SentenceType::Question => "question",
+        SentenceType::Exclamation => "exclamation",
+        SentenceType::Statement => "statement",
+        SentenceType::Imperative => "imperative",
+    };
+    let length = match words.len() {
+        0..=3 => "short",
+        4..=10 => "medium",
+        _ => "long",
+    };
+
+    let mut features = vec![];
+    if let Some(first) = words.first() {
+        features.push(format!("first={first}"));
+    }
+    if words.len() > 1 {
+        features.push(format!("first2={} {}", words[0], words[1]));
+    }
+    features.push(format!("end={end}"));
+    features.push(format!("type={sentence_type}"));
+    features.push(format!("length={length}"));
+    features.extend(words.iter().map(|word| format!("word={word}")));
+    features
+}
+
+/// Tagger labeling utterances with dialogue acts, such as "greeting", "question", or "closing".
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::dialogue::DialogueActTagger;
+///
+/// let mut tagger = DialogueActTagger::pretrained();
+///
+/// assert_eq!(tagger.tag("Hello, thanks for holding.").as_deref(), Some("greeting"));
+/// assert_eq!(tagger.tag("How long will the repair take?").as_deref(), Some("question"));
+/// assert_eq!(tagger.tag("Thanks, that's very helpful!").as_deref(), Some("thanks"));
+///
+/// tagger.train(&[("Let me transfer you.", "transfer"), ("I'll transfer you to billing.", "transfer")]);
+/// assert_eq!(tagger.tag("Let me transfer you to sales.").as_deref(), Some("transfer"));
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct DialogueActTagger {
+    classifier: NaiveBayesClassifier,
+}
+
+impl DialogueActTagger {
+    // checks the classifier of a loaded tagger
+    pub(crate) fn check_consistency(&self) -> Result<(), RnltkError> {
+        self.classifier.check_consistency()
+    }
+
+    /// Creates new instance of DialogueActTagger that hasn't been trained, for custom dialogue acts.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates new instance of DialogueActTagger trained on built-in customer service utterances tagged
+    /// "greeting", "closing", "question", "answer", "thanks", "acknowledgement", "request", or "apology".
+    pub fn pretrained() -> Self {
+        let mut tagger = Self::new();
+        tagger.train(&TRAINING_UTTERANCES);
+        tagger
+    }
+
+    /// Trains the tagger further on `(utterance, dialogue act)` pairs.
+    pub fn train<U: AsRef<str>, L: ToString>(&mut self, utterances: &[(U, L)]) {
+        let batch: Vec<(Vec<String>, String)> = utterances.iter()
+            .map(|(utterance, act)| (get_dialogue_act_features(utterance.as_ref()), act.to_string()))
+            .collect();
+        self.classifier.partial_fit(&batch);
+    }
+
+    /// Gets the probability of every dialogue act for `utterance`.
+    pub fn tag_proba(&self, utterance: &str) -> BTreeMap<String, f64> {
+        self.classifier.predict_proba(&get_dialogue_act_features(utterance))
+    }
+
+    /// Gets the most likely dialogue act of `utterance`, or `None` if the tagger hasn't been trained.
+    pub fn tag(&self, utterance: &str) -> Option<String> {
+        self.classifier.predict(&get_dialogue_act_features(utterance))
+    }
+
+    /// Gets the dialogue acts the tagger knows, in sorted order.
+    pub fn dialogue_acts(&self) -> Vec<&str> {
+        self.classifier.classes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pretrained_tags() {
+        let tagger = DialogueActTagger::pretrained();
+        let tags: Vec<Option<String>> = ["Good evening!", "Bye, have a good one.", "Yes, it is.", "Please refund me.", "Sorry for the trouble.", "Okay."]
+            .iter()
+            .map(|utterance| tagger.tag(utterance))
+            .collect();
+        let expected = ["greeting", "closing", "answer", "request", "apology", "acknowledgement"];
+        assert_eq!(tags, expected.iter().map(|tag| Some(tag.to_string())).collect::<Vec<Option<String>>>());
+        assert_eq!(tagger.dialogue_acts().len(), 8);
+    }
+
+    #[test]
+    fn untrained_tagger() {
+        assert_eq!(DialogueActTagger::new().tag("hello"), None);
+        assert!(DialogueActTagger::new().tag_proba("hello").is_empty());
+        assert_eq!(get_dialogue_act_features(""), vec!["end=empty", "type=statement", "length=short"]);
+    }
+}
@@ -1,42 +1,226 @@
 //! Functionality for performing matrix operations on document term frequencies.
 
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt;
+
 use nalgebra::{Matrix, Dyn, VecStorage};
+use serde::{Serialize, Deserialize};
 
+use crate::cancel::CancellationToken;
+use crate::corpus::{Corpus, DocId};
 use crate::error::RnltkError;
+use crate::{similarity, token};
+use crate::token::intern::{Interner, Symbol};
+use crate::token::{SparseTermFrequencies, Vocabulary};
+use crate::trace::stage_span;
 
 pub type GenericMatrix = Matrix<f64, Dyn, Dyn, VecStorage<f64, Dyn, Dyn>>;
 
+/// Layout of a term frequency matrix handed to [`DocumentTermFrequencies::with_orientation`].
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+    /// Each row is a term and each column is a document (terms × documents).
+    /// This is the layout used internally by [`DocumentTermFrequencies`].
+    TermRows,
+    /// Each row is a document and each column is a term (documents × terms).
+    DocumentRows,
+}
+
 /// Struct for holding the matrix of `document_term_frequencies`
-#[derive(Debug, Clone)]
+///
+/// Deserializing fails unless the matrix passes the checks of [`DocumentTermFrequencies::new`].
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(try_from = "UncheckedDocumentTermFrequencies")]
 pub struct DocumentTermFrequencies {
     pub document_term_frequencies: GenericMatrix
 }
 
+// DocumentTermFrequencies as deserialized, before its frequencies are checked
+#[derive(Deserialize)]
+struct UncheckedDocumentTermFrequencies {
+    document_term_frequencies: GenericMatrix
+}
+
+impl TryFrom<UncheckedDocumentTermFrequencies> for DocumentTermFrequencies {
+    type Error = RnltkError;
+
+    fn try_from(value: UncheckedDocumentTermFrequencies) -> Result<Self, Self::Error> {
+        DocumentTermFrequencies::new(value.document_term_frequencies)
+    }
+}
+
 /// Struct for holding the resulting `tfidf_matrix`
 /// from [`DocumentTermFrequencies::get_tfidf_from_term_frequencies`]
-#[derive(Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct TfidfMatrix {
     tfidf_matrix: GenericMatrix
 }
 
 /// Struct for holding the resulting `cosine_similarity_matrix`
 /// from [`TfidfMatrix::get_cosine_similarity_from_tfidf`]
-#[derive(Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct CosineSimilarityMatrix {
     cosine_similarity_matrix: GenericMatrix
 }
 
 /// Struct for holding the resulting `cosine_similarity_matrix`
 /// from [`TfidfMatrix::get_cosine_similarity_from_tfidf`]
-#[derive(Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct LsaCosineSimilarityMatrix {
     lsa_cosine_similarity_matrix: GenericMatrix
 }
 
+/// Struct for holding a fitted Latent Semantic Analysis (LSA) model, created with [`LsaModel::new`],
+/// that can embed new text into the latent space of the documents it was fitted on.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct LsaModel {
+    terms: Vec<String>,
+    term_indices: HashMap<String, usize>,
+    inverse_document_frequencies: Vec<f64>,
+    // \Sigma_k^{-1} U_k^T, mapping a term vector to the latent space
+    term_projection: GenericMatrix,
+    // V_k^T, one column per document
+    document_embeddings: GenericMatrix,
+    doc_ids: Vec<DocId>,
+}
+
+/// Longest row or column label printed by [`LabeledMatrix`]'s `Display` before truncation.
+const MAX_LABEL_WIDTH: usize = 12;
+/// Most rows or columns printed by [`LabeledMatrix`]'s `Display` before truncation.
+const MAX_DISPLAY_CELLS: usize = 12;
+
+/// Struct for holding a matrix along with its row and column labels, used for
+/// pretty-printing term and document matrices.
+/// 
+/// The `Display` implementation prints a header row of column labels followed by one line
+/// per row. Labels longer than 12 characters are truncated with `…` and only the first 12
+/// rows and columns are shown. Values are printed with 2 decimal places unless a precision
+/// is given, e.g. `format!("{:.4}", labeled_matrix)`.
+#[derive(Debug, Clone)]
+pub struct LabeledMatrix {
+    matrix: GenericMatrix,
+    row_labels: Vec<String>,
+    column_labels: Vec<String>,
+}
+
+impl LabeledMatrix {
+    /// Creates new instance of LabeledMatrix from a `matrix` and its `row_labels` and `column_labels`.
+    /// 
+    /// # Errors
+    /// 
+    /// Returns [`RnltkError::LabelCountMismatch`] if the number of labels doesn't match the
+    /// number of rows or columns.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rnltk::document::LabeledMatrix;
+    /// use nalgebra::DMatrix;
+    /// 
+    /// let matrix = DMatrix::from_row_slice(2, 2, &[1., 0.25,
+    ///     0.25, 1.,]);
+    /// let labeled_matrix = LabeledMatrix::new(matrix, vec!["first", "second"], vec!["first", "second"]).unwrap();
+    /// 
+    /// assert_eq!(labeled_matrix.to_string(), "         first  second\nfirst     1.00    0.25\nsecond    0.25    1.00\n");
+    /// ```
+    pub fn new<R: ToString, C: ToString>(matrix: GenericMatrix, row_labels: Vec<R>, column_labels: Vec<C>) -> Result<Self, RnltkError> {
+        if row_labels.len() != matrix.nrows() || column_labels.len() != matrix.ncols() {
+            return Err(RnltkError::LabelCountMismatch);
+        }
+        Ok(LabeledMatrix {
+            matrix,
+            row_labels: row_labels.iter().map(|label| label.to_string()).collect(),
+            column_labels: column_labels.iter().map(|label| label.to_string()).collect(),
+        })
+    }
+
+    fn with_default_labels(matrix: &GenericMatrix, row_prefix: &str, column_prefix: &str) -> Self {
+        LabeledMatrix {
+            row_labels: (1..=matrix.nrows()).map(|index| format!("{} {}", row_prefix, index)).collect(),
+            column_labels: (1..=matrix.ncols()).map(|index| format!("{} {}", column_prefix, index)).collect(),
+            matrix: matrix.clone(),
+        }
+    }
+
+    /// Gets the labeled matrix.
+    pub fn get_matrix(&self) -> &GenericMatrix {
+        &self.matrix
+    }
+
+    /// Gets the row labels.
+    pub fn get_row_labels(&self) -> &[String] {
+        &self.row_labels
+    }
+
+    /// Gets the column labels.
+    pub fn get_column_labels(&self) -> &[String] {
+        &self.column_labels
+    }
+}
+
+fn truncate_label(label: &str) -> String {
+    if label.chars().count() > MAX_LABEL_WIDTH {
+        let mut truncated: String = label.chars().take(MAX_LABEL_WIDTH - 1).collect();
+        truncated.push('…');
+        truncated
+    } else {
+        label.to_string()
+    }
+}
+
+impl fmt::Display for LabeledMatrix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let precision = f.precision().unwrap_or(2);
+        let num_rows = self.matrix.nrows().min(MAX_DISPLAY_CELLS);
+        let num_cols = self.matrix.ncols().min(MAX_DISPLAY_CELLS);
+        let row_labels: Vec<String> = self.row_labels.iter().take(num_rows).map(|label| truncate_label(label)).collect();
+        let column_labels: Vec<String> = self.column_labels.iter().take(num_cols).map(|label| truncate_label(label)).collect();
+
+        let row_label_width = row_labels.iter().map(|label| label.chars().count()).max().unwrap_or(0);
+        let value_width = (0..num_rows)
+            .flat_map(|row_index| (0..num_cols).map(move |col_index| (row_index, col_index)))
+            .map(|index| format!("{:.*}", precision, self.matrix[index]).len())
+            .chain(column_labels.iter().map(|label| label.chars().count()))
+            .max()
+            .unwrap_or(0);
+        let rows_truncated = self.matrix.nrows() > num_rows;
+        let cols_truncated = self.matrix.ncols() > num_cols;
+
+        write!(f, "{:width$}", "", width = row_label_width)?;
+        for label in &column_labels {
+            write!(f, "  {:>width$}", label, width = value_width)?;
+        }
+        if cols_truncated {
+            write!(f, "  …")?;
+        }
+        writeln!(f)?;
+
+        for (row_index, label) in row_labels.iter().enumerate() {
+            write!(f, "{:<width$}", label, width = row_label_width)?;
+            for col_index in 0..num_cols {
+                write!(f, "  {:>width$.precision$}", self.matrix[(row_index, col_index)], width = value_width, precision = precision)?;
+            }
+            if cols_truncated {
+                write!(f, "  …")?;
+            }
+            writeln!(f)?;
+        }
+        if rows_truncated {
+            writeln!(f, "… {} more rows", self.matrix.nrows() - num_rows)?;
+        }
+        Ok(())
+    }
+}
+
 impl DocumentTermFrequencies {
-    /// Creates new instance of DocumentTermFrequencies from a [`DMatrix`].
+    /// Creates new instance of DocumentTermFrequencies from a terms × documents [`DMatrix`],
+    /// like [`DocumentTermFrequencies::from_term_rows`].
     /// 
     /// [`DMatrix`]: nalgebra::DMatrix
+    /// 
+    /// # Errors
+    /// 
+    /// See [`DocumentTermFrequencies::with_orientation`].
     ///
     /// # Examples
     ///
@@ -56,12 +240,230 @@ impl DocumentTermFrequencies {
     ///     0., 0., 1., 0.,
     ///     1., 0., 0., 0.,]);
     /// 
-    /// let document_term_frequencies: DocumentTermFrequencies = DocumentTermFrequencies::new(term_frequencies);
+    /// let document_term_frequencies: DocumentTermFrequencies = DocumentTermFrequencies::new(term_frequencies).unwrap();
+    /// ```
+    pub fn new(document_term_frequencies: GenericMatrix) -> Result<Self, RnltkError> {
+        Self::with_orientation(document_term_frequencies, Orientation::TermRows)
+    }
+
+    /// Creates new instance of DocumentTermFrequencies from a matrix laid out according to `orientation`.
+    /// 
+    /// Matrices with [`Orientation::DocumentRows`] are transposed so the stored matrix is always
+    /// terms × documents. The matrix is validated before being stored.
+    /// 
+    /// # Errors
+    /// 
+    /// Returns [`RnltkError::TermFrequenciesEmpty`] if the matrix has no terms or no documents.
+    /// 
+    /// Returns [`RnltkError::TermFrequenciesInvalid`] if any frequency is negative or not finite.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rnltk::document::{DocumentTermFrequencies, Orientation};
+    /// use nalgebra::DMatrix;
+    /// 
+    /// // 2 documents (rows) × 3 terms (columns)
+    /// let term_frequencies = DMatrix::from_row_slice(2, 3, &[1., 0., 2.,
+    ///     0., 1., 1.,]);
+    /// 
+    /// let document_term_frequencies = DocumentTermFrequencies::with_orientation(term_frequencies, Orientation::DocumentRows).unwrap();
+    /// assert_eq!(document_term_frequencies.num_terms(), 3);
+    /// assert_eq!(document_term_frequencies.num_documents(), 2);
     /// ```
-    pub fn new(document_term_frequencies: GenericMatrix) -> Self {
-        DocumentTermFrequencies {
+    pub fn with_orientation(term_frequencies: GenericMatrix, orientation: Orientation) -> Result<Self, RnltkError> {
+        let document_term_frequencies = match orientation {
+            Orientation::TermRows => term_frequencies,
+            Orientation::DocumentRows => term_frequencies.transpose(),
+        };
+        validate_term_frequencies(&document_term_frequencies)?;
+
+        Ok(DocumentTermFrequencies {
             document_term_frequencies
+        })
+    }
+
+    /// Creates new instance of DocumentTermFrequencies from a terms × documents matrix,
+    /// where each row is a term and each column is a document.
+    /// 
+    /// # Errors
+    /// 
+    /// See [`DocumentTermFrequencies::with_orientation`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rnltk::document::DocumentTermFrequencies;
+    /// use rnltk::sample_data;
+    /// 
+    /// let document_term_frequencies = DocumentTermFrequencies::from_term_rows(sample_data::get_term_frequencies()).unwrap();
+    /// assert_eq!(document_term_frequencies.num_terms(), 11);
+    /// assert_eq!(document_term_frequencies.num_documents(), 4);
+    /// ```
+    pub fn from_term_rows(term_frequencies: GenericMatrix) -> Result<Self, RnltkError> {
+        Self::with_orientation(term_frequencies, Orientation::TermRows)
+    }
+
+    /// Creates new instance of DocumentTermFrequencies from a documents × terms matrix,
+    /// where each row is a document and each column is a term. The matrix is transposed
+    /// into the terms × documents layout.
+    /// 
+    /// # Errors
+    /// 
+    /// See [`DocumentTermFrequencies::with_orientation`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rnltk::document::DocumentTermFrequencies;
+    /// use rnltk::sample_data;
+    /// 
+    /// let document_rows = sample_data::get_term_frequencies().transpose();
+    /// let document_term_frequencies = DocumentTermFrequencies::from_document_rows(document_rows).unwrap();
+    /// assert_eq!(document_term_frequencies.num_terms(), 11);
+    /// assert_eq!(document_term_frequencies.num_documents(), 4);
+    /// ```
+    pub fn from_document_rows(term_frequencies: GenericMatrix) -> Result<Self, RnltkError> {
+        Self::with_orientation(term_frequencies, Orientation::DocumentRows)
+    }
+
+    /// Creates new instance of DocumentTermFrequencies from integer `term_counts` of each document, like those from
+    /// [`token::get_term_counts_from_sentences`]. Rows follow the sorted terms of all documents, and terms
+    /// missing from a document count 0, so the counts don't need to be aligned.
+    /// 
+    /// # Errors
+    /// 
+    /// Returns [`RnltkError::TermFrequenciesEmpty`] if there are no terms or no documents.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rnltk::document::DocumentTermFrequencies;
+    /// use rnltk::token;
+    /// 
+    /// let term_counts = token::get_term_counts_from_sentences(&["fear leads to anger", "anger leads to hatred"]);
+    /// let document_term_frequencies = DocumentTermFrequencies::from_term_counts(&term_counts).unwrap();
+    ///
+    /// assert_eq!(document_term_frequencies.num_terms(), 5);
+    /// assert_eq!(document_term_frequencies.document_term_frequencies[(0, 1)], 1.);
+    /// ```
+    pub fn from_term_counts(term_counts: &[BTreeMap<String, u64>]) -> Result<Self, RnltkError> {
+        let terms: Vec<&String> = term_counts.iter().flat_map(|counts| counts.keys()).collect::<BTreeSet<&String>>().into_iter().collect();
+        let term_frequencies = GenericMatrix::from_fn(terms.len(), term_counts.len(), |row, column| {
+            term_counts[column].get(terms[row]).copied().unwrap_or_default() as f64
+        });
+        Self::from_term_rows(term_frequencies)
+    }
+
+    /// Creates new instance of DocumentTermFrequencies from the interned tokens of each document, counting them by id.
+    /// Row `i` holds the counts of the term with [`Symbol`] index `i`, so the terms of the rows are those of
+    /// [`Interner::iter`]. Symbols must come from `interner`: only those beyond its last string are skipped,
+    /// so a symbol from another interner is otherwise counted as whichever term has its index.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RnltkError::TermFrequenciesEmpty`] if the interner or `documents` are empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rnltk::document::DocumentTermFrequencies;
+    /// use rnltk::token;
+    /// use rnltk::token::intern::Interner;
+    ///
+    /// let mut interner = Interner::new();
+    /// let documents: Vec<_> = ["fear leads to anger", "anger leads to hatred"].iter()
+    ///     .map(|sentence| interner.intern_tokens(token::tokenize_sentence(sentence)))
+    ///     .collect();
+    /// let document_term_frequencies = DocumentTermFrequencies::from_symbol_documents(&documents, &interner).unwrap();
+    /// let terms: Vec<&str> = interner.iter().map(|(_, term)| term).collect();
+    ///
+    /// assert_eq!(terms, vec!["fear", "leads", "to", "anger", "hatred"]);
+    /// assert_eq!(document_term_frequencies.document_term_frequencies[(4, 1)], 1.);
+    /// ```
+    pub fn from_symbol_documents(documents: &[Vec<Symbol>], interner: &Interner) -> Result<Self, RnltkError> {
+        let mut term_frequencies = GenericMatrix::zeros(interner.len(), documents.len());
+        for (column, symbols) in documents.iter().enumerate() {
+            for symbol in symbols.iter().filter(|symbol| symbol.index() < interner.len()) {
+                term_frequencies[(symbol.index(), column)] += 1.;
+            }
+        }
+        Self::from_term_rows(term_frequencies)
+    }
+
+    /// Creates new instance of DocumentTermFrequencies from documents encoded with a [`Vocabulary`], where row `i`
+    /// holds the term with id `i`. Ids that aren't in `vocabulary` are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RnltkError::TermFrequenciesEmpty`] if the vocabulary or `documents` are empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rnltk::document::DocumentTermFrequencies;
+    /// use rnltk::token::{self, Vocabulary};
+    ///
+    /// let mut vocabulary = Vocabulary::new();
+    /// let sentences = ["fear leads to anger", "anger leads to hatred"];
+    /// sentences.iter().for_each(|sentence| vocabulary.add_tokens(token::tokenize_sentence(sentence)));
+    /// let documents: Vec<Vec<u32>> = sentences.iter().map(|sentence| vocabulary.encode(token::tokenize_sentence(sentence))).collect();
+    /// let document_term_frequencies = DocumentTermFrequencies::from_encoded_documents(&documents, &vocabulary).unwrap();
+    ///
+    /// assert_eq!(document_term_frequencies.document_term_frequencies[(vocabulary.get_id("hatred").unwrap() as usize, 1)], 1.);
+    /// ```
+    pub fn from_encoded_documents(documents: &[Vec<u32>], vocabulary: &Vocabulary) -> Result<Self, RnltkError> {
+        let documents: Vec<Vec<Symbol>> = documents.iter().map(|ids| ids.iter().map(|id| Symbol(*id)).collect()).collect();
+        Self::from_symbol_documents(&documents, vocabulary.get_interner())
+    }
+
+    /// Creates new instance of DocumentTermFrequencies from [`SparseTermFrequencies`], where row `i` holds the counts
+    /// of `term_frequencies.terms[i]`. Term indices outside the vocabulary are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RnltkError::TermFrequenciesEmpty`] if there are no terms or no documents.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rnltk::document::DocumentTermFrequencies;
+    /// use rnltk::token;
+    ///
+    /// let term_frequencies = token::get_sparse_term_frequencies_from_sentences(&["fear leads to anger", "anger leads to hatred"]);
+    /// let document_term_frequencies = DocumentTermFrequencies::from_sparse(&term_frequencies).unwrap();
+    ///
+    /// assert_eq!(document_term_frequencies.num_terms(), 5);
+    /// assert_eq!(document_term_frequencies.document_term_frequencies[(2, 1)], 1.);
+    /// ```
+    pub fn from_sparse(term_frequencies: &SparseTermFrequencies) -> Result<Self, RnltkError> {
+        let mut matrix = GenericMatrix::zeros(term_frequencies.num_terms(), term_frequencies.num_documents());
+        for (column, counts) in term_frequencies.documents.iter().enumerate() {
+            for (row, count) in counts.iter().filter(|(row, _)| *row < term_frequencies.num_terms()) {
+                matrix[(*row, column)] += count;
+            }
         }
+        Self::from_term_rows(matrix)
+    }
+
+    /// Labels the `document_term_frequencies` matrix with its `terms` (rows) and `documents` (columns)
+    /// for pretty-printing.
+    /// 
+    /// # Errors
+    /// 
+    /// Returns [`RnltkError::LabelCountMismatch`] if the number of labels doesn't match the matrix.
+    pub fn with_labels<T: ToString, D: ToString>(&self, terms: Vec<T>, documents: Vec<D>) -> Result<LabeledMatrix, RnltkError> {
+        LabeledMatrix::new(self.document_term_frequencies.clone(), terms, documents)
+    }
+
+    /// Gets the number of terms (rows) in `document_term_frequencies`.
+    pub fn num_terms(&self) -> usize {
+        self.document_term_frequencies.nrows()
+    }
+
+    /// Gets the number of documents (columns) in `document_term_frequencies`.
+    pub fn num_documents(&self) -> usize {
+        self.document_term_frequencies.ncols()
     }
 
     /// Gets the Term Frequency–Inverse Document Frequency (TF-IDF) matrix of the 
@@ -82,29 +484,122 @@ impl DocumentTermFrequencies {
     /// use rnltk::document::DocumentTermFrequencies;
     /// use rnltk::sample_data;
     /// 
-    /// let document_term_frequencies: DocumentTermFrequencies = DocumentTermFrequencies::new(sample_data::get_term_frequencies());
+    /// let document_term_frequencies: DocumentTermFrequencies = DocumentTermFrequencies::new(sample_data::get_term_frequencies()).unwrap();
     /// let tfidf_matrix = document_term_frequencies.get_tfidf_from_term_frequencies();
     /// ```
     pub fn get_tfidf_from_term_frequencies(&self) -> TfidfMatrix {
+        stage_span!("tfidf", terms = self.num_terms(), documents = self.num_documents());
+        self.get_tfidf_with_weights(&vec![1.; self.num_documents()])
+    }
+
+    /// Gets the TF-IDF matrix like [`DocumentTermFrequencies::get_tfidf_from_term_frequencies`], but with
+    /// each document, \\(D_j\\), contributing its weight, \\(w_j\\), (e.g. recency or importance) to the
+    /// document frequency statistics instead of 1, so \\(idf_i = ln(\sum_j w_j / \sum_{j : t_i \in D_j} w_j)\\).
+    /// With every weight set to 1 this is identical to the unweighted TF-IDF matrix.
+    /// 
+    /// # Errors
+    /// 
+    /// Returns [`RnltkError::DocumentWeightsInvalid`] if the number of `weights` doesn't match the number of
+    /// documents, or if any weight is negative or not finite.
+    ///
+    /// # Examples
+    /// 
+    /// ```
+    /// use rnltk::document::DocumentTermFrequencies;
+    /// use nalgebra::DMatrix;
+    /// 
+    /// // "war" only appears in the old first document, "peace" in both
+    /// let term_frequencies = DMatrix::from_row_slice(2, 2, &[1., 0.,
+    ///     1., 1.,]);
+    /// let document_term_frequencies = DocumentTermFrequencies::new(term_frequencies).unwrap();
+    /// let weights = DocumentTermFrequencies::get_decay_weights(&[30., 0.], 30.);
+    /// let tfidf_matrix = document_term_frequencies.get_tfidf_from_term_frequencies_weighted(&weights).unwrap();
+    /// 
+    /// assert_eq!(weights, vec![0.5, 1.]);
+    /// assert!(tfidf_matrix.get_tfidf_matrix()[(0, 0)] > 0.);
+    /// ```
+    pub fn get_tfidf_from_term_frequencies_weighted(&self, weights: &[f64]) -> Result<TfidfMatrix, RnltkError> {
+        validate_document_weights(weights, self.num_documents())?;
+        stage_span!("tfidf", terms = self.num_terms(), documents = self.num_documents());
+        Ok(self.get_tfidf_with_weights(weights))
+    }
+
+    /// Gets the TF-IDF matrix using scikit-learn's definition, matching `TfidfTransformer(norm="l2", smooth_idf=smooth_idf)`
+    /// with its other parameters left as defaults, for parity with Python pipelines.
+    /// 
+    /// scikit-learn adds 1 to every inverse document frequency so terms appearing in every document
+    /// aren't ignored, \\(idf_i = ln(n / n_i) + 1\\), or with `smooth_idf`, as if an extra document
+    /// contained every term once, \\(idf_i = ln((1 + n) / (1 + n_i)) + 1\\). Documents are then normalized
+    /// as in [`DocumentTermFrequencies::get_tfidf_from_term_frequencies`], except that empty documents stay 0.
+    ///
+    /// # Examples
+    /// 
+    /// ```
+    /// use rnltk::document::DocumentTermFrequencies;
+    /// use nalgebra::DMatrix;
+    /// 
+    /// // terms are rows and documents are columns, the transpose of scikit-learn's layout
+    /// let term_frequencies = DMatrix::from_row_slice(3, 2, &[3., 2.,
+    ///     0., 0.,
+    ///     1., 0.,]);
+    /// let tfidf_matrix = DocumentTermFrequencies::new(term_frequencies).unwrap().get_tfidf_from_term_frequencies_sklearn(true);
+    /// 
+    /// assert_eq!(tfidf_matrix.get_tfidf_matrix()[(0, 1)], 1.);
+    /// ```
+    pub fn get_tfidf_from_term_frequencies_sklearn(&self, smooth_idf: bool) -> TfidfMatrix {
+        stage_span!("tfidf", terms = self.num_terms(), documents = self.num_documents());
         let mut document_term_frequencies = self.document_term_frequencies.clone();
+        let smoothing = if smooth_idf { 1. } else { 0. };
+        let num_documents = self.num_documents() as f64 + smoothing;
+        for mut row in document_term_frequencies.row_iter_mut() {
+            let document_frequency = row.iter().filter(|frequency| **frequency > 0.).count() as f64 + smoothing;
+            let inverse_document_frequency = (num_documents / document_frequency).ln() + 1.;
+            row.scale_mut(inverse_document_frequency);
+        }
+
+        for mut column in document_term_frequencies.column_iter_mut() {
+            let norm = column.norm();
+            if norm > 0. {
+                column.unscale_mut(norm);
+            }
+        }
+
+        TfidfMatrix {
+            tfidf_matrix: document_term_frequencies
+        }
+    }
+
+    /// Gets exponential time-decay weights for documents that are `ages` old, where a document
+    /// loses half of its weight every `half_life` (in the same unit as `ages`), for use with
+    /// [`DocumentTermFrequencies::get_tfidf_from_term_frequencies_weighted`] and [`TfidfMatrix::get_weighted_centroid`].
+    pub fn get_decay_weights(ages: &[f64], half_life: f64) -> Vec<f64> {
+        ages.iter().map(|age| 0.5_f64.powf(age / half_life)).collect()
+    }
+
+    fn get_tfidf_with_weights(&self, weights: &[f64]) -> TfidfMatrix {
+        let mut document_term_frequencies = self.document_term_frequencies.clone();
+        let total_weight: f64 = weights.iter().sum();
         for row_index in 0..document_term_frequencies.nrows() {
-            let term_count: f64 = document_term_frequencies.row(row_index).iter().fold(0., |acc, frequency| {
+            let term_weight: f64 = document_term_frequencies.row(row_index).iter().zip(weights).fold(0., |acc, (frequency, weight)| {
                 if frequency > &0. {
-                    acc + 1.
+                    acc + weight
                 } else {
                     acc
                 }
             });
+            // terms only found in documents weighted 0 carry no weight, rather than an infinite idf
+            let inverse_document_frequency = if term_weight > 0. { (total_weight / term_weight).ln() } else { 0. };
             for col_index in 0..document_term_frequencies.ncols() {
                 let term_frequency = &document_term_frequencies[(row_index, col_index)];
-                let inverse_document_frequency = (document_term_frequencies.ncols() as f64 / term_count).ln();
                 document_term_frequencies[(row_index, col_index)] = term_frequency * inverse_document_frequency;
             }
         }
     
         for mut column in document_term_frequencies.column_iter_mut() {
-            let normalized = column.normalize();
-            column.copy_from(&normalized);
+            let norm = column.norm();
+            if norm > 0. {
+                column.unscale_mut(norm);
+            }
         }
     
         TfidfMatrix {
@@ -113,7 +608,66 @@ impl DocumentTermFrequencies {
     }
 }
 
+fn validate_document_weights(weights: &[f64], num_documents: usize) -> Result<(), RnltkError> {
+    if weights.len() != num_documents || weights.iter().any(|weight| !weight.is_finite() || *weight < 0.) {
+        return Err(RnltkError::DocumentWeightsInvalid);
+    }
+    Ok(())
+}
+
+// whether `term_indices` maps every term to its position in `terms`, and nothing else
+pub(crate) fn term_indices_match(terms: &[String], term_indices: &HashMap<String, usize>) -> bool {
+    term_indices.len() == terms.len() && terms.iter().enumerate().all(|(index, term)| term_indices.get(term) == Some(&index))
+}
+
+fn validate_term_frequencies(term_frequencies: &GenericMatrix) -> Result<(), RnltkError> {
+    if term_frequencies.nrows() == 0 || term_frequencies.ncols() == 0 {
+        return Err(RnltkError::TermFrequenciesEmpty);
+    }
+    if term_frequencies.iter().any(|frequency| !frequency.is_finite() || *frequency < 0.) {
+        return Err(RnltkError::TermFrequenciesInvalid);
+    }
+    Ok(())
+}
+
 impl TfidfMatrix {
+    /// Gets the weighted centroid of the TF-IDF document vectors, \(\sum_j w_j D_j / \sum_j w_j\),
+    /// so that important or recent documents pull the centroid towards their terms.
+    /// If every weight is 0 the centroid is the zero vector.
+    /// 
+    /// # Errors
+    /// 
+    /// Returns [`RnltkError::DocumentWeightsInvalid`] if the number of `weights` doesn't match the number of
+    /// documents, or if any weight is negative or not finite.
+    ///
+    /// # Examples
+    /// 
+    /// ```
+    /// use rnltk::document::DocumentTermFrequencies;
+    /// use nalgebra::DMatrix;
+    /// 
+    /// let term_frequencies = DMatrix::from_row_slice(2, 2, &[1., 0.,
+    ///     0., 1.,]);
+    /// let tfidf_matrix = DocumentTermFrequencies::new(term_frequencies).unwrap().get_tfidf_from_term_frequencies();
+    /// let centroid = tfidf_matrix.get_weighted_centroid(&[3., 1.]).unwrap();
+    /// 
+    /// assert_eq!(centroid, vec![0.75, 0.25]);
+    /// ```
+    pub fn get_weighted_centroid(&self, weights: &[f64]) -> Result<Vec<f64>, RnltkError> {
+        validate_document_weights(weights, self.tfidf_matrix.ncols())?;
+        let total_weight: f64 = weights.iter().sum();
+        let mut centroid = vec![0.; self.tfidf_matrix.nrows()];
+        if total_weight == 0. {
+            return Ok(centroid);
+        }
+        for (column, weight) in self.tfidf_matrix.column_iter().zip(weights) {
+            for (value, weight_value) in centroid.iter_mut().zip(column.iter()) {
+                *value += weight * weight_value / total_weight;
+            }
+        }
+        Ok(centroid)
+    }
+
     /// Gets the TF-IDF matrix that was created from [`DocumentTermFrequencies::get_tfidf_from_term_frequencies`].
     /// 
     /// This ensures the user can't instantiate their own instance of [`TfidfMatrix`] and must use the 
@@ -122,6 +676,15 @@ impl TfidfMatrix {
         &self.tfidf_matrix
     }
 
+    /// Labels the TF-IDF matrix with its `terms` (rows) and `documents` (columns) for pretty-printing.
+    /// 
+    /// # Errors
+    /// 
+    /// Returns [`RnltkError::LabelCountMismatch`] if the number of labels doesn't match the matrix.
+    pub fn with_labels<T: ToString, D: ToString>(&self, terms: Vec<T>, documents: Vec<D>) -> Result<LabeledMatrix, RnltkError> {
+        LabeledMatrix::new(self.tfidf_matrix.clone(), terms, documents)
+    }
+
     /// Gets the cosine similarity matrix from the [`TfidfMatrix`]'s `tfidf_matrix`.
     /// 
     /// Normally, calculating the cosine similarity of two document vectors would look like
@@ -139,11 +702,12 @@ impl TfidfMatrix {
     /// use rnltk::document::DocumentTermFrequencies;
     /// use rnltk::sample_data;
     /// 
-    /// let document_term_frequencies: DocumentTermFrequencies = DocumentTermFrequencies::new(sample_data::get_term_frequencies());
+    /// let document_term_frequencies: DocumentTermFrequencies = DocumentTermFrequencies::new(sample_data::get_term_frequencies()).unwrap();
     /// let tfidf_matrix = document_term_frequencies.get_tfidf_from_term_frequencies();
     /// let cosine_similarity_matrix = tfidf_matrix.get_cosine_similarity_from_tfidf();
     /// ```
     pub fn get_cosine_similarity_from_tfidf(&self) -> CosineSimilarityMatrix {
+        stage_span!("cosine_similarity", documents = self.tfidf_matrix.ncols());
         let num_cols = self.tfidf_matrix.ncols();
         let mut cosine_similarity_matrix: GenericMatrix = GenericMatrix::zeros(num_cols, num_cols);
         for col_index in 0..num_cols {
@@ -180,15 +744,53 @@ impl TfidfMatrix {
     /// use rnltk::document::DocumentTermFrequencies;
     /// use rnltk::sample_data;
     /// 
-    /// let document_term_frequencies: DocumentTermFrequencies = DocumentTermFrequencies::new(sample_data::get_term_frequencies());
+    /// let document_term_frequencies: DocumentTermFrequencies = DocumentTermFrequencies::new(sample_data::get_term_frequencies()).unwrap();
     /// let tfidf_matrix = document_term_frequencies.get_tfidf_from_term_frequencies();
     /// let lsa_cosine_similarity_matrix = tfidf_matrix.get_lsa_cosine_similarity_from_tfidf(2).unwrap();
     /// ```
     pub fn get_lsa_cosine_similarity_from_tfidf(&self, k: usize) -> Result<LsaCosineSimilarityMatrix, RnltkError> {
+        self.get_lsa_cosine_similarity_from_tfidf_cancellable(k, &CancellationToken::new())
+    }
+
+    /// Gets the Latent Semantic Analysis (LSA) cosine similarity matrix like
+    /// [`TfidfMatrix::get_lsa_cosine_similarity_from_tfidf`], checking `cancellation_token`
+    /// before and after the SVD and between every row of the similarity matrix.
+    /// 
+    /// The SVD itself runs to completion once started since nalgebra offers no way to interrupt it.
+    /// 
+    /// # Errors
+    /// 
+    /// Returns [`RnltkError::LsaOutOfBounds`] if `k` is greater than the number of documents.
+    /// 
+    /// Returns [`RnltkError::Cancelled`] if `cancellation_token` was cancelled.
+    ///
+    /// # Examples
+    /// 
+    /// ```
+    /// use rnltk::cancel::CancellationToken;
+    /// use rnltk::document::DocumentTermFrequencies;
+    /// use rnltk::error::RnltkError;
+    /// use rnltk::sample_data;
+    /// 
+    /// let document_term_frequencies: DocumentTermFrequencies = DocumentTermFrequencies::new(sample_data::get_term_frequencies()).unwrap();
+    /// let tfidf_matrix = document_term_frequencies.get_tfidf_from_term_frequencies();
+    /// let cancellation_token = CancellationToken::new();
+    /// cancellation_token.cancel();
+    /// 
+    /// let lsa_result = tfidf_matrix.get_lsa_cosine_similarity_from_tfidf_cancellable(2, &cancellation_token);
+    /// assert_eq!(lsa_result.unwrap_err(), RnltkError::Cancelled);
+    /// ```
+    pub fn get_lsa_cosine_similarity_from_tfidf_cancellable(&self, k: usize, cancellation_token: &CancellationToken) -> Result<LsaCosineSimilarityMatrix, RnltkError> {
         if k > self.tfidf_matrix.ncols() {
             return Err(RnltkError::LsaOutOfBounds);
         }
-        let svd_matrix = self.tfidf_matrix.clone().svd(true, true);
+        stage_span!("lsa", k = k, documents = self.tfidf_matrix.ncols());
+        cancellation_token.check()?;
+        let svd_matrix = {
+            stage_span!("svd", rows = self.tfidf_matrix.nrows(), columns = self.tfidf_matrix.ncols());
+            self.tfidf_matrix.clone().svd(true, true)
+        };
+        cancellation_token.check()?;
         let mut v_t = svd_matrix.v_t.unwrap();
 
         let mut v_tk = v_t.view_mut((0, 0), (k, v_t.ncols()));
@@ -201,6 +803,7 @@ impl TfidfMatrix {
         let num_cols = v_tk.ncols();
         let mut lsa_cosine_similarity_matrix: GenericMatrix = GenericMatrix::zeros(num_cols, num_cols);
         for col_index in 0..num_cols {
+            cancellation_token.check()?;
             for inner_col_index in 0..num_cols {
                 if col_index == inner_col_index {
                     lsa_cosine_similarity_matrix[(col_index, inner_col_index)] = 1.
@@ -231,6 +834,28 @@ impl CosineSimilarityMatrix {
     pub fn get_cosine_similarity_matrix(&self) -> &GenericMatrix {
         &self.cosine_similarity_matrix
     }
+
+    /// Labels the rows and columns of the cosine similarity matrix with `documents` for pretty-printing.
+    /// 
+    /// # Errors
+    /// 
+    /// Returns [`RnltkError::LabelCountMismatch`] if the number of labels doesn't match the matrix.
+    ///
+    /// # Examples
+    /// 
+    /// ```
+    /// use rnltk::document::DocumentTermFrequencies;
+    /// use rnltk::sample_data;
+    /// 
+    /// let document_term_frequencies: DocumentTermFrequencies = DocumentTermFrequencies::new(sample_data::get_term_frequencies()).unwrap();
+    /// let cosine_similarity_matrix = document_term_frequencies.get_tfidf_from_term_frequencies().get_cosine_similarity_from_tfidf();
+    /// let labeled_matrix = cosine_similarity_matrix.with_labels(vec!["Dickens", "Melville", "Shakespeare", "Romeo"]).unwrap();
+    /// println!("{}", labeled_matrix);
+    /// ```
+    pub fn with_labels<D: ToString>(&self, documents: Vec<D>) -> Result<LabeledMatrix, RnltkError> {
+        let column_labels: Vec<String> = documents.iter().map(|document| document.to_string()).collect();
+        LabeledMatrix::new(self.cosine_similarity_matrix.clone(), documents, column_labels)
+    }
 }
 
 impl LsaCosineSimilarityMatrix {
@@ -242,6 +867,236 @@ impl LsaCosineSimilarityMatrix {
     pub fn get_lsa_cosine_similarity_matrix(&self) -> &GenericMatrix {
         &self.lsa_cosine_similarity_matrix
     }
+
+    /// Labels the rows and columns of the LSA cosine similarity matrix with `documents` for pretty-printing.
+    /// 
+    /// # Errors
+    /// 
+    /// Returns [`RnltkError::LabelCountMismatch`] if the number of labels doesn't match the matrix.
+    pub fn with_labels<D: ToString>(&self, documents: Vec<D>) -> Result<LabeledMatrix, RnltkError> {
+        let column_labels: Vec<String> = documents.iter().map(|document| document.to_string()).collect();
+        LabeledMatrix::new(self.lsa_cosine_similarity_matrix.clone(), documents, column_labels)
+    }
+}
+
+impl LsaModel {
+    /// Creates new instance of LsaModel by applying Singular Value Decomposition (SVD) to the TF-IDF
+    /// weighted `term_frequencies` and keeping the `k` largest singular values. `terms` labels the
+    /// rows of `term_frequencies` and becomes the vocabulary used by [`LsaModel::embed_sentence`].
+    /// 
+    /// # Errors
+    /// 
+    /// Returns [`RnltkError::LabelCountMismatch`] if the number of `terms` doesn't match the number of rows.
+    /// 
+    /// Returns [`RnltkError::LsaOutOfBounds`] if `k` is greater than the number of terms or documents.
+    ///
+    /// # Examples
+    /// 
+    /// ```
+    /// use rnltk::document::{DocumentTermFrequencies, LsaModel};
+    /// use nalgebra::DMatrix;
+    /// 
+    /// let terms = vec!["bees", "honey", "hive", "rocket", "orbit"];
+    /// let term_frequencies = DMatrix::from_row_slice(5, 4, &[2., 1., 0., 0.,
+    ///     1., 2., 0., 0.,
+    ///     1., 1., 0., 0.,
+    ///     0., 0., 2., 1.,
+    ///     0., 0., 1., 2.,]);
+    /// let lsa_model = LsaModel::new(terms, &DocumentTermFrequencies::new(term_frequencies).unwrap(), 2).unwrap();
+    /// 
+    /// assert_eq!(lsa_model.get_document_embeddings().shape(), (2, 4));
+    /// ```
+    pub fn new<T: ToString>(terms: Vec<T>, term_frequencies: &DocumentTermFrequencies, k: usize) -> Result<Self, RnltkError> {
+        Self::new_cancellable(terms, term_frequencies, k, &CancellationToken::new())
+    }
+
+    /// Creates new instance of LsaModel like [`LsaModel::new`], checking `cancellation_token`
+    /// before and after the SVD.
+    /// 
+    /// The SVD itself runs to completion once started since nalgebra offers no way to interrupt it.
+    /// 
+    /// # Errors
+    /// 
+    /// Returns any error of [`LsaModel::new`].
+    /// 
+    /// Returns [`RnltkError::Cancelled`] if `cancellation_token` was cancelled.
+    pub fn new_cancellable<T: ToString>(terms: Vec<T>, term_frequencies: &DocumentTermFrequencies, k: usize, cancellation_token: &CancellationToken) -> Result<Self, RnltkError> {
+        let document_term_frequencies = &term_frequencies.document_term_frequencies;
+        if terms.len() != document_term_frequencies.nrows() {
+            return Err(RnltkError::LabelCountMismatch);
+        }
+        if k > document_term_frequencies.nrows().min(document_term_frequencies.ncols()) {
+            return Err(RnltkError::LsaOutOfBounds);
+        }
+        stage_span!("lsa_model", k = k, terms = terms.len(), documents = document_term_frequencies.ncols());
+
+        let num_documents = document_term_frequencies.ncols() as f64;
+        let inverse_document_frequencies = document_term_frequencies.row_iter().map(|row| {
+            let term_count = row.iter().filter(|frequency| **frequency > 0.).count() as f64;
+            if term_count == 0. { 0. } else { (num_documents / term_count).ln() }
+        }).collect();
+
+        let tfidf_matrix = term_frequencies.get_tfidf_from_term_frequencies().tfidf_matrix.map(|weight| if weight.is_nan() { 0. } else { weight });
+        cancellation_token.check()?;
+        let svd_matrix = {
+            stage_span!("svd", rows = tfidf_matrix.nrows(), columns = tfidf_matrix.ncols());
+            tfidf_matrix.svd(true, true)
+        };
+        cancellation_token.check()?;
+        let u_k = svd_matrix.u.unwrap().columns(0, k).into_owned();
+        let inverse_singular_values = GenericMatrix::from_diagonal(&svd_matrix.singular_values.rows(0, k).map(|value| if value > 0. { 1. / value } else { 0. }));
+        let document_embeddings = svd_matrix.v_t.unwrap().rows(0, k).into_owned();
+
+        let terms: Vec<String> = terms.iter().map(|term| term.to_string()).collect();
+        let term_indices = terms.iter().enumerate().map(|(index, term)| (term.clone(), index)).collect();
+        let doc_ids = (0..document_embeddings.ncols() as u64).map(DocId).collect();
+        Ok(LsaModel {
+            terms,
+            term_indices,
+            inverse_document_frequencies,
+            term_projection: inverse_singular_values * u_k.transpose(),
+            document_embeddings,
+            doc_ids,
+        })
+    }
+
+    // checks that the vocabulary, projection, embeddings, and document ids of a loaded model agree
+    pub(crate) fn check_consistency(&self) -> Result<(), RnltkError> {
+        let num_terms = self.terms.len();
+        let problem = if self.term_projection.ncols() != num_terms || self.inverse_document_frequencies.len() != num_terms {
+            Some(format!("{num_terms} terms but {} projection columns and {} inverse document frequencies",
+                self.term_projection.ncols(), self.inverse_document_frequencies.len()))
+        } else if !term_indices_match(&self.terms, &self.term_indices) {
+            Some("term indices don't match the terms".to_string())
+        } else if self.document_embeddings.nrows() != self.term_projection.nrows() {
+            Some(format!("{} dimensional projection but {} dimensional document embeddings", self.term_projection.nrows(), self.document_embeddings.nrows()))
+        } else if self.doc_ids.len() != self.document_embeddings.ncols() {
+            Some(format!("{} document ids but {} documents", self.doc_ids.len(), self.document_embeddings.ncols()))
+        } else {
+            None
+        };
+        problem.map_or(Ok(()), |problem| Err(RnltkError::ModelFormat(problem)))
+    }
+
+    /// Creates new instance of LsaModel from a [`Corpus`], using its terms as the vocabulary and
+    /// its [`DocId`]s to identify documents.
+    /// 
+    /// # Errors
+    /// 
+    /// Returns [`RnltkError::TermFrequenciesEmpty`] if the corpus has no tokens and
+    /// [`RnltkError::LsaOutOfBounds`] if `k` is greater than the number of terms or documents.
+    pub fn from_corpus(corpus: &Corpus, k: usize) -> Result<Self, RnltkError> {
+        let mut lsa_model = LsaModel::new(corpus.get_terms(), &corpus.get_document_term_frequencies()?, k)?;
+        lsa_model.doc_ids = corpus.get_doc_ids();
+        Ok(lsa_model)
+    }
+
+    /// Gets the ids of the documents, one per column of [`LsaModel::get_document_embeddings`].
+    /// Models created with [`LsaModel::new`] number documents by column.
+    pub fn get_doc_ids(&self) -> &[DocId] {
+        &self.doc_ids
+    }
+
+    /// Gets the vocabulary of the model, in row order.
+    pub fn get_terms(&self) -> &[String] {
+        &self.terms
+    }
+
+    /// Gets the latent embedding of every document as the columns of a k × documents matrix.
+    pub fn get_document_embeddings(&self) -> &GenericMatrix {
+        &self.document_embeddings
+    }
+
+    /// Embeds `text` into the latent space by tokenizing it, building a TF-IDF term vector against the
+    /// stored vocabulary, and folding it in with \(\hat{q} = \Sigma_k^{-1} U_k^T q\).
+    /// 
+    /// Words outside the vocabulary are ignored, so a query with no known words embeds to the zero vector.
+    /// The result can be compared to the columns of [`LsaModel::get_document_embeddings`] with cosine similarity.
+    ///
+    /// # Examples
+    /// 
+    /// ```
+    /// use rnltk::corpus::CorpusReader;
+    /// use rnltk::document::LsaModel;
+    /// 
+    /// let corpus = CorpusReader::new().read_texts(vec![
+    ///     "bees make honey in the hive",
+    ///     "the hive of bees",
+    ///     "the rocket reached orbit",
+    ///     "a rocket in orbit",
+    /// ]).unwrap();
+    /// let lsa_model = LsaModel::from_corpus(&corpus, 2).unwrap();
+    /// 
+    /// let embedding = lsa_model.embed_sentence("honey bees");
+    /// assert_eq!(embedding.len(), 2);
+    /// assert!(lsa_model.embed_sentence("unknown words").iter().all(|value| *value == 0.));
+    /// ```
+    pub fn embed_sentence(&self, text: &str) -> Vec<f64> {
+        let mut term_vector = nalgebra::DVector::zeros(self.terms.len());
+        for token in token::tokenize_sentence(text) {
+            if let Some(index) = self.term_indices.get(&token) {
+                term_vector[*index] += self.inverse_document_frequencies[*index];
+            }
+        }
+        let norm = term_vector.norm();
+        if norm > 0. {
+            term_vector /= norm;
+        }
+        (&self.term_projection * term_vector).iter().copied().collect()
+    }
+
+    /// Ranks every document by the cosine similarity of its embedding to the embedding of `text`,
+    /// returning `(document id, similarity)` pairs from most to least similar.
+    ///
+    /// # Examples
+    /// 
+    /// ```
+    /// use rnltk::corpus::{CorpusReader, DocId};
+    /// use rnltk::document::LsaModel;
+    /// 
+    /// let corpus = CorpusReader::new().read_texts(vec![
+    ///     "bees make honey in the hive",
+    ///     "the hive of bees",
+    ///     "the rocket reached orbit",
+    ///     "a rocket in orbit",
+    /// ]).unwrap();
+    /// let lsa_model = LsaModel::from_corpus(&corpus, 2).unwrap();
+    /// let ranking = lsa_model.rank_documents("orbit");
+    /// 
+    /// assert!(ranking[0].0 >= DocId(2) && ranking[1].0 >= DocId(2));
+    /// ```
+    pub fn rank_documents(&self, text: &str) -> Vec<(DocId, f64)> {
+        let embedding = self.embed_sentence(text);
+        let mut ranking: Vec<(DocId, f64)> = self.doc_ids.iter().zip(self.document_embeddings.column_iter())
+            .map(|(doc_id, column)| (*doc_id, similarity::cosine(&embedding, column.as_slice())))
+            .collect();
+        ranking.sort_by(|(_, first), (_, second)| second.total_cmp(first));
+        ranking
+    }
+}
+
+impl fmt::Display for DocumentTermFrequencies {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&LabeledMatrix::with_default_labels(&self.document_term_frequencies, "Term", "Document"), f)
+    }
+}
+
+impl fmt::Display for TfidfMatrix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&LabeledMatrix::with_default_labels(&self.tfidf_matrix, "Term", "Document"), f)
+    }
+}
+
+impl fmt::Display for CosineSimilarityMatrix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&LabeledMatrix::with_default_labels(&self.cosine_similarity_matrix, "Document", "Document"), f)
+    }
+}
+
+impl fmt::Display for LsaCosineSimilarityMatrix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&LabeledMatrix::with_default_labels(&self.lsa_cosine_similarity_matrix, "Document", "Document"), f)
+    }
 }
 
 #[cfg(test)]
@@ -253,7 +1108,7 @@ mod tests {
     
     #[test]
     fn tfidf() {
-        let document_term_frequencies: DocumentTermFrequencies = DocumentTermFrequencies::new(sample_data::get_term_frequencies());
+        let document_term_frequencies: DocumentTermFrequencies = DocumentTermFrequencies::new(sample_data::get_term_frequencies()).unwrap();
         let tfidf_matrix= DMatrix::from_row_slice(11, 4, &[0.3535533905932738, 0., 0., 0.,
                                                                             0., FRAC_1_SQRT_2, 0., 0.,
                                                                             0., 0., 0.447213595499958, 0.33333333333333337,
@@ -271,7 +1126,7 @@ mod tests {
 
     #[test]
     fn cosine_similarity() {
-        let document_term_frequencies: DocumentTermFrequencies = DocumentTermFrequencies::new(sample_data::get_term_frequencies());
+        let document_term_frequencies: DocumentTermFrequencies = DocumentTermFrequencies::new(sample_data::get_term_frequencies()).unwrap();
         let tfidf_matrix = document_term_frequencies.get_tfidf_from_term_frequencies();
         let cosine_similarity_matrix = DMatrix::from_row_slice(4, 4, &[1., 0., 0., 0.,
                                                                                             0., 1., 0., 0.,
@@ -283,7 +1138,7 @@ mod tests {
 
     #[test]
     fn lsa_cosine_similarity() {
-        let document_term_frequencies: DocumentTermFrequencies = DocumentTermFrequencies::new(sample_data::get_term_frequencies());
+        let document_term_frequencies: DocumentTermFrequencies = DocumentTermFrequencies::new(sample_data::get_term_frequencies()).unwrap();
         let tfidf_matrix = document_term_frequencies.get_tfidf_from_term_frequencies();
         let lsa_cosine_similarity_matrix = DMatrix::from_row_slice(4, 4, &[1., 0.5, 0.5, 0.5,
                                                                                             0.5, 1., 0.5, 0.5,
@@ -292,4 +1147,134 @@ mod tests {
         let output = tfidf_matrix.get_lsa_cosine_similarity_from_tfidf(2).unwrap();
         assert_eq!(output.lsa_cosine_similarity_matrix, lsa_cosine_similarity_matrix);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn document_rows_are_transposed() {
+        let term_rows = DocumentTermFrequencies::from_term_rows(sample_data::get_term_frequencies()).unwrap();
+        let document_rows = DocumentTermFrequencies::from_document_rows(sample_data::get_term_frequencies().transpose()).unwrap();
+        assert_eq!(term_rows.document_term_frequencies, document_rows.document_term_frequencies);
+        assert_eq!(
+            term_rows.get_tfidf_from_term_frequencies().tfidf_matrix,
+            document_rows.get_tfidf_from_term_frequencies().tfidf_matrix
+        );
+    }
+
+    #[test]
+    fn empty_term_frequencies_error() {
+        let error = DocumentTermFrequencies::from_term_rows(GenericMatrix::zeros(0, 3)).unwrap_err();
+        assert_eq!(error, RnltkError::TermFrequenciesEmpty);
+    }
+
+    #[test]
+    fn negative_term_frequencies_error() {
+        let term_frequencies = DMatrix::from_row_slice(2, 2, &[1., -1., 0., 2.]);
+        let error = DocumentTermFrequencies::from_document_rows(term_frequencies.clone()).unwrap_err();
+        assert_eq!(error, RnltkError::TermFrequenciesInvalid);
+        assert_eq!(DocumentTermFrequencies::new(term_frequencies).unwrap_err(), RnltkError::TermFrequenciesInvalid);
+    }
+
+    #[test]
+    fn deserialization_validates_term_frequencies() {
+        let document_term_frequencies = DocumentTermFrequencies::new(sample_data::get_term_frequencies()).unwrap();
+        let json = serde_json::to_string(&document_term_frequencies).unwrap();
+        let deserialized: DocumentTermFrequencies = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.document_term_frequencies, document_term_frequencies.document_term_frequencies);
+
+        let negative = DocumentTermFrequencies { document_term_frequencies: DMatrix::from_row_slice(1, 2, &[1., -1.]) };
+        let error = serde_json::from_str::<DocumentTermFrequencies>(&serde_json::to_string(&negative).unwrap()).unwrap_err();
+        assert!(error.to_string().contains("finite and non-negative"), "{error}");
+        let empty = DocumentTermFrequencies { document_term_frequencies: GenericMatrix::zeros(0, 2) };
+        assert!(serde_json::from_str::<DocumentTermFrequencies>(&serde_json::to_string(&empty).unwrap()).is_err());
+    }
+
+    #[test]
+    fn labeled_matrix_display() {
+        let document_term_frequencies = DocumentTermFrequencies::new(DMatrix::from_row_slice(2, 2, &[1., 0., 2., 3.])).unwrap();
+        let labeled_matrix = document_term_frequencies.with_labels(vec!["dagger", "a very long term name"], vec!["doc a", "doc b"]).unwrap();
+        let expected = "              doc a  doc b\ndagger          1.0    0.0\na very long…    2.0    3.0\n";
+        assert_eq!(format!("{:.1}", labeled_matrix), expected);
+    }
+
+    #[test]
+    fn default_labels_display_truncates() {
+        let document_term_frequencies = DocumentTermFrequencies::new(GenericMatrix::zeros(13, 1)).unwrap();
+        let output = document_term_frequencies.to_string();
+        assert!(output.starts_with("         Document 1\nTerm 1         0.00\n"));
+        assert!(output.ends_with("… 1 more rows\n"));
+    }
+
+    #[test]
+    fn label_count_mismatch_error() {
+        let document_term_frequencies = DocumentTermFrequencies::new(sample_data::get_term_frequencies()).unwrap();
+        let error = document_term_frequencies.get_tfidf_from_term_frequencies().get_cosine_similarity_from_tfidf().with_labels(vec!["one"]).unwrap_err();
+        assert_eq!(error, RnltkError::LabelCountMismatch);
+    }
+
+    #[test]
+    fn tfidf_serde_round_trip() {
+        let document_term_frequencies: DocumentTermFrequencies = DocumentTermFrequencies::new(sample_data::get_term_frequencies()).unwrap();
+        let tfidf_matrix = document_term_frequencies.get_tfidf_from_term_frequencies();
+        let json = serde_json::to_string(&tfidf_matrix).unwrap();
+        let deserialized: TfidfMatrix = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.tfidf_matrix, tfidf_matrix.tfidf_matrix);
+    }
+
+    #[test]
+    fn lsa_fold_in_matches_document_embeddings() {
+        let texts = vec!["bees make honey in the hive", "the hive of bees", "the rocket reached orbit", "a rocket in orbit"];
+        let corpus = crate::corpus::CorpusReader::new().read_texts(texts.clone()).unwrap();
+        let lsa_model = LsaModel::from_corpus(&corpus, 2).unwrap();
+        for (index, text) in texts.iter().enumerate() {
+            let embedding = lsa_model.embed_sentence(text);
+            let expected = lsa_model.get_document_embeddings().column(index);
+            for (value, expected_value) in embedding.iter().zip(expected.iter()) {
+                assert!((value - expected_value).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn lsa_ranking_keeps_ids_of_filtered_corpus() {
+        let texts = vec!["bees make honey in the hive", "skip", "the hive of bees", "the rocket reached orbit", "a rocket in orbit"];
+        let corpus = crate::corpus::CorpusReader::new().read_texts(texts).unwrap().filter(|document| document.text != "skip");
+        let lsa_model = LsaModel::from_corpus(&corpus, 2).unwrap();
+        assert_eq!(lsa_model.get_doc_ids(), [DocId(0), DocId(2), DocId(3), DocId(4)]);
+        let ranking = lsa_model.rank_documents("rocket orbit");
+        assert!(ranking[..2].iter().all(|(doc_id, _)| *doc_id >= DocId(3)));
+    }
+
+    #[test]
+    fn lsa_model_errors() {
+        let term_frequencies = DocumentTermFrequencies::new(DMatrix::from_row_slice(2, 2, &[1., 0., 0., 1.])).unwrap();
+        assert_eq!(LsaModel::new(vec!["one"], &term_frequencies, 1).unwrap_err(), RnltkError::LabelCountMismatch);
+        assert_eq!(LsaModel::new(vec!["one", "two"], &term_frequencies, 3).unwrap_err(), RnltkError::LsaOutOfBounds);
+        let cancellation_token = CancellationToken::new();
+        cancellation_token.cancel();
+        assert_eq!(LsaModel::new_cancellable(vec!["one", "two"], &term_frequencies, 1, &cancellation_token).unwrap_err(), RnltkError::Cancelled);
+    }
+
+    #[test]
+    fn unit_weights_match_unweighted_tfidf() {
+        let document_term_frequencies = DocumentTermFrequencies::new(sample_data::get_term_frequencies()).unwrap();
+        let unweighted = document_term_frequencies.get_tfidf_from_term_frequencies();
+        let weighted = document_term_frequencies.get_tfidf_from_term_frequencies_weighted(&[1.; 4]).unwrap();
+        assert_eq!(unweighted.tfidf_matrix, weighted.tfidf_matrix);
+    }
+
+    #[test]
+    fn invalid_document_weights() {
+        let document_term_frequencies = DocumentTermFrequencies::new(sample_data::get_term_frequencies()).unwrap();
+        assert_eq!(document_term_frequencies.get_tfidf_from_term_frequencies_weighted(&[1.; 3]).unwrap_err(), RnltkError::DocumentWeightsInvalid);
+        assert_eq!(document_term_frequencies.get_tfidf_from_term_frequencies_weighted(&[1., 1., -1., 1.]).unwrap_err(), RnltkError::DocumentWeightsInvalid);
+    }
+
+    #[test]
+    fn zero_document_weights() {
+        let document_term_frequencies = DocumentTermFrequencies::new(DMatrix::from_row_slice(2, 2, &[1., 0., 1., 1.])).unwrap();
+        let tfidf_matrix = document_term_frequencies.get_tfidf_from_term_frequencies_weighted(&[0., 1.]).unwrap();
+        assert!(tfidf_matrix.get_tfidf_matrix().iter().all(|weight| *weight == 0.));
+
+        let tfidf_matrix = document_term_frequencies.get_tfidf_from_term_frequencies_weighted(&[0., 0.]).unwrap();
+        assert!(tfidf_matrix.get_tfidf_matrix().iter().all(|weight| weight.is_finite()));
+    }
+}
@@ -14,5 +14,124 @@ pub enum RnltkError {
     #[error("Could not stem term due to non-ASCII characters present")]
     StemNonAscii,
     #[error("Value 'k' must fall within 1 <= k <= n, where n is the number of columns in the TF-IDF matrix")]
-    LsaOutOfBounds
+    LsaOutOfBounds,
+    /// A term frequency matrix had no terms or no documents
+    #[error("Term frequency matrix must contain at least one term and one document")]
+    TermFrequenciesEmpty,
+    /// A term frequency matrix contained a negative or non-finite frequency, or a fractional one where counts are needed
+    #[error("Term frequencies must be finite and non-negative, and whole numbers where counts are needed")]
+    TermFrequenciesInvalid,
+    /// A dictionary file could not be read
+    #[error("Could not read dictionary: {0}")]
+    DictionaryIo(String),
+    /// A dictionary file was read but could not be parsed
+    #[error("Invalid dictionary format: {0}")]
+    DictionaryFormat(String),
+    /// The number of row or column labels didn't match the size of the matrix being labeled
+    #[error("Number of labels must match the number of matrix rows and columns")]
+    LabelCountMismatch,
+    /// A sentiment lexicon file could not be read
+    #[error("Could not read lexicon: {0}")]
+    LexiconIo(String),
+    /// A sentiment lexicon was read but could not be parsed in its detected format
+    #[error("Could not load lexicon as {format}: {reason}")]
+    LexiconLoad {
+        format: String,
+        reason: String
+    },
+    /// A corpus document or directory could not be read
+    #[error("Could not read corpus: {0}")]
+    CorpusIo(String),
+    /// The operation was cancelled through a [`CancellationToken`](crate::cancel::CancellationToken)
+    #[error("Operation was cancelled")]
+    Cancelled,
+    /// Results could not be converted to or from a columnar data format
+    #[error("Columnar conversion failed: {0}")]
+    Interop(String),
+    /// Document weights must match the number of documents and be finite and non-negative
+    #[error("Document weights must have one finite, non-negative weight per document")]
+    DocumentWeightsInvalid,
+    /// Time periods must have a positive length
+    #[error("Period length must be positive")]
+    InvalidPeriod,
+    /// Documents must span at most [`MAX_PERIODS`](crate::trends::MAX_PERIODS) periods, since every period between them
+    /// is kept, and every period must start at a timestamp that fits in an `i64`
+    #[error("Documents span too many periods")]
+    TooManyPeriods,
+    /// N-gram models must have an order of at least 1
+    #[error("N-gram order must be at least 1")]
+    InvalidNgramOrder,
+    /// Additive (add-k) smoothing needs a finite, positive k and Kneser-Ney smoothing a discount between 0 and 1
+    #[error("Additive smoothing must be finite and positive, and Kneser-Ney discounts must be between 0 and 1")]
+    InvalidSmoothing,
+    /// Logistic regression needs a finite, positive learning rate and a finite, non-negative L2 penalty
+    #[error("Learning rate must be finite and positive, and the L2 penalty must be finite and non-negative")]
+    InvalidTrainingConfig,
+    /// Text generation needs a finite, positive temperature and a non-zero top-k and beam width
+    #[error("Temperature must be finite and positive, and top-k and beam width must be at least 1")]
+    InvalidGenerationConfig,
+    /// Compared matrices must have the same number of rows and columns
+    #[error("Matrices have different shapes")]
+    MatrixShapeMismatch,
+    /// Chunks must allow at least one token, and their overlap must be smaller than their size
+    #[error("Chunk size must be at least 1 and larger than the overlap")]
+    InvalidChunkSize,
+    /// Lexicon expansion needs a damping factor greater than 0 and at most 1
+    #[error("Damping must be greater than 0 and at most 1")]
+    InvalidExpansionConfig,
+    /// A deserialized word graph needs sorted, unique words, a list of edges for every word, and neighbors among its words
+    #[error("Invalid word graph: {0}")]
+    InvalidWordGraph(String),
+    /// A deserialized pipeline needs a configuration for its fallback language
+    #[error("Pipeline has no configuration for its fallback language")]
+    PipelineFallbackMissing,
+    /// Deduplication needs non-zero shingle size, hashes, and bands, bands that divide the hashes, a threshold between 0 and 1,
+    /// and a maximum Hamming distance below 64
+    #[error("Shingle size, hashes, and bands must be at least 1, bands must divide hashes, the threshold must be between 0 and 1, and the maximum Hamming distance must be below 64")]
+    InvalidDedupConfig,
+    /// Intent matching needs a confidence threshold between 0 and 1
+    #[error("Intent threshold must be between 0 and 1")]
+    InvalidIntentThreshold,
+    /// A slot extractor pattern isn't a valid regex
+    #[error("Invalid slot pattern: {0}")]
+    InvalidSlotPattern(String),
+    /// A sentence separator pattern isn't a valid regex
+    #[error("Invalid sentence pattern: {0}")]
+    InvalidSentencePattern(String),
+    /// A stop word list could not be read
+    #[error("Could not read stop words: {0}")]
+    StopWordsIo(String),
+    /// A stop word list was read but is not a JSON array of strings
+    #[error("Invalid stop word list: {0}")]
+    StopWordsFormat(String),
+    /// Per-token values must have one value for every token of the document
+    #[error("Number of values must match the number of tokens")]
+    TokenCountMismatch,
+    /// Summaries need a relevance weight between 0 and 1
+    #[error("Summary lambda must be between 0 and 1")]
+    InvalidSummaryConfig,
+    /// Topic models need at least one topic, no more seeded topics than topics, seed words from the vocabulary, and a finite, non-negative seed weight
+    #[error("Invalid topic model configuration: {0}")]
+    InvalidTopicConfig(String),
+    /// A tokenizer pattern isn't a valid regex
+    #[error("Invalid tokenizer pattern: {0}")]
+    InvalidTokenizerPattern(String),
+    /// A model file could not be read or written
+    #[error("Could not access model file: {0}")]
+    ModelIo(String),
+    /// A model file was read but is not a supported model of the expected kind
+    #[error("Invalid model file: {0}")]
+    ModelFormat(String),
+    /// A tokenizer vocabulary could not be read
+    #[error("Could not read vocabulary: {0}")]
+    VocabularyIo(String),
+    /// A tokenizer vocabulary was read but is unusable
+    #[error("Invalid vocabulary: {0}")]
+    VocabularyFormat(String),
+    /// A sentiment lexicon entry was parsed but its values were unusable
+    #[error("Invalid lexicon entry '{word}': {reason}")]
+    LexiconFormat {
+        word: String,
+        reason: String
+    }
 }
\ No newline at end of file
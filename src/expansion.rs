@@ -0,0 +1,378 @@
+//! Module containing semi-supervised sentiment lexicon expansion.
+//!
+//! Starting from seed words with known valence and arousal, scores are propagated over a
+//! [`WordGraph`] of related words, built either from corpus co-occurrences or from word embeddings.
+//! The result is a list of [`LexiconCandidate`]s with a confidence for each, meant to be
+//! reviewed by hand before being added to a lexicon.
+
+use std::collections::{BTreeMap, HashMap};
+
+use serde::{Serialize, Deserialize};
+
+use crate::error::RnltkError;
+use crate::sentiment::{CustomWords, Score, SentimentDictValue};
+
+/// Weighted, undirected graph of related words.
+///
+/// Deserializing fails unless the words are sorted and unique, every word has a list of edges,
+/// and every neighbor is one of the words.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(try_from = "UncheckedWordGraph")]
+pub struct WordGraph {
+    words: Vec<String>,
+    // neighbors and edge weights for every word, sorted by neighbor index
+    edges: Vec<Vec<(usize, f64)>>,
+}
+
+// WordGraph as deserialized, before its words and edges are checked
+#[derive(Deserialize)]
+struct UncheckedWordGraph {
+    words: Vec<String>,
+    edges: Vec<Vec<(usize, f64)>>,
+}
+
+impl TryFrom<UncheckedWordGraph> for WordGraph {
+    type Error = RnltkError;
+
+    fn try_from(value: UncheckedWordGraph) -> Result<Self, Self::Error> {
+        if !value.words.windows(2).all(|pair| pair[0] < pair[1]) {
+            return Err(RnltkError::InvalidWordGraph("words must be sorted and unique".to_string()));
+        }
+        if value.edges.len() != value.words.len() {
+            return Err(RnltkError::InvalidWordGraph(format!("{} words but {} lists of edges", value.words.len(), value.edges.len())));
+        }
+        if let Some((neighbor, _)) = value.edges.iter().flatten().find(|(neighbor, _)| *neighbor >= value.words.len()) {
+            return Err(RnltkError::InvalidWordGraph(format!("neighbor {} is out of range for {} words", neighbor, value.words.len())));
+        }
+        Ok(WordGraph {
+            words: value.words,
+            edges: value.edges,
+        })
+    }
+}
+
+impl WordGraph {
+    /// Creates new instance of WordGraph from tokenized `documents`, connecting words that occur within
+    /// `window` tokens of each other. Edges are weighted by the number of co-occurrences, and words
+    /// occurring fewer than `min_count` times across all documents are left out.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rnltk::expansion::WordGraph;
+    ///
+    /// let documents = vec![vec!["happy", "sunny", "day"], vec!["sunny", "beach", "day"]];
+    /// let graph = WordGraph::from_cooccurrences(&documents, 2, 2);
+    ///
+    /// assert_eq!(graph.words(), &["day", "sunny"]);
+    /// assert_eq!(graph.weight("sunny", "day"), 2.);
+    /// ```
+    pub fn from_cooccurrences<T: AsRef<str>>(documents: &[Vec<T>], window: usize, min_count: usize) -> Self {
+        let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+        for token in documents.iter().flatten() {
+            *counts.entry(token.as_ref()).or_insert(0) += 1;
+        }
+        let words: Vec<String> = counts.into_iter()
+            .filter(|(_, count)| *count >= min_count)
+            .map(|(word, _)| word.to_string())
+            .collect();
+        let indices = index_words(&words);
+
+        let mut edges: Vec<BTreeMap<usize, f64>> = vec![BTreeMap::new(); words.len()];
+        for document in documents {
+            let nodes: Vec<Option<usize>> = document.iter().map(|token| indices.get(token.as_ref()).copied()).collect();
+            for (position, first) in nodes.iter().enumerate() {
+                for second in nodes.iter().skip(position + 1).take(window) {
+                    if let (Some(first), Some(second)) = (*first, *second) {
+                        if first != second {
+                            *edges[first].entry(second).or_insert(0.) += 1.;
+                            *edges[second].entry(first).or_insert(0.) += 1.;
+                        }
+                    }
+                }
+            }
+        }
+
+        WordGraph {
+            words,
+            edges: edges.into_iter().map(|neighbors| neighbors.into_iter().collect()).collect(),
+        }
+    }
+
+    /// Creates new instance of WordGraph from word `embeddings`, connecting every word to its `neighbors`
+    /// most cosine-similar words. Edges are weighted by cosine similarity, and dissimilar words
+    /// (a similarity of 0 or less) are never connected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use rnltk::expansion::WordGraph;
+    ///
+    /// let embeddings = HashMap::from([
+    ///     ("joy".to_string(), vec![1., 0.1]),
+    ///     ("delight".to_string(), vec![0.9, 0.2]),
+    ///     ("grief".to_string(), vec![-1., 0.1]),
+    /// ]);
+    /// let graph = WordGraph::from_embeddings(&embeddings, 1);
+    ///
+    /// assert!(graph.weight("joy", "delight") > 0.9);
+    /// assert_eq!(graph.weight("joy", "grief"), 0.);
+    /// ```
+    pub fn from_embeddings(embeddings: &HashMap<String, Vec<f64>>, neighbors: usize) -> Self {
+        let mut words: Vec<String> = embeddings.keys().cloned().collect();
+        words.sort();
+        let vectors: Vec<&Vec<f64>> = words.iter().map(|word| &embeddings[word]).collect();
+
+        let mut edges: Vec<BTreeMap<usize, f64>> = vec![BTreeMap::new(); words.len()];
+        for (first, first_vector) in vectors.iter().enumerate() {
+            let mut similarities: Vec<(usize, f64)> = vectors.iter().enumerate()
+                .filter(|(second, _)| *second != first)
+                .map(|(second, second_vector)| (second, cosine_similarity(first_vector, second_vector)))
+                .filter(|(_, similarity)| *similarity > 0.)
+                .collect();
+            similarities.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+            for (second, similarity) in similarities.into_iter().take(neighbors) {
+                edges[first].insert(second, similarity);
+                edges[second].insert(first, similarity);
+            }
+        }
+
+        WordGraph {
+            words,
+            edges: edges.into_iter().map(|neighbors| neighbors.into_iter().collect()).collect(),
+        }
+    }
+
+    /// Gets the words in the graph, in sorted order.
+    pub fn words(&self) -> &[String] {
+        &self.words
+    }
+
+    /// Gets the weight of the edge between `first` and `second`, or 0 if they aren't connected.
+    pub fn weight(&self, first: &str, second: &str) -> f64 {
+        let (Ok(first), Ok(second)) = (self.index_of(first), self.index_of(second)) else {
+            return 0.;
+        };
+        self.edges[first].iter()
+            .find(|(neighbor, _)| *neighbor == second)
+            .map_or(0., |(_, weight)| *weight)
+    }
+
+    fn index_of(&self, word: &str) -> Result<usize, usize> {
+        self.words.binary_search_by(|probe| probe.as_str().cmp(word))
+    }
+}
+
+fn index_words(words: &[String]) -> HashMap<&str, usize> {
+    words.iter().enumerate().map(|(index, word)| (word.as_str(), index)).collect()
+}
+
+fn cosine_similarity(first: &[f64], second: &[f64]) -> f64 {
+    let dot: f64 = first.iter().zip(second).map(|(a, b)| a * b).sum();
+    let norms = first.iter().map(|a| a * a).sum::<f64>().sqrt() * second.iter().map(|b| b * b).sum::<f64>().sqrt();
+    if norms == 0. {
+        0.
+    } else {
+        dot / norms
+    }
+}
+
+/// Settings for [`expand_lexicon`].
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct ExpansionConfig {
+    /// Number of propagation rounds. Scores spread one edge further from the seeds in every round
+    pub iterations: usize,
+    /// Share of confidence kept with every edge a score travels, between 0 (exclusive) and 1
+    pub damping: f64,
+}
+
+impl Default for ExpansionConfig {
+    fn default() -> Self {
+        Self {
+            iterations: 20,
+            damping: 0.85,
+        }
+    }
+}
+
+/// Candidate lexicon entry produced by [`expand_lexicon`].
+///
+/// The `mean` of each score is the weighted average of the word's scored neighbors, and the `std` is how
+/// much those neighbors disagree. `confidence` is between 0 and 1, and falls with the share of the word's
+/// edges that lead to scored words and with its distance from the seeds.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct LexiconCandidate {
+    pub word: String,
+    pub valence: Score,
+    pub arousal: Score,
+    pub confidence: f64,
+}
+
+impl LexiconCandidate {
+    /// Converts the candidate to a [`SentimentDictValue`], ready to be added to a lexicon once reviewed.
+    pub fn to_dict_value(&self) -> SentimentDictValue {
+        SentimentDictValue::from_scores(&self.word, self.valence, self.arousal)
+    }
+}
+
+/// Propagates the valence and arousal of `seeds` over `graph` to the words without a score.
+///
+/// Seeds are matched to graph words by their lexicon key. Every round, each unscored word takes the
+/// average of its neighbors' scores weighted by edge weight and neighbor confidence, so words connected
+/// mostly to positive seeds become positive. Candidates are sorted by descending confidence, and words
+/// that no score reaches are left out.
+///
+/// # Errors
+///
+/// Returns [`RnltkError::InvalidExpansionConfig`] if `damping` isn't within 0 (exclusive) and 1.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::expansion::{self, ExpansionConfig, WordGraph};
+/// use rnltk::sentiment::{CustomWords, Score, SentimentDictValue};
+///
+/// let seeds = CustomWords::from([
+///     ("happy".to_string(), SentimentDictValue::from_scores("happy", Score::new(8.5, 1.), Score::new(6.0, 1.))),
+///     ("sad".to_string(), SentimentDictValue::from_scores("sad", Score::new(1.6, 1.), Score::new(3.5, 1.))),
+/// ]);
+/// let documents = vec![
+///     vec!["happy", "sunshine"], vec!["sunshine", "happy"], vec!["sad", "rain"], vec!["rain", "sad", "sunshine"],
+/// ];
+/// let graph = WordGraph::from_cooccurrences(&documents, 1, 1);
+/// let candidates = expansion::expand_lexicon(&seeds, &graph, ExpansionConfig::default()).unwrap();
+/// let sunshine = candidates.iter().find(|candidate| candidate.word == "sunshine").unwrap();
+/// let rain = candidates.iter().find(|candidate| candidate.word == "rain").unwrap();
+///
+/// assert!(sunshine.valence.mean > 5. && rain.valence.mean < 5.);
+/// ```
+pub fn expand_lexicon(seeds: &CustomWords, graph: &WordGraph, config: ExpansionConfig) -> Result<Vec<LexiconCandidate>, RnltkError> {
+    if !(config.damping > 0. && config.damping <= 1.) {
+        return Err(RnltkError::InvalidExpansionConfig);
+    }
+
+    let mut is_seed = vec![false; graph.words.len()];
+    let mut confidence = vec![0.; graph.words.len()];
+    // valence and arousal means for every word
+    let mut means = vec![[0.; 2]; graph.words.len()];
+    let mut stds = vec![[0.; 2]; graph.words.len()];
+    for (word, value) in seeds {
+        if let Ok(index) = graph.index_of(word) {
+            is_seed[index] = true;
+            confidence[index] = 1.;
+            means[index] = [value.get_valence().mean, value.get_arousal().mean];
+        }
+    }
+
+    for _ in 0..config.iterations {
+        let mut next_confidence = confidence.clone();
+        let mut next_means = means.clone();
+        for (index, neighbors) in graph.edges.iter().enumerate().filter(|(index, _)| !is_seed[*index]) {
+            let total_weight: f64 = neighbors.iter().map(|(_, weight)| weight).sum();
+            let scored_weight: f64 = neighbors.iter().map(|(neighbor, weight)| weight * confidence[*neighbor]).sum();
+            if scored_weight == 0. {
+                continue;
+            }
+            next_confidence[index] = config.damping * scored_weight / total_weight;
+            for dimension in 0..2 {
+                next_means[index][dimension] = neighbors.iter()
+                    .map(|(neighbor, weight)| weight * confidence[*neighbor] * means[*neighbor][dimension])
+                    .sum::<f64>() / scored_weight;
+            }
+        }
+        confidence = next_confidence;
+        means = next_means;
+    }
+
+    for (index, neighbors) in graph.edges.iter().enumerate().filter(|(index, _)| !is_seed[*index] && confidence[*index] > 0.) {
+        let scored_weight: f64 = neighbors.iter().map(|(neighbor, weight)| weight * confidence[*neighbor]).sum();
+        for dimension in 0..2 {
+            let variance = neighbors.iter()
+                .map(|(neighbor, weight)| weight * confidence[*neighbor] * (means[*neighbor][dimension] - means[index][dimension]).powi(2))
+                .sum::<f64>() / scored_weight;
+            stds[index][dimension] = variance.sqrt();
+        }
+    }
+
+    let mut candidates: Vec<LexiconCandidate> = graph.words.iter().enumerate()
+        .filter(|(index, _)| !is_seed[*index] && confidence[*index] > 0.)
+        .map(|(index, word)| LexiconCandidate {
+            word: word.to_string(),
+            valence: Score::new(means[index][0], stds[index][0]),
+            arousal: Score::new(means[index][1], stds[index][1]),
+            confidence: confidence[index],
+        })
+        .collect();
+    candidates.sort_by(|a, b| b.confidence.total_cmp(&a.confidence).then_with(|| a.word.cmp(&b.word)));
+
+    Ok(candidates)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seed(word: &str, valence: f64, arousal: f64) -> (String, SentimentDictValue) {
+        (word.to_string(), SentimentDictValue::from_scores(word, Score::new(valence, 1.), Score::new(arousal, 1.)))
+    }
+
+    #[test]
+    fn test_confidence_falls_with_distance() {
+        let seeds = CustomWords::from([seed("great", 8., 6.)]);
+        let documents = vec![vec!["great", "near", "far"]];
+        let graph = WordGraph::from_cooccurrences(&documents, 1, 1);
+        let candidates = expand_lexicon(&seeds, &graph, ExpansionConfig::default()).unwrap();
+        let words: Vec<&str> = candidates.iter().map(|candidate| candidate.word.as_str()).collect();
+        assert_eq!(words, vec!["near", "far"]);
+        assert!(candidates[0].confidence > candidates[1].confidence);
+        assert!((candidates[1].valence.mean - 8.).abs() < 1e-9);
+        assert_eq!(candidates[1].valence.std, 0.);
+    }
+
+    #[test]
+    fn test_mixed_neighbors_disagree() {
+        let seeds = CustomWords::from([seed("good", 8., 5.), seed("bad", 2., 5.)]);
+        let documents = vec![vec!["good", "meh", "bad"]];
+        let graph = WordGraph::from_cooccurrences(&documents, 1, 1);
+        let candidates = expand_lexicon(&seeds, &graph, ExpansionConfig::default()).unwrap();
+        assert_eq!(candidates.len(), 1);
+        assert!((candidates[0].valence.mean - 5.).abs() < 1e-9);
+        assert!((candidates[0].valence.std - 3.).abs() < 1e-9);
+        assert!((candidates[0].confidence - 0.85).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_unreachable_words_are_left_out() {
+        let seeds = CustomWords::from([seed("good", 8., 5.)]);
+        let documents = vec![vec!["good", "day"], vec!["lone", "island"]];
+        let graph = WordGraph::from_cooccurrences(&documents, 1, 1);
+        let candidates = expand_lexicon(&seeds, &graph, ExpansionConfig::default()).unwrap();
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].word, "day");
+    }
+
+    #[test]
+    fn test_deserialization_validates_graph() {
+        let graph = WordGraph::from_cooccurrences(&[vec!["good", "day"]], 1, 1);
+        let json = serde_json::to_string(&graph).unwrap();
+        assert_eq!(serde_json::from_str::<WordGraph>(&json).unwrap(), graph);
+        for json in [
+            r#"{"words":["a","b"],"edges":[[[5,1.0]],[]]}"#,
+            r#"{"words":["a","b"],"edges":[[]]}"#,
+            r#"{"words":["b","a"],"edges":[[],[]]}"#,
+            r#"{"words":["a","a"],"edges":[[],[]]}"#,
+        ] {
+            assert!(serde_json::from_str::<WordGraph>(json).unwrap_err().to_string().contains("Invalid word graph"));
+        }
+    }
+
+    #[test]
+    fn test_invalid_damping() {
+        let graph = WordGraph::default();
+        for damping in [0., 1.5, f64::NAN] {
+            let config = ExpansionConfig { damping, ..ExpansionConfig::default() };
+            assert_eq!(expand_lexicon(&CustomWords::new(), &graph, config).unwrap_err(), RnltkError::InvalidExpansionConfig);
+        }
+    }
+}
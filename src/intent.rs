@@ -0,0 +1,453 @@
+//! Module containing a lightweight intent matcher, which classifies chatbot messages by comparing them
+//! with example utterances of each intent.
+//!
+//! Examples and messages are compared by the cosine similarity of their TF-IDF vectors, using the smoothed
+//! inverse document frequency \\(idf_i = ln(\frac{1 + n}{1 + n_i}) + 1\\) over all `n` examples.
+//! The confidence of an intent is the similarity of its closest example.
+//!
+//! A [`SlotFiller`] extracts slots such as dates, numbers, or entries of custom lists from the same message,
+//! so [`IntentMatcher::parse`] turns an utterance into a structured [`Intent`].
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::OnceLock;
+
+use regex::Regex;
+use serde::{Serialize, Deserialize};
+
+use crate::error::RnltkError;
+use crate::token::{self, CaseFolding, TokenConfig};
+
+/// Result of [`IntentMatcher::match_intent`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct IntentMatch {
+    pub name: String,
+    /// Similarity of the message to the closest example of the best intent, between 0 and 1
+    pub confidence: f64,
+    /// Whether no intent reached the threshold, so `name` is the fallback intent
+    pub is_fallback: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct IntentExample {
+    intent: String,
+    text: String,
+    term_counts: BTreeMap<String, u64>,
+}
+
+/// Matcher classifying messages into intents defined by example utterances.
+///
+/// Messages are tokenized with a [`TokenConfig`] that stems words but keeps stop words, since words
+/// such as "how" or "not" often carry the intent. Messages whose best intent has a confidence below the
+/// threshold get the fallback intent, if one is set.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::intent::IntentMatcher;
+///
+/// let mut matcher = IntentMatcher::new(0.3).unwrap().fallback("unknown");
+/// matcher.add_intent("greeting", &["hello there", "hi", "good morning"]);
+/// matcher.add_intent("reset_password", &["how do I reset my password", "I forgot my password"]);
+///
+/// let reset = matcher.match_intent("I need to reset my password").unwrap();
+/// assert_eq!(reset.name, "reset_password");
+///
+/// let unknown = matcher.match_intent("what's the weather like").unwrap();
+/// assert_eq!((unknown.name.as_str(), unknown.is_fallback), ("unknown", true));
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct IntentMatcher {
+    threshold: f64,
+    fallback: Option<String>,
+    config: TokenConfig,
+    examples: Vec<IntentExample>,
+    // number of examples containing each term
+    document_frequencies: BTreeMap<String, u64>,
+}
+
+impl IntentMatcher {
+    /// Creates new instance of IntentMatcher with no intents, matching messages whose best intent
+    /// has a confidence of at least `threshold`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RnltkError::InvalidIntentThreshold`] if `threshold` isn't between 0 and 1.
+    pub fn new(threshold: f64) -> Result<Self, RnltkError> {
+        if !(0. ..=1.).contains(&threshold) {
+            return Err(RnltkError::InvalidIntentThreshold);
+        }
+        Ok(IntentMatcher {
+            threshold,
+            fallback: None,
+            config: TokenConfig::builder().remove_stop_words(false).build(),
+            examples: vec![],
+            document_frequencies: BTreeMap::new(),
+        })
+    }
+
+    // checks that the threshold of a loaded matcher is one `new` accepts
+    pub(crate) fn check_consistency(&self) -> Result<(), RnltkError> {
+        if !(0. ..=1.).contains(&self.threshold) {
+            return Err(RnltkError::ModelFormat(format!("threshold {} isn't between 0 and 1", self.threshold)));
+        }
+        Ok(())
+    }
+
+    /// Sets the intent given to messages that don't reach the threshold. Defaults to none, so
+    /// such messages don't match.
+    pub fn fallback(mut self, fallback: impl ToString) -> Self {
+        self.fallback = Some(fallback.to_string());
+        self
+    }
+
+    /// Sets the configuration used to tokenize examples and messages. Intents added before
+    /// are tokenized again.
+    pub fn token_config(mut self, config: TokenConfig) -> Self {
+        self.config = config;
+        let examples = std::mem::take(&mut self.examples);
+        self.document_frequencies.clear();
+        for example in examples {
+            self.add_example(example.intent, example.text);
+        }
+        self
+    }
+
+    /// Adds `examples` of the intent `name`, adding to any examples it already has.
+    /// Examples without any tokens are skipped.
+    pub fn add_intent<T: AsRef<str>>(&mut self, name: impl ToString, examples: &[T]) {
+        let name = name.to_string();
+        for example in examples {
+            self.add_example(name.clone(), example.as_ref().to_string());
+        }
+    }
+
+    fn add_example(&mut self, intent: String, text: String) {
+        let term_counts = token::get_term_counts_from_sentence_configurable(&text, self.config.clone());
+        if term_counts.is_empty() {
+            return;
+        }
+        for term in term_counts.keys() {
+            *self.document_frequencies.entry(term.to_string()).or_insert(0) += 1;
+        }
+        self.examples.push(IntentExample { intent, text, term_counts });
+    }
+
+    /// Gets the names of the intents with at least one example, in sorted order.
+    pub fn intents(&self) -> Vec<&str> {
+        let mut intents: Vec<&str> = self.examples.iter().map(|example| example.intent.as_str()).collect();
+        intents.sort_unstable();
+        intents.dedup();
+        intents
+    }
+
+    fn tfidf(&self, term_counts: &BTreeMap<String, u64>) -> BTreeMap<String, f64> {
+        let num_examples = self.examples.len() as f64;
+        term_counts.iter()
+            .map(|(term, count)| {
+                let document_frequency = self.document_frequencies.get(term).copied().unwrap_or_default() as f64;
+                let inverse_document_frequency = ((1. + num_examples) / (1. + document_frequency)).ln() + 1.;
+                (term.to_string(), *count as f64 * inverse_document_frequency)
+            })
+            .collect()
+    }
+
+    /// Gets the confidence of every intent for `message`, in descending order of confidence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rnltk::intent::IntentMatcher;
+    ///
+    /// let mut matcher = IntentMatcher::new(0.5).unwrap();
+    /// matcher.add_intent("order_status", &["where is my order", "track my order"]);
+    /// matcher.add_intent("cancel_order", &["cancel my order"]);
+    /// let ranked = matcher.rank_intents("please cancel the order");
+    ///
+    /// assert_eq!(ranked[0].0, "cancel_order");
+    /// assert!(ranked[0].1 > ranked[1].1);
+    /// ```
+    pub fn rank_intents(&self, message: &str) -> Vec<(String, f64)> {
+        let message_weights = self.tfidf(&token::get_term_counts_from_sentence_configurable(message, self.config.clone()));
+        let mut confidences: BTreeMap<&str, f64> = BTreeMap::new();
+        for example in &self.examples {
+            let similarity = sparse_cosine(&message_weights, &self.tfidf(&example.term_counts));
+            let confidence = confidences.entry(&example.intent).or_insert(0.);
+            *confidence = confidence.max(similarity);
+        }
+        let mut ranked: Vec<(String, f64)> = confidences.into_iter().map(|(name, confidence)| (name.to_string(), confidence)).collect();
+        // stable sort keeps ties in alphabetical order
+        ranked.sort_by(|first, second| second.1.total_cmp(&first.1));
+        ranked
+    }
+
+    /// Gets the intent of `message`, or the fallback intent if no intent reaches the threshold.
+    /// Returns `None` if no intent reaches the threshold and there is no fallback.
+    pub fn match_intent(&self, message: &str) -> Option<IntentMatch> {
+        let (name, confidence) = self.rank_intents(message).into_iter().next().unwrap_or_default();
+        if !name.is_empty() && confidence >= self.threshold {
+            Some(IntentMatch { name, confidence, is_fallback: false })
+        } else {
+            self.fallback.as_ref().map(|fallback| IntentMatch { name: fallback.to_string(), confidence, is_fallback: true })
+        }
+    }
+
+    /// Gets the intent of `message` as with [`IntentMatcher::match_intent`], together with the slots
+    /// `slot_filler` finds in it.
+    pub fn parse(&self, message: &str, slot_filler: &SlotFiller) -> Option<Intent> {
+        self.match_intent(message).map(|matched| Intent {
+            name: matched.name,
+            confidence: matched.confidence,
+            is_fallback: matched.is_fallback,
+            slots: slot_filler.extract(message),
+        })
+    }
+}
+
+/// A slot value found in a message by a [`SlotFiller`].
+///
+/// `value` is the matched text, or the entry as written in the gazetteer for [`SlotExtractor::gazetteer`] slots,
+/// and `start` and `end` are the byte offsets of the match in the message.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Slot {
+    pub name: String,
+    pub value: String,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Result of [`IntentMatcher::parse`], an [`IntentMatch`] together with the slots of the message.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Intent {
+    pub name: String,
+    pub confidence: f64,
+    pub is_fallback: bool,
+    /// Slots in the order they appear in the message
+    pub slots: Vec<Slot>,
+}
+
+const MONTHS: &str = "january|jan|february|feb|march|mar|april|apr|may|june|jun|july|jul|august|aug|september|sept|sep|october|oct|november|nov|december|dec";
+const WEEKDAYS: &str = "monday|mon|tuesday|tues|tue|wednesday|wed|thursday|thurs|thu|friday|fri|saturday|sat|sunday|sun";
+
+fn date_regex() -> &'static Regex {
+    static DATE: OnceLock<Regex> = OnceLock::new();
+    DATE.get_or_init(|| {
+        let pattern = [
+            r"\d{4}-\d{1,2}-\d{1,2}".to_string(),
+            r"\d{1,2}/\d{1,2}(?:/\d{2,4})?".to_string(),
+            format!(r"(?:{MONTHS})\.?\s+\d{{1,2}}(?:st|nd|rd|th)?(?:,?\s+\d{{4}})?"),
+            format!(r"\d{{1,2}}(?:st|nd|rd|th)?\s+(?:of\s+)?(?:{MONTHS})(?:,?\s+\d{{4}})?"),
+            format!(r"(?:(?:next|this|last)\s+)?(?:{WEEKDAYS}|week|month|year)"),
+            "today|tonight|tomorrow|yesterday".to_string(),
+        ].join("|");
+        Regex::new(&format!(r"(?i)\b(?:{pattern})\b")).expect("Invalid regex")
+    })
+}
+
+fn number_regex() -> &'static Regex {
+    static NUMBER: OnceLock<Regex> = OnceLock::new();
+    NUMBER.get_or_init(|| Regex::new(r"[-+]?\b(?:\d{1,3}(?:,\d{3})+|\d+)(?:\.\d+)?\b").expect("Invalid regex"))
+}
+
+#[derive(Debug, Clone)]
+enum ExtractorKind {
+    Pattern(Regex),
+    // regex matching any entry, and the entries by their lowercase form
+    Gazetteer(Regex, BTreeMap<String, String>),
+}
+
+/// Extractor finding the values of a slot in a message, used by [`SlotFiller`].
+#[derive(Debug, Clone)]
+pub struct SlotExtractor(ExtractorKind);
+
+impl SlotExtractor {
+    /// Creates new instance of SlotExtractor finding dates, such as "2024-03-01", "3/1/2024", "March 1st",
+    /// "1 March 2024", "next friday", or "tomorrow".
+    pub fn date() -> Self {
+        SlotExtractor(ExtractorKind::Pattern(date_regex().clone()))
+    }
+
+    /// Creates new instance of SlotExtractor finding numbers written with digits, such as "3", "-2.5", or "1,000".
+    pub fn number() -> Self {
+        SlotExtractor(ExtractorKind::Pattern(number_regex().clone()))
+    }
+
+    /// Creates new instance of SlotExtractor finding text matching the regex `pattern`. If the pattern has
+    /// capture groups, the first group that matched is the value.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RnltkError::InvalidSlotPattern`] if `pattern` isn't a valid regex.
+    pub fn pattern(pattern: &str) -> Result<Self, RnltkError> {
+        let regex = Regex::new(pattern).map_err(|error| RnltkError::InvalidSlotPattern(error.to_string()))?;
+        Ok(SlotExtractor(ExtractorKind::Pattern(regex)))
+    }
+
+    /// Creates new instance of SlotExtractor finding any of the `entries`, such as product or city names,
+    /// as whole words regardless of case. Longer entries are preferred over entries they contain.
+    pub fn gazetteer<T: ToString>(entries: impl IntoIterator<Item = T>) -> Self {
+        let entries: Vec<String> = entries.into_iter()
+            .map(|entry| entry.to_string())
+            .filter(|entry| !entry.trim().is_empty())
+            .collect();
+        // the regex only knows simple case folding, so it searches for every entry as given, lowercased, and
+        // case folded, while matches are looked up by their case folded form
+        let mut alternatives: Vec<String> = entries.iter()
+            .flat_map(|entry| [entry.clone(), entry.to_lowercase(), CaseFolding::Unicode.fold(entry).into_owned()])
+            .collect::<BTreeSet<String>>()
+            .into_iter()
+            .collect();
+        let entries: BTreeMap<String, String> = entries.into_iter()
+            .map(|entry| (CaseFolding::Unicode.fold(&entry).into_owned(), entry))
+            .collect();
+        alternatives.sort_by_key(|entry| std::cmp::Reverse(entry.len()));
+        let alternatives: Vec<String> = alternatives.iter().map(|entry| regex::escape(entry)).collect();
+        // an empty alternation would match everywhere, so an empty gazetteer matches nothing
+        let pattern = if alternatives.is_empty() { r"[^\s\S]".to_string() } else { format!(r"(?i)\b(?:{})\b", alternatives.join("|")) };
+        SlotExtractor(ExtractorKind::Gazetteer(Regex::new(&pattern).expect("Invalid regex"), entries))
+    }
+
+    // (value, start, end) of every match in `message`
+    fn find(&self, message: &str) -> Vec<(String, usize, usize)> {
+        match &self.0 {
+            ExtractorKind::Pattern(regex) => regex.captures_iter(message)
+                .map(|captures| {
+                    let found = captures.iter().skip(1).flatten().next().unwrap_or_else(|| captures.get(0).expect("match has a whole capture"));
+                    (found.as_str().to_string(), found.start(), found.end())
+                })
+                .collect(),
+            ExtractorKind::Gazetteer(regex, entries) => regex.find_iter(message)
+                .filter_map(|found| {
+                    let entry = entries.get(CaseFolding::Unicode.fold(found.as_str()).as_ref())?;
+                    Some((entry.to_string(), found.start(), found.end()))
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Named slots to extract from messages.
+///
+/// Slots are extracted in the order they were added, and text already taken by an earlier slot isn't
+/// used again, so add more specific slots, such as dates, before numbers.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::intent::{IntentMatcher, SlotExtractor, SlotFiller};
+///
+/// let mut matcher = IntentMatcher::new(0.3).unwrap();
+/// matcher.add_intent("book_flight", &["book a flight to Paris", "book seats to Berlin", "I want to fly to Berlin"]);
+/// let slot_filler = SlotFiller::new()
+///     .slot("date", SlotExtractor::date())
+///     .slot("passengers", SlotExtractor::number())
+///     .slot("city", SlotExtractor::gazetteer(["Paris", "Berlin", "New York"]));
+///
+/// let intent = matcher.parse("Book 2 seats to new york on March 3rd", &slot_filler).unwrap();
+/// let slots: Vec<(&str, &str)> = intent.slots.iter().map(|slot| (slot.name.as_str(), slot.value.as_str())).collect();
+///
+/// assert_eq!(intent.name, "book_flight");
+/// assert_eq!(slots, vec![("passengers", "2"), ("city", "New York"), ("date", "March 3rd")]);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SlotFiller {
+    slots: Vec<(String, SlotExtractor)>,
+}
+
+impl SlotFiller {
+    /// Creates new instance of SlotFiller with no slots.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the slot `name`, found with `extractor`.
+    pub fn slot(mut self, name: impl ToString, extractor: SlotExtractor) -> Self {
+        self.slots.push((name.to_string(), extractor));
+        self
+    }
+
+    /// Gets the slots found in `message`, in the order they appear.
+    pub fn extract(&self, message: &str) -> Vec<Slot> {
+        let mut slots: Vec<Slot> = vec![];
+        for (name, extractor) in &self.slots {
+            for (value, start, end) in extractor.find(message) {
+                if slots.iter().all(|slot| end <= slot.start || start >= slot.end) {
+                    slots.push(Slot { name: name.to_string(), value, start, end });
+                }
+            }
+        }
+        slots.sort_by_key(|slot| slot.start);
+        slots
+    }
+}
+
+fn sparse_cosine(first: &BTreeMap<String, f64>, second: &BTreeMap<String, f64>) -> f64 {
+    let dot_product: f64 = first.iter().filter_map(|(term, weight)| second.get(term).map(|other| weight * other)).sum();
+    let first_norm = first.values().map(|weight| weight * weight).sum::<f64>().sqrt();
+    let second_norm = second.values().map(|weight| weight * weight).sum::<f64>().sqrt();
+    if first_norm == 0. || second_norm == 0. {
+        return 0.;
+    }
+    dot_product / (first_norm * second_norm)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matcher() -> IntentMatcher {
+        let mut matcher = IntentMatcher::new(0.4).unwrap();
+        matcher.add_intent("greeting", &["hello", "hi there", "good morning"]);
+        matcher.add_intent("goodbye", &["bye", "see you later", "good night"]);
+        matcher.add_intent("greeting", &["hey"]);
+        matcher
+    }
+
+    #[test]
+    fn exact_example_matches_fully() {
+        let matched = matcher().match_intent("See you later!").unwrap();
+        assert_eq!(matched.name, "goodbye");
+        assert!((matched.confidence - 1.).abs() < 1e-12);
+        assert_eq!(matcher().match_intent("Hey!").unwrap().name, "greeting");
+        assert_eq!(matcher().intents(), vec!["goodbye", "greeting"]);
+    }
+
+    #[test]
+    fn below_threshold_without_fallback() {
+        assert_eq!(matcher().match_intent("quantum chromodynamics"), None);
+        assert_eq!(matcher().match_intent(""), None);
+        assert_eq!(IntentMatcher::new(0.5).unwrap().fallback("none").match_intent("hello").unwrap().confidence, 0.);
+        assert!(matches!(IntentMatcher::new(1.5), Err(RnltkError::InvalidIntentThreshold)));
+    }
+
+    #[test]
+    fn slot_extractors() {
+        let slot_filler = SlotFiller::new()
+            .slot("date", SlotExtractor::date())
+            .slot("order", SlotExtractor::pattern(r"#(\d+)").unwrap())
+            .slot("amount", SlotExtractor::number())
+            .slot("empty", SlotExtractor::gazetteer(Vec::<String>::new()));
+        let slots = slot_filler.extract("Refund 1,250.50 for order #42 from 2024-03-01, not tomorrow");
+        let values: Vec<(&str, &str)> = slots.iter().map(|slot| (slot.name.as_str(), slot.value.as_str())).collect();
+        assert_eq!(values, vec![("amount", "1,250.50"), ("order", "42"), ("date", "2024-03-01"), ("date", "tomorrow")]);
+        assert!(matches!(SlotExtractor::pattern("("), Err(RnltkError::InvalidSlotPattern(_))));
+        assert!(SlotFiller::new().slot("date", SlotExtractor::date()).extract("Maybe monday-ish isn't a marchday").iter().all(|slot| slot.value == "monday"));
+    }
+
+    #[test]
+    fn gazetteer_matches_case_variants() {
+        let values = |entries: &[&str], message: &str| -> Vec<String> {
+            SlotFiller::new().slot("place", SlotExtractor::gazetteer(entries.to_vec())).extract(message).into_iter().map(|slot| slot.value).collect()
+        };
+        assert_eq!(values(&["ς"], "σ"), vec!["ς"]);
+        assert_eq!(values(&["λόγοσ"], "λόγος"), vec!["λόγοσ"]);
+        assert_eq!(values(&["Straße"], "STRASSE or STRAßE"), vec!["Straße", "Straße"]);
+        assert_eq!(values(&["İzmir"], "İzmir or izmir"), vec!["İzmir"]);
+    }
+
+    #[test]
+    fn changing_config_keeps_intents() {
+        let matcher = matcher().token_config(TokenConfig::builder().stem(false).build());
+        assert_eq!(matcher.intents(), vec!["goodbye", "greeting"]);
+        assert_eq!(matcher.match_intent("good morning").unwrap().name, "greeting");
+    }
+}
@@ -0,0 +1,141 @@
+//! Module containing language identification from function words, and stop word lists for the supported languages.
+//!
+//! Detection counts how many words of a text are frequent function words ("the", "und", "que", ...) of each
+//! language, which works well for sentences and documents but not for a word or two.
+
+use std::fmt;
+
+use serde::{Serialize, Deserialize};
+
+use crate::token::{self, StopWords};
+
+const SPANISH_STOP_WORDS: [&str; 80] = ["de", "la", "que", "el", "en", "y", "a", "los", "del", "se", "las", "por", "un", "para", "con", "no", "una", "su", "al", "lo", "como", "más", "pero", "sus", "le", "ya", "o", "este", "sí", "porque", "esta", "entre", "cuando", "muy", "sin", "sobre", "también", "me", "hasta", "hay", "donde", "quien", "desde", "todo", "nos", "durante", "todos", "uno", "les", "ni", "contra", "otros", "ese", "eso", "ante", "ellos", "e", "esto", "mí", "antes", "algunos", "qué", "unos", "yo", "otro", "otras", "otra", "él", "tanto", "esa", "estos", "mucho", "nada", "muchos", "ella", "es", "son", "fue", "ha", "está"];
+
+const FRENCH_STOP_WORDS: [&str; 68] = ["au", "aux", "avec", "ce", "ces", "dans", "de", "des", "du", "elle", "en", "et", "eux", "il", "je", "la", "le", "les", "leur", "lui", "ma", "mais", "me", "même", "mes", "moi", "mon", "ne", "nos", "notre", "nous", "on", "ou", "par", "pas", "pour", "qu", "que", "qui", "sa", "se", "ses", "son", "sur", "ta", "te", "tes", "toi", "ton", "tu", "un", "une", "vos", "votre", "vous", "c", "d", "j", "l", "à", "m", "n", "s", "t", "y", "été", "est", "sont"];
+
+const GERMAN_STOP_WORDS: [&str; 71] = ["aber", "alle", "als", "also", "am", "an", "auch", "auf", "aus", "bei", "bin", "bis", "bist", "da", "damit", "dann", "der", "den", "des", "dem", "die", "das", "dass", "du", "er", "es", "ein", "eine", "einem", "einen", "einer", "eines", "für", "hat", "hatte", "ich", "ihr", "im", "in", "ist", "ja", "kann", "mit", "nach", "nicht", "noch", "nun", "oder", "sein", "sie", "sind", "so", "über", "um", "und", "uns", "von", "vor", "war", "wie", "wir", "wird", "zu", "zum", "zur", "werden", "wurde", "man", "mich", "mir", "sich"];
+
+const ITALIAN_STOP_WORDS: [&str; 72] = ["ad", "al", "allo", "ai", "agli", "alla", "alle", "con", "col", "da", "dal", "dallo", "dai", "dagli", "dalla", "dalle", "di", "del", "dello", "dei", "degli", "della", "delle", "in", "nel", "nello", "nei", "negli", "nella", "nelle", "su", "sul", "sullo", "sui", "sugli", "sulla", "sulle", "per", "tra", "contro", "io", "tu", "lui", "lei", "noi", "voi", "loro", "mio", "mia", "il", "lo", "la", "i", "gli", "le", "un", "uno", "una", "e", "è", "ed", "che", "non", "più", "ma", "come", "anche", "sono", "era", "questo", "questa", "quello"];
+
+const PORTUGUESE_STOP_WORDS: [&str; 72] = ["de", "a", "o", "que", "e", "do", "da", "em", "um", "para", "com", "não", "uma", "os", "no", "se", "na", "por", "mais", "as", "dos", "como", "mas", "ao", "ele", "das", "à", "seu", "sua", "ou", "quando", "muito", "nos", "já", "eu", "também", "só", "pelo", "pela", "até", "isso", "ela", "entre", "depois", "sem", "mesmo", "aos", "seus", "quem", "nas", "me", "esse", "eles", "você", "essa", "num", "nem", "suas", "meu", "às", "minha", "numa", "pelos", "elas", "qual", "nós", "lhe", "este", "é", "são", "foi", "está"];
+
+const DUTCH_STOP_WORDS: [&str; 72] = ["de", "en", "van", "ik", "te", "dat", "die", "in", "een", "hij", "het", "niet", "zijn", "is", "was", "op", "aan", "met", "als", "voor", "had", "er", "maar", "om", "hem", "dan", "zou", "of", "wat", "mijn", "men", "dit", "zo", "door", "over", "ze", "zich", "bij", "ook", "tot", "je", "mij", "uit", "der", "daar", "haar", "naar", "heb", "hoe", "heeft", "hebben", "deze", "u", "want", "nog", "zal", "me", "zij", "nu", "geen", "omdat", "iets", "worden", "toch", "al", "waren", "veel", "meer", "doen", "toen", "moet", "ben"];
+
+/// Languages that [`detect_language`] can identify.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Language {
+    English,
+    Spanish,
+    French,
+    German,
+    Italian,
+    Portuguese,
+    Dutch,
+}
+
+impl Language {
+    /// Every supported language, in the order ties are broken by [`detect_language`].
+    pub const ALL: [Language; 7] = [Language::English, Language::Spanish, Language::French, Language::German, Language::Italian, Language::Portuguese, Language::Dutch];
+
+    /// Gets the ISO 639-1 code of the language, such as "en".
+    pub fn code(&self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::Spanish => "es",
+            Language::French => "fr",
+            Language::German => "de",
+            Language::Italian => "it",
+            Language::Portuguese => "pt",
+            Language::Dutch => "nl",
+        }
+    }
+
+    /// Gets the language with the ISO 639-1 `code`, if it's supported.
+    pub fn from_code(code: &str) -> Option<Language> {
+        Language::ALL.into_iter().find(|language| language.code().eq_ignore_ascii_case(code))
+    }
+
+    /// Gets the stop words of the language. English uses the NLTK list of [`token::get_stop_words`].
+    pub fn stop_words(&self) -> &'static [&'static str] {
+        match self {
+            Language::English => token::StopWordList::Nltk.words(),
+            Language::Spanish => &SPANISH_STOP_WORDS,
+            Language::French => &FRENCH_STOP_WORDS,
+            Language::German => &GERMAN_STOP_WORDS,
+            Language::Italian => &ITALIAN_STOP_WORDS,
+            Language::Portuguese => &PORTUGUESE_STOP_WORDS,
+            Language::Dutch => &DUTCH_STOP_WORDS,
+        }
+    }
+
+    /// Gets the stop words of the language as [`StopWords`].
+    pub fn get_stop_words(&self) -> StopWords {
+        self.stop_words().iter().map(|word| word.to_string()).collect::<Vec<String>>().into()
+    }
+}
+
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.code())
+    }
+}
+
+/// Gets the most likely language of `text`, or `None` if none of its words are function words of a supported language.
+///
+/// Each language scores the number of words of `text`, case folded, that are among its stop words. The highest
+/// score wins, with ties going to the language listed first in [`Language::ALL`].
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::language::{self, Language};
+///
+/// assert_eq!(language::detect_language("The cat is sleeping on the sofa."), Some(Language::English));
+/// assert_eq!(language::detect_language("El gato está durmiendo en el sofá."), Some(Language::Spanish));
+/// assert_eq!(language::detect_language("Die Katze schläft auf dem Sofa und träumt."), Some(Language::German));
+/// assert_eq!(language::detect_language("12345"), None);
+/// ```
+pub fn detect_language(text: &str) -> Option<Language> {
+    let tokens = token::tokenize_sentence(text);
+    let mut best: Option<(Language, usize)> = None;
+    for language in Language::ALL {
+        let stop_words = language.stop_words();
+        let score = tokens.iter().filter(|token| stop_words.contains(&token.as_str())).count();
+        if score > 0 && best.is_none_or(|(_, best_score)| score > best_score) {
+            best = Some((language, score));
+        }
+    }
+    best.map(|(language, _)| language)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_each_language() {
+        let samples = [
+            (Language::English, "I would like to know where the nearest train station is, please."),
+            (Language::Spanish, "Me gustaría saber dónde está la estación de tren más cercana, por favor."),
+            (Language::French, "Je voudrais savoir où se trouve la gare la plus proche, s'il vous plaît."),
+            (Language::German, "Ich möchte wissen, wo der nächste Bahnhof ist, bitte."),
+            (Language::Italian, "Vorrei sapere dove si trova la stazione più vicina, per favore."),
+            (Language::Portuguese, "Eu gostaria de saber onde fica a estação de trem mais próxima, por favor."),
+            (Language::Dutch, "Ik zou graag willen weten waar het dichtstbijzijnde station is, alstublieft."),
+        ];
+        for (language, text) in samples {
+            assert_eq!(detect_language(text), Some(language), "{text}");
+        }
+    }
+
+    #[test]
+    fn language_codes() {
+        for language in Language::ALL {
+            assert_eq!(Language::from_code(language.code()), Some(language));
+            assert!(language.get_stop_words().contains(language.stop_words()[0]));
+        }
+        assert_eq!(Language::from_code("EN"), Some(Language::English));
+        assert_eq!(Language::from_code("xx"), None);
+        assert_eq!(Language::German.to_string(), "de");
+    }
+}
@@ -0,0 +1,205 @@
+//! Module containing pluggable lookup backends for large sentiment lexicons.
+//!
+//! [`SentimentModel`](crate::sentiment::SentimentModel) keeps small, editable lexicons in a
+//! [`CustomWords`] `HashMap`. For million-entry lexicons, the per-entry `String` and `Vec`
+//! allocations dominate startup time and memory, so a read-only [`LexiconBackend`] can be
+//! attached with [`SentimentModelBuilder::backend`](crate::sentiment::SentimentModelBuilder::backend)
+//! instead. [`CompactLexicon`] is the built-in backend; FST or memory-mapped stores can be
+//! plugged in by implementing the trait.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::Range;
+
+use crate::sentiment::{CustomWords, Score, SentimentDictValue};
+
+/// Read-only lookup of sentiment lexicon entries.
+pub trait LexiconBackend: fmt::Debug + Send + Sync {
+    /// Gets the entry for `term`, or `None` if the term is not in the lexicon.
+    fn get(&self, term: &str) -> Option<Cow<'_, SentimentDictValue>>;
+
+    /// Gets the valence and arousal [`Score`]s of `term`, or `None` if the term is not in the lexicon.
+    /// Backends that build entries on lookup can override this to skip building the word and stem.
+    fn get_scores(&self, term: &str) -> Option<(Score, Score)> {
+        self.get(term).map(|value| (value.get_valence(), value.get_arousal()))
+    }
+
+    /// Checks if `term` is in the lexicon.
+    fn contains(&self, term: &str) -> bool {
+        self.get(term).is_some()
+    }
+
+    /// Gets the number of entries in the lexicon.
+    fn len(&self) -> usize;
+
+    /// Checks if the lexicon has no entries.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl LexiconBackend for HashMap<String, SentimentDictValue> {
+    fn get(&self, term: &str) -> Option<Cow<'_, SentimentDictValue>> {
+        HashMap::get(self, term).map(Cow::Borrowed)
+    }
+
+    fn contains(&self, term: &str) -> bool {
+        self.contains_key(term)
+    }
+
+    fn len(&self) -> usize {
+        HashMap::len(self)
+    }
+}
+
+/// Memory-compact, read-only lexicon backend.
+///
+/// All words and stems are stored in a single string buffer, their ranges in two index buffers,
+/// and all valence/arousal values in a single `f64` buffer, sorted by word, so a lexicon needs four
+/// allocations regardless of its size. Lookups are a binary search over slices of the word buffer,
+/// and [`LexiconBackend::get_scores`] doesn't allocate; [`LexiconBackend::get`] builds an owned entry.
+#[derive(Debug, Clone, Default)]
+pub struct CompactLexicon {
+    text: String,
+    words: Vec<Range<usize>>,
+    stems: Vec<Range<usize>>,
+    // valence average, arousal average, valence std, arousal std for every entry
+    values: Vec<[f64; 4]>,
+}
+
+impl CompactLexicon {
+    /// Creates new instance of CompactLexicon from `(word, stem, avg, std)` entries, where `avg`
+    /// and `std` are `[valence, arousal]` pairs. If a word appears more than once, the last entry wins.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rnltk::lexicon::{CompactLexicon, LexiconBackend};
+    ///
+    /// let lexicon = CompactLexicon::from_entries(vec![
+    ///     ("bees", "bee", [3.2, 6.51], [2.07, 2.14]),
+    ///     ("abduction", "abduct", [2.76, 5.53], [2.06, 2.43]),
+    /// ]);
+    /// let entry = lexicon.get("bees").unwrap();
+    ///
+    /// assert_eq!(lexicon.len(), 2);
+    /// assert_eq!(entry.stem, "bee");
+    /// assert_eq!(entry.avg, vec![3.2, 6.51]);
+    /// assert!(!lexicon.contains("honey"));
+    /// ```
+    pub fn from_entries<W, S, I>(entries: I) -> Self
+    where
+        W: AsRef<str>,
+        S: AsRef<str>,
+        I: IntoIterator<Item = (W, S, [f64; 2], [f64; 2])>,
+    {
+        let mut entries: Vec<(W, S, [f64; 2], [f64; 2])> = entries.into_iter().collect();
+        // stable sort keeps duplicates in insertion order so the last one can win
+        entries.sort_by(|first, second| first.0.as_ref().cmp(second.0.as_ref()));
+
+        let mut lexicon = CompactLexicon::default();
+        let mut entries = entries.into_iter().peekable();
+        while let Some((word, stem, avg, std)) = entries.next() {
+            let word = word.as_ref();
+            if entries.peek().is_some_and(|next| next.0.as_ref() == word) {
+                continue;
+            }
+            let word_range = lexicon.push_text(word);
+            let stem_range = lexicon.push_text(stem.as_ref());
+            lexicon.words.push(word_range);
+            lexicon.stems.push(stem_range);
+            lexicon.values.push([avg[0], avg[1], std[0], std[1]]);
+        }
+        lexicon.text.shrink_to_fit();
+        lexicon
+    }
+
+    fn push_text(&mut self, text: &str) -> Range<usize> {
+        let start = self.text.len();
+        self.text.push_str(text);
+        start..self.text.len()
+    }
+
+    fn position(&self, term: &str) -> Option<usize> {
+        self.words.binary_search_by(|range| self.text[range.clone()].cmp(term)).ok()
+    }
+}
+
+impl From<&CustomWords> for CompactLexicon {
+    fn from(custom_words: &CustomWords) -> Self {
+        CompactLexicon::from_entries(custom_words.iter().map(|(word, value)| {
+            let (valence, arousal) = (value.get_valence(), value.get_arousal());
+            (word.as_str(), value.get_stem(), [valence.mean, arousal.mean], [valence.std, arousal.std])
+        }))
+    }
+}
+
+impl LexiconBackend for CompactLexicon {
+    fn get(&self, term: &str) -> Option<Cow<'_, SentimentDictValue>> {
+        self.position(term).map(|index| {
+            let [valence_avg, arousal_avg, valence_std, arousal_std] = self.values[index];
+            Cow::Owned(SentimentDictValue::from_scores_with_stem(
+                term,
+                &self.text[self.stems[index].clone()],
+                Score::new(valence_avg, valence_std),
+                Score::new(arousal_avg, arousal_std),
+            ))
+        })
+    }
+
+    fn get_scores(&self, term: &str) -> Option<(Score, Score)> {
+        self.position(term).map(|index| {
+            let [valence_avg, arousal_avg, valence_std, arousal_std] = self.values[index];
+            (Score::new(valence_avg, valence_std), Score::new(arousal_avg, arousal_std))
+        })
+    }
+
+    fn contains(&self, term: &str) -> bool {
+        self.position(term).is_some()
+    }
+
+    fn len(&self) -> usize {
+        self.words.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sample_data;
+
+    #[test]
+    fn compact_lexicon_matches_custom_words() {
+        let custom_words = sample_data::get_sample_custom_word_dict();
+        let lexicon = CompactLexicon::from(&custom_words);
+        assert_eq!(LexiconBackend::len(&lexicon), custom_words.len());
+        for (word, value) in &custom_words {
+            assert_eq!(lexicon.get(word).unwrap().into_owned(), *value);
+            assert_eq!(lexicon.get_scores(word), custom_words.get_scores(word));
+        }
+    }
+
+    #[test]
+    fn last_duplicate_wins() {
+        let lexicon = CompactLexicon::from_entries(vec![
+            ("bees", "bee", [1., 1.], [1., 1.]),
+            ("ants", "ant", [2., 2.], [2., 2.]),
+            ("bees", "be", [3., 3.], [3., 3.]),
+        ]);
+        assert_eq!(lexicon.len(), 2);
+        let entry = lexicon.get("bees").unwrap();
+        assert_eq!(entry.get_stem(), "be");
+        assert_eq!(entry.get_valence(), Score::new(3., 3.));
+        assert_eq!(lexicon.get_scores("bees"), Some((Score::new(3., 3.), Score::new(3., 3.))));
+        // the replaced entry's word and stem aren't kept
+        assert_eq!(lexicon.text, "antsantbeesbe");
+    }
+
+    #[test]
+    fn empty_lexicon() {
+        let lexicon = CompactLexicon::default();
+        assert!(lexicon.is_empty());
+        assert!(lexicon.get("").is_none());
+    }
+}
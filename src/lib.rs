@@ -48,6 +48,22 @@
 //! let sentiment = SentimentModel::new(custom_words_sentiment_hashmap);
 //! ```
 //! 
+//! Outputs are deterministic: maps returned by RNLTK are `BTreeMap`s iterating in key order, and ranked
+//! results break ties in a documented order, so results can be compared across runs and snapshot tested.
+//! 
+//! Enable the `tracing` feature to instrument major pipeline stages (tokenization batches, TF-IDF, SVD,
+//! corpus ingestion, and lexicon loading) with [`tracing`](https://docs.rs/tracing) spans. A batch is
+//! spanned as `tokenize_batch`, and each of its documents as a nested `tokenize_document`.
+//! 
+//! Enable the `morphology` feature for lemmatization and spell checking with Hunspell dictionaries.
+//! 
+//! Enable the `arrow` feature to convert term frequencies, TF-IDF matrices, and sentiment scores to Arrow
+//! record batches, and the `parquet` feature to write them to Parquet files. The `polars` feature adds
+//! conversions to and from polars DataFrames.
+//! 
+//! Enable the `bench_fixtures` feature for reproducible synthetic corpora to benchmark with, as used by the
+//! benchmark suite run with `cargo bench --features bench_fixtures`.
+//! 
 //! Checkout the examples folder in the github project repository for more comprehensive examples.
 //! 
 
@@ -56,4 +72,43 @@ pub mod sentiment;
 pub mod stem;
 pub mod error;
 pub mod sample_data;
-pub mod document;
\ No newline at end of file
+pub mod document;
+pub mod similarity;
+pub mod qa;
+pub mod truecase;
+pub mod sampling;
+pub mod sentence;
+pub mod classify;
+pub mod dialogue;
+pub mod intent;
+pub mod persist;
+pub mod analysis;
+pub mod summarize;
+pub mod lexicon;
+pub mod expansion;
+pub mod corpus;
+pub mod dedup;
+pub mod cancel;
+pub mod trends;
+pub mod conversation;
+pub mod lm;
+pub mod compat;
+pub mod lint;
+pub mod treebank;
+pub mod wordpiece;
+pub mod regexp;
+pub mod coref;
+pub mod topic;
+pub mod language;
+pub mod pipeline;
+#[cfg(feature = "morphology")]
+pub mod morphology;
+#[cfg(feature = "arrow")]
+pub mod columnar;
+#[cfg(feature = "polars")]
+pub mod dataframe;
+#[cfg(feature = "bench_fixtures")]
+pub mod bench_fixtures;
+
+mod ascii;
+mod trace;
\ No newline at end of file
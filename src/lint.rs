@@ -0,0 +1,236 @@
+//! Module containing a rule-based checker that flags terms from a replacement lexicon, such as
+//! gendered or non-inclusive language, together with their location and suggested replacements.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+
+use serde::{Serialize, Deserialize};
+
+use crate::error::RnltkError;
+use crate::{stem, token};
+
+/// Struct for holding a flagged term found by [`LanguageChecker::check`].
+///
+/// `start` and `end` are byte offsets into the checked text and `char_start` and `char_end` are
+/// the equivalent character offsets, as in [`token::TokenSpan`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct LintIssue {
+    /// The flagged text as it appears in the checked text
+    pub text: String,
+    /// The lexicon term that matched
+    pub term: String,
+    pub suggestions: Vec<String>,
+    pub start: usize,
+    pub end: usize,
+    pub char_start: usize,
+    pub char_end: usize,
+}
+
+#[derive(Debug, Clone)]
+struct Rule {
+    term: String,
+    suggestions: Vec<String>,
+}
+
+/// Rule-based checker that flags terms from a replacement lexicon mapping terms, which may be
+/// several words long, to suggested replacements.
+///
+/// Terms are matched case-insensitively on whole tokens, never across sentences, preferring the
+/// longest term at each position. With [`LanguageChecker::match_stems`], inflected forms such as
+/// plurals are matched as well.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::lint::LanguageChecker;
+///
+/// let checker = LanguageChecker::new()
+///     .add_rule("chairman", vec!["chairperson", "chair"])
+///     .add_rule("man hours", vec!["person hours", "work hours"]);
+/// let text = "The Chairman estimated 40 man hours.";
+/// let issues = checker.check(text);
+///
+/// assert_eq!(issues.len(), 2);
+/// assert_eq!(issues[0].text, "Chairman");
+/// assert_eq!(issues[0].suggestions, vec!["chairperson", "chair"]);
+/// assert_eq!(&text[issues[1].start..issues[1].end], "man hours");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct LanguageChecker {
+    rules: HashMap<Vec<String>, Rule>,
+    max_term_length: usize,
+    match_stems: bool,
+}
+
+impl LanguageChecker {
+    /// Creates new instance of LanguageChecker without any rules.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates new instance of LanguageChecker from a JSON dictionary mapping terms to their suggested replacements,
+    /// e.g. `{"chairman": ["chairperson", "chair"]}`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RnltkError::LexiconLoad`] if the contents aren't a JSON dictionary of string arrays.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rnltk::lint::LanguageChecker;
+    ///
+    /// let checker = LanguageChecker::from_json(r#"{"mankind": ["humankind", "humanity"]}"#).unwrap();
+    ///
+    /// assert_eq!(checker.check("For all mankind")[0].suggestions, vec!["humankind", "humanity"]);
+    /// ```
+    pub fn from_json(contents: &str) -> Result<Self, RnltkError> {
+        let lexicon: BTreeMap<String, Vec<String>> = serde_json::from_str(contents)
+            .map_err(|error| RnltkError::LexiconLoad { format: "JSON dictionary".to_string(), reason: error.to_string() })?;
+        Ok(lexicon.into_iter().fold(LanguageChecker::new(), |checker, (term, suggestions)| checker.add_rule(term, suggestions)))
+    }
+
+    /// Creates new instance of LanguageChecker from a JSON dictionary file at `path`, as in [`LanguageChecker::from_json`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RnltkError::LexiconIo`] if the file could not be read.
+    ///
+    /// Returns [`RnltkError::LexiconLoad`] if the contents aren't a JSON dictionary of string arrays.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, RnltkError> {
+        let contents = fs::read_to_string(path).map_err(|error| RnltkError::LexiconIo(error.to_string()))?;
+        LanguageChecker::from_json(&contents)
+    }
+
+    /// Adds a rule flagging `term` with the given `suggestions`, replacing any existing rule for the same term.
+    /// Terms without any tokens are ignored.
+    pub fn add_rule<T: ToString, S: ToString>(mut self, term: T, suggestions: Vec<S>) -> Self {
+        let term = term.to_string();
+        let key = self.normalize(&token::tokenize_sentence(&term));
+        if !key.is_empty() {
+            self.max_term_length = self.max_term_length.max(key.len());
+            self.rules.insert(key, Rule {
+                term,
+                suggestions: suggestions.iter().map(|suggestion| suggestion.to_string()).collect(),
+            });
+        }
+        self
+    }
+
+    /// Sets whether terms are compared by their [`stem::get`] stems, so e.g. a rule for "stewardess" also flags
+    /// "stewardesses". Irregular plurals such as "chairmen" need rules of their own.
+    /// Rules already added are updated.
+    pub fn match_stems(mut self, match_stems: bool) -> Self {
+        self.match_stems = match_stems;
+        let rules = std::mem::take(&mut self.rules);
+        self.max_term_length = 0;
+        rules.into_values().fold(self, |checker, rule| checker.add_rule(rule.term, rule.suggestions))
+    }
+
+    /// Gets the number of rules.
+    pub fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    /// Checks if the checker has no rules.
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    fn normalize(&self, tokens: &[String]) -> Vec<String> {
+        if self.match_stems {
+            tokens.iter().map(|token| stem::get(token).unwrap_or_else(|_| token.to_string())).collect()
+        } else {
+            tokens.to_vec()
+        }
+    }
+
+    /// Gets every flagged term in `text`, in order of appearance.
+    pub fn check(&self, text: &str) -> Vec<LintIssue> {
+        let mut issues = vec![];
+        // characters before the start of the previous sentence, so each part of `text` is only counted once
+        let (mut previous_start, mut char_offset) = (0, 0);
+        for (sentence_start, sentence_end) in token::sentence_bounds(text) {
+            let spans = token::tokenize_sentence_with_spans(&text[sentence_start..sentence_end]);
+            let tokens: Vec<String> = spans.iter().map(|span| span.token.clone()).collect();
+            let tokens = self.normalize(&tokens);
+            char_offset += text[previous_start..sentence_start].chars().count();
+            previous_start = sentence_start;
+
+            let mut index = 0;
+            while index < tokens.len() {
+                let longest_match = (1..=self.max_term_length.min(tokens.len() - index)).rev()
+                    .find_map(|length| self.rules.get(&tokens[index..index + length]).map(|rule| (length, rule)));
+                let Some((length, rule)) = longest_match else {
+                    index += 1;
+                    continue;
+                };
+                let (first, last) = (&spans[index], &spans[index + length - 1]);
+                let (start, end) = (sentence_start + first.start, sentence_start + last.end);
+                issues.push(LintIssue {
+                    text: text[start..end].to_string(),
+                    term: rule.term.clone(),
+                    suggestions: rule.suggestions.clone(),
+                    start,
+                    end,
+                    char_start: char_offset + first.char_start,
+                    char_end: char_offset + last.char_end,
+                });
+                index += length;
+            }
+        }
+        issues
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn terms_do_not_match_across_sentences() {
+        let checker = LanguageChecker::new().add_rule("man hours", vec!["person hours"]);
+        assert!(checker.check("Ask the man. Hours later he left.").is_empty());
+    }
+
+    #[test]
+    fn longest_term_wins() {
+        let checker = LanguageChecker::new()
+            .add_rule("man", vec!["person"])
+            .add_rule("man made", vec!["artificial"]);
+        let issues = checker.check("A man made this, said the man.");
+        let terms: Vec<&str> = issues.iter().map(|issue| issue.term.as_str()).collect();
+        assert_eq!(terms, vec!["man made", "man"]);
+    }
+
+    #[test]
+    fn stems_match_inflections() {
+        let checker = LanguageChecker::new().add_rule("stewardess", vec!["flight attendant"]);
+        assert!(checker.check("Two stewardesses arrived").is_empty());
+        let checker = checker.match_stems(true);
+        assert_eq!(checker.check("Two stewardesses arrived")[0].text, "stewardesses");
+    }
+
+    #[test]
+    fn char_offsets_after_multibyte_text() {
+        let text = "Café owner. The chairman spoke.";
+        let issue = &LanguageChecker::new().add_rule("chairman", vec!["chair"]).check(text)[0];
+        let by_chars: String = text.chars().skip(issue.char_start).take(issue.char_end - issue.char_start).collect();
+        assert_eq!(by_chars, "chairman");
+        assert_eq!(issue.end - issue.start, issue.char_end - issue.char_start);
+
+        let text = "Café chairman. Naïve chairman! Über chairman.";
+        let issues = LanguageChecker::new().add_rule("chairman", vec!["chair"]).check(text);
+        assert_eq!(issues.len(), 3);
+        for issue in issues {
+            let by_chars: String = text.chars().skip(issue.char_start).take(issue.char_end - issue.char_start).collect();
+            assert_eq!(by_chars, "chairman");
+        }
+    }
+
+    #[test]
+    fn invalid_lexicon() {
+        assert!(matches!(LanguageChecker::from_json("[\"chairman\"]"), Err(RnltkError::LexiconLoad { .. })));
+    }
+}
@@ -0,0 +1,619 @@
+//! Module containing an n-gram language model with configurable smoothing and perplexity evaluation.
+//!
+//! Sentences are tokenized with [`token::tokenize_sentence`] and padded with `order - 1`
+//! [`SENTENCE_START`] tokens and one [`SENTENCE_END`] token. Words that weren't seen during
+//! training are scored as [`UNKNOWN`].
+//!
+//! Text can be generated from a trained model by sampling with [`NgramModel::generate`], tuned by a
+//! [`GenerationConfig`], or by searching for the most probable continuations with [`NgramModel::beam_search`].
+
+use std::collections::{BTreeSet, HashMap};
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Serialize, Deserialize};
+
+use crate::error::RnltkError;
+use crate::token;
+
+/// Padding token placed before every sentence.
+pub const SENTENCE_START: &str = "<s>";
+/// Token marking the end of every sentence.
+pub const SENTENCE_END: &str = "</s>";
+/// Token that words outside the training vocabulary are mapped to.
+pub const UNKNOWN: &str = "<unk>";
+
+/// Smoothing strategies for [`NgramModel`] probabilities.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum Smoothing {
+    /// Add-one smoothing, \\(P(w \mid h) = \frac{c(h, w) + 1}{c(h) + V}\\)
+    Laplace,
+    /// Add-k smoothing, \\(P(w \mid h) = \frac{c(h, w) + k}{c(h) + kV}\\)
+    AddK(f64),
+    /// Interpolated Kneser-Ney smoothing with an absolute `discount`, usually 0.75,
+    /// which backs off to continuation counts of lower orders
+    KneserNey {
+        discount: f64,
+    },
+}
+
+impl Smoothing {
+    fn validate(self) -> Result<Self, RnltkError> {
+        let valid = match self {
+            Smoothing::Laplace => true,
+            Smoothing::AddK(k) => k.is_finite() && k > 0.,
+            Smoothing::KneserNey { discount } => discount > 0. && discount < 1.,
+        };
+        if valid { Ok(self) } else { Err(RnltkError::InvalidSmoothing) }
+    }
+}
+
+impl Default for Smoothing {
+    fn default() -> Self {
+        Smoothing::KneserNey {
+            discount: 0.75,
+        }
+    }
+}
+
+/// Sampling controls for [`NgramModel::generate`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct GenerationConfig {
+    /// Sharpens (below 1) or flattens (above 1) the next word distribution. Must be positive; temperatures
+    /// approaching 0 always pick the most probable word, as does a `top_k` of 1
+    pub temperature: f64,
+    /// Only sample from the `top_k` most probable next words
+    pub top_k: Option<usize>,
+    /// Maximum number of words to generate
+    pub max_length: usize,
+    /// Seed of the random number generator, so the same seed generates the same text
+    pub seed: u64,
+}
+
+impl Default for GenerationConfig {
+    fn default() -> Self {
+        Self {
+            temperature: 1.,
+            top_k: None,
+            max_length: 20,
+            seed: 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct ContextCounts {
+    total: f64,
+    distinct_followers: f64,
+}
+
+/// Struct for holding an n-gram language model, created with [`NgramModel::new`] and trained with [`NgramModel::train`].
+#[derive(Debug, Clone)]
+pub struct NgramModel {
+    order: usize,
+    smoothing: Smoothing,
+    vocabulary: BTreeSet<String>,
+    // ngram_counts[n - 1] holds the counts of every n-gram
+    ngram_counts: Vec<HashMap<Vec<String>, f64>>,
+    // context_counts[n - 1] holds the totals of every (n - 1)-gram context of an n-gram
+    context_counts: Vec<HashMap<Vec<String>, ContextCounts>>,
+    // continuation_counts[n - 1] holds the number of distinct words preceding every n-gram
+    continuation_counts: Vec<HashMap<Vec<String>, f64>>,
+    // continuation_context_counts[n - 1] holds the continuation totals of every (n - 1)-gram context
+    continuation_context_counts: Vec<HashMap<Vec<String>, ContextCounts>>,
+}
+
+impl NgramModel {
+    /// Creates new, untrained instance of NgramModel of the given `order` (2 for bigrams, 3 for trigrams, ...)
+    /// using `smoothing`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RnltkError::InvalidNgramOrder`] if `order` is 0.
+    ///
+    /// Returns [`RnltkError::InvalidSmoothing`] if add-k smoothing has a `k` that isn't positive and finite,
+    /// or Kneser-Ney smoothing has a `discount` that isn't between 0 and 1.
+    pub fn new(order: usize, smoothing: Smoothing) -> Result<Self, RnltkError> {
+        if order == 0 {
+            return Err(RnltkError::InvalidNgramOrder);
+        }
+        Ok(NgramModel {
+            order,
+            smoothing: smoothing.validate()?,
+            vocabulary: BTreeSet::from([SENTENCE_END.to_string(), UNKNOWN.to_string()]),
+            ngram_counts: vec![HashMap::new(); order],
+            context_counts: vec![HashMap::new(); order],
+            continuation_counts: vec![HashMap::new(); order],
+            continuation_context_counts: vec![HashMap::new(); order],
+        })
+    }
+
+    /// Creates new instance of NgramModel of the given `order` trained on `sentences`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RnltkError::InvalidNgramOrder`] if `order` is 0.
+    ///
+    /// Returns [`RnltkError::InvalidSmoothing`] if `smoothing` is invalid, as for [`NgramModel::new`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rnltk::lm::{NgramModel, Smoothing};
+    ///
+    /// let sentences = vec!["the bees make honey", "the bees sting", "the wasps sting"];
+    /// let model = NgramModel::from_sentences(2, Smoothing::Laplace, &sentences).unwrap();
+    ///
+    /// assert!(model.get_probability(&["the"], "bees") > model.get_probability(&["the"], "wasps"));
+    /// ```
+    pub fn from_sentences(order: usize, smoothing: Smoothing, sentences: &[&str]) -> Result<Self, RnltkError> {
+        let mut model = NgramModel::new(order, smoothing)?;
+        model.train(sentences);
+        Ok(model)
+    }
+
+    /// Updates the n-gram counts with `sentences`.
+    pub fn train(&mut self, sentences: &[&str]) {
+        for sentence in sentences {
+            let padded = self.pad(&token::tokenize_sentence(sentence));
+            for word in &padded[self.order - 1..] {
+                self.vocabulary.insert(word.to_string());
+            }
+            for n in 1..=self.order {
+                for ngram in padded.windows(n) {
+                    if ngram[n - 1] != SENTENCE_START {
+                        *self.ngram_counts[n - 1].entry(ngram.to_vec()).or_insert(0.) += 1.;
+                    }
+                }
+            }
+        }
+        self.update_derived_counts();
+    }
+
+    fn update_derived_counts(&mut self) {
+        for n in 1..=self.order {
+            let mut context_counts: HashMap<Vec<String>, ContextCounts> = HashMap::new();
+            for (ngram, count) in &self.ngram_counts[n - 1] {
+                let counts = context_counts.entry(ngram[..n - 1].to_vec()).or_default();
+                counts.total += count;
+                counts.distinct_followers += 1.;
+            }
+            self.context_counts[n - 1] = context_counts;
+
+            let mut continuation_counts: HashMap<Vec<String>, f64> = HashMap::new();
+            if n < self.order {
+                for ngram in self.ngram_counts[n].keys() {
+                    *continuation_counts.entry(ngram[1..].to_vec()).or_insert(0.) += 1.;
+                }
+            }
+            let mut continuation_context_counts: HashMap<Vec<String>, ContextCounts> = HashMap::new();
+            for (ngram, count) in &continuation_counts {
+                let counts = continuation_context_counts.entry(ngram[..n - 1].to_vec()).or_default();
+                counts.total += count;
+                counts.distinct_followers += 1.;
+            }
+            self.continuation_counts[n - 1] = continuation_counts;
+            self.continuation_context_counts[n - 1] = continuation_context_counts;
+        }
+    }
+
+    fn pad(&self, tokens: &[String]) -> Vec<String> {
+        let mut padded = vec![SENTENCE_START.to_string(); self.order - 1];
+        padded.extend(tokens.iter().map(|token| token.to_string()));
+        padded.push(SENTENCE_END.to_string());
+        padded
+    }
+
+    /// Gets the order of the model.
+    pub fn get_order(&self) -> usize {
+        self.order
+    }
+
+    /// Gets the smoothing strategy of the model.
+    pub fn get_smoothing(&self) -> Smoothing {
+        self.smoothing
+    }
+
+    /// Sets the smoothing strategy. Counts don't depend on smoothing, so strategies can be
+    /// compared on the same trained model.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RnltkError::InvalidSmoothing`] if `smoothing` is invalid, as for [`NgramModel::new`],
+    /// leaving the current strategy in place.
+    pub fn set_smoothing(&mut self, smoothing: Smoothing) -> Result<(), RnltkError> {
+        self.smoothing = smoothing.validate()?;
+        Ok(())
+    }
+
+    /// Gets the vocabulary of the model, including [`SENTENCE_END`] and [`UNKNOWN`].
+    pub fn get_vocabulary(&self) -> &BTreeSet<String> {
+        &self.vocabulary
+    }
+
+    fn normalize_word(&self, word: &str) -> String {
+        if self.vocabulary.contains(word) || word == SENTENCE_START {
+            word.to_string()
+        } else {
+            UNKNOWN.to_string()
+        }
+    }
+
+    /// Gets the probability of `word` following `context`. Only the last `order - 1` words of
+    /// `context` are used, and shorter contexts are padded with [`SENTENCE_START`].
+    /// Words outside the vocabulary are scored as [`UNKNOWN`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rnltk::lm::{NgramModel, Smoothing};
+    ///
+    /// let model = NgramModel::from_sentences(2, Smoothing::AddK(0.5), &["bees buzz", "bees sting"]).unwrap();
+    /// let vocabulary_size = model.get_vocabulary().len() as f64;
+    ///
+    /// // c("bees buzz") = 1, c("bees") = 2
+    /// assert_eq!(model.get_probability(&["bees"], "buzz"), 1.5 / (2. + 0.5 * vocabulary_size));
+    /// ```
+    pub fn get_probability(&self, context: &[&str], word: &str) -> f64 {
+        let history_length = self.order - 1;
+        let mut history: Vec<String> = context.iter().rev().take(history_length).rev().map(|word| self.normalize_word(word)).collect();
+        while history.len() < history_length {
+            history.insert(0, SENTENCE_START.to_string());
+        }
+        let word = self.normalize_word(word);
+        let vocabulary_size = self.vocabulary.len() as f64;
+
+        match self.smoothing {
+            Smoothing::Laplace => self.additive_probability(&history, word, 1., vocabulary_size),
+            Smoothing::AddK(k) => self.additive_probability(&history, word, k, vocabulary_size),
+            Smoothing::KneserNey { discount } => self.kneser_ney_probability(&history, word, discount, true),
+        }
+    }
+
+    fn additive_probability(&self, history: &[String], word: String, k: f64, vocabulary_size: f64) -> f64 {
+        let n = history.len() + 1;
+        let context_total = self.context_counts[n - 1].get(history).map_or(0., |counts| counts.total);
+        let mut ngram = history.to_vec();
+        ngram.push(word);
+        let count = self.ngram_counts[n - 1].get(&ngram).copied().unwrap_or(0.);
+        (count + k) / (context_total + k * vocabulary_size)
+    }
+
+    fn kneser_ney_probability(&self, history: &[String], word: String, discount: f64, highest_order: bool) -> f64 {
+        let n = history.len() + 1;
+        let (ngram_counts, context_counts) = if highest_order {
+            (&self.ngram_counts[n - 1], &self.context_counts[n - 1])
+        } else {
+            (&self.continuation_counts[n - 1], &self.continuation_context_counts[n - 1])
+        };
+        let context = context_counts.get(history).copied().unwrap_or_default();
+        let lower_order_probability = if n == 1 {
+            1. / self.vocabulary.len() as f64
+        } else if context.total == 0. {
+            return self.kneser_ney_probability(&history[1..], word, discount, false);
+        } else {
+            self.kneser_ney_probability(&history[1..], word.clone(), discount, false)
+        };
+        if context.total == 0. {
+            return lower_order_probability;
+        }
+
+        let mut ngram = history.to_vec();
+        ngram.push(word);
+        let count = ngram_counts.get(&ngram).copied().unwrap_or(0.);
+        let interpolation_weight = discount * context.distinct_followers / context.total;
+        (count - discount).max(0.) / context.total + interpolation_weight * lower_order_probability
+    }
+
+    /// Gets the natural log probability of `sentence`, including the probability of it ending.
+    pub fn get_sentence_log_probability(&self, sentence: &str) -> f64 {
+        let padded = self.pad(&token::tokenize_sentence(sentence));
+        let padded: Vec<&str> = padded.iter().map(|word| word.as_str()).collect();
+        (self.order - 1..padded.len())
+            .map(|index| self.get_probability(&padded[..index], padded[index]).ln())
+            .sum()
+    }
+
+    /// Gets the perplexity of the model on `sentences`, \\(\exp(-\frac{1}{N} \sum \ln P(w_i \mid h_i))\\),
+    /// where \\(N\\) counts every word plus one [`SENTENCE_END`] per sentence. Lower is better.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rnltk::lm::{NgramModel, Smoothing};
+    ///
+    /// let training = vec!["the bees make honey", "the bees sting", "the wasps sting", "wasps make nests"];
+    /// let test = vec!["the wasps make honey"];
+    /// let mut model = NgramModel::from_sentences(3, Smoothing::Laplace, &training).unwrap();
+    /// let laplace_perplexity = model.get_perplexity(&test);
+    /// model.set_smoothing(Smoothing::default()).unwrap();
+    /// let kneser_ney_perplexity = model.get_perplexity(&test);
+    ///
+    /// assert!(kneser_ney_perplexity < laplace_perplexity);
+    /// ```
+    pub fn get_perplexity(&self, sentences: &[&str]) -> f64 {
+        let num_words: usize = sentences.iter().map(|sentence| token::tokenize_sentence(sentence).len() + 1).sum();
+        if num_words == 0 {
+            return f64::NAN;
+        }
+        let log_probability: f64 = sentences.iter().map(|sentence| self.get_sentence_log_probability(sentence)).sum();
+        (-log_probability / num_words as f64).exp()
+    }
+
+    /// Gets the probability of every word that can follow `context`, excluding [`UNKNOWN`],
+    /// sorted from most to least probable.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rnltk::lm::{NgramModel, Smoothing};
+    ///
+    /// let model = NgramModel::from_sentences(2, Smoothing::default(), &["bees buzz", "bees buzz", "bees sting"]).unwrap();
+    /// let distribution = model.get_next_word_distribution(&["bees"]);
+    ///
+    /// assert_eq!(distribution[0].0, "buzz");
+    /// ```
+    pub fn get_next_word_distribution(&self, context: &[&str]) -> Vec<(String, f64)> {
+        let mut distribution: Vec<(String, f64)> = self.vocabulary.iter()
+            .filter(|word| *word != UNKNOWN)
+            .map(|word| (word.to_string(), self.get_probability(context, word)))
+            .collect();
+        // stable sort keeps ties in alphabetical order
+        distribution.sort_by(|(_, first), (_, second)| second.total_cmp(first));
+        distribution
+    }
+
+    /// Generates words following `context` by sampling from the model until [`SENTENCE_END`] is
+    /// drawn or `config.max_length` words are generated. [`SENTENCE_END`] isn't included in the result.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RnltkError::InvalidGenerationConfig`] if the temperature isn't positive and finite, or if `top_k` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rnltk::lm::{GenerationConfig, NgramModel, Smoothing};
+    ///
+    /// let sentences = vec!["the bees make honey", "the bees sting", "the wasps sting"];
+    /// let model = NgramModel::from_sentences(2, Smoothing::default(), &sentences).unwrap();
+    /// let config = GenerationConfig { temperature: 0.7, top_k: Some(3), seed: 42, ..Default::default() };
+    ///
+    /// // the same seed always generates the same text
+    /// assert_eq!(model.generate(&["the"], &config).unwrap(), model.generate(&["the"], &config).unwrap());
+    ///
+    /// let greedy = GenerationConfig { top_k: Some(1), ..Default::default() };
+    /// assert_eq!(model.generate(&["the"], &greedy).unwrap(), vec!["bees", "sting"]);
+    /// ```
+    pub fn generate(&self, context: &[&str], config: &GenerationConfig) -> Result<Vec<String>, RnltkError> {
+        if !config.temperature.is_finite() || config.temperature <= 0. || config.top_k == Some(0) {
+            return Err(RnltkError::InvalidGenerationConfig);
+        }
+        let mut rng = StdRng::seed_from_u64(config.seed);
+        let mut history: Vec<String> = context.iter().map(|word| word.to_string()).collect();
+        let mut generated: Vec<String> = vec![];
+
+        while generated.len() < config.max_length {
+            let history_words: Vec<&str> = history.iter().map(|word| word.as_str()).collect();
+            let mut distribution = self.get_next_word_distribution(&history_words);
+            if let Some(top_k) = config.top_k {
+                distribution.truncate(top_k);
+            }
+            let word = sample_with_temperature(distribution, config.temperature, &mut rng);
+            if word == SENTENCE_END {
+                break;
+            }
+            history.push(word.clone());
+            generated.push(word);
+        }
+
+        Ok(generated)
+    }
+
+    /// Gets the `beam_width` most probable continuations of `context` of up to `max_length` words,
+    /// found with beam search, together with their natural log probabilities and sorted from most
+    /// to least probable. Continuations that end the sentence have [`SENTENCE_END`] removed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RnltkError::InvalidGenerationConfig`] if `beam_width` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rnltk::lm::{NgramModel, Smoothing};
+    ///
+    /// let sentences = vec!["the bees make honey", "the bees make honey", "the bees sting"];
+    /// let model = NgramModel::from_sentences(2, Smoothing::default(), &sentences).unwrap();
+    /// let continuations = model.beam_search(&["the"], 2, 5).unwrap();
+    ///
+    /// assert_eq!(continuations[0].0, vec!["bees", "make", "honey"]);
+    /// assert!(continuations[0].1 > continuations[1].1);
+    /// ```
+    pub fn beam_search(&self, context: &[&str], beam_width: usize, max_length: usize) -> Result<Vec<(Vec<String>, f64)>, RnltkError> {
+        if beam_width == 0 {
+            return Err(RnltkError::InvalidGenerationConfig);
+        }
+        let context: Vec<String> = context.iter().map(|word| word.to_string()).collect();
+        let mut beams: Vec<(Vec<String>, f64)> = vec![(vec![], 0.)];
+        let mut finished: Vec<(Vec<String>, f64)> = vec![];
+
+        for _ in 0..max_length {
+            let mut candidates: Vec<(Vec<String>, f64)> = vec![];
+            for (words, log_probability) in &beams {
+                let history: Vec<&str> = context.iter().chain(words.iter()).map(|word| word.as_str()).collect();
+                for (word, probability) in self.get_next_word_distribution(&history).into_iter().take(beam_width) {
+                    let mut continuation = words.clone();
+                    continuation.push(word);
+                    candidates.push((continuation, log_probability + probability.ln()));
+                }
+            }
+            candidates.sort_by(|(_, first), (_, second)| second.total_cmp(first));
+            candidates.truncate(beam_width);
+
+            beams = vec![];
+            for (mut words, log_probability) in candidates {
+                if words.last().is_some_and(|word| word == SENTENCE_END) {
+                    words.pop();
+                    finished.push((words, log_probability));
+                } else {
+                    beams.push((words, log_probability));
+                }
+            }
+            // stop once no unfinished beam can beat the finished continuations
+            let worst_finished = (finished.len() >= beam_width).then(|| {
+                let mut log_probabilities: Vec<f64> = finished.iter().map(|(_, log_probability)| *log_probability).collect();
+                log_probabilities.sort_by(|first, second| second.total_cmp(first));
+                log_probabilities[beam_width - 1]
+            });
+            beams.retain(|(_, log_probability)| worst_finished.is_none_or(|worst| *log_probability > worst));
+            if beams.is_empty() {
+                break;
+            }
+        }
+
+        finished.extend(beams);
+        finished.sort_by(|(_, first), (_, second)| second.total_cmp(first));
+        finished.truncate(beam_width);
+        Ok(finished)
+    }
+}
+
+// samples a word from `distribution`, sorted from most to least probable, sharpened by a positive `temperature`
+fn sample_with_temperature(mut distribution: Vec<(String, f64)>, temperature: f64, rng: &mut StdRng) -> String {
+    // p^(1/T) underflows at low temperatures, so weights are computed in log space relative to the most probable word
+    let log_weights: Vec<f64> = distribution.iter().map(|(_, probability)| probability.ln() / temperature).collect();
+    let max_log_weight = log_weights.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let weights: Vec<f64> = log_weights.iter().map(|log_weight| (log_weight - max_log_weight).exp()).collect();
+    let total: f64 = weights.iter().sum();
+    if !total.is_finite() || total <= 0. {
+        return distribution.swap_remove(0).0;
+    }
+    let mut threshold = rng.gen::<f64>() * total;
+    for (index, weight) in weights.iter().enumerate() {
+        if threshold < *weight {
+            return distribution.swap_remove(index).0;
+        }
+        threshold -= weight;
+    }
+    // rounding can leave a sliver of the threshold, which belongs to the last word with any weight
+    let last_index = weights.iter().rposition(|weight| *weight > 0.).unwrap_or(0);
+    distribution.swap_remove(last_index).0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SENTENCES: [&str; 4] = ["the bees make honey", "the bees sting", "the wasps sting", "wasps make nests"];
+
+    fn assert_distribution_sums_to_one(model: &NgramModel, context: &[&str]) {
+        let total: f64 = model.get_vocabulary().iter().map(|word| model.get_probability(context, word)).sum();
+        assert!((total - 1.).abs() < 1e-9, "total was {}", total);
+    }
+
+    #[test]
+    fn probabilities_sum_to_one() {
+        for smoothing in [Smoothing::Laplace, Smoothing::AddK(0.1), Smoothing::default()] {
+            for order in 1..=3 {
+                let model = NgramModel::from_sentences(order, smoothing, &SENTENCES).unwrap();
+                assert_distribution_sums_to_one(&model, &["the", "bees"]);
+                assert_distribution_sums_to_one(&model, &["unseen", "context"]);
+                assert_distribution_sums_to_one(&model, &[]);
+            }
+        }
+    }
+
+    #[test]
+    fn unknown_words_get_probability() {
+        let model = NgramModel::from_sentences(2, Smoothing::default(), &SENTENCES).unwrap();
+        let probability = model.get_probability(&["the"], "hornets");
+        assert!(probability > 0. && probability < model.get_probability(&["the"], "bees"));
+    }
+
+    #[test]
+    fn generation_respects_max_length_and_top_k() {
+        let model = NgramModel::from_sentences(2, Smoothing::default(), &SENTENCES).unwrap();
+        let config = GenerationConfig { top_k: Some(1), max_length: 2, seed: 7, ..Default::default() };
+        let generated = model.generate(&[], &config).unwrap();
+        assert_eq!(generated, vec!["the", "bees"]);
+    }
+
+    #[test]
+    fn low_temperature_is_greedy() {
+        // without ties, so the most probable word is always unique
+        let sentences = ["the bees make honey", "the bees make honey", "the bees sting", "the wasps sting"];
+        let model = NgramModel::from_sentences(2, Smoothing::default(), &sentences).unwrap();
+        let greedy = model.generate(&[], &GenerationConfig { top_k: Some(1), ..Default::default() }).unwrap();
+        assert_eq!(greedy, vec!["the", "bees", "make", "honey"]);
+        for seed in 0..10 {
+            for temperature in [0.001, 1e-300] {
+                assert_eq!(model.generate(&[], &GenerationConfig { temperature, seed, ..Default::default() }).unwrap(), greedy);
+            }
+        }
+    }
+
+    #[test]
+    fn different_seeds_vary_generation() {
+        let model = NgramModel::from_sentences(1, Smoothing::Laplace, &SENTENCES).unwrap();
+        let generations: BTreeSet<Vec<String>> = (0..10)
+            .map(|seed| model.generate(&[], &GenerationConfig { temperature: 2., seed, ..Default::default() }).unwrap())
+            .collect();
+        assert!(generations.len() > 1);
+    }
+
+    #[test]
+    fn invalid_generation_config() {
+        let model = NgramModel::from_sentences(2, Smoothing::default(), &SENTENCES).unwrap();
+        for temperature in [-1., 0., f64::NAN] {
+            let invalid_temperature = GenerationConfig { temperature, ..Default::default() };
+            assert_eq!(model.generate(&[], &invalid_temperature).unwrap_err(), RnltkError::InvalidGenerationConfig);
+        }
+        let zero_top_k = GenerationConfig { top_k: Some(0), ..Default::default() };
+        assert_eq!(model.generate(&[], &zero_top_k).unwrap_err(), RnltkError::InvalidGenerationConfig);
+        assert_eq!(model.beam_search(&[], 0, 5).unwrap_err(), RnltkError::InvalidGenerationConfig);
+    }
+
+    #[test]
+    fn beam_search_log_probabilities_match_model() {
+        let model = NgramModel::from_sentences(2, Smoothing::default(), &SENTENCES).unwrap();
+        let continuations = model.beam_search(&[], 3, 10).unwrap();
+        assert_eq!(continuations.len(), 3);
+        for (words, log_probability) in continuations {
+            assert!((model.get_sentence_log_probability(&words.join(" ")) - log_probability).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn invalid_order() {
+        assert_eq!(NgramModel::new(0, Smoothing::Laplace).unwrap_err(), RnltkError::InvalidNgramOrder);
+    }
+
+    #[test]
+    fn invalid_smoothing() {
+        let invalid = [
+            Smoothing::AddK(0.),
+            Smoothing::AddK(-0.5),
+            Smoothing::AddK(f64::NAN),
+            Smoothing::AddK(f64::INFINITY),
+            Smoothing::KneserNey { discount: 0. },
+            Smoothing::KneserNey { discount: 1. },
+            Smoothing::KneserNey { discount: -0.25 },
+            Smoothing::KneserNey { discount: f64::NAN },
+        ];
+        let mut model = NgramModel::from_sentences(2, Smoothing::Laplace, &SENTENCES).unwrap();
+        for smoothing in invalid {
+            assert_eq!(NgramModel::new(2, smoothing).unwrap_err(), RnltkError::InvalidSmoothing, "{smoothing:?}");
+            assert_eq!(model.set_smoothing(smoothing).unwrap_err(), RnltkError::InvalidSmoothing, "{smoothing:?}");
+            assert_eq!(model.get_smoothing(), Smoothing::Laplace);
+        }
+        assert_eq!(model.set_smoothing(Smoothing::AddK(0.01)), Ok(()));
+        assert!(model.get_probability(&["the"], "bees").is_finite());
+    }
+
+    #[test]
+    fn empty_perplexity() {
+        let model = NgramModel::from_sentences(2, Smoothing::Laplace, &SENTENCES).unwrap();
+        assert!(model.get_perplexity(&[]).is_nan());
+    }
+}
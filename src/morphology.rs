@@ -0,0 +1,280 @@
+//! Module containing dictionary-based lemmatization and spell checking.
+//!
+//! [`HunspellDictionary`] reads Hunspell `.aff`/`.dic` dictionary pairs in pure Rust, so languages
+//! where rule-based stemming fails can still be lemmatized without linking against native libraries.
+//! [`PorterMorphology`] is the rule-based fallback built on [`stem::get`] for when no dictionary
+//! is available. Both implement the [`Morphology`] trait so they can be swapped freely.
+
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::Path;
+
+use regex::Regex;
+
+use crate::error::RnltkError;
+use crate::stem;
+
+/// Common interface for lemmatizers and spell checkers.
+pub trait Morphology {
+    /// Checks if `word` is correctly spelled.
+    fn check(&self, word: &str) -> bool;
+
+    /// Gets the possible lemmas (dictionary forms) of `word`, in sorted order.
+    /// An empty vector means the word is unknown.
+    fn lemmatize(&self, word: &str) -> Vec<String>;
+}
+
+/// Rule-based fallback that treats every ASCII word as correctly spelled and
+/// lemmatizes using the Porter stemmer from [`stem::get`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PorterMorphology;
+
+impl Morphology for PorterMorphology {
+    fn check(&self, word: &str) -> bool {
+        word.is_ascii()
+    }
+
+    fn lemmatize(&self, word: &str) -> Vec<String> {
+        stem::get(word).map(|stemmed_word| vec![stemmed_word]).unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FlagType {
+    Short,
+    Long,
+    Numeric,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AffixKind {
+    Prefix,
+    Suffix,
+}
+
+#[derive(Debug, Clone)]
+struct AffixRule {
+    kind: AffixKind,
+    flag: String,
+    strip: String,
+    add: String,
+    condition: Regex,
+}
+
+/// Pure-Rust reader for Hunspell dictionaries.
+///
+/// Only the subset of the affix format needed for lemmatization is supported: `FLAG`, `PFX` and
+/// `SFX` rules with their conditions. Continuation classes and compounding are ignored.
+#[derive(Debug, Clone)]
+pub struct HunspellDictionary {
+    words: HashMap<String, BTreeSet<String>>,
+    rules: Vec<AffixRule>,
+}
+
+impl HunspellDictionary {
+    /// Creates new instance of HunspellDictionary from the contents of an `.aff` file, `affixes`,
+    /// and a `.dic` file, `dictionary`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RnltkError::DictionaryFormat`] if an affix rule is malformed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rnltk::morphology::{HunspellDictionary, Morphology};
+    ///
+    /// let affixes = "SET UTF-8\nSFX S Y 1\nSFX S 0 s .\n";
+    /// let dictionary = "2\nbee/S\nhoney\n";
+    /// let hunspell = HunspellDictionary::from_strs(affixes, dictionary).unwrap();
+    ///
+    /// assert!(hunspell.check("bees"));
+    /// assert!(!hunspell.check("honeys"));
+    /// assert_eq!(hunspell.lemmatize("bees"), vec!["bee"]);
+    /// ```
+    pub fn from_strs(affixes: &str, dictionary: &str) -> Result<Self, RnltkError> {
+        let mut flag_type = FlagType::Short;
+        let mut rules: Vec<AffixRule> = vec![];
+
+        for line in affixes.lines() {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            match fields.as_slice() {
+                ["FLAG", "long", ..] => flag_type = FlagType::Long,
+                ["FLAG", "num", ..] => flag_type = FlagType::Numeric,
+                // Rule headers (`SFX D Y 4`) only have four fields, so they fall through to the ignored arm
+                [kind @ ("PFX" | "SFX"), flag, strip, add, condition, ..] => {
+                    let kind = if *kind == "PFX" { AffixKind::Prefix } else { AffixKind::Suffix };
+                    rules.push(parse_rule(kind, flag, strip, add, condition)?);
+                }
+                _ => {}
+            }
+        }
+
+        let mut words: HashMap<String, BTreeSet<String>> = HashMap::new();
+        for (index, line) in dictionary.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || (index == 0 && line.chars().all(|character| character.is_ascii_digit())) {
+                continue;
+            }
+            let entry = line.split(['\t', ' ']).next().unwrap_or_default();
+            let (word, flags) = match entry.split_once('/') {
+                Some((word, flags)) => (word, split_flags(flags, flag_type)),
+                None => (entry, BTreeSet::new()),
+            };
+            words.entry(word.to_string()).or_default().extend(flags);
+        }
+
+        Ok(HunspellDictionary {
+            words,
+            rules,
+        })
+    }
+
+    /// Creates new instance of HunspellDictionary from the `.aff` file at `affix_path`
+    /// and the `.dic` file at `dictionary_path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RnltkError::DictionaryIo`] if either file could not be read and
+    /// [`RnltkError::DictionaryFormat`] if an affix rule is malformed.
+    pub fn from_paths(affix_path: impl AsRef<Path>, dictionary_path: impl AsRef<Path>) -> Result<Self, RnltkError> {
+        let affixes = fs::read_to_string(affix_path).map_err(|error| RnltkError::DictionaryIo(error.to_string()))?;
+        let dictionary = fs::read_to_string(dictionary_path).map_err(|error| RnltkError::DictionaryIo(error.to_string()))?;
+        Self::from_strs(&affixes, &dictionary)
+    }
+
+    /// Gets the number of stem entries in the dictionary.
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    /// Checks if the dictionary has no stem entries.
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    fn lemmas_for(&self, word: &str) -> BTreeSet<String> {
+        let mut lemmas = BTreeSet::new();
+        if self.words.contains_key(word) {
+            lemmas.insert(word.to_string());
+        }
+        for rule in &self.rules {
+            let candidate = match rule.kind {
+                AffixKind::Suffix => word.strip_suffix(rule.add.as_str()).map(|base| format!("{}{}", base, rule.strip)),
+                AffixKind::Prefix => word.strip_prefix(rule.add.as_str()).map(|base| format!("{}{}", rule.strip, base)),
+            };
+            let Some(candidate) = candidate else { continue };
+            if candidate.is_empty() || !rule.condition.is_match(&candidate) {
+                continue;
+            }
+            if let Some(flags) = self.words.get(&candidate) {
+                if flags.contains(&rule.flag) {
+                    lemmas.insert(candidate);
+                }
+            }
+        }
+        lemmas
+    }
+}
+
+impl Morphology for HunspellDictionary {
+    fn check(&self, word: &str) -> bool {
+        !self.lemmatize(word).is_empty()
+    }
+
+    fn lemmatize(&self, word: &str) -> Vec<String> {
+        let mut lemmas = self.lemmas_for(word);
+        if lemmas.is_empty() {
+            lemmas = self.lemmas_for(&word.to_lowercase());
+        }
+        lemmas.into_iter().collect()
+    }
+}
+
+fn parse_rule(kind: AffixKind, flag: &str, strip: &str, add: &str, condition: &str) -> Result<AffixRule, RnltkError> {
+    let strip = if strip == "0" { "" } else { strip };
+    let add = add.split('/').next().unwrap_or_default();
+    let add = if add == "0" { "" } else { add };
+    let pattern = match kind {
+        AffixKind::Suffix => format!("{}$", condition),
+        AffixKind::Prefix => format!("^{}", condition),
+    };
+    let condition = Regex::new(&pattern).map_err(|_| RnltkError::DictionaryFormat(format!("invalid affix condition '{}'", condition)))?;
+
+    Ok(AffixRule {
+        kind,
+        flag: flag.to_string(),
+        strip: strip.to_string(),
+        add: add.to_string(),
+        condition,
+    })
+}
+
+fn split_flags(flags: &str, flag_type: FlagType) -> BTreeSet<String> {
+    match flag_type {
+        FlagType::Short => flags.chars().map(String::from).collect(),
+        FlagType::Long => flags.chars().collect::<Vec<char>>().chunks(2).map(|pair| pair.iter().collect()).collect(),
+        FlagType::Numeric => flags.split(',').map(|flag| flag.trim().to_string()).collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AFFIXES: &str = "SET UTF-8
+TRY esianrtolcdugmphbyfvkwz
+
+PFX U Y 1
+PFX U 0 un .
+
+SFX D Y 3
+SFX D 0 d e
+SFX D y ied [^aeiou]y
+SFX D 0 ed [^ey]
+";
+
+    const DICTIONARY: &str = "4
+happy/U
+carry/D
+walk/D
+hope/D
+";
+
+    #[test]
+    fn suffix_lemmatization() {
+        let hunspell = HunspellDictionary::from_strs(AFFIXES, DICTIONARY).unwrap();
+        assert_eq!(hunspell.len(), 4);
+        assert_eq!(hunspell.lemmatize("carried"), vec!["carry"]);
+        assert_eq!(hunspell.lemmatize("hoped"), vec!["hope"]);
+        assert_eq!(hunspell.lemmatize("Walked"), vec!["walk"]);
+    }
+
+    #[test]
+    fn prefix_lemmatization() {
+        let hunspell = HunspellDictionary::from_strs(AFFIXES, DICTIONARY).unwrap();
+        assert_eq!(hunspell.lemmatize("unhappy"), vec!["happy"]);
+        assert!(!hunspell.check("unwalk"));
+    }
+
+    #[test]
+    fn spell_check() {
+        let hunspell = HunspellDictionary::from_strs(AFFIXES, DICTIONARY).unwrap();
+        assert!(hunspell.check("walk"));
+        assert!(!hunspell.check("carryed"));
+        assert!(!hunspell.check("happied"));
+    }
+
+    #[test]
+    fn long_flags() {
+        let hunspell = HunspellDictionary::from_strs("FLAG long\nSFX Aa Y 1\nSFX Aa 0 s .\n", "1\nbee/AaBb\n").unwrap();
+        assert_eq!(hunspell.lemmatize("bees"), vec!["bee"]);
+    }
+
+    #[test]
+    fn porter_fallback() {
+        let morphology = PorterMorphology;
+        assert_eq!(morphology.lemmatize("pencils"), vec!["pencil"]);
+        assert!(morphology.lemmatize("hopè").is_empty());
+    }
+}
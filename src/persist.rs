@@ -0,0 +1,271 @@
+//! Module containing a versioned file format for saving and loading trained models.
+//!
+//! A model file is a JSON object wrapping the serialized model with the format version, the kind of model,
+//! and the version of rnltk that wrote it. Models hold their configuration and vocabulary, so a loaded model
+//! predicts exactly like the one that was saved, on any machine.
+//!
+//! ```json
+//! {"format":"rnltk-model","format_version":1,"kind":"naive_bayes","crate_version":"0.4.0","model":{...}}
+//! ```
+
+use std::fs;
+use std::path::Path;
+
+use serde::{Serialize, Deserialize};
+use serde::de::DeserializeOwned;
+
+use crate::classify::{LogisticRegressionClassifier, NaiveBayesClassifier};
+use crate::dialogue::DialogueActTagger;
+use crate::document::LsaModel;
+use crate::error::RnltkError;
+use crate::intent::IntentMatcher;
+use crate::topic::TopicModel;
+use crate::truecase::TruecaseModel;
+
+/// Newest model file format version, written by [`PersistentModel::save`]. Files with this or
+/// an older version can be loaded.
+pub const MODEL_FORMAT_VERSION: u32 = 1;
+
+const MODEL_FORMAT: &str = "rnltk-model";
+
+#[derive(Serialize)]
+struct ModelFileRef<'a, T> {
+    format: &'a str,
+    format_version: u32,
+    kind: &'a str,
+    crate_version: &'a str,
+    model: &'a T,
+}
+
+// the header is checked before the model is parsed, so a wrong kind is reported as such
+#[derive(Deserialize)]
+struct ModelFileHeader {
+    format: String,
+    format_version: u32,
+    kind: String,
+}
+
+#[derive(Deserialize)]
+struct ModelFileBody<T> {
+    model: T,
+}
+
+/// Trained model that can be saved to and loaded from a versioned model file.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::classify::{Classifier, NaiveBayesClassifier};
+/// use rnltk::persist::PersistentModel;
+///
+/// let mut classifier = NaiveBayesClassifier::default();
+/// classifier.fit(&[(vec!["great", "fun"], "positive"), (vec!["boring", "dull"], "negative")]);
+///
+/// let json = classifier.to_json().unwrap();
+/// let loaded = NaiveBayesClassifier::from_json(&json).unwrap();
+///
+/// assert_eq!(loaded, classifier);
+/// assert_eq!(loaded.predict(&["fun"]).as_deref(), Some("positive"));
+/// ```
+pub trait PersistentModel: Serialize + DeserializeOwned {
+    /// Name of the kind of model, stored in the file so one kind of model can't be loaded as another.
+    const KIND: &'static str;
+
+    /// Checks that the parts of a parsed model fit together, such as a vocabulary with one row of a matrix
+    /// per term, since a model file may have been edited or truncated by hand.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RnltkError::ModelFormat`] describing the first inconsistency found.
+    fn validate(&self) -> Result<(), RnltkError> {
+        Ok(())
+    }
+
+    /// Serializes the model to a model file string.
+    fn to_json(&self) -> Result<String, RnltkError> {
+        let file = ModelFileRef {
+            format: MODEL_FORMAT,
+            format_version: MODEL_FORMAT_VERSION,
+            kind: Self::KIND,
+            crate_version: env!("CARGO_PKG_VERSION"),
+            model: self,
+        };
+        serde_json::to_string(&file).map_err(|error| RnltkError::ModelFormat(error.to_string()))
+    }
+
+    /// Deserializes a model from a model file string.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RnltkError::ModelFormat`] if `json` isn't a model file, was written by a newer format version,
+    /// holds a different kind of model, or the model couldn't be parsed or isn't consistent, as checked by
+    /// [`PersistentModel::validate`].
+    fn from_json(json: &str) -> Result<Self, RnltkError> {
+        let header: ModelFileHeader = serde_json::from_str(json).map_err(|error| RnltkError::ModelFormat(error.to_string()))?;
+        check_header(&header.format, header.format_version, &header.kind, Self::KIND)?;
+        let body: ModelFileBody<Self> = serde_json::from_str(json).map_err(|error| RnltkError::ModelFormat(error.to_string()))?;
+        body.model.validate()?;
+        Ok(body.model)
+    }
+
+    /// Saves the model to a model file at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RnltkError::ModelIo`] if the file couldn't be written.
+    fn save(&self, path: impl AsRef<Path>) -> Result<(), RnltkError> {
+        fs::write(path, self.to_json()?).map_err(|error| RnltkError::ModelIo(error.to_string()))
+    }
+
+    /// Loads a model from the model file at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RnltkError::ModelIo`] if the file couldn't be read, or any error of [`PersistentModel::from_json`].
+    fn load(path: impl AsRef<Path>) -> Result<Self, RnltkError> {
+        let json = fs::read_to_string(path).map_err(|error| RnltkError::ModelIo(error.to_string()))?;
+        Self::from_json(&json)
+    }
+}
+
+fn check_header(format: &str, format_version: u32, kind: &str, expected_kind: &str) -> Result<(), RnltkError> {
+    if format != MODEL_FORMAT {
+        Err(RnltkError::ModelFormat(format!("not an rnltk model file (format '{format}')")))
+    } else if format_version > MODEL_FORMAT_VERSION {
+        Err(RnltkError::ModelFormat(format!("format version {format_version} is newer than the supported version {MODEL_FORMAT_VERSION}")))
+    } else if kind != expected_kind {
+        Err(RnltkError::ModelFormat(format!("expected a '{expected_kind}' model, found '{kind}'")))
+    } else {
+        Ok(())
+    }
+}
+
+impl PersistentModel for NaiveBayesClassifier {
+    const KIND: &'static str = "naive_bayes";
+
+    fn validate(&self) -> Result<(), RnltkError> {
+        self.check_consistency()
+    }
+}
+
+impl PersistentModel for LogisticRegressionClassifier {
+    const KIND: &'static str = "logistic_regression";
+
+    fn validate(&self) -> Result<(), RnltkError> {
+        self.check_consistency()
+    }
+}
+
+impl PersistentModel for TruecaseModel {
+    const KIND: &'static str = "truecase";
+}
+
+impl PersistentModel for LsaModel {
+    const KIND: &'static str = "lsa";
+
+    fn validate(&self) -> Result<(), RnltkError> {
+        self.check_consistency()
+    }
+}
+
+impl PersistentModel for TopicModel {
+    const KIND: &'static str = "topic_model";
+
+    fn validate(&self) -> Result<(), RnltkError> {
+        self.check_consistency()
+    }
+}
+
+impl PersistentModel for IntentMatcher {
+    const KIND: &'static str = "intent_matcher";
+
+    fn validate(&self) -> Result<(), RnltkError> {
+        self.check_consistency()
+    }
+}
+
+impl PersistentModel for DialogueActTagger {
+    const KIND: &'static str = "dialogue_act_tagger";
+
+    fn validate(&self) -> Result<(), RnltkError> {
+        self.check_consistency()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::classify::Classifier;
+    use crate::document::{DocumentTermFrequencies, GenericMatrix};
+    use crate::topic::TopicConfig;
+
+    fn trained_logistic_regression() -> LogisticRegressionClassifier {
+        let mut classifier = LogisticRegressionClassifier::default();
+        classifier.fit(&[(vec!["great", "fun"], "positive"), (vec!["boring", "dull"], "negative")]);
+        classifier
+    }
+
+    #[test]
+    fn save_and_load_file() {
+        let classifier = trained_logistic_regression();
+        let path = std::env::temp_dir().join(format!("rnltk-model-{}.json", std::process::id()));
+        classifier.save(&path).unwrap();
+        let loaded = LogisticRegressionClassifier::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(loaded, classifier);
+        assert_eq!(loaded.predict_proba(&["fun"]), classifier.predict_proba(&["fun"]));
+    }
+
+    #[test]
+    fn wrong_kind_is_rejected() {
+        let json = trained_logistic_regression().to_json().unwrap();
+        let error = NaiveBayesClassifier::from_json(&json).unwrap_err();
+        assert_eq!(error, RnltkError::ModelFormat("expected a 'naive_bayes' model, found 'logistic_regression'".to_string()));
+    }
+
+    #[test]
+    fn newer_format_version_is_rejected() {
+        let json = TruecaseModel::new().to_json().unwrap().replace("\"format_version\":1", "\"format_version\":2");
+        assert!(matches!(TruecaseModel::from_json(&json), Err(RnltkError::ModelFormat(message)) if message.contains("newer")));
+        assert!(matches!(TruecaseModel::from_json("{}"), Err(RnltkError::ModelFormat(_))));
+    }
+
+    #[test]
+    fn inconsistent_models_are_rejected() {
+        let terms = vec!["bees", "honey", "rocket"];
+        let term_frequencies = DocumentTermFrequencies::new(GenericMatrix::from_row_slice(3, 2, &[2., 0., 1., 0., 0., 3.])).unwrap();
+        let json = LsaModel::new(terms.clone(), &term_frequencies, 2).unwrap().to_json().unwrap();
+        assert!(LsaModel::from_json(&json).is_ok());
+        let missing_term = json.replace("\"terms\":[\"bees\",\"honey\",\"rocket\"]", "\"terms\":[\"bees\",\"honey\"]");
+        assert_ne!(missing_term, json);
+        assert!(matches!(LsaModel::from_json(&missing_term), Err(RnltkError::ModelFormat(message)) if message.contains("terms")));
+
+        let config = TopicConfig { num_topics: 2, iterations: 5, ..TopicConfig::default() };
+        let json = TopicModel::new(terms, &term_frequencies, &config).unwrap().to_json().unwrap();
+        assert!(TopicModel::from_json(&json).is_ok());
+        let extra_document = json.replace("\"doc_ids\":[0,1]", "\"doc_ids\":[0,1,2]");
+        assert_ne!(extra_document, json);
+        assert!(matches!(TopicModel::from_json(&extra_document), Err(RnltkError::ModelFormat(message)) if message.contains("documents")));
+
+        let json = trained_logistic_regression().to_json().unwrap();
+        let unknown_class = json.replacen("\"negative\":", "\"neutral\":", 1);
+        assert!(matches!(LogisticRegressionClassifier::from_json(&unknown_class), Err(RnltkError::ModelFormat(_))));
+        let negative_rate = json.replacen("\"learning_rate\":", "\"learning_rate\":-", 1);
+        assert_ne!(negative_rate, json);
+        assert!(matches!(LogisticRegressionClassifier::from_json(&negative_rate), Err(RnltkError::ModelFormat(message)) if message.contains("learning rate")));
+
+        let mut naive_bayes = NaiveBayesClassifier::default();
+        naive_bayes.fit(&[(vec!["great"], "positive"), (vec!["dull"], "negative")]);
+        let json = naive_bayes.to_json().unwrap();
+        let unknown_class = json.replacen("\"negative\":", "\"neutral\":", 1);
+        assert!(matches!(NaiveBayesClassifier::from_json(&unknown_class), Err(RnltkError::ModelFormat(_))));
+
+        let json = IntentMatcher::new(0.5).unwrap().to_json().unwrap().replace("\"threshold\":0.5", "\"threshold\":2.0");
+        assert!(matches!(IntentMatcher::from_json(&json), Err(RnltkError::ModelFormat(_))));
+    }
+
+    #[test]
+    fn missing_file() {
+        assert!(matches!(TruecaseModel::load("does/not/exist.json"), Err(RnltkError::ModelIo(_))));
+    }
+}
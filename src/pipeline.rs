@@ -0,0 +1,175 @@
+//! Module containing a tokenization pipeline that picks stop words and stemming per document based on its
+//! detected language, so corpora mixing languages can be processed in one pass.
+
+use std::collections::BTreeMap;
+
+use serde::{Serialize, Deserialize};
+
+use crate::error::RnltkError;
+use crate::language::{self, Language};
+use crate::token::{self, TokenConfig};
+
+/// Struct for holding a document processed by a [`Pipeline`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ProcessedDocument {
+    /// Language whose configuration the document was tokenized with
+    pub language: Language,
+    /// Whether `language` was detected, rather than being the fallback or given to [`Pipeline::process_as`]
+    pub detected: bool,
+    pub tokens: Vec<String>,
+}
+
+/// Pipeline tokenizing each document with the [`TokenConfig`] of its language.
+///
+/// [`Pipeline::auto`] configures every language of [`Language::ALL`] with its own stop words. Only English
+/// is stemmed, since the Porter stemmer is English-only; [`Pipeline::language_config`] overrides the
+/// configuration of any language, for example to turn off stemming or to supply a custom stop word list.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::language::Language;
+/// use rnltk::pipeline::Pipeline;
+///
+/// let pipeline = Pipeline::auto();
+/// let documents = pipeline.process_all(&["The bees are making honey.", "Las abejas hacen la miel."]);
+///
+/// assert_eq!(documents[0].language, Language::English);
+/// assert_eq!(documents[0].tokens, vec!["bee", "make", "honei"]);
+/// assert_eq!(documents[1].language, Language::Spanish);
+/// assert_eq!(documents[1].tokens, vec!["abejas", "hacen", "miel"]);
+/// ```
+///
+/// Deserializing fails unless the fallback language has a configuration, which is used for any language without one.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(try_from = "UncheckedPipeline")]
+pub struct Pipeline {
+    configs: BTreeMap<Language, TokenConfig>,
+    fallback: Language,
+}
+
+// Pipeline as deserialized, before its fallback language is checked
+#[derive(Deserialize)]
+struct UncheckedPipeline {
+    configs: BTreeMap<Language, TokenConfig>,
+    fallback: Language,
+}
+
+impl TryFrom<UncheckedPipeline> for Pipeline {
+    type Error = RnltkError;
+
+    fn try_from(value: UncheckedPipeline) -> Result<Self, Self::Error> {
+        if !value.configs.contains_key(&value.fallback) {
+            return Err(RnltkError::PipelineFallbackMissing);
+        }
+        Ok(Pipeline {
+            configs: value.configs,
+            fallback: value.fallback,
+        })
+    }
+}
+
+impl Pipeline {
+    /// Creates new instance of Pipeline configuring every supported language with its stop words,
+    /// stemming English only, and falling back to English for documents whose language isn't detected.
+    pub fn auto() -> Self {
+        let configs = Language::ALL.into_iter().map(|language| {
+            let config = match language {
+                Language::English => TokenConfig::default(),
+                _ => TokenConfig::builder().stem(false).stop_words(language.get_stop_words()).build(),
+            };
+            (language, config)
+        }).collect();
+        Pipeline {
+            configs,
+            fallback: Language::English,
+        }
+    }
+
+    /// Sets the configuration used for documents in `language`, replacing the one from [`Pipeline::auto`].
+    pub fn language_config(mut self, language: Language, config: TokenConfig) -> Self {
+        self.configs.insert(language, config);
+        self
+    }
+
+    /// Sets the language assumed for documents whose language isn't detected. Defaults to English.
+    pub fn fallback(mut self, language: Language) -> Self {
+        self.fallback = language;
+        self
+    }
+
+    /// Gets the configuration used for documents in `language`, which is the fallback language's
+    /// configuration if `language` has none.
+    pub fn get_config(&self, language: Language) -> &TokenConfig {
+        self.configs.get(&language)
+            .or_else(|| self.configs.get(&self.fallback))
+            .expect("Pipelines have a configuration for their fallback language")
+    }
+
+    /// Tokenizes `document` with the configuration of its detected language, or of the fallback language
+    /// if none is detected.
+    pub fn process(&self, document: &str) -> ProcessedDocument {
+        match language::detect_language(document) {
+            Some(language) => ProcessedDocument { detected: true, ..self.process_as(document, language) },
+            None => self.process_as(document, self.fallback),
+        }
+    }
+
+    /// Tokenizes `document` with the configuration of `language`, skipping detection, for documents
+    /// whose language is already known.
+    pub fn process_as(&self, document: &str, language: Language) -> ProcessedDocument {
+        let config = self.get_config(language);
+        let tokens = token::tokenize_into_sentences(document).iter()
+            .flat_map(|sentence| token::tokenize_sentence_configurable(sentence, config.clone()))
+            .collect();
+        ProcessedDocument {
+            language,
+            detected: false,
+            tokens,
+        }
+    }
+
+    /// Tokenizes every document of `documents` with [`Pipeline::process`], in order.
+    pub fn process_all<S: AsRef<str>>(&self, documents: &[S]) -> Vec<ProcessedDocument> {
+        documents.iter().map(|document| self.process(document.as_ref())).collect()
+    }
+}
+
+impl Default for Pipeline {
+    fn default() -> Self {
+        Self::auto()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overrides_and_fallback() {
+        let pipeline = Pipeline::auto()
+            .language_config(Language::English, TokenConfig::builder().stem(false).build())
+            .fallback(Language::German);
+        let english = pipeline.process("The bees are making honey");
+        assert_eq!((english.language, english.detected), (Language::English, true));
+        assert_eq!(english.tokens, vec!["bees", "making", "honey"]);
+
+        let unknown = pipeline.process("Zyxx qwop");
+        assert_eq!((unknown.language, unknown.detected), (Language::German, false));
+        assert_eq!(unknown.tokens, vec!["zyxx", "qwop"]);
+
+        let forced = pipeline.process_as("Der Hund und die Katze", Language::German);
+        assert_eq!((forced.detected, forced.tokens), (false, vec!["hund".to_string(), "katze".to_string()]));
+        assert!(!pipeline.get_config(Language::French).stem);
+    }
+
+    #[test]
+    fn deserialization_checks_fallback() {
+        assert!(matches!(serde_json::from_str::<Pipeline>(r#"{"configs":{},"fallback":"English"}"#), Err(error) if error.to_string().contains("fallback language")));
+
+        let config = serde_json::to_string(&TokenConfig::builder().stem(false).build()).unwrap();
+        let pipeline: Pipeline = serde_json::from_str(&format!(r#"{{"configs":{{"German":{config}}},"fallback":"German"}}"#)).unwrap();
+        let processed = pipeline.process_as("The bees are making honey", Language::English);
+        assert_eq!(processed.tokens, vec!["bees", "making", "honey"]);
+    }
+}
@@ -0,0 +1,191 @@
+//! Module containing retrieval-based, extractive question answering over a set of documents.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Serialize, Deserialize};
+
+use crate::corpus::DocId;
+use crate::similarity;
+use crate::token::{self, TokenConfig};
+
+/// Term frequency saturation parameter used by [`rank_passages`].
+const BM25_K1: f64 = 1.2;
+/// Document length normalization parameter used by [`rank_passages`].
+const BM25_B: f64 = 0.75;
+
+/// Struct for holding a candidate answer sentence returned from [`answer_question`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Answer {
+    /// The answer sentence, as split by [`token::tokenize_into_sentences`]
+    pub sentence: String,
+    /// Id of the source document, its index in the `documents` slice
+    pub doc_id: DocId,
+    /// Score of the answer within [0...1], higher is better
+    pub score: f64,
+}
+
+/// Ranks `documents` against a `query` using Okapi BM25, returning `(document_index, score)`
+/// pairs sorted from best to worst match. Documents that share no terms with the query are omitted.
+///
+/// The query and the documents are tokenized with the default [`TokenConfig`], so stop words are
+/// removed and terms are stemmed before scoring.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::qa;
+///
+/// let documents = vec![
+///     "The Eiffel Tower is located in Paris.",
+///     "Bees make honey from the nectar of flowers.",
+/// ];
+/// let ranked = qa::rank_passages("Where is the Eiffel Tower?", &documents);
+///
+/// assert_eq!(ranked.len(), 1);
+/// assert_eq!(ranked[0].0, 0);
+/// ```
+pub fn rank_passages(query: &str, documents: &[&str]) -> Vec<(usize, f64)> {
+    let query_terms: BTreeSet<String> = tokenize(query).into_iter().collect();
+    let documents_terms: Vec<Vec<String>> = documents.iter().map(|document| tokenize(document)).collect();
+    if documents_terms.is_empty() {
+        return vec![];
+    }
+
+    let num_documents = documents_terms.len() as f64;
+    let average_length = documents_terms.iter().map(|terms| terms.len()).sum::<usize>() as f64 / num_documents;
+    let mut document_frequencies: BTreeMap<&str, f64> = BTreeMap::new();
+    for terms in &documents_terms {
+        for term in terms.iter().map(String::as_str).collect::<BTreeSet<&str>>() {
+            *document_frequencies.entry(term).or_insert(0.) += 1.;
+        }
+    }
+
+    let mut ranked: Vec<(usize, f64)> = documents_terms.iter().enumerate().filter_map(|(document_index, terms)| {
+        let mut term_counts: BTreeMap<&str, f64> = BTreeMap::new();
+        for term in terms {
+            *term_counts.entry(term).or_insert(0.) += 1.;
+        }
+        let length_ratio = if average_length > 0. { terms.len() as f64 / average_length } else { 0. };
+
+        let score: f64 = query_terms.iter().filter_map(|query_term| {
+            let term_frequency = term_counts.get(query_term.as_str())?;
+            let document_frequency = document_frequencies[query_term.as_str()];
+            let inverse_document_frequency = ((num_documents - document_frequency + 0.5) / (document_frequency + 0.5) + 1.).ln();
+            Some(inverse_document_frequency * term_frequency * (BM25_K1 + 1.)
+                / (term_frequency + BM25_K1 * (1. - BM25_B + BM25_B * length_ratio)))
+        }).sum();
+
+        if score > 0. {
+            Some((document_index, score))
+        } else {
+            None
+        }
+    }).collect();
+    ranked.sort_by(|(first_index, first_score), (second_index, second_score)| {
+        second_score.total_cmp(first_score).then(first_index.cmp(second_index))
+    });
+
+    ranked
+}
+
+/// Answers a `question` by extracting the best matching sentences from `documents`.
+///
+/// The `num_passages` best documents are retrieved with [`rank_passages`], then each of their
+/// sentences is scored as the average of its lexical overlap with the question (the fraction of
+/// question terms it contains) and its TF-IDF cosine similarity to the question. Both are computed on
+/// terms from the default [`TokenConfig`], like [`rank_passages`]. Up to `num_answers` sentences are
+/// returned from best to worst.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::corpus::DocId;
+/// use rnltk::qa;
+///
+/// let documents = vec![
+///     "Paris is the capital of France. The Eiffel Tower is located in Paris.",
+///     "Bees make honey from the nectar of flowers. Honey never spoils.",
+/// ];
+/// let answers = qa::answer_question("What do bees make?", &documents, 1, 1);
+///
+/// assert_eq!(answers[0].sentence, "Bees make honey from the nectar of flowers");
+/// assert_eq!(answers[0].doc_id, DocId(1));
+/// ```
+pub fn answer_question(question: &str, documents: &[&str], num_passages: usize, num_answers: usize) -> Vec<Answer> {
+    let question_terms: BTreeSet<String> = tokenize(question).into_iter().collect();
+    if question_terms.is_empty() {
+        return vec![];
+    }
+
+    let mut answers: Vec<Answer> = rank_passages(question, documents).into_iter().take(num_passages).flat_map(|(document_index, _)| {
+        token::tokenize_into_sentences(documents[document_index]).into_iter().map(move |sentence| (document_index, sentence))
+    }).filter_map(|(document_index, sentence)| {
+        let sentence_terms: BTreeSet<String> = tokenize(&sentence).into_iter().collect();
+        let overlap = question_terms.intersection(&sentence_terms).count() as f64 / question_terms.len() as f64;
+        if overlap == 0. {
+            return None;
+        }
+        let cosine = tfidf_cosine(question, &sentence);
+        Some(Answer {
+            sentence,
+            doc_id: DocId(document_index as u64),
+            score: (overlap + cosine) / 2.,
+        })
+    }).collect();
+    answers.sort_by(|first, second| second.score.total_cmp(&first.score).then(first.doc_id.cmp(&second.doc_id)));
+    answers.truncate(num_answers);
+
+    answers
+}
+
+fn tokenize(text: &str) -> Vec<String> {
+    token::tokenize_sentence_configurable(text, TokenConfig::default())
+}
+
+// cosine similarity of the smoothed TF-IDF vectors of the terms `tokenize` finds in `first` and `second`
+fn tfidf_cosine(first: &str, second: &str) -> f64 {
+    let term_frequencies = token::get_term_frequencies_from_sentences_configurable(&[first, second], TokenConfig::default());
+    let weighted = similarity::smoothed_tfidf(&term_frequencies);
+    similarity::cosine(&weighted[0], &weighted[1])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn documents() -> Vec<&'static str> {
+        vec![
+            "Paris is the capital of France. The Eiffel Tower is located in Paris.",
+            "Bees make honey from the nectar of flowers. Honey never spoils.",
+            "The Louvre in Paris is the most visited museum in the world.",
+        ]
+    }
+
+    #[test]
+    fn passages_ranked_by_bm25() {
+        let ranked = rank_passages("Which museum in Paris is the most visited?", &documents());
+        let ranked_indices: Vec<usize> = ranked.iter().map(|(document_index, _)| *document_index).collect();
+        assert_eq!(ranked_indices, vec![2, 0]);
+    }
+
+    #[test]
+    fn answer_from_best_passage() {
+        let answers = answer_question("Where is the Eiffel Tower located?", &documents(), 2, 2);
+        assert_eq!(answers[0].sentence, "The Eiffel Tower is located in Paris");
+        assert_eq!(answers[0].doc_id, DocId(0));
+        assert_eq!(answers.len(), 1);
+    }
+
+    #[test]
+    fn scores_use_stemmed_terms_without_stop_words() {
+        let answers = answer_question("Which flowers do bees visit?", &["The bee visited the flower."], 1, 1);
+        // every question term is in the answer once stemmed, so overlap and cosine are both 1
+        assert!((answers[0].score - 1.).abs() < 1e-10, "{}", answers[0].score);
+    }
+
+    #[test]
+    fn no_answer_for_unrelated_question() {
+        let answers = answer_question("Who wrote Hamlet?", &documents(), 3, 3);
+        assert!(answers.is_empty());
+    }
+}
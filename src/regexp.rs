@@ -0,0 +1,143 @@
+//! Module containing a tokenizer driven by a user supplied regex, mirroring NLTK's `RegexpTokenizer`, for
+//! domain-specific text such as log lines, chemical names, or code identifiers.
+//!
+//! Tokens are either the matches of the pattern or, with [`RegexpTokenizer::gaps`], the text between them.
+//! No casing or punctuation changes are made, so tokens are always slices of the input.
+
+use regex::Regex;
+
+use crate::error::RnltkError;
+use crate::token::TokenSpan;
+
+/// Tokenizer that splits text with a regex.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::regexp::RegexpTokenizer;
+///
+/// let tokenizer = RegexpTokenizer::new(r"\w+|\$[\d.]+|\S+").unwrap();
+/// assert_eq!(tokenizer.tokenize("Good muffins cost $3.88 in N.Y."), vec!["Good", "muffins", "cost", "$3.88", "in", "N", ".Y."]);
+///
+/// let tokenizer = RegexpTokenizer::new(r"\s*[|=]\s*").unwrap().gaps(true);
+/// assert_eq!(tokenizer.tokenize("level=ERROR | module = parser"), vec!["level", "ERROR", "module", "parser"]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct RegexpTokenizer {
+    regex: Regex,
+    gaps: bool,
+    discard_empty: bool,
+}
+
+impl RegexpTokenizer {
+    /// Creates new instance of RegexpTokenizer whose tokens are the matches of `pattern`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RnltkError::InvalidTokenizerPattern`] if `pattern` isn't a valid regex.
+    pub fn new(pattern: &str) -> Result<Self, RnltkError> {
+        let regex = Regex::new(pattern).map_err(|error| RnltkError::InvalidTokenizerPattern(error.to_string()))?;
+        Ok(RegexpTokenizer {
+            regex,
+            gaps: false,
+            discard_empty: true,
+        })
+    }
+
+    /// Sets whether the pattern matches the separators between tokens rather than the tokens. Defaults to `false`.
+    pub fn gaps(mut self, gaps: bool) -> Self {
+        self.gaps = gaps;
+        self
+    }
+
+    /// Sets whether empty tokens are dropped. Defaults to `true`.
+    ///
+    /// Empty tokens only arise from gaps at the start or end of the text, adjacent gaps, or patterns that
+    /// can match the empty string.
+    pub fn discard_empty(mut self, discard_empty: bool) -> Self {
+        self.discard_empty = discard_empty;
+        self
+    }
+
+    /// Gets the pattern of the tokenizer.
+    pub fn get_pattern(&self) -> &str {
+        self.regex.as_str()
+    }
+
+    /// Converts `text` to tokens.
+    pub fn tokenize(&self, text: &str) -> Vec<String> {
+        self.spans(text).into_iter().map(|(start, end)| text[start..end].to_string()).collect()
+    }
+
+    /// Converts `text` to tokens together with their byte and character offsets into `text`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rnltk::regexp::RegexpTokenizer;
+    ///
+    /// let tokenizer = RegexpTokenizer::new(r"[A-Z][a-z]?\d*").unwrap();
+    /// let spans = tokenizer.tokenize_with_spans("né C6H12O6");
+    ///
+    /// assert_eq!(spans[0].token, "C6");
+    /// assert_eq!((spans[0].start, spans[0].end), (4, 6));
+    /// assert_eq!((spans[0].char_start, spans[0].char_end), (3, 5));
+    /// ```
+    pub fn tokenize_with_spans(&self, text: &str) -> Vec<TokenSpan> {
+        let mut chars = 0;
+        let mut offset = 0;
+        let mut char_offset = |byte: usize| {
+            chars += text[offset..byte].chars().count();
+            offset = byte;
+            chars
+        };
+        self.spans(text).into_iter().map(|(start, end)| {
+            let char_start = char_offset(start);
+            let char_end = char_offset(end);
+            TokenSpan {
+                token: text[start..end].to_string(),
+                start,
+                end,
+                char_start,
+                char_end,
+            }
+        }).collect()
+    }
+
+    // byte ranges of the tokens, in order
+    fn spans(&self, text: &str) -> Vec<(usize, usize)> {
+        let spans: Vec<(usize, usize)> = if self.gaps {
+            let mut spans = Vec::new();
+            let mut start = 0;
+            for separator in self.regex.find_iter(text) {
+                spans.push((start, separator.start()));
+                start = separator.end();
+            }
+            spans.push((start, text.len()));
+            spans
+        } else {
+            self.regex.find_iter(text).map(|token| (token.start(), token.end())).collect()
+        };
+        spans.into_iter().filter(|(start, end)| !self.discard_empty || start < end).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_and_gaps() {
+        let identifiers = RegexpTokenizer::new(r"[A-Za-z_][A-Za-z0-9_]*").unwrap();
+        assert_eq!(identifiers.tokenize("let total_2 = count + 1;"), vec!["let", "total_2", "count"]);
+
+        let lines = RegexpTokenizer::new(",").unwrap().gaps(true);
+        assert_eq!(lines.tokenize(",a,,b,"), vec!["a", "b"]);
+        assert_eq!(lines.clone().discard_empty(false).tokenize(",a,,b,"), vec!["", "a", "", "b", ""]);
+        assert_eq!(lines.get_pattern(), ",");
+
+        let spans = lines.tokenize_with_spans("ä,b");
+        assert_eq!((spans[1].start, spans[1].char_start), (3, 2));
+        assert!(matches!(RegexpTokenizer::new("("), Err(RnltkError::InvalidTokenizerPattern(_))));
+    }
+}
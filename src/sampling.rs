@@ -0,0 +1,238 @@
+//! Module containing seeded random sampling of sentences and documents for manual corpus inspection
+//! and for balancing corpora before classifier training.
+//!
+//! All functions return indices into the caller's data or a [`CorpusView`] rather than copies, and the
+//! same `seed` always produces the same sample.
+
+use std::collections::BTreeMap;
+
+use rand::rngs::StdRng;
+use rand::seq::index;
+use rand::{Rng, SeedableRng};
+
+use crate::corpus::{Corpus, CorpusDocument, CorpusView};
+use crate::token;
+
+/// Samples up to `n` distinct indices from `0..population` uniformly at random.
+/// Indices are returned in ascending order.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::sampling;
+///
+/// let sample = sampling::sample_indices(100, 5, 42);
+///
+/// assert_eq!(sample.len(), 5);
+/// assert_eq!(sample, sampling::sample_indices(100, 5, 42));
+/// ```
+pub fn sample_indices(population: usize, n: usize, seed: u64) -> Vec<usize> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut sample = index::sample(&mut rng, population, n.min(population)).into_vec();
+    sample.sort_unstable();
+    sample
+}
+
+/// Samples up to `n` distinct indices, where the chance of picking index `i` is proportional
+/// to `weights[i]`. Indices with a weight of 0 (or a negative or non-finite weight) are never picked.
+/// Indices are returned in ascending order.
+///
+/// Uses the Efraimidis–Spirakis method of keeping the `n` largest keys \\(u_i^{1 / w_i}\\).
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::sampling;
+///
+/// let weights = vec![0., 1., 5., 0., 2.];
+/// let sample = sampling::sample_weighted_indices(&weights, 3, 7);
+///
+/// assert_eq!(sample, vec![1, 2, 4]);
+/// ```
+pub fn sample_weighted_indices(weights: &[f64], n: usize, seed: u64) -> Vec<usize> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut keyed: Vec<(f64, usize)> = weights.iter().enumerate()
+        .filter(|(_, weight)| weight.is_finite() && **weight > 0.)
+        .map(|(index, weight)| (rng.gen::<f64>().powf(1. / weight), index))
+        .collect();
+    keyed.sort_by(|(first_key, _), (second_key, _)| second_key.total_cmp(first_key));
+
+    let mut sample: Vec<usize> = keyed.into_iter().take(n).map(|(_, index)| index).collect();
+    sample.sort_unstable();
+    sample
+}
+
+/// Samples up to `n_per_stratum` indices from every stratum, where `strata[i]` is the stratum
+/// (category, length bucket, sentiment label, ...) of item `i`.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::sampling;
+///
+/// let categories = vec!["sports", "news", "sports", "sports", "news"];
+/// let sample = sampling::sample_stratified_indices(&categories, 1, 3);
+///
+/// assert_eq!(sample["news"].len(), 1);
+/// assert_eq!(sample["sports"].len(), 1);
+/// ```
+pub fn sample_stratified_indices<K: Ord + Clone>(strata: &[K], n_per_stratum: usize, seed: u64) -> BTreeMap<K, Vec<usize>> {
+    let mut members: BTreeMap<K, Vec<usize>> = BTreeMap::new();
+    for (index, stratum) in strata.iter().enumerate() {
+        members.entry(stratum.clone()).or_default().push(index);
+    }
+
+    members.into_iter().enumerate().map(|(stratum_index, (stratum, indices))| {
+        let sample = sample_indices(indices.len(), n_per_stratum, seed.wrapping_add(stratum_index as u64))
+            .into_iter()
+            .map(|sample_index| indices[sample_index])
+            .collect();
+        (stratum, sample)
+    }).collect()
+}
+
+/// Gets the length stratum of each text, defined as its token count divided by `bucket_width`,
+/// for use with [`sample_stratified_indices`].
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::sampling;
+///
+/// let texts = vec!["short one", "a slightly longer sentence than that", "tiny"];
+/// let strata = sampling::length_strata(&texts, 5);
+///
+/// assert_eq!(strata, vec![0, 1, 0]);
+/// ```
+pub fn length_strata(texts: &[&str], bucket_width: usize) -> Vec<usize> {
+    texts.iter().map(|text| token::tokenize_sentence(text).len() / bucket_width.max(1)).collect()
+}
+
+/// Splits every document into sentences with [`token::tokenize_into_sentences`] and samples
+/// up to `n` of them uniformly at random, returned in corpus order.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::sampling;
+///
+/// let documents = vec!["Why hello there. General Kenobi!", "You are a bold one."];
+/// let sentences = sampling::sample_sentences(&documents, 2, 11);
+///
+/// assert_eq!(sentences.len(), 2);
+/// ```
+pub fn sample_sentences(documents: &[&str], n: usize, seed: u64) -> Vec<String> {
+    let mut sentences: Vec<String> = documents.iter().flat_map(|document| token::tokenize_into_sentences(document)).collect();
+    let sample = sample_indices(sentences.len(), n, seed);
+    let mut sample_iter = sample.into_iter().peekable();
+    let mut index = 0;
+    sentences.retain(|_| {
+        let keep = sample_iter.peek() == Some(&index);
+        if keep {
+            sample_iter.next();
+        }
+        index += 1;
+        keep
+    });
+    sentences
+}
+
+/// Downsamples every class of `corpus` to the size of its smallest class, where `class` gets the class
+/// (label, category, ...) of a document, so a classifier isn't trained mostly on the majority class.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::corpus::CorpusReader;
+/// use rnltk::sampling;
+///
+/// let corpus = CorpusReader::new().read_texts(vec!["great movie", "loved it", "what fun", "boring"]).unwrap();
+/// let labels = ["positive", "positive", "positive", "negative"];
+/// let balanced = sampling::balance_classes(&corpus, |document| labels[document.id.0 as usize], 3);
+///
+/// assert_eq!(balanced.len(), 2);
+/// assert_eq!(balanced.get_documents()[1].text, "boring");
+/// ```
+pub fn balance_classes<'a, K: Ord + Clone>(corpus: &'a Corpus, class: impl Fn(&CorpusDocument) -> K, seed: u64) -> CorpusView<'a> {
+    let classes: Vec<K> = corpus.get_documents().iter().map(class).collect();
+    let mut class_sizes: BTreeMap<&K, usize> = BTreeMap::new();
+    for document_class in &classes {
+        *class_sizes.entry(document_class).or_insert(0) += 1;
+    }
+    let smallest_class = class_sizes.values().copied().min().unwrap_or(0);
+    sample_per_stratum(corpus, &classes, smallest_class, seed)
+}
+
+/// Samples up to `max_per_source` documents from every source of `corpus`, where `source` gets the source
+/// (website, author, file directory, ...) of a document, so no single source dominates the corpus.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::corpus::CorpusReader;
+/// use rnltk::sampling;
+///
+/// let corpus = CorpusReader::new().read_texts(vec!["a", "b", "c", "d", "e"]).unwrap();
+/// let sources = ["blog", "blog", "blog", "news", "blog"];
+/// let capped = sampling::cap_per_source(&corpus, |document| sources[document.id.0 as usize], 2, 9);
+///
+/// assert_eq!(capped.len(), 3);
+/// assert!(capped.get_documents().iter().any(|document| document.text == "d"));
+/// ```
+pub fn cap_per_source<'a, K: Ord + Clone>(corpus: &'a Corpus, source: impl Fn(&CorpusDocument) -> K, max_per_source: usize, seed: u64) -> CorpusView<'a> {
+    let sources: Vec<K> = corpus.get_documents().iter().map(source).collect();
+    sample_per_stratum(corpus, &sources, max_per_source, seed)
+}
+
+fn sample_per_stratum<'a, K: Ord + Clone>(corpus: &'a Corpus, strata: &[K], n_per_stratum: usize, seed: u64) -> CorpusView<'a> {
+    let documents = corpus.get_documents();
+    let ids = sample_stratified_indices(strata, n_per_stratum, seed)
+        .into_values()
+        .flatten()
+        .map(|index| documents[index].id);
+    CorpusView::new(corpus, ids)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sample_is_reproducible() {
+        assert_eq!(sample_indices(1000, 10, 1), sample_indices(1000, 10, 1));
+        assert_ne!(sample_indices(1000, 10, 1), sample_indices(1000, 10, 2));
+    }
+
+    #[test]
+    fn sample_larger_than_population() {
+        assert_eq!(sample_indices(3, 10, 1), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn weighted_sample_prefers_heavy_items() {
+        let weights = vec![1., 1000., 1.];
+        let heavy_count = (0..100).filter(|seed| sample_weighted_indices(&weights, 1, *seed) == vec![1]).count();
+        assert!(heavy_count > 90);
+    }
+
+    #[test]
+    fn stratified_sample_covers_every_stratum() {
+        let strata = vec![0, 1, 1, 2, 2, 2];
+        let sample = sample_stratified_indices(&strata, 2, 5);
+        assert_eq!(sample[&0], vec![0]);
+        assert_eq!(sample[&1], vec![1, 2]);
+        assert_eq!(sample[&2].len(), 2);
+        assert!(sample[&2].iter().all(|index| strata[*index] == 2));
+    }
+
+    #[test]
+    fn balanced_classes_have_equal_sizes() {
+        let corpus = crate::corpus::CorpusReader::new().read_texts((0..10).map(|index| index.to_string()).collect()).unwrap();
+        let balanced = balance_classes(&corpus, |document| document.id.0 % 3 == 0, 4);
+        assert_eq!(balanced.len(), 8);
+        assert_eq!(balanced.get_documents().iter().filter(|document| document.id.0 % 3 == 0).count(), 4);
+        assert!(balanced.get_doc_ids().windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(balanced.to_corpus().get_doc_ids(), balanced.get_doc_ids());
+        assert_eq!(balanced, balance_classes(&corpus, |document| document.id.0 % 3 == 0, 4));
+    }
+}
@@ -0,0 +1,153 @@
+//! Module containing rule-based detection of sentence types, such as questions and commands.
+//!
+//! Types are found from terminal punctuation and a few cues from the first words of the sentence, so they are
+//! a quick pre-filter rather than a parse. Sentences should keep their punctuation, as they do with
+//! [`tokenize_into_sentences_with_delimiters`](crate::token::tokenize_into_sentences_with_delimiters).
+
+use serde::{Serialize, Deserialize};
+
+use crate::token;
+
+// words that start a question even without a question mark, such as "how do I reset it"
+const QUESTION_STARTERS: [&str; 28] = [
+    "what", "who", "whom", "whose", "where", "when", "why", "how", "which",
+    "is", "are", "am", "was", "were", "do", "does", "did", "can", "could", "will",
+    "would", "should", "shall", "may", "might", "have", "has", "had",
+];
+
+// words that can precede the verb of a command, as in "please close it" or "don't go"
+const IMPERATIVE_PREFIXES: [&str; 7] = ["please", "kindly", "just", "now", "always", "never", "don't"];
+
+// subjects that make a leading "do" a question or emphasis rather than a command, as in "do you ship abroad"
+const SUBJECT_PRONOUNS: [&str; 6] = ["i", "you", "we", "they", "he", "she"];
+
+// common verbs that start commands in their base form
+const IMPERATIVE_VERBS: [&str; 88] = [
+    "add", "allow", "answer", "ask", "be", "book", "bring", "buy", "call", "cancel",
+    "change", "check", "choose", "clean", "clear", "close", "come", "compare", "consider", "continue",
+    "copy", "create", "delete", "describe", "download", "drop", "email", "enable", "disable", "enter",
+    "explain", "fill", "find", "fix", "follow", "forget", "get", "give", "go", "help",
+    "hold", "install", "keep", "leave", "let", "list", "listen", "load", "log", "look",
+    "make", "move", "note", "open", "order", "pay", "pick", "play", "print", "put",
+    "read", "remember", "remind", "remove", "rename", "repeat", "reset", "restart", "run", "save",
+    "schedule", "search", "select", "send", "set", "show", "sign", "start", "stop", "take",
+    "tell", "text", "try", "turn", "update", "use", "wait", "write",
+];
+
+/// The type of a sentence, from [`get_sentence_type`].
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SentenceType {
+    /// Asks something, like "Where is it?"
+    Question,
+    /// Exclaims something, like "What a day!"
+    Exclamation,
+    /// States something, like "It is raining."
+    Statement,
+    /// Gives a command or makes a request, like "Please close the door."
+    Imperative,
+}
+
+// lowercased words of `sentence` with surrounding punctuation removed, keeping apostrophes inside words
+fn words(sentence: &str) -> Vec<String> {
+    sentence.split_whitespace()
+        .map(|word| word.replace('\u{2019}', "'").trim_matches(|c: char| !c.is_alphanumeric()).to_lowercase())
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+fn is_imperative(words: &[String]) -> bool {
+    if words.iter().any(|word| word == "please") {
+        return true;
+    }
+    let mut index = 0;
+    while words.get(index).is_some_and(|word| IMPERATIVE_PREFIXES.contains(&word.as_str())) {
+        index += 1;
+    }
+    match words.get(index).map(String::as_str) {
+        Some("do") => words.get(index + 1).is_none_or(|next| !SUBJECT_PRONOUNS.contains(&next.as_str())),
+        Some(word) => IMPERATIVE_VERBS.contains(&word),
+        None => false,
+    }
+}
+
+/// Gets the type of `sentence` from its terminal punctuation and first words.
+///
+/// A sentence is a question if it ends with "?", or if it has no terminal punctuation and starts with a question word
+/// or an auxiliary verb such as "is" or "can". Otherwise it is imperative if it contains "please" or starts with a
+/// common verb in its base form, possibly after words such as "please", "just", or "don't". Remaining sentences
+/// ending with "!" are exclamations, and the rest are statements.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::sentence::{self, SentenceType};
+///
+/// assert_eq!(sentence::get_sentence_type("Where did you go?"), SentenceType::Question);
+/// assert_eq!(sentence::get_sentence_type("how do I reset my password"), SentenceType::Question);
+/// assert_eq!(sentence::get_sentence_type("Don't forget the milk!"), SentenceType::Imperative);
+/// assert_eq!(sentence::get_sentence_type("What a day!"), SentenceType::Exclamation);
+/// assert_eq!(sentence::get_sentence_type("The door is open."), SentenceType::Statement);
+/// ```
+pub fn get_sentence_type(sentence: &str) -> SentenceType {
+    let trimmed = sentence.trim_end_matches(|c: char| c.is_whitespace() || matches!(c, '"' | '\'' | ')' | ']' | '\u{201D}' | '\u{2019}'));
+    let terminal: String = trimmed.chars().rev().take_while(|c| matches!(c, '.' | '!' | '?')).collect();
+    let words = words(trimmed);
+    if terminal.contains('?') || (terminal.is_empty() && words.first().is_some_and(|word| QUESTION_STARTERS.contains(&word.as_str()))) {
+        SentenceType::Question
+    } else if is_imperative(&words) {
+        SentenceType::Imperative
+    } else if terminal.contains('!') {
+        SentenceType::Exclamation
+    } else {
+        SentenceType::Statement
+    }
+}
+
+/// Splits `document` into sentences with [`tokenize_into_sentences_with_delimiters`](crate::token::tokenize_into_sentences_with_delimiters)
+/// and gets the type of each, as with [`get_sentence_type`].
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::sentence::{self, SentenceType};
+///
+/// let sentence_types = sentence::get_sentence_types("My order is late. Where is it?! Cancel it now.");
+/// let types: Vec<SentenceType> = sentence_types.iter().map(|(_, sentence_type)| *sentence_type).collect();
+///
+/// assert_eq!(sentence_types[1].0, "Where is it?!");
+/// assert_eq!(types, vec![SentenceType::Statement, SentenceType::Question, SentenceType::Imperative]);
+/// ```
+pub fn get_sentence_types(document: &str) -> Vec<(String, SentenceType)> {
+    token::tokenize_into_sentences_with_delimiters(document).into_iter()
+        .map(|sentence| {
+            let sentence_type = get_sentence_type(&sentence);
+            (sentence, sentence_type)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn punctuation_decides_first() {
+        assert_eq!(get_sentence_type("You did what?!"), SentenceType::Question);
+        assert_eq!(get_sentence_type("\"Is it over?\""), SentenceType::Question);
+        assert_eq!(get_sentence_type("Please, what time is it?"), SentenceType::Question);
+        assert_eq!(get_sentence_type("What a mess."), SentenceType::Statement);
+        assert_eq!(get_sentence_type("It's over!!!"), SentenceType::Exclamation);
+    }
+
+    #[test]
+    fn imperative_cues() {
+        assert_eq!(get_sentence_type("Do not touch that."), SentenceType::Imperative);
+        assert_eq!(get_sentence_type("Just send it"), SentenceType::Imperative);
+        assert_eq!(get_sentence_type("I would like a refund please."), SentenceType::Imperative);
+        assert_eq!(get_sentence_type("Do you ship abroad"), SentenceType::Question);
+        assert_eq!(get_sentence_type("Do it later."), SentenceType::Imperative);
+        assert_eq!(get_sentence_type("Do you ship abroad."), SentenceType::Statement);
+        assert_eq!(get_sentence_type("The stop was missed."), SentenceType::Statement);
+        assert_eq!(get_sentence_type(""), SentenceType::Statement);
+    }
+}
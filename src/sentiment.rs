@@ -1,19 +1,60 @@
 //! Module containing types used to get valence and arousal sentiment scores.
+//!
+//! Maps returned by this module are [`BTreeMap`]s, so they iterate and serialize in key order. The
+//! [`CustomWords`] and [`CustomStems`] lexicons passed in remain `HashMap`s, since they are only used for lookups.
 
-use std::{collections::HashMap, borrow::Cow};
+use std::{collections::{BTreeMap, HashMap, hash_map::Entry}, borrow::Cow};
 use std::f64::consts::PI;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
 
 use serde::{Serialize, Deserialize};
 
-use crate::stem;
+use crate::{stem, token};
 use crate::error::RnltkError;
+use crate::lexicon::LexiconBackend;
+use crate::token::CaseFolding;
+use crate::trace::stage_span;
 
 pub type CustomWords = HashMap<String, SentimentDictValue>;
 pub type CustomStems = HashMap<String, SentimentDictValue>;
 
+// Angular cutoffs for different emotional states (same on top and bottom)
+const ANGULAR_CUTOFFS: [f64; 9] = [0.0, 18.43, 45.0, 71.57, 90.0, 108.43, 135.0, 161.57, 180.0];
+
+// Terms to return for bottom, top half of circumplex
+const LOWER_TERMS: [&str; 8] = [
+    "contented", "serene", "relaxed", "calm",
+    "bored", "lethargic", "depressed", "sad"
+];
+const UPPER_TERMS: [&str; 8] = [
+    "happy", "elated", "excited", "alert",
+    "tense", "nervous", "stressed", "upset"
+];
+
+/// Gets the angle in degrees, normalized radius, and normalized arousal of `valence` and `arousal` on the circumplex.
+fn get_circumplex_coordinates(valence: f64, arousal: f64) -> (f64, f64, f64) {
+    // Normalize valence and arousal, using polar coordinates to get angle
+    // clockwise along bottom, counterclockwise along top
+    let normalized_valence = ((valence - 1.0) - 4.0) / 4.0;
+    let normalized_arousal = ((arousal - 1.0) - 4.0) / 4.0;
+    let mut radius = (normalized_valence.powi(2).abs() + normalized_arousal.powi(2).abs()).sqrt();
+    let direction = (normalized_valence / radius).acos().to_degrees();
+
+    //  Normalize radius for "strength" of emotion
+    if direction <= 45.0 || direction >= 135.0 {
+        radius /= (normalized_arousal.powi(2).abs() + 1.0).sqrt();
+    } else {
+        radius /= (normalized_valence.powi(2).abs() + 1.0).sqrt();
+    }
+    (direction, radius, normalized_arousal)
+}
+
 /// Struct for holding raw arousal and sentiment values for
 /// `average` and `standard_deviation`.
-#[derive(Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct RawSentiment {
     pub average: f64,
     pub standard_deviation: f64
@@ -28,8 +69,84 @@ impl RawSentiment {
     }
 }
 
+/// Struct for holding the lexicon valence and arousal of a single token
+/// from [`SentimentModel::tag_tokens`]. Both values are `None` if the token
+/// does not exist in the sentiment lexicons.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TokenSentiment {
+    pub token: String,
+    pub valence: Option<f64>,
+    pub arousal: Option<f64>
+}
+
+/// Struct for holding the mean and standard deviation of a valence or arousal rating.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct Score {
+    pub mean: f64,
+    pub std: f64
+}
+
+impl Score {
+    /// Creates new instance of Score.
+    pub fn new(mean: f64, std: f64) -> Self {
+        Score {
+            mean,
+            std
+        }
+    }
+}
+
+/// Struct for holding the position of a valence and arousal rating on the circumplex, the circle that
+/// sentiment descriptions are read from.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::sentiment::CircumplexPoint;
+///
+/// let point = CircumplexPoint::from_scores(1.0, 9.0).unwrap();
+/// let (x, y) = point.to_cartesian();
+///
+/// assert_eq!(point.angle, 135.0);
+/// assert_eq!(point.radius, 1.0);
+/// assert!((x + 0.5_f64.sqrt()).abs() < 1e-12 && (y - 0.5_f64.sqrt()).abs() < 1e-12);
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct CircumplexPoint {
+    /// Direction of the emotion in degrees, counterclockwise from positive valence, from 0 up to 360;
+    /// 90 is high arousal and 270 is low arousal
+    pub angle: f64,
+    /// Intensity of the emotion, from 0 at the neutral center to 1 on the edge of the circumplex
+    pub radius: f64,
+}
+
+impl CircumplexPoint {
+    /// Creates new instance of CircumplexPoint from `valence` and `arousal` scores, or `None` if they
+    /// aren't between 1 and 9 (inclusive). The neutral center has an angle of 0.
+    pub fn from_scores(valence: f64, arousal: f64) -> Option<Self> {
+        if !(1.0..=9.0).contains(&valence) || !(1.0..=9.0).contains(&arousal) {
+            return None;
+        }
+        if valence == 5.0 && arousal == 5.0 {
+            return Some(CircumplexPoint { angle: 0.0, radius: 0.0 });
+        }
+        let (direction, radius, normalized_arousal) = get_circumplex_coordinates(valence, arousal);
+        let angle = if normalized_arousal > 0.0 { direction } else { (360.0 - direction) % 360.0 };
+        Some(CircumplexPoint { angle, radius })
+    }
+
+    /// Gets the `(x, y)` position of the point for plotting, where x grows with valence and y with arousal.
+    pub fn to_cartesian(&self) -> (f64, f64) {
+        let radians = self.angle.to_radians();
+        (self.radius * radians.cos(), self.radius * radians.sin())
+    }
+}
+
 /// Struct for creating the basis of the sentiment lexicon.
-#[derive(Serialize, Deserialize, Debug)]
+///
+/// Deserializing an entry fails unless `avg` and `std` each hold exactly two values.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(try_from = "UncheckedSentimentDictValue")]
 pub struct SentimentDictValue {
     /// The full, unstemmed word
     pub word: String,
@@ -52,11 +169,325 @@ impl SentimentDictValue {
             std
         }
     }
+
+    /// Creates new instance of SentimentDictValue from `valence` and `arousal` [`Score`]s, stemming `word`
+    /// with [`stem::get`]. Words that can't be stemmed are their own stem.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rnltk::sentiment::{Score, SentimentDictValue};
+    /// 
+    /// let value = SentimentDictValue::from_scores("abduction", Score::new(2.76, 2.06), Score::new(5.53, 2.43));
+    ///
+    /// assert_eq!(value.get_stem(), "abduct");
+    /// assert_eq!(value.get_arousal(), Score { mean: 5.53, std: 2.43 });
+    /// ```
+    pub fn from_scores(word: impl ToString, valence: Score, arousal: Score) -> Self {
+        let word = word.to_string();
+        let stem = stem::get(&word).unwrap_or_else(|_| word.clone());
+        Self::from_scores_with_stem(word, stem, valence, arousal)
+    }
+
+    /// Creates new instance of SentimentDictValue from `valence` and `arousal` [`Score`]s with a given `stem`.
+    pub fn from_scores_with_stem(word: impl ToString, stem: impl ToString, valence: Score, arousal: Score) -> Self {
+        Self::new(word.to_string(), stem.to_string(), vec![valence.mean, arousal.mean], vec![valence.std, arousal.std])
+    }
+
+    /// Gets the full, unstemmed word.
+    pub fn get_word(&self) -> &str {
+        &self.word
+    }
+
+    /// Gets the stemmed version of the word.
+    pub fn get_stem(&self) -> &str {
+        &self.stem
+    }
+
+    /// Gets the valence [`Score`], with any missing value as 0.
+    pub fn get_valence(&self) -> Score {
+        self.get_score(0)
+    }
+
+    /// Gets the arousal [`Score`], with any missing value as 0.
+    pub fn get_arousal(&self) -> Score {
+        self.get_score(1)
+    }
+
+    fn get_score(&self, index: usize) -> Score {
+        Score::new(self.avg.get(index).copied().unwrap_or_default(), self.std.get(index).copied().unwrap_or_default())
+    }
+}
+
+// SentimentDictValue as deserialized, before its values are checked
+#[derive(Deserialize)]
+struct UncheckedSentimentDictValue {
+    word: String,
+    stem: String,
+    avg: Vec<f64>,
+    std: Vec<f64>
+}
+
+impl TryFrom<UncheckedSentimentDictValue> for SentimentDictValue {
+    type Error = RnltkError;
+
+    fn try_from(value: UncheckedSentimentDictValue) -> Result<Self, Self::Error> {
+        for (name, values) in [("avg", &value.avg), ("std", &value.std)] {
+            if values.len() != 2 {
+                return Err(RnltkError::LexiconFormat {
+                    word: value.word,
+                    reason: format!("'{}' must hold a valence and an arousal value, found {} value(s)", name, values.len()),
+                });
+            }
+        }
+        Ok(SentimentDictValue::new(value.word, value.stem, value.avg, value.std))
+    }
+}
+
+/// Lexicon file formats understood by [`load`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LexiconFormat {
+    /// A JSON object keyed by word, the format of [`CustomWords`]
+    JsonDict,
+    /// A JSON array of [`SentimentDictValue`] entries
+    JsonArray,
+    /// CSV with a `word,stem,valence_avg,arousal_avg,valence_std,arousal_std` header.
+    /// The `stem` column is optional; if it is missing, words are stemmed with [`stem::get`].
+    Csv,
+}
+
+impl fmt::Display for LexiconFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LexiconFormat::JsonDict => write!(f, "JSON dictionary"),
+            LexiconFormat::JsonArray => write!(f, "JSON array"),
+            LexiconFormat::Csv => write!(f, "CSV"),
+        }
+    }
+}
+
+impl LexiconFormat {
+    /// Detects the format of lexicon `contents` from its first non-whitespace character:
+    /// `{` is a JSON dictionary, `[` is a JSON array, and anything else is treated as CSV.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rnltk::sentiment::LexiconFormat;
+    /// 
+    /// assert_eq!(LexiconFormat::detect("  {\"abduction\": {}}"), LexiconFormat::JsonDict);
+    /// assert_eq!(LexiconFormat::detect("word,valence_avg"), LexiconFormat::Csv);
+    /// ```
+    pub fn detect(contents: &str) -> Self {
+        match contents.trim_start().chars().next() {
+            Some('{') => LexiconFormat::JsonDict,
+            Some('[') => LexiconFormat::JsonArray,
+            _ => LexiconFormat::Csv,
+        }
+    }
+}
+
+/// Loads a [`CustomWords`] lexicon from the file at `path`, detecting its [`LexiconFormat`].
+/// 
+/// # Errors
+/// 
+/// Returns [`RnltkError::LexiconIo`] if the file could not be read.
+/// 
+/// Returns [`RnltkError::LexiconLoad`], naming the detected format, if the contents could not be parsed.
+/// 
+/// Returns [`RnltkError::LexiconFormat`] if a JSON entry's `avg` or `std` doesn't hold exactly two values.
+pub fn load(path: impl AsRef<Path>) -> Result<CustomWords, RnltkError> {
+    let contents = fs::read_to_string(path).map_err(|error| RnltkError::LexiconIo(error.to_string()))?;
+    load_from_str(&contents)
+}
+
+/// Loads a [`CustomWords`] lexicon from `contents`, detecting its [`LexiconFormat`].
+/// 
+/// # Errors
+/// 
+/// Returns [`RnltkError::LexiconLoad`], naming the detected format, if the contents could not be parsed.
+/// 
+/// Returns [`RnltkError::LexiconFormat`] if a JSON entry's `avg` or `std` doesn't hold exactly two values.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::sentiment;
+/// 
+/// let json_array = r#"[{"word": "abduction", "stem": "abduct", "avg": [2.76, 5.53], "std": [2.06, 2.43]}]"#;
+/// let csv = "word,valence_avg,arousal_avg,valence_std,arousal_std\nabduction,2.76,5.53,2.06,2.43\n";
+/// 
+/// let from_json = sentiment::load_from_str(json_array).unwrap();
+/// let from_csv = sentiment::load_from_str(csv).unwrap();
+/// 
+/// assert_eq!(from_json, from_csv);
+/// assert_eq!(from_csv["abduction"].stem, "abduct");
+/// ```
+pub fn load_from_str(contents: &str) -> Result<CustomWords, RnltkError> {
+    let format = LexiconFormat::detect(contents);
+    stage_span!("lexicon_load", format = format.to_string().as_str(), bytes = contents.len());
+    let load_error = |reason: String| RnltkError::LexiconLoad { format: format.to_string(), reason };
+    match format {
+        // entries are checked after parsing so a bad entry is reported as RnltkError::LexiconFormat
+        LexiconFormat::JsonDict => {
+            let entries: HashMap<String, UncheckedSentimentDictValue> = serde_json::from_str(contents).map_err(|error| load_error(error.to_string()))?;
+            entries.into_iter().map(|(word, entry)| Ok((word, SentimentDictValue::try_from(entry)?))).collect()
+        },
+        LexiconFormat::JsonArray => {
+            let entries: Vec<UncheckedSentimentDictValue> = serde_json::from_str(contents).map_err(|error| load_error(error.to_string()))?;
+            entries.into_iter().map(|entry| SentimentDictValue::try_from(entry).map(|entry| (entry.word.clone(), entry))).collect()
+        },
+        LexiconFormat::Csv => load_csv(contents).map_err(load_error),
+    }
+}
+
+fn load_csv(contents: &str) -> Result<CustomWords, String> {
+    let mut reader = csv::Reader::from_reader(contents.as_bytes());
+    let headers = reader.headers().map_err(|error| error.to_string())?.clone();
+    let column = |name: &str| headers.iter().position(|header| header.trim() == name);
+    let required_column = |name: &str| column(name).ok_or_else(|| format!("missing '{}' column", name));
+
+    let word_column = required_column("word")?;
+    let stem_column = column("stem");
+    let value_columns = [
+        required_column("valence_avg")?,
+        required_column("arousal_avg")?,
+        required_column("valence_std")?,
+        required_column("arousal_std")?,
+    ];
+
+    let mut custom_words = CustomWords::new();
+    for (line, record) in reader.records().enumerate() {
+        let record = record.map_err(|error| error.to_string())?;
+        let field = |index: usize| record.get(index).map(str::trim).unwrap_or_default();
+        let word = field(word_column).to_string();
+        let stem = match stem_column.map(field) {
+            Some(stem) if !stem.is_empty() => stem.to_string(),
+            _ => stem::get(&word).unwrap_or_else(|_| word.clone()),
+        };
+        let mut values = [0.0; 4];
+        for (value, index) in values.iter_mut().zip(value_columns) {
+            *value = field(index).parse::<f64>().map_err(|_| format!("invalid number '{}' on record {}", field(index), line + 1))?;
+        }
+        let [valence_avg, arousal_avg, valence_std, arousal_std] = values;
+        let value = SentimentDictValue::from_scores_with_stem(&word, stem, Score::new(valence_avg, valence_std), Score::new(arousal_avg, arousal_std));
+        custom_words.insert(word, value);
+    }
+    Ok(custom_words)
+}
+
+/// Builder for [`SentimentModel`], created with [`SentimentModel::builder`].
+#[derive(Debug, Clone, Default)]
+pub struct SentimentModelBuilder {
+    custom_words: Option<CustomWords>,
+    custom_stems: Option<CustomStems>,
+    backend: Option<Arc<dyn LexiconBackend>>,
+    case_folding: CaseFolding,
+}
+
+impl SentimentModelBuilder {
+    /// Sets the `custom_words` sentiment lexicon. Defaults to an empty lexicon.
+    pub fn words(mut self, custom_words: CustomWords) -> Self {
+        self.custom_words = Some(custom_words);
+        self
+    }
+
+    /// Sets the `custom_stems` lexicon of stemmed words. Defaults to an empty lexicon.
+    pub fn stems(mut self, custom_stems: CustomStems) -> Self {
+        self.custom_stems = Some(custom_stems);
+        self
+    }
+
+    /// Sets a read-only [`LexiconBackend`] consulted after the `custom_words` and `custom_stems`
+    /// lexicons, for lexicons too large to hold as a [`CustomWords`] `HashMap`.
+    /// Terms added with [`SentimentModel::add_term_without_replacement`] or
+    /// [`SentimentModel::add_term_with_replacement`] shadow backend entries.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rnltk::sentiment::SentimentModel;
+    /// use rnltk::lexicon::CompactLexicon;
+    /// use rnltk::sample_data;
+    /// 
+    /// let lexicon = CompactLexicon::from(&sample_data::get_sample_custom_word_dict());
+    /// let sentiment = SentimentModel::builder()
+    ///     .backend(lexicon)
+    ///     .build();
+    /// 
+    /// assert_eq!(sentiment.get_valence_for_single_term("abduction"), 2.76);
+    /// ```
+    pub fn backend(mut self, backend: impl LexiconBackend + 'static) -> Self {
+        self.backend = Some(Arc::new(backend));
+        self
+    }
+
+    /// Sets how terms are case folded before they are looked up, so "Happy" finds the lexicon entry "happy".
+    /// The keys of the `custom_words` and `custom_stems` lexicons are folded the same way when the model is built,
+    /// while a backend is searched for the folded term and then for the term as given. Defaults to [`CaseFolding::Unicode`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rnltk::sentiment::SentimentModel;
+    /// use rnltk::token::CaseFolding;
+    /// use rnltk::sample_data;
+    ///
+    /// let sentiment = SentimentModel::new(sample_data::get_sample_custom_word_dict());
+    /// assert!(sentiment.does_term_exist("ABDUCTION"));
+    ///
+    /// let sentiment = SentimentModel::builder()
+    ///     .words(sample_data::get_sample_custom_word_dict())
+    ///     .case_folding(CaseFolding::Turkic)
+    ///     .build();
+    /// assert!(!sentiment.does_term_exist("ABDUCTION"));
+    /// ```
+    pub fn case_folding(mut self, case_folding: CaseFolding) -> Self {
+        self.case_folding = case_folding;
+        self
+    }
+
+    /// Builds the configured [`SentimentModel`].
+    pub fn build(self) -> SentimentModel {
+        let custom_stems = self.custom_stems.unwrap_or_else(|| {
+            let custom_stems_dict = SentimentDictValue::from_scores_with_stem("", "", Score::new(0.0, 0.0), Score::new(0.0, 0.0));
+            HashMap::from([("".to_string(), custom_stems_dict)])
+        });
+
+        SentimentModel {
+            custom_words: fold_keys(self.custom_words.unwrap_or_default(), self.case_folding),
+            custom_stems: fold_keys(custom_stems, self.case_folding),
+            backend: self.backend,
+            case_folding: self.case_folding,
+        }
+    }
+}
+
+// case folds the keys of `lexicon` so folded terms can be looked up. When several keys fold to the same term,
+// the key that was already folded wins, so "apple" keeps its own entry over "Apple"
+fn fold_keys(lexicon: HashMap<String, SentimentDictValue>, case_folding: CaseFolding) -> HashMap<String, SentimentDictValue> {
+    let mut folded: HashMap<String, (SentimentDictValue, bool)> = HashMap::with_capacity(lexicon.len());
+    for (key, value) in lexicon {
+        let folded_key = case_folding.fold(&key).into_owned();
+        let is_folded = folded_key == key;
+        match folded.entry(folded_key) {
+            Entry::Vacant(entry) => {
+                entry.insert((value, is_folded));
+            },
+            Entry::Occupied(mut entry) if is_folded => {
+                entry.insert((value, is_folded));
+            },
+            Entry::Occupied(_) => {},
+        }
+    }
+    folded.into_iter().map(|(key, (value, _))| (key, value)).collect()
 }
 
 pub struct SentimentModel {
     custom_words: CustomWords,
     custom_stems: CustomStems,
+    backend: Option<Arc<dyn LexiconBackend>>,
+    case_folding: CaseFolding,
 }
 
 impl SentimentModel {
@@ -85,13 +516,38 @@ impl SentimentModel {
     /// }
     /// ```
     pub fn new(custom_words: CustomWords) -> Self {
-        let custom_stems_dict = SentimentDictValue::new("".to_string(), "".to_string(), vec![0.0, 0.0], vec![0.0, 0.0]);
-        let custom_stems = HashMap::from([("".to_string(), custom_stems_dict)]);
-        
-        SentimentModel {
-            custom_words,
-            custom_stems,
-        }
+        SentimentModel::builder().words(custom_words).build()
+    }
+
+    /// Creates a [`SentimentModelBuilder`] for configuring a new SentimentModel.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rnltk::sentiment::{SentimentModel, CustomStems};
+    /// use rnltk::sample_data;
+    /// 
+    /// let custom_stem_dict = r#"
+    /// {
+    ///     "abduct": {
+    ///         "word": "abduction",
+    ///         "stem": "abduct",
+    ///         "avg": [2.76, 5.53],
+    ///         "std": [2.06, 2.43]
+    ///     }
+    /// }"#;
+    /// let custom_stems: CustomStems = serde_json::from_str(custom_stem_dict).unwrap();
+    /// 
+    /// let sentiment = SentimentModel::builder()
+    ///     .words(sample_data::get_sample_custom_word_dict())
+    ///     .stems(custom_stems)
+    ///     .build();
+    /// 
+    /// assert!(sentiment.does_term_exist("bees"));
+    /// assert!(sentiment.does_term_exist("abduct"));
+    /// ```
+    pub fn builder() -> SentimentModelBuilder {
+        SentimentModelBuilder::default()
     }
 
     /// Adds new `custom_stems` lexicon of stemmed words.
@@ -122,7 +578,7 @@ impl SentimentModel {
     /// }
     /// ```
     pub fn add_custom_stems(&mut self, custom_stems: CustomStems) {
-        self.custom_stems = custom_stems        
+        self.custom_stems = fold_keys(custom_stems, self.case_folding)
     }
 
     /// Checks if a `term` exists in the sentiment dictionaries.
@@ -141,7 +597,26 @@ impl SentimentModel {
     /// }
     /// ```
     pub fn does_term_exist(&self, term: &str) -> bool {
-        self.custom_words.contains_key(term) || self.custom_stems.contains_key(term)
+        let folded: &str = &self.case_folding.fold(term);
+        self.custom_words.contains_key(folded)
+            || self.custom_stems.contains_key(folded)
+            || self.backend.as_ref().is_some_and(|backend| backend.contains(folded) || (folded != term && backend.contains(term)))
+    }
+
+    // valence and arousal of `term`
+    fn lookup_scores(&self, term: &str) -> Option<(Score, Score)> {
+        let folded: &str = &self.case_folding.fold(term);
+        if let Some(value) = self.custom_words.get(folded).or_else(|| self.custom_stems.get(folded)) {
+            return Some((value.get_valence(), value.get_arousal()));
+        }
+        let backend = self.backend.as_ref()?;
+        backend.get_scores(folded).or_else(|| (folded != term).then(|| backend.get_scores(term)).flatten())
+    }
+
+    // backends are keyed however they were built, so `term` is also looked up as given when its folded form isn't found
+    fn backend_get(&self, folded: &str, term: &str) -> Option<Cow<'_, SentimentDictValue>> {
+        let backend = self.backend.as_ref()?;
+        backend.get(folded).or_else(|| (folded != term).then(|| backend.get(term)).flatten())
     }
 
     /// Gets the raw arousal values ([`RawSentiment`]) for a given `term` word token.
@@ -161,21 +636,10 @@ impl SentimentModel {
     /// assert_eq!(vec![arousal.average, arousal.standard_deviation], correct_arousal);
     /// ```
     pub fn get_raw_arousal(&self, term: &str) -> RawSentiment {
-        let mut average = 0.0;
-        let mut std_dev = 0.0; 
-
-        if !self.does_term_exist(term) {
-            return RawSentiment::new(average, std_dev);
-        } else if self.custom_words.contains_key(term) {
-            let sentiment_info = self.custom_words.get(term).unwrap();
-            average = sentiment_info.avg[1];
-            std_dev = sentiment_info.std[1];
-        } else if self.custom_stems.contains_key(term) {
-            let sentiment_info = self.custom_stems.get(term).unwrap();
-            average = sentiment_info.avg[1];
-            std_dev = sentiment_info.std[1];
+        match self.lookup_scores(term) {
+            Some((_, arousal)) => RawSentiment::new(arousal.mean, arousal.std),
+            None => RawSentiment::new(0.0, 0.0),
         }
-        RawSentiment::new(average, std_dev)
     }
 
     /// Gets the raw valence values ([`RawSentiment`]) for a given `term` word token.
@@ -195,21 +659,10 @@ impl SentimentModel {
     /// assert_eq!(vec![valence.average, valence.standard_deviation], correct_valence);
     /// ```
     pub fn get_raw_valence(&self, term: &str) -> RawSentiment {
-        let mut average = 0.0;
-        let mut std_dev = 0.0; 
-
-        if !self.does_term_exist(term) {
-            return RawSentiment::new(average, std_dev);
-        } else if self.custom_words.contains_key(term) {
-            let sentiment_info = self.custom_words.get(term).unwrap();
-            average = sentiment_info.avg[0];
-            std_dev = sentiment_info.std[0];
-        } else if self.custom_stems.contains_key(term) {
-            let sentiment_info = self.custom_stems.get(term).unwrap();
-            average = sentiment_info.avg[0];
-            std_dev = sentiment_info.std[0];
+        match self.lookup_scores(term) {
+            Some((valence, _)) => RawSentiment::new(valence.mean, valence.std),
+            None => RawSentiment::new(0.0, 0.0),
         }
-        RawSentiment::new(average, std_dev)
     }
 
     /// Gets the arousal value for a given `term` word token.
@@ -282,13 +735,18 @@ impl SentimentModel {
     /// 
     /// assert_eq!(arousal, correct_arousal);
     /// ```
-    pub fn get_arousal_for_term_vector(&self, terms: &Vec<&str>) -> f64 {
+    pub fn get_arousal_for_term_vector<I>(&self, terms: I) -> f64
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
         let c = 2.0 * PI;
         let mut prob: Vec<f64> = vec![];
         let mut prob_sum = 0.0;
         let mut arousal_means: Vec<f64> = vec![];
 
         for term in terms {
+            let term = term.as_ref();
             if self.does_term_exist(term) {
                 let raw_arousal = self.get_raw_arousal(term);
                 
@@ -337,13 +795,18 @@ impl SentimentModel {
     /// 
     /// assert_eq!(valence, correct_valence);
     /// ```
-    pub fn get_valence_for_term_vector(&self, terms: &Vec<&str>) -> f64 {
+    pub fn get_valence_for_term_vector<I>(&self, terms: I) -> f64
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
         let c = 2.0 * PI;
         let mut prob: Vec<f64> = vec![];
         let mut prob_sum = 0.0;
         let mut valence_means: Vec<f64> = vec![];
 
         for term in terms {
+            let term = term.as_ref();
             if self.does_term_exist(term) {
                 let raw_valence = self.get_raw_valence(term);
                 
@@ -362,12 +825,72 @@ impl SentimentModel {
         valence
     }
 
+    /// Gets the valence and arousal of each token in `terms`, in order, producing
+    /// a sequence suitable for sequence models and visualizations.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rnltk::sentiment::{SentimentModel, CustomWords, TokenSentiment};
+    /// use rnltk::sample_data;
+    /// 
+    /// let custom_words_sentiment_hashmap: CustomWords = sample_data::get_sample_custom_word_dict();
+    /// 
+    /// let sentiment = SentimentModel::new(custom_words_sentiment_hashmap);
+    /// let tagged_tokens = sentiment.tag_tokens(&["the", "bees"]);
+    /// 
+    /// assert_eq!(tagged_tokens, vec![
+    ///     TokenSentiment { token: "the".to_string(), valence: None, arousal: None },
+    ///     TokenSentiment { token: "bees".to_string(), valence: Some(3.2), arousal: Some(6.51) },
+    /// ]);
+    /// ```
+    pub fn tag_tokens<I>(&self, terms: I) -> Vec<TokenSentiment>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        terms.into_iter().map(|term| {
+            let term = term.as_ref();
+            let (valence, arousal) = if self.does_term_exist(term) {
+                (Some(self.get_valence_for_single_term(term)), Some(self.get_arousal_for_single_term(term)))
+            } else {
+                (None, None)
+            };
+            TokenSentiment {
+                token: term.to_string(),
+                valence,
+                arousal
+            }
+        }).collect()
+    }
+
+    /// Tokenizes `sentence` with [`token::tokenize_sentence`] and gets the valence and
+    /// arousal of each resulting token, in order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rnltk::sentiment::{SentimentModel, CustomWords};
+    /// use rnltk::sample_data;
+    /// 
+    /// let custom_words_sentiment_hashmap: CustomWords = sample_data::get_sample_custom_word_dict();
+    /// 
+    /// let sentiment = SentimentModel::new(custom_words_sentiment_hashmap);
+    /// let tagged_tokens = sentiment.tag_sentence("I betrayed the bees!");
+    /// let valences: Vec<Option<f64>> = tagged_tokens.iter().map(|tagged_token| tagged_token.valence).collect();
+    /// 
+    /// assert_eq!(valences, vec![None, Some(2.57), None, Some(3.2)]);
+    /// ```
+    pub fn tag_sentence(&self, sentence: &str) -> Vec<TokenSentiment> {
+        self.tag_tokens(token::tokenize_sentence(sentence))
+    }
+
     /// Gets the valence, arousal sentiment for a `term` word token.
     ///
     /// # Examples
     ///
     /// ```
-    /// use std::collections::HashMap;
+    /// use std::collections::BTreeMap;
     /// use rnltk::sentiment::{SentimentModel, CustomWords};
     /// use rnltk::sample_data;
     /// 
@@ -375,12 +898,12 @@ impl SentimentModel {
     /// 
     /// let sentiment = SentimentModel::new(custom_words_sentiment_hashmap);
     /// let sentiment_info = sentiment.get_sentiment_for_term("abduction");
-    /// let sentiment_map = HashMap::from([("valence", 2.76), ("arousal", 5.53)]);
+    /// let sentiment_map = BTreeMap::from([("valence", 2.76), ("arousal", 5.53)]);
     /// 
     /// assert_eq!(sentiment_info, sentiment_map);
     /// ```
-    pub fn get_sentiment_for_term(&self, term: &str) -> HashMap<&str, f64> {
-        let mut sentiment: HashMap<&str, f64>  = HashMap::new();
+    pub fn get_sentiment_for_term(&self, term: &str) -> BTreeMap<&str, f64> {
+        let mut sentiment: BTreeMap<&str, f64>  = BTreeMap::new();
         sentiment.insert("valence", self.get_valence_for_single_term(term));
         sentiment.insert("arousal", self.get_arousal_for_single_term(term));
 
@@ -392,7 +915,7 @@ impl SentimentModel {
     /// # Examples
     ///
     /// ```
-    /// use std::collections::HashMap;
+    /// use std::collections::BTreeMap;
     /// use rnltk::sentiment::{SentimentModel, CustomWords};
     /// 
     /// let custom_word_dict = r#"
@@ -414,14 +937,19 @@ impl SentimentModel {
     /// 
     /// let sentiment = SentimentModel::new(custom_words_sentiment_hashmap);
     /// let sentiment_info = sentiment.get_sentiment_for_term_vector(&vec!["I", "betrayed", "the", "bees"]);
-    /// let sentiment_map = HashMap::from([("valence", 2.865615384615385), ("arousal", 6.881952380952381)]);
+    /// let sentiment_map = BTreeMap::from([("valence", 2.865615384615385), ("arousal", 6.881952380952381)]);
     /// 
     /// assert_eq!(sentiment_info, sentiment_map);
     /// ```
-    pub fn get_sentiment_for_term_vector(&self, terms: &Vec<&str>) -> HashMap<&str, f64> {
-        let mut sentiment: HashMap<&str, f64>  = HashMap::new();
-        sentiment.insert("valence", self.get_valence_for_term_vector(terms));
-        sentiment.insert("arousal", self.get_arousal_for_term_vector(terms));
+    pub fn get_sentiment_for_term_vector<I>(&self, terms: I) -> BTreeMap<&str, f64>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let terms: Vec<I::Item> = terms.into_iter().collect();
+        let mut sentiment: BTreeMap<&str, f64>  = BTreeMap::new();
+        sentiment.insert("valence", self.get_valence_for_term_vector(&terms));
+        sentiment.insert("arousal", self.get_arousal_for_term_vector(&terms));
 
         sentiment
     }
@@ -453,32 +981,7 @@ impl SentimentModel {
             return Cow::from("average");
         }
 
-        // Angular cutoffs for different emotional states (same on top and bottom)
-        let angular_cutoffs = vec![0.0, 18.43, 45.0, 71.57, 90.0, 108.43, 135.0, 161.57, 180.0];
-
-        // Terms to return for bottom, top half of circumplex
-        let lower_term = vec![
-            "contented", "serene", "relaxed", "calm",
-            "bored", "lethargic", "depressed", "sad"
-        ];
-        let upper_term = vec![
-            "happy", "elated", "excited", "alert",
-            "tense", "nervous", "stressed", "upset"
-        ];
-
-        // Normalize valence and arousal, using polar coordinates to get angle
-        // clockwise along bottom, counterclockwise along top
-        let normalized_valence = ((valence - 1.0) - 4.0) / 4.0;
-        let normalized_arousal = ((arousal - 1.0) - 4.0) / 4.0;
-        let mut radius = (normalized_valence.powi(2).abs() + normalized_arousal.powi(2).abs()).sqrt();
-        let direction = (normalized_valence / radius).acos().to_degrees();
-
-        //  Normalize radius for "strength" of emotion
-        if direction <= 45.0 || direction >= 135.0 {
-            radius /= (normalized_arousal.powi(2).abs() + 1.0).sqrt();
-        } else {
-            radius /= (normalized_valence.powi(2).abs() + 1.0).sqrt();
-        }
+        let (direction, radius, normalized_arousal) = get_circumplex_coordinates(*valence, *arousal);
 
         let mut modify = "";
         
@@ -491,9 +994,9 @@ impl SentimentModel {
         }
 
         // Use normalized arousal to determine if we're on bottom of top of circumplex
-        let mut term = lower_term;
+        let mut term = LOWER_TERMS;
         if normalized_arousal > 0.0 {
-            term = upper_term;
+            term = UPPER_TERMS;
         }
 
         let description;
@@ -501,7 +1004,7 @@ impl SentimentModel {
         // Walk along angular boundaries until we determine which "slice"
         // our valence and arousal point lies in, return corresponding term
         for index in 0..term.len() {
-            if direction >= angular_cutoffs[index] && direction <= angular_cutoffs[index + 1] {
+            if direction >= ANGULAR_CUTOFFS[index] && direction <= ANGULAR_CUTOFFS[index + 1] {
                 description = format!("{}{}", modify, term[index]);
                 return Cow::from(description);
             }
@@ -511,12 +1014,77 @@ impl SentimentModel {
         Cow::from("unknown")
     }
 
+    /// Gets the [`CircumplexPoint`] of `valence` and `arousal` scores, or `None` if they aren't between 1 and 9 (inclusive).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rnltk::sentiment::{SentimentModel, CustomWords};
+    /// use rnltk::sample_data;
+    ///
+    /// let custom_words_sentiment_hashmap: CustomWords = sample_data::get_sample_custom_word_dict();
+    ///
+    /// let sentiment = SentimentModel::new(custom_words_sentiment_hashmap);
+    /// let point = sentiment.get_circumplex_point(&2.76, &5.53).unwrap();
+    ///
+    /// assert!(point.angle > 161.57 && point.angle < 180.0);
+    /// assert!(point.radius > 0.5 && point.radius <= 0.75);
+    /// assert_eq!(sentiment.get_circumplex_point(&0.0, &5.53), None);
+    /// ```
+    pub fn get_circumplex_point(&self, valence: &f64, arousal: &f64) -> Option<CircumplexPoint> {
+        CircumplexPoint::from_scores(*valence, *arousal)
+    }
+
+    /// Gets the `n` Russel-like descriptions nearest to `valence` and `arousal` scores, nearest first, with the
+    /// angular distance in degrees from the point to the center of each description's slice of the circumplex.
+    ///
+    /// Unlike [`get_sentiment_description`](Self::get_sentiment_description), descriptions aren't modified by
+    /// the strength of the emotion. Returns no descriptions if `valence` or `arousal` aren't between 1 and 9
+    /// (inclusive), or if both are 5, since the center of the circumplex has no direction.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rnltk::sentiment::{SentimentModel, CustomWords};
+    /// use rnltk::sample_data;
+    ///
+    /// let custom_words_sentiment_hashmap: CustomWords = sample_data::get_sample_custom_word_dict();
+    ///
+    /// let sentiment = SentimentModel::new(custom_words_sentiment_hashmap);
+    /// let sentiment_descriptions = sentiment.get_sentiment_descriptions(&3.2, &6.51, 2);
+    /// let descriptions: Vec<&str> = sentiment_descriptions.iter().map(|(description, _)| *description).collect();
+    ///
+    /// assert_eq!(descriptions, vec!["stressed", "nervous"]);
+    /// assert!(sentiment_descriptions[0].1 < sentiment_descriptions[1].1);
+    /// ```
+    pub fn get_sentiment_descriptions(&self, valence: &f64, arousal: &f64, n: usize) -> Vec<(&'static str, f64)> {
+        let angle = match CircumplexPoint::from_scores(*valence, *arousal) {
+            Some(point) if point.radius > 0.0 => point.angle,
+            _ => return vec![],
+        };
+
+        let mut descriptions: Vec<(&'static str, f64)> = (0..UPPER_TERMS.len())
+            .flat_map(|index| {
+                let center = (ANGULAR_CUTOFFS[index] + ANGULAR_CUTOFFS[index + 1]) / 2.0;
+                [(UPPER_TERMS[index], center), (LOWER_TERMS[index], 360.0 - center)]
+            })
+            .map(|(term, center)| {
+                let difference = (angle - center).abs();
+                (term, difference.min(360.0 - difference))
+            })
+            .collect();
+        // stable sort keeps ties in circumplex order
+        descriptions.sort_by(|first, second| first.1.total_cmp(&second.1));
+        descriptions.truncate(n);
+        descriptions
+    }
+
     /// Gets the Russel-like description given a `term` word token.
     ///
     /// # Examples
     ///
     /// ```
-    /// use std::collections::HashMap;
+    /// use std::collections::BTreeMap;
     /// use rnltk::sentiment::{SentimentModel, CustomWords};
     /// use rnltk::sample_data;
     /// 
@@ -566,7 +1134,11 @@ impl SentimentModel {
     /// 
     /// assert_eq!(sentiment_description, description);
     /// ```
-    pub fn get_term_vector_description(&self, terms: &Vec<&str>) -> Cow<'static, str> {
+    pub fn get_term_vector_description<I>(&self, terms: I) -> Cow<'static, str>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
         let sentiment = self.get_sentiment_for_term_vector(terms);
         if sentiment.get("arousal").unwrap() == &0.0 {
             return Cow::from("unknown");
@@ -585,7 +1157,7 @@ impl SentimentModel {
     /// # Examples
     ///
     /// ```
-    /// use std::collections::HashMap;
+    /// use std::collections::BTreeMap;
     /// use rnltk::sentiment::{SentimentModel, CustomWords};
     /// use rnltk::error::RnltkError;
     /// use rnltk::sample_data;
@@ -597,7 +1169,7 @@ impl SentimentModel {
     /// match sentiment_return_value {
     ///     Ok(_) => {
     ///         let sentiment_info = sentiment.get_sentiment_for_term("squanch");
-    ///         let sentiment_map = HashMap::from([("valence", 2.0), ("arousal", 8.5)]);
+    ///         let sentiment_map = BTreeMap::from([("valence", 2.0), ("arousal", 8.5)]);
     /// 
     ///         assert_eq!(sentiment_info, sentiment_map);
     ///     },
@@ -609,25 +1181,10 @@ impl SentimentModel {
             return Err(RnltkError::SentimentTermExists);
         } else {
             let stemmed_word = stem::get(term)?;
-            let word = term.to_string();
-            let avg = vec![*valence, *arousal];
-            let std = vec![1.0, 1.0];
-            let word_dict_value = SentimentDictValue {
-                word: word.clone(),
-                stem: stemmed_word.clone(),
-                avg,
-                std
-            };
-            let avg = vec![*valence, *arousal];
-            let std = vec![1.0, 1.0];
-            let stem_dict_value = SentimentDictValue {
-                word,
-                stem: stemmed_word,
-                avg,
-                std
-            };
-            self.custom_words.insert(term.to_string(), word_dict_value);
-            self.custom_stems.insert(term.to_string(), stem_dict_value);
+            let dict_value = SentimentDictValue::from_scores_with_stem(term, stemmed_word, Score::new(*valence, 1.0), Score::new(*arousal, 1.0));
+            let key = self.case_folding.fold(term).into_owned();
+            self.custom_words.insert(key.clone(), dict_value.clone());
+            self.custom_stems.insert(key, dict_value);
         }
         Ok(())
     }
@@ -644,7 +1201,7 @@ impl SentimentModel {
     /// # Examples
     ///
     /// ```
-    /// use std::collections::HashMap;
+    /// use std::collections::BTreeMap;
     /// use rnltk::sentiment::{SentimentModel, CustomWords};
     /// use rnltk::error::RnltkError;
     /// use rnltk::sample_data;
@@ -656,7 +1213,7 @@ impl SentimentModel {
     /// match sentiment_return_value {
     ///     Ok(_) => {
     ///         let sentiment_info = sentiment.get_sentiment_for_term("abduction");
-    ///         let sentiment_map = HashMap::from([("valence", 8.0), ("arousal", 8.5)]);
+    ///         let sentiment_map = BTreeMap::from([("valence", 8.0), ("arousal", 8.5)]);
     /// 
     ///         assert_eq!(sentiment_info, sentiment_map);
     ///     },
@@ -664,35 +1221,26 @@ impl SentimentModel {
     /// }
     /// ```
     pub fn add_term_with_replacement(&mut self, term: &'static str, valence: &f64, arousal: &f64) -> Result<(), RnltkError>{
-        if self.custom_words.contains_key(term) {
-            let dict_value = self.custom_words.get_mut(term).unwrap();
+        let folded: &str = &self.case_folding.fold(term);
+        if self.custom_words.contains_key(folded) {
+            let dict_value = self.custom_words.get_mut(folded).unwrap();
             dict_value.avg[0] = *valence;
             dict_value.avg[1] = *arousal;
-        } else if self.custom_stems.contains_key(term) {
-            let dict_value = self.custom_stems.get_mut(term).unwrap();
+        } else if self.custom_stems.contains_key(folded) {
+            let dict_value = self.custom_stems.get_mut(folded).unwrap();
             dict_value.avg[0] = *valence;
             dict_value.avg[1] = *arousal;
+        } else if let Some(backend_value) = self.backend_get(folded, term) {
+            let mut dict_value = backend_value.into_owned();
+            dict_value.avg[0] = *valence;
+            dict_value.avg[1] = *arousal;
+            self.custom_words.insert(folded.to_string(), dict_value);
         } else {
             let stemmed_word = stem::get(term)?;
-            let word = term.to_string();
-            let avg = vec![*valence, *arousal];
-            let std = vec![1.0, 1.0];
-            let word_dict_value = SentimentDictValue {
-                word: word.clone(),
-                stem: stemmed_word.clone(),
-                avg,
-                std
-            };
-            let avg = vec![*valence, *arousal];
-            let std = vec![1.0, 1.0];
-            let stem_dict_value = SentimentDictValue {
-                word,
-                stem: stemmed_word,
-                avg,
-                std
-            };
-            self.custom_words.insert(term.to_string(), word_dict_value);
-            self.custom_stems.insert(term.to_string(), stem_dict_value);
+            let dict_value = SentimentDictValue::from_scores_with_stem(term, stemmed_word, Score::new(*valence, 1.0), Score::new(*arousal, 1.0));
+            let key = self.case_folding.fold(term).into_owned();
+            self.custom_words.insert(key.clone(), dict_value.clone());
+            self.custom_stems.insert(key, dict_value);
         }
         Ok(())
     }
@@ -781,7 +1329,7 @@ mod tests {
         let setup = Setup::new();
         let sentiment = SentimentModel::new(setup.custom_words);
         let sentiment_info = sentiment.get_sentiment_for_term("abduction");
-        let sentiment_map = HashMap::from([("valence", 2.76), ("arousal", 5.53)]);
+        let sentiment_map = BTreeMap::from([("valence", 2.76), ("arousal", 5.53)]);
 
         assert_eq!(sentiment_info, sentiment_map);
     }
@@ -791,7 +1339,7 @@ mod tests {
         let setup = Setup::new();
         let sentiment = SentimentModel::new(setup.custom_words);
         let sentiment_info = sentiment.get_sentiment_for_term_vector(&vec!["I", "betrayed", "the", "bees"]);
-        let sentiment_map = HashMap::from([("valence", 2.865615384615385), ("arousal", 6.881952380952381)]);
+        let sentiment_map = BTreeMap::from([("valence", 2.865615384615385), ("arousal", 6.881952380952381)]);
 
         assert_eq!(sentiment_info, sentiment_map);
     }
@@ -806,6 +1354,43 @@ mod tests {
         assert_eq!(sentiment_description, description);
     }
 
+    #[test]
+    fn sentiment_descriptions() {
+        let setup = Setup::new();
+        let sentiment = SentimentModel::new(setup.custom_words);
+        // on the border between the top and bottom halves, "upset" and "sad" are equally near
+        let sentiment_descriptions = sentiment.get_sentiment_descriptions(&1.0, &5.0, 3);
+        assert_eq!(sentiment_descriptions.iter().map(|(description, _)| *description).collect::<Vec<&str>>(), vec!["upset", "sad", "stressed"]);
+        assert!((sentiment_descriptions[0].1 - 9.215).abs() < 1e-9);
+        assert_eq!(sentiment_descriptions[0].1, sentiment_descriptions[1].1);
+
+        assert_eq!(sentiment.get_sentiment_descriptions(&2.76, &5.53, 1)[0].0, sentiment.get_sentiment_description(&2.76, &5.53));
+        assert_eq!(sentiment.get_sentiment_descriptions(&5.0, &5.0, 3), vec![]);
+        assert_eq!(sentiment.get_sentiment_descriptions(&0.0, &5.0, 3), vec![]);
+        assert_eq!(sentiment.get_sentiment_descriptions(&9.0, &1.0, 16).len(), 16);
+    }
+
+    #[test]
+    fn circumplex_point() {
+        let lower = CircumplexPoint::from_scores(9.0, 1.0).unwrap();
+        assert_eq!(lower.angle, 315.0);
+        assert_eq!(lower.radius, 1.0);
+        // on the valence axis the angle is 0 or 180, never 360
+        assert_eq!(CircumplexPoint::from_scores(7.0, 5.0).unwrap().angle, 0.0);
+        assert_eq!(CircumplexPoint::from_scores(3.0, 5.0).unwrap().angle, 180.0);
+        assert_eq!(CircumplexPoint::from_scores(5.0, 5.0), Some(CircumplexPoint { angle: 0.0, radius: 0.0 }));
+        assert_eq!(CircumplexPoint::from_scores(5.0, 9.5), None);
+    }
+
+    #[test]
+    fn term_sentiment_is_ordered() {
+        let setup = Setup::new();
+        let sentiment = SentimentModel::new(setup.custom_words);
+        let keys: Vec<&str> = sentiment.get_sentiment_for_term("bees").into_keys().collect();
+        assert_eq!(keys, vec!["arousal", "valence"]);
+        assert_eq!(serde_json::to_string(&sentiment.get_sentiment_for_term_vector(["bees"])).unwrap(), r#"{"arousal":6.51,"valence":3.2}"#);
+    }
+
     #[test]
     fn term_description() {
         let setup = Setup::new();
@@ -832,7 +1417,7 @@ mod tests {
         let mut sentiment = SentimentModel::new(setup.custom_words);
         sentiment.add_term_with_replacement("abduction", &8.0, &8.5).unwrap();
         let sentiment_info = sentiment.get_sentiment_for_term("abduction");
-        let sentiment_map = HashMap::from([("valence", 8.0), ("arousal", 8.5)]);
+        let sentiment_map = BTreeMap::from([("valence", 8.0), ("arousal", 8.5)]);
 
         assert_eq!(sentiment_info, sentiment_map);
     }
@@ -859,9 +1444,119 @@ mod tests {
         let mut sentiment = SentimentModel::new(setup.custom_words);
         sentiment.add_term_without_replacement("squanch", &2.0, &8.5).unwrap();
         let sentiment_info = sentiment.get_sentiment_for_term("squanch");
-        let sentiment_map = HashMap::from([("valence", 2.0), ("arousal", 8.5)]);
+        let sentiment_map = BTreeMap::from([("valence", 2.0), ("arousal", 8.5)]);
 
         assert_eq!(sentiment_info, sentiment_map);
     }
 
+    #[test]
+    fn load_test_lexicon() {
+        let setup = Setup::new();
+        let custom_words = load("test_data/test.json").unwrap();
+        assert_eq!(custom_words, setup.custom_words);
+    }
+
+    #[test]
+    fn load_error_names_format() {
+        let error = load_from_str("word,valence_avg\nabduction,2.76\n").unwrap_err();
+        assert_eq!(error, RnltkError::LexiconLoad { format: "CSV".to_string(), reason: "missing 'arousal_avg' column".to_string() });
+
+        let error = load_from_str("[{\"word\": \"abduction\"}]").unwrap_err();
+        assert!(matches!(error, RnltkError::LexiconLoad { format, .. } if format == "JSON array"));
+    }
+
+    #[test]
+    fn scores_round_trip_through_vecs() {
+        let value = SentimentDictValue::from_scores_with_stem("bees", "bee", Score::new(1.5, 0.5), Score::new(6.5, 2.));
+        assert_eq!((value.avg.clone(), value.std.clone()), (vec![1.5, 6.5], vec![0.5, 2.]));
+        assert_eq!(value.get_valence(), Score::new(1.5, 0.5));
+
+        let partial = SentimentDictValue::new("bees".to_string(), "bee".to_string(), vec![1.5], vec![]);
+        assert_eq!(partial.get_arousal(), Score::new(0., 0.));
+    }
+
+    #[test]
+    fn short_avg_is_rejected() {
+        let contents = r#"{"abduction": {"word": "abduction", "stem": "abduct", "avg": [2.76], "std": [2.06, 2.43]}}"#;
+        let error = RnltkError::LexiconFormat {
+            word: "abduction".to_string(),
+            reason: "'avg' must hold a valence and an arousal value, found 1 value(s)".to_string(),
+        };
+        assert_eq!(load_from_str(contents).unwrap_err(), error);
+
+        let message = serde_json::from_str::<CustomWords>(contents).unwrap_err().to_string();
+        assert!(message.starts_with(&error.to_string()), "{}", message);
+    }
+
+    #[test]
+    fn long_std_is_rejected_in_arrays() {
+        let contents = r#"[{"word": "abduction", "stem": "abduct", "avg": [2.76, 5.53], "std": [2.06, 2.43, 1.0]}]"#;
+        assert!(matches!(load_from_str(contents).unwrap_err(), RnltkError::LexiconFormat { word, .. } if word == "abduction"));
+    }
+
+    #[test]
+    fn added_terms_shadow_backend() {
+        let setup = Setup::new();
+        let lexicon = crate::lexicon::CompactLexicon::from(&setup.custom_words);
+        let mut sentiment = SentimentModel::builder().backend(lexicon).build();
+        assert!(sentiment.does_term_exist("betrayed"));
+        assert_eq!(sentiment.add_term_without_replacement("betrayed", &1.0, &1.0), Err(RnltkError::SentimentTermExists));
+        sentiment.add_term_with_replacement("betrayed", &1.0, &1.5).unwrap();
+        assert_eq!(sentiment.get_arousal_for_single_term("betrayed"), 1.5);
+    }
+
+    #[test]
+    fn builder_defaults_to_empty_lexicons() {
+        let sentiment = SentimentModel::builder().build();
+        assert!(!sentiment.does_term_exist("abduction"));
+        assert_eq!(sentiment.get_term_description("abduction"), "unknown");
+    }
+
+    #[test]
+    fn term_vectors_accept_owned_and_borrowed_tokens() {
+        let setup = Setup::new();
+        let sentiment = SentimentModel::new(setup.custom_words);
+        let owned: Vec<String> = vec!["i".to_string(), "betrayed".to_string(), "the".to_string(), "bees".to_string()];
+        let borrowed = ["i", "betrayed", "the", "bees"];
+
+        assert_eq!(sentiment.get_valence_for_term_vector(&owned), sentiment.get_valence_for_term_vector(borrowed));
+        assert_eq!(sentiment.get_sentiment_for_term_vector(owned.iter().map(String::as_str)), sentiment.get_sentiment_for_term_vector(owned));
+    }
+
+    #[test]
+    fn tag_tokens() {
+        let setup = Setup::new();
+        let sentiment = SentimentModel::new(setup.custom_words);
+        let tagged_tokens = sentiment.tag_sentence("I betrayed the bees");
+        let arousals: Vec<Option<f64>> = tagged_tokens.iter().map(|tagged_token| tagged_token.arousal).collect();
+        let tokens: Vec<&str> = tagged_tokens.iter().map(|tagged_token| tagged_token.token.as_str()).collect();
+
+        assert_eq!(tokens, vec!["i", "betrayed", "the", "bees"]);
+        assert_eq!(arousals, vec![None, Some(7.24), None, Some(6.51)]);
+    }
+
+    #[test]
+    fn mixed_case_lexicon_entries() {
+        let entry = |word: &str, valence: f64| SentimentDictValue::from_scores_with_stem(word, word, Score::new(valence, 1.), Score::new(5., 1.));
+        let custom_words: CustomWords = HashMap::from([
+            ("NASA".to_string(), entry("NASA", 7.)),
+            ("Straße".to_string(), entry("Straße", 5.5)),
+            ("Apple".to_string(), entry("Apple", 6.)),
+            ("apple".to_string(), entry("apple", 6.5)),
+        ]);
+        let sentiment = SentimentModel::new(custom_words.clone());
+        assert!(sentiment.does_term_exist("NASA") && sentiment.does_term_exist("nasa"));
+        assert_eq!(sentiment.get_valence_for_single_term("Straße"), 5.5);
+        assert_eq!(sentiment.get_valence_for_single_term("STRASSE"), 5.5);
+        assert_eq!(sentiment.get_valence_for_single_term("Apple"), 6.5);
+
+        let backend = SentimentModel::builder().backend(custom_words).build();
+        assert_eq!(backend.get_valence_for_single_term("NASA"), 7.);
+        assert_eq!(backend.get_valence_for_single_term("Straße"), 5.5);
+
+        let mut sentiment = SentimentModel::new(HashMap::new());
+        sentiment.add_term_with_replacement("Hubble", &8., &6.).unwrap();
+        assert!(sentiment.does_term_exist("HUBBLE"));
+        assert_eq!(sentiment.add_term_without_replacement("hubble", &1., &1.), Err(RnltkError::SentimentTermExists));
+    }
 }
@@ -0,0 +1,236 @@
+//! Module containing functions used to compare short texts without building a full corpus pipeline.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use crate::token;
+
+/// Word embedding lookup used by [`SimilarityMethod::EmbeddingCosine`],
+/// mapping a lowercased token to its dense vector.
+pub type WordEmbeddings = HashMap<String, Vec<f64>>;
+
+/// Strategy used by [`sentence_similarity`] to compare two texts.
+#[derive(Debug, Clone, Copy)]
+pub enum SimilarityMethod<'a> {
+    /// Jaccard overlap of the two token sets, \\(\frac{|A \cap B|}{|A \cup B|}\\).
+    TokenOverlap,
+    /// Cosine similarity of TF-IDF vectors fit on the pair of texts.
+    ///
+    /// Since a pair only contains two documents, the smoothed inverse document frequency
+    /// \\(idf_i = ln(\frac{1 + n}{1 + n_i}) + 1\\) is used so shared terms still contribute.
+    TfidfCosine,
+    /// Cosine similarity of the averaged word embeddings of each text.
+    /// Tokens without an embedding are ignored.
+    EmbeddingCosine(&'a WordEmbeddings),
+}
+
+/// Gets the similarity of two short texts, `first` and `second`, using the given `method`.
+///
+/// Both texts are tokenized with [`token::tokenize_sentence`]. The returned score falls
+/// within [0...1] for [`SimilarityMethod::TokenOverlap`] and [`SimilarityMethod::TfidfCosine`];
+/// [`SimilarityMethod::EmbeddingCosine`] can be negative if the embeddings are.
+/// A text with no usable tokens has a similarity of 0 with everything.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::similarity::{self, SimilarityMethod};
+///
+/// let overlap = similarity::sentence_similarity("How do I reset my password?", "How can I reset my password", SimilarityMethod::TokenOverlap);
+/// assert_eq!(overlap, 5. / 7.);
+///
+/// let tfidf = similarity::sentence_similarity("reset my password", "reset my password", SimilarityMethod::TfidfCosine);
+/// assert!((tfidf - 1.).abs() < 1e-10);
+/// ```
+pub fn sentence_similarity(first: &str, second: &str, method: SimilarityMethod) -> f64 {
+    let first_tokens = token::tokenize_sentence(first);
+    let second_tokens = token::tokenize_sentence(second);
+    if first_tokens.is_empty() || second_tokens.is_empty() {
+        return 0.;
+    }
+
+    match method {
+        SimilarityMethod::TokenOverlap => token_overlap(&first_tokens, &second_tokens),
+        SimilarityMethod::TfidfCosine => tfidf_cosine(first, second),
+        SimilarityMethod::EmbeddingCosine(embeddings) => {
+            match (average_embedding(&first_tokens, embeddings), average_embedding(&second_tokens, embeddings)) {
+                (Some(first_embedding), Some(second_embedding)) => cosine(&first_embedding, &second_embedding),
+                _ => 0.,
+            }
+        }
+    }
+}
+
+fn token_overlap(first_tokens: &[String], second_tokens: &[String]) -> f64 {
+    let first_set: BTreeSet<&String> = first_tokens.iter().collect();
+    let second_set: BTreeSet<&String> = second_tokens.iter().collect();
+    let intersection = first_set.intersection(&second_set).count() as f64;
+    let union = first_set.union(&second_set).count() as f64;
+
+    intersection / union
+}
+
+/// Finds clusters of near-equivalent sentences across a corpus of `sentences`.
+///
+/// Every sentence is weighted with the smoothed TF-IDF described in [`SimilarityMethod::TfidfCosine`],
+/// fit on the whole corpus, and sentence pairs with a cosine similarity of at least `threshold`
+/// are linked. Linked sentences are grouped transitively, so each returned cluster holds the indices
+/// of two or more sentences in ascending order. Clusters are ordered by their first index.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::similarity;
+///
+/// let sentences = vec![
+///     "How do I reset my password?",
+///     "What are your opening hours?",
+///     "How can I reset my password?",
+///     "When are you open?",
+/// ];
+/// let paraphrases = similarity::find_paraphrases(&sentences, 0.6);
+///
+/// assert_eq!(paraphrases, vec![vec![0, 2]]);
+/// ```
+pub fn find_paraphrases(sentences: &[&str], threshold: f64) -> Vec<Vec<usize>> {
+    let weighted = smoothed_tfidf_vectors(sentences);
+    let mut parents: Vec<usize> = (0..sentences.len()).collect();
+
+    for index in 0..weighted.len() {
+        for inner_index in (index + 1)..weighted.len() {
+            if cosine(&weighted[index], &weighted[inner_index]) >= threshold {
+                let root = find_root(&mut parents, index);
+                let inner_root = find_root(&mut parents, inner_index);
+                parents[root.max(inner_root)] = root.min(inner_root);
+            }
+        }
+    }
+
+    let mut clusters: Vec<Vec<usize>> = vec![vec![]; sentences.len()];
+    for index in 0..sentences.len() {
+        let root = find_root(&mut parents, index);
+        clusters[root].push(index);
+    }
+    clusters.retain(|cluster| cluster.len() > 1);
+
+    clusters
+}
+
+fn find_root(parents: &mut [usize], index: usize) -> usize {
+    let mut root = index;
+    while parents[root] != root {
+        root = parents[root];
+    }
+    parents[index] = root;
+    root
+}
+
+fn tfidf_cosine(first: &str, second: &str) -> f64 {
+    let weighted = smoothed_tfidf_vectors(&[first, second]);
+
+    cosine(&weighted[0], &weighted[1])
+}
+
+fn smoothed_tfidf_vectors(sentences: &[&str]) -> Vec<Vec<f64>> {
+    smoothed_tfidf(&token::get_term_frequencies_from_sentences(sentences))
+}
+
+// weights each document's term frequencies with the smoothed inverse document frequency
+pub(crate) fn smoothed_tfidf(term_frequencies: &[BTreeMap<String, f64>]) -> Vec<Vec<f64>> {
+    let num_documents = term_frequencies.len() as f64;
+    let mut document_frequencies: HashMap<&str, f64> = HashMap::new();
+    for (term, _) in term_frequencies.iter().flatten().filter(|(_, frequency)| **frequency > 0.) {
+        *document_frequencies.entry(term).or_insert(0.) += 1.;
+    }
+    term_frequencies.iter().map(|frequencies| {
+        frequencies.iter().map(|(term, frequency)| {
+            let document_frequency = document_frequencies.get(term.as_str()).copied().unwrap_or(0.);
+            let inverse_document_frequency = ((1. + num_documents) / (1. + document_frequency)).ln() + 1.;
+            frequency * inverse_document_frequency
+        }).collect()
+    }).collect()
+}
+
+fn average_embedding(tokens: &[String], embeddings: &WordEmbeddings) -> Option<Vec<f64>> {
+    let mut sum: Vec<f64> = vec![];
+    let mut count = 0.;
+    for embedding in tokens.iter().filter_map(|token| embeddings.get(token)) {
+        if sum.is_empty() {
+            sum = vec![0.; embedding.len()];
+        }
+        sum.iter_mut().zip(embedding).for_each(|(total, value)| *total += value);
+        count += 1.;
+    }
+    if count == 0. {
+        return None;
+    }
+    Some(sum.into_iter().map(|total| total / count).collect())
+}
+
+pub(crate) fn cosine(first: &[f64], second: &[f64]) -> f64 {
+    let dot_product: f64 = first.iter().zip(second).map(|(a, b)| a * b).sum();
+    let first_norm = first.iter().map(|a| a * a).sum::<f64>().sqrt();
+    let second_norm = second.iter().map(|b| b * b).sum::<f64>().sqrt();
+    if first_norm == 0. || second_norm == 0. {
+        return 0.;
+    }
+    dot_product / (first_norm * second_norm)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn token_overlap_similarity() {
+        let similarity = sentence_similarity("the cat sat", "the cat ran", SimilarityMethod::TokenOverlap);
+        assert_eq!(similarity, 0.5);
+    }
+
+    #[test]
+    fn tfidf_similarity() {
+        let identical = sentence_similarity("reset my password", "Reset my password!", SimilarityMethod::TfidfCosine);
+        let disjoint = sentence_similarity("reset my password", "opening hours", SimilarityMethod::TfidfCosine);
+        let partial = sentence_similarity("reset my password", "change my password", SimilarityMethod::TfidfCosine);
+        assert!((identical - 1.).abs() < 1e-10);
+        assert_eq!(disjoint, 0.);
+        assert!(partial > 0. && partial < 1.);
+    }
+
+    #[test]
+    fn embedding_similarity() {
+        let embeddings = WordEmbeddings::from([
+            ("king".to_string(), vec![1., 0.]),
+            ("queen".to_string(), vec![1., 0.]),
+            ("apple".to_string(), vec![0., 1.]),
+        ]);
+        let similar = sentence_similarity("king", "queen", SimilarityMethod::EmbeddingCosine(&embeddings));
+        let different = sentence_similarity("king", "apple", SimilarityMethod::EmbeddingCosine(&embeddings));
+        let unknown = sentence_similarity("king", "banana", SimilarityMethod::EmbeddingCosine(&embeddings));
+        assert_eq!(similar, 1.);
+        assert_eq!(different, 0.);
+        assert_eq!(unknown, 0.);
+    }
+
+    #[test]
+    fn paraphrase_clusters_are_transitive() {
+        let sentences = vec!["reset my password", "opening hours", "reset my password please", "please reset my password now"];
+        let paraphrases = find_paraphrases(&sentences, 0.7);
+        assert_eq!(paraphrases, vec![vec![0, 2, 3]]);
+    }
+
+    #[test]
+    fn smoothed_inverse_document_frequencies() {
+        let term_frequencies = vec![
+            BTreeMap::from([("bees".to_string(), 2.), ("honey".to_string(), 1.)]),
+            BTreeMap::from([("bees".to_string(), 1.), ("honey".to_string(), 0.)]),
+        ];
+        let weighted = smoothed_tfidf(&term_frequencies);
+        let rare = (3_f64 / 2.).ln() + 1.;
+        assert_eq!(weighted, vec![vec![2., rare], vec![1., 0.]]);
+    }
+
+    #[test]
+    fn empty_text_similarity() {
+        assert_eq!(sentence_similarity("", "hello", SimilarityMethod::TokenOverlap), 0.);
+    }
+}
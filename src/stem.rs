@@ -1,12 +1,15 @@
 //! Module containing function used to stem strings.
 
 use std::str;
+use crate::ascii;
 use crate::error::RnltkError;
 
 struct Stemmer {
     bytes: Vec<u8>,
     bytes_length: usize,
     offset: usize,
+    // consonants[index] <=> bytes[index] is a consonant, updated whenever bytes change
+    consonants: Vec<bool>,
 }
 
 impl Stemmer {
@@ -16,10 +19,13 @@ impl Stemmer {
         } else {
             let bytes = word.to_ascii_lowercase().into_bytes();
             let bytes_length = bytes.len();
+            let mut consonants = Vec::new();
+            ascii::classify_consonants(&bytes, &mut consonants, 0);
             Ok(Stemmer { 
                 bytes, 
                 bytes_length, 
-                offset: 0 
+                offset: 0,
+                consonants,
             })
         }
     }
@@ -27,17 +33,7 @@ impl Stemmer {
     /// stem.is_consonant(index) is true <=> stem[index] is a consonant
     #[inline]
     fn is_consonant(&self, index: usize) -> bool {
-        match self.bytes[index] {
-            b'a' | b'e' | b'i' | b'o' | b'u' => false,
-            b'y' => {
-                if index == 0 {
-                    true
-                } else {
-                    !self.is_consonant(index - 1)
-                }
-            }
-            _ => true,
-        }
+        self.consonants[index]
     }
 
     /// stem.measure() measures the number of consonant sequences in [0, offset).
@@ -152,6 +148,7 @@ impl Stemmer {
             self.bytes[self.offset + index] = *byte;
         }
         self.bytes_length = self.offset + len;
+        ascii::classify_consonants(&self.bytes, &mut self.consonants, self.offset);
     }
 
     /// self.replace(s) is used further down.
@@ -236,6 +233,7 @@ impl Stemmer {
             self.update_offset("y");
             if self.has_vowel() {
                 self.bytes[self.bytes_length - 1] = b'i';
+                ascii::classify_consonants(&self.bytes, &mut self.consonants, self.bytes_length - 1);
             }
         }
     }
@@ -243,6 +241,7 @@ impl Stemmer {
     /// stem.step2() maps double suffices to single ones. so -ization ( = -ize
     /// plus -ation) maps to -ize etc. note that the string before the suffix
     /// must give m(z) > 0.
+    #[allow(clippy::collapsible_match)]
     fn step2(&mut self) {
         match self.bytes[self.bytes_length - 2] {
             b'a' => {
@@ -343,6 +342,7 @@ impl Stemmer {
     }
 
     /// stem.step3() deals with -ic-, -full, -ness etc. similar strategy to step2.
+    #[allow(clippy::collapsible_match)]
     fn step3(&mut self) {
         match self.bytes[self.bytes_length - 1] {
             b'e' => {
@@ -0,0 +1,297 @@
+//! Module containing extractive summarization, which picks the most representative sentences of a document,
+//! and headline generation from a document's keyphrases.
+//!
+//! Sentences are chosen with maximal marginal relevance (MMR): each pick balances how well a sentence
+//! represents the whole document against how similar it is to the sentences already picked, so a
+//! summary doesn't repeat itself when a document has several near-identical passages.
+
+use std::collections::{BTreeMap, HashMap};
+
+use serde::{Serialize, Deserialize};
+
+use crate::error::RnltkError;
+use crate::similarity;
+use crate::token::{self, TokenConfig};
+
+/// Target length of a summary made by [`summarize`]. Sentences are only added while the summary stays
+/// within the target, so a summary can be shorter than the target but never longer.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SummaryLength {
+    /// At most this many sentences
+    Sentences(usize),
+    /// At most this many words, counted with [`token::tokenize_sentence`]
+    Words(usize),
+    /// At most this many characters, counting the sentences themselves but not the spaces joining them
+    Characters(usize),
+}
+
+impl SummaryLength {
+    fn size_of(&self, sentence: &str) -> usize {
+        match self {
+            SummaryLength::Sentences(_) => 1,
+            SummaryLength::Words(_) => token::tokenize_sentence(sentence).len(),
+            SummaryLength::Characters(_) => sentence.chars().count(),
+        }
+    }
+
+    fn limit(&self) -> usize {
+        match self {
+            SummaryLength::Sentences(limit) | SummaryLength::Words(limit) | SummaryLength::Characters(limit) => *limit,
+        }
+    }
+}
+
+/// Struct for configuring [`summarize`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SummaryConfig {
+    pub length: SummaryLength,
+    /// Weight of relevance against diversity within [0...1]. 1 ranks sentences by relevance alone, while
+    /// lower values increasingly penalize sentences similar to those already in the summary.
+    pub lambda: f64,
+}
+
+impl Default for SummaryConfig {
+    fn default() -> Self {
+        SummaryConfig {
+            length: SummaryLength::Sentences(3),
+            lambda: 0.7,
+        }
+    }
+}
+
+/// Struct for holding a sentence picked by [`summarize`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SummarySentence {
+    /// Index of the sentence within the document
+    pub index: usize,
+    /// The sentence, as split by [`token::tokenize_into_sentences_with_delimiters`]
+    pub text: String,
+    /// Relevance of the sentence to the whole document within [0...1]
+    pub score: f64,
+}
+
+/// Gets an extractive summary of `document` within the target length of `config`, in document order.
+///
+/// Sentences are represented as TF-IDF vectors of their stemmed terms without stop words, and a sentence's
+/// relevance is its cosine similarity to the sum of every sentence vector. Sentences are then picked one at a time
+/// by the highest \\(\lambda \cdot relevance - (1 - \lambda) \cdot redundancy\\), where redundancy is the
+/// highest similarity to a sentence already picked. Sentences that would take the summary past its target
+/// length are skipped, and sentences without any terms are never picked.
+///
+/// # Errors
+///
+/// Returns [`RnltkError::InvalidSummaryConfig`] if `lambda` isn't within [0...1].
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::summarize::{self, SummaryConfig, SummaryLength};
+///
+/// let document = "The rover landed on Mars in February. The rover landed on Mars after a long journey. \
+///     Scientists will study rocks collected by the rover. Lunch was served at noon.";
+/// let config = SummaryConfig { length: SummaryLength::Sentences(2), lambda: 0.5 };
+/// let summary = summarize::summarize(document, &config).unwrap();
+/// let sentences: Vec<&str> = summary.iter().map(|sentence| sentence.text.as_str()).collect();
+///
+/// assert_eq!(sentences, vec!["The rover landed on Mars after a long journey.", "Scientists will study rocks collected by the rover."]);
+/// ```
+pub fn summarize(document: &str, config: &SummaryConfig) -> Result<Vec<SummarySentence>, RnltkError> {
+    if !(0. ..=1.).contains(&config.lambda) {
+        return Err(RnltkError::InvalidSummaryConfig);
+    }
+    let sentences = token::tokenize_into_sentences_with_delimiters(document);
+    let sentence_refs: Vec<&str> = sentences.iter().map(String::as_str).collect();
+    let term_frequencies = token::get_term_frequencies_from_sentences_configurable(&sentence_refs, TokenConfig::default());
+    let vectors = similarity::smoothed_tfidf(&term_frequencies);
+
+    let mut centroid = vec![0.; vectors.first().map_or(0, Vec::len)];
+    for vector in &vectors {
+        centroid.iter_mut().zip(vector).for_each(|(total, weight)| *total += weight);
+    }
+    let relevance: Vec<f64> = vectors.iter().map(|vector| similarity::cosine(vector, &centroid)).collect();
+
+    let mut candidates: Vec<usize> = (0..sentences.len())
+        .filter(|index| vectors[*index].iter().any(|weight| *weight > 0.))
+        .collect();
+    let mut picked: Vec<usize> = vec![];
+    let mut size = 0;
+    while !candidates.is_empty() {
+        let scores: Vec<f64> = candidates.iter().map(|candidate| {
+            let redundancy = picked.iter()
+                .map(|index| similarity::cosine(&vectors[*candidate], &vectors[*index]))
+                .fold(0., f64::max);
+            config.lambda * relevance[*candidate] - (1. - config.lambda) * redundancy
+        }).collect();
+        // ties go to the earlier sentence
+        let best = (0..candidates.len()).fold(0, |best, position| if scores[position] > scores[best] { position } else { best });
+        let candidate = candidates.remove(best);
+        let candidate_size = config.length.size_of(&sentences[candidate]);
+        if size + candidate_size <= config.length.limit() {
+            size += candidate_size;
+            picked.push(candidate);
+        }
+    }
+
+    picked.sort_unstable();
+    Ok(picked.into_iter().map(|index| SummarySentence {
+        index,
+        text: sentences[index].clone(),
+        score: relevance[index],
+    }).collect())
+}
+
+/// Struct for configuring [`generate_headlines`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct HeadlineConfig {
+    /// Longest headline, in words
+    pub max_words: usize,
+    /// Number of headline candidates returned
+    pub num_candidates: usize,
+}
+
+impl Default for HeadlineConfig {
+    fn default() -> Self {
+        HeadlineConfig {
+            max_words: 8,
+            num_candidates: 5,
+        }
+    }
+}
+
+/// Struct for holding a headline returned from [`generate_headlines`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct HeadlineCandidate {
+    /// The headline in title case
+    pub title: String,
+    /// The lowercased keyphrases the headline is made of, in order
+    pub keyphrases: Vec<String>,
+    /// Score of the headline within [0...1], relative to the best candidate
+    pub score: f64,
+}
+
+/// Gets headline candidates for `document` made from its top keyphrases, sorted from best to worst.
+///
+/// Keyphrases are the phrases of [`token::get_phrase_frequencies`], scored as in RAKE: each word scores
+/// the total length of the phrases it appears in divided by its count, and a phrase scores the sum of its
+/// words. Candidates are single keyphrases and pairs of keyphrases without words in common, joined
+/// with a colon in the order they first appear in the document. A pair scores the sum of its keyphrases,
+/// and candidates longer than `max_words` are left out.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::summarize::{self, HeadlineConfig};
+///
+/// let document = "Heavy rain flooded the subway stations on Monday. The subway flooding stranded thousands \
+///     of commuters, and the mayor promised new drainage pumps.";
+/// let headlines = summarize::generate_headlines(document, &HeadlineConfig::default());
+///
+/// assert_eq!(headlines[0].title, "Heavy Rain Flooded: Mayor Promised New Drainage Pumps");
+/// assert_eq!(headlines[0].score, 1.);
+/// ```
+pub fn generate_headlines(document: &str, config: &HeadlineConfig) -> Vec<HeadlineCandidate> {
+    let phrases = token::get_phrases(document, &token::get_stop_words().into());
+
+    // RAKE word scores, degree over frequency
+    let mut word_stats: HashMap<&str, (f64, f64)> = HashMap::new();
+    for phrase in &phrases {
+        for word in phrase {
+            let (degree, frequency) = word_stats.entry(word).or_insert((0., 0.));
+            *degree += phrase.len() as f64;
+            *frequency += 1.;
+        }
+    }
+
+    // distinct keyphrases in order of first appearance
+    let mut keyphrases: Vec<(&[String], f64)> = vec![];
+    let mut seen: BTreeMap<String, usize> = BTreeMap::new();
+    for phrase in phrases.iter().filter(|phrase| phrase.len() <= config.max_words) {
+        if let std::collections::btree_map::Entry::Vacant(entry) = seen.entry(phrase.join(" ")) {
+            entry.insert(keyphrases.len());
+            let score = phrase.iter().map(|word| word_stats[word.as_str()].0 / word_stats[word.as_str()].1).sum();
+            keyphrases.push((phrase, score));
+        }
+    }
+
+    let mut candidates: Vec<(Vec<usize>, f64)> = keyphrases.iter().enumerate().map(|(index, (_, score))| (vec![index], *score)).collect();
+    for first in 0..keyphrases.len() {
+        for second in (first + 1)..keyphrases.len() {
+            let (first_words, first_score) = keyphrases[first];
+            let (second_words, second_score) = keyphrases[second];
+            if first_words.len() + second_words.len() <= config.max_words && !first_words.iter().any(|word| second_words.contains(word)) {
+                candidates.push((vec![first, second], first_score + second_score));
+            }
+        }
+    }
+    // stable sort keeps ties with single keyphrases first, then in document order
+    candidates.sort_by(|(_, first_score), (_, second_score)| second_score.total_cmp(first_score));
+    candidates.truncate(config.num_candidates);
+
+    let best_score = candidates.first().map_or(0., |(_, score)| *score);
+    candidates.into_iter().map(|(indices, score)| {
+        let keyphrases: Vec<String> = indices.iter().map(|index| keyphrases[*index].0.join(" ")).collect();
+        let title = keyphrases.iter().map(|keyphrase| title_case(keyphrase)).collect::<Vec<String>>().join(": ");
+        HeadlineCandidate {
+            title,
+            keyphrases,
+            score: if best_score > 0. { score / best_score } else { 0. },
+        }
+    }).collect()
+}
+
+fn title_case(phrase: &str) -> String {
+    phrase.split(' ').map(|word| {
+        let mut chars = word.chars();
+        chars.next().map_or_else(String::new, |first| first.to_uppercase().chain(chars).collect())
+    }).collect::<Vec<String>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DOCUMENT: &str = "Solar panels convert sunlight into electricity. Solar panels convert sunlight into electricity cheaply. \
+        Wind turbines also generate electricity. Batteries store electricity for the night.";
+
+    #[test]
+    fn diversity_skips_redundant_sentences() {
+        let relevant = summarize(DOCUMENT, &SummaryConfig { length: SummaryLength::Sentences(2), lambda: 1. }).unwrap();
+        let indices: Vec<usize> = relevant.iter().map(|sentence| sentence.index).collect();
+        assert_eq!(indices, vec![0, 1]);
+
+        let diverse = summarize(DOCUMENT, &SummaryConfig { length: SummaryLength::Sentences(2), lambda: 0.3 }).unwrap();
+        let indices: Vec<usize> = diverse.iter().map(|sentence| sentence.index).collect();
+        assert_eq!(indices.len(), 2);
+        assert!(!(indices.contains(&0) && indices.contains(&1)));
+    }
+
+    #[test]
+    fn length_controls() {
+        let words = summarize(DOCUMENT, &SummaryConfig { length: SummaryLength::Words(10), ..SummaryConfig::default() }).unwrap();
+        assert!(words.iter().map(|sentence| token::tokenize_sentence(&sentence.text).len()).sum::<usize>() <= 10);
+        assert!(!words.is_empty());
+
+        let characters = summarize(DOCUMENT, &SummaryConfig { length: SummaryLength::Characters(50), ..SummaryConfig::default() }).unwrap();
+        assert!(characters.iter().map(|sentence| sentence.text.chars().count()).sum::<usize>() <= 50);
+        assert!(!characters.is_empty());
+
+        assert!(summarize(DOCUMENT, &SummaryConfig { length: SummaryLength::Sentences(0), ..SummaryConfig::default() }).unwrap().is_empty());
+        assert!(summarize("", &SummaryConfig::default()).unwrap().is_empty());
+        assert!(matches!(summarize(DOCUMENT, &SummaryConfig { lambda: 1.5, ..SummaryConfig::default() }), Err(RnltkError::InvalidSummaryConfig)));
+    }
+
+    #[test]
+    fn headlines_fit_the_word_limit() {
+        let config = HeadlineConfig { max_words: 3, num_candidates: 10 };
+        let headlines = generate_headlines(DOCUMENT, &config);
+        assert!(!headlines.is_empty() && headlines.len() <= 10);
+        for headline in &headlines {
+            assert!(headline.keyphrases.iter().map(|keyphrase| keyphrase.split(' ').count()).sum::<usize>() <= 3);
+            assert!(headline.score > 0. && headline.score <= 1.);
+        }
+        assert!(headlines.windows(2).all(|pair| pair[0].score >= pair[1].score));
+        assert_eq!(title_case("solar panels"), "Solar Panels");
+        assert!(generate_headlines("", &config).is_empty());
+        assert!(generate_headlines(DOCUMENT, &HeadlineConfig { num_candidates: 0, ..config }).is_empty());
+    }
+}
@@ -1,35 +1,2717 @@
 //! Module containing functions used to tokenize strings and get term frequencies.
+//!
+//! Term frequencies and counts are [`BTreeMap`]s and [`StopWords`] serialize as a sorted list, so outputs
+//! iterate and serialize in the same order on every run.
 
-use std::collections::BTreeMap;
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::ops::Range;
+use std::path::Path;
+use std::sync::OnceLock;
 
 use regex::Regex;
+use serde::{Serialize, Deserialize};
+use unicode_normalization::UnicodeNormalization;
+use unicode_normalization::char::is_combining_mark;
 
+use crate::ascii;
+use crate::document::{GenericMatrix, LabeledMatrix};
+use crate::error::RnltkError;
 use crate::stem;
+use crate::trace::stage_span;
+use intern::{Interner, Symbol};
 
+pub mod collocations;
+pub mod intern;
+
+const NLTK_STOP_WORDS: [&str; 179] = ["i", "me", "my", "myself", "we", "our", "ours", "ourselves", "you", "you're", "you've", "you'll", "you'd", "your", "yours", "yourself", "yourselves", "he", "him", "his", "himself", "she", "she's", "her", "hers", "herself", "it", "it's", "its", "itself", "they", "them", "their", "theirs", "themselves", "what", "which", "who", "whom", "this", "that", "that'll", "these", "those", "am", "is", "are", "was", "were", "be", "been", "being", "have", "has", "had", "having", "do", "does", "did", "doing", "a", "an", "the", "and", "but", "if", "or", "because", "as", "until", "while", "of", "at", "by", "for", "with", "about", "against", "between", "into", "through", "during", "before", "after", "above", "below", "to", "from", "up", "down", "in", "out", "on", "off", "over", "under", "again", "further", "then", "once", "here", "there", "when", "where", "why", "how", "all", "any", "both", "each", "few", "more", "most", "other", "some", "such", "no", "nor", "not", "only", "own", "same", "so", "than", "too", "very", "s", "t", "can", "will", "just", "don", "don't", "should", "should've", "now", "d", "ll", "m", "o", "re", "ve", "y", "ain", "aren", "aren't", "couldn", "couldn't", "didn", "didn't", "doesn", "doesn't", "hadn", "hadn't", "hasn", "hasn't", "haven", "haven't", "isn", "isn't", "ma", "mightn", "mightn't", "mustn", "mustn't", "needn", "needn't", "shan", "shan't", "shouldn", "shouldn't", "wasn", "wasn't", "weren", "weren't", "won", "won't", "wouldn", "wouldn't"];
+
+// stop list of the SMART information retrieval system (Salton, 1971)
+const SMART_STOP_WORDS: [&str; 570] = ["a", "a's", "able", "about", "above", "according", "accordingly", "across", "actually", "after", "afterwards", "again", "against", "ain't", "all", "allow", "allows", "almost", "alone", "along", "already", "also", "although", "always", "am", "among", "amongst", "an", "and", "another", "any", "anybody", "anyhow", "anyone", "anything", "anyway", "anyways", "anywhere", "apart", "appear", "appreciate", "appropriate", "are", "aren't", "around", "as", "aside", "ask", "asking", "associated", "at", "available", "away", "awfully", "b", "be", "became", "because", "become", "becomes", "becoming", "been", "before", "beforehand", "behind", "being", "believe", "below", "beside", "besides", "best", "better", "between", "beyond", "both", "brief", "but", "by", "c", "c'mon", "c's", "came", "can", "can't", "cannot", "cant", "cause", "causes", "certain", "certainly", "changes", "clearly", "co", "com", "come", "comes", "concerning", "consequently", "consider", "considering", "contain", "containing", "contains", "corresponding", "could", "couldn't", "course", "currently", "d", "definitely", "described", "despite", "did", "didn't", "different", "do", "does", "doesn't", "doing", "don't", "done", "down", "downwards", "during", "e", "each", "edu", "eg", "eight", "either", "else", "elsewhere", "enough", "entirely", "especially", "et", "etc", "even", "ever", "every", "everybody", "everyone", "everything", "everywhere", "ex", "exactly", "example", "except", "f", "far", "few", "fifth", "first", "five", "followed", "following", "follows", "for", "former", "formerly", "forth", "four", "from", "further", "furthermore", "g", "get", "gets", "getting", "given", "gives", "go", "goes", "going", "gone", "got", "gotten", "greetings", "h", "had", "hadn't", "happens", "hardly", "has", "hasn't", "have", "haven't", "having", "he", "he's", "hello", "help", "hence", "her", "here", "here's", "hereafter", "hereby", "herein", "hereupon", "hers", "herself", "hi", "him", "himself", "his", "hither", "hopefully", "how", "howbeit", "however", "i", "i'd", "i'll", "i'm", "i've", "ie", "if", "ignored", "immediate", "in", "inasmuch", "inc", "indeed", "indicate", "indicated", "indicates", "inner", "insofar", "instead", "into", "inward", "is", "isn't", "it", "it'd", "it'll", "it's", "its", "itself", "j", "just", "k", "keep", "keeps", "kept", "know", "knows", "known", "l", "last", "lately", "later", "latter", "latterly", "least", "less", "lest", "let", "let's", "like", "liked", "likely", "little", "look", "looking", "looks", "ltd", "m", "mainly", "many", "may", "maybe", "me", "mean", "meanwhile", "merely", "might", "more", "moreover", "most", "mostly", "much", "must", "my", "myself", "n", "name", "namely", "nd", "near", "nearly", "necessary", "need", "needs", "neither", "never", "nevertheless", "new", "next", "nine", "no", "nobody", "non", "none", "noone", "nor", "normally", "not", "nothing", "novel", "now", "nowhere", "o", "obviously", "of", "off", "often", "oh", "ok", "okay", "old", "on", "once", "one", "ones", "only", "onto", "or", "other", "others", "otherwise", "ought", "our", "ours", "ourselves", "out", "outside", "over", "overall", "own", "p", "particular", "particularly", "per", "perhaps", "placed", "please", "plus", "possible", "presumably", "probably", "provides", "q", "que", "quite", "qv", "r", "rather", "rd", "re", "really", "reasonably", "regarding", "regardless", "regards", "relatively", "respectively", "right", "s", "said", "same", "saw", "say", "saying", "says", "second", "secondly", "see", "seeing", "seem", "seemed", "seeming", "seems", "seen", "self", "selves", "sensible", "sent", "serious", "seriously", "seven", "several", "shall", "she", "should", "shouldn't", "since", "six", "so", "some", "somebody", "somehow", "someone", "something", "sometime", "sometimes", "somewhat", "somewhere", "soon", "sorry", "specified", "specify", "specifying", "still", "sub", "such", "sup", "sure", "t", "t's", "take", "taken", "tell", "tends", "th", "than", "thank", "thanks", "thanx", "that", "that's", "thats", "the", "their", "theirs", "them", "themselves", "then", "thence", "there", "there's", "thereafter", "thereby", "therefore", "therein", "theres", "thereupon", "these", "they", "they'd", "they'll", "they're", "they've", "think", "third", "this", "thorough", "thoroughly", "those", "though", "three", "through", "throughout", "thru", "thus", "to", "together", "too", "took", "toward", "towards", "tried", "tries", "truly", "try", "trying", "twice", "two", "u", "un", "under", "unfortunately", "unless", "unlikely", "until", "unto", "up", "upon", "us", "use", "used", "useful", "uses", "using", "usually", "uucp", "v", "value", "various", "very", "via", "viz", "vs", "w", "want", "wants", "was", "wasn't", "way", "we", "we'd", "we'll", "we're", "we've", "welcome", "well", "went", "were", "weren't", "what", "what's", "whatever", "when", "whence", "whenever", "where", "where's", "whereafter", "whereas", "whereby", "wherein", "whereupon", "wherever", "whether", "which", "while", "whither", "who", "who's", "whoever", "whole", "whom", "whose", "why", "will", "willing", "wish", "with", "within", "without", "won't", "wonder", "would", "wouldn't", "x", "y", "yes", "yet", "you", "you'd", "you'll", "you're", "you've", "your", "yours", "yourself", "yourselves", "z", "zero"];
+
+// the most frequent English function words, as used by many search engines
+const MINIMAL_STOP_WORDS: [&str; 25] = ["a", "an", "and", "are", "as", "at", "be", "by", "for", "from", "has", "he", "in", "is", "it", "its", "of", "on", "that", "the", "to", "was", "were", "will", "with"];
+
+/// Gets the NLTK English stop word list, the same words as [`StopWordList::Nltk`].
 pub fn get_stop_words() -> Vec<String> {
-    ["i", "me", "my", "myself", "we", "our", "ours", "ourselves", "you", "you're", "you've", "you'll", "you'd", "your", "yours", "yourself", "yourselves", "he", "him", "his", "himself", "she", "she's", "her", "hers", "herself", "it", "it's", "its", "itself", "they", "them", "their", "theirs", "themselves", "what", "which", "who", "whom", "this", "that", "that'll", "these", "those", "am", "is", "are", "was", "were", "be", "been", "being", "have", "has", "had", "having", "do", "does", "did", "doing", "a", "an", "the", "and", "but", "if", "or", "because", "as", "until", "while", "of", "at", "by", "for", "with", "about", "against", "between", "into", "through", "during", "before", "after", "above", "below", "to", "from", "up", "down", "in", "out", "on", "off", "over", "under", "again", "further", "then", "once", "here", "there", "when", "where", "why", "how", "all", "any", "both", "each", "few", "more", "most", "other", "some", "such", "no", "nor", "not", "only", "own", "same", "so", "than", "too", "very", "s", "t", "can", "will", "just", "don", "don't", "should", "should've", "now", "d", "ll", "m", "o", "re", "ve", "y", "ain", "aren", "aren't", "couldn", "couldn't", "didn", "didn't", "doesn", "doesn't", "hadn", "hadn't", "hasn", "hasn't", "haven", "haven't", "isn", "isn't", "ma", "mightn", "mightn't", "mustn", "mustn't", "needn", "needn't", "shan", "shan't", "shouldn", "shouldn't", "wasn", "wasn't", "weren", "weren't", "won", "won't", "wouldn", "wouldn't"]
+    NLTK_STOP_WORDS
         .map(String::from)
         .to_vec()
 }
 
+/// Set of stop words for filtering tokens, with constant-time lookups where a `Vec<String>` would
+/// have to be searched for every token. Functions taking stop words accept anything convertible into
+/// StopWords, such as the `Vec<String>` from [`get_stop_words`], so a StopWords can be built once and reused.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::token::{self, StopWords};
+/// 
+/// let mut stop_words = StopWords::from(token::get_stop_words());
+/// stop_words.insert("general");
+/// let tokenized_text = token::tokenize_sentence_without_stop_words("Why hello there. General Kenobi!", stop_words);
+///
+/// assert_eq!(tokenized_text, vec!["hello", "kenobi"]);
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(from = "Vec<String>", into = "Vec<String>")]
+pub struct StopWords(HashSet<String>);
+
+impl StopWords {
+    /// Creates new instance of StopWords without any words.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Gets whether `word` is a stop word.
+    pub fn contains(&self, word: &str) -> bool {
+        self.0.contains(word)
+    }
+
+    /// Adds `word` as a stop word, returning whether it was new.
+    pub fn insert(&mut self, word: impl ToString) -> bool {
+        self.0.insert(word.to_string())
+    }
+
+    /// Removes `word` from the stop words, returning whether it was present.
+    pub fn remove(&mut self, word: &str) -> bool {
+        self.0.remove(word)
+    }
+
+    /// Gets the number of stop words.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Checks if there are no stop words.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Creates new instance of StopWords from one of the built-in English stop word lists.
+    pub fn english(list: StopWordList) -> Self {
+        list.words().iter().map(|word| word.to_string()).collect()
+    }
+
+    /// Creates new instance of StopWords from the stop word list file at `path`, in any format read by
+    /// [`StopWords::from_reader`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RnltkError::StopWordsIo`] if the file couldn't be read, or any error of [`StopWords::from_reader`].
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, RnltkError> {
+        let file = File::open(path).map_err(|error| RnltkError::StopWordsIo(error.to_string()))?;
+        Self::from_reader(BufReader::new(file))
+    }
+
+    /// Creates new instance of StopWords from a `reader` of a stop word list.
+    ///
+    /// Lists are either plain text with one word per line, or a JSON array of words when the first
+    /// character that isn't whitespace or part of a comment is `[`. In both formats, everything after a `#`
+    /// on a line is a comment. Words are trimmed, blank lines are skipped, and words are kept as written,
+    /// so lists should be lowercase to match tokens.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RnltkError::StopWordsIo`] if the list couldn't be read.
+    ///
+    /// Returns [`RnltkError::StopWordsFormat`] if a JSON list isn't an array of strings.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rnltk::token::{self, StopWords, TokenConfig};
+    ///
+    /// let text_list = "# team stop words\nhello\n\nthere # greeting\n";
+    /// let json_list = "# team stop words\n[\"hello\", \"there\"]";
+    ///
+    /// let stop_words = StopWords::from_reader(text_list.as_bytes()).unwrap();
+    /// assert_eq!(stop_words, StopWords::from_reader(json_list.as_bytes()).unwrap());
+    ///
+    /// let config = TokenConfig::builder().stem(false).stop_words(stop_words).build();
+    /// assert_eq!(token::tokenize_sentence_configurable("Hello there, Kenobi", config), vec!["kenobi"]);
+    /// ```
+    pub fn from_reader(mut reader: impl BufRead) -> Result<Self, RnltkError> {
+        let mut contents = String::new();
+        reader.read_to_string(&mut contents).map_err(|error| RnltkError::StopWordsIo(error.to_string()))?;
+        let lines = contents.lines().map(|line| line.split_once('#').map_or(line, |(words, _)| words));
+        let uncommented: Vec<&str> = lines.collect();
+        if uncommented.iter().find_map(|line| line.trim_start().chars().next()) == Some('[') {
+            let words: Vec<String> = serde_json::from_str(&uncommented.join("\n"))
+                .map_err(|error| RnltkError::StopWordsFormat(error.to_string()))?;
+            Ok(words.into_iter().map(|word| word.trim().to_string()).filter(|word| !word.is_empty()).collect())
+        } else {
+            Ok(uncommented.into_iter().map(str::trim).filter(|word| !word.is_empty()).map(String::from).collect())
+        }
+    }
+
+    /// Compares these stop words with `other`, listing the words found in only one of them.
+    pub fn diff(&self, other: &StopWords) -> StopWordsDiff {
+        let only_in = |left: &StopWords, right: &StopWords| {
+            let mut words: Vec<String> = left.0.difference(&right.0).cloned().collect();
+            words.sort();
+            words
+        };
+        StopWordsDiff {
+            only_in_self: only_in(self, other),
+            only_in_other: only_in(other, self),
+        }
+    }
+}
+
+impl From<Vec<String>> for StopWords {
+    fn from(stop_words: Vec<String>) -> Self {
+        StopWords(stop_words.into_iter().collect())
+    }
+}
+
+impl FromIterator<String> for StopWords {
+    fn from_iter<I: IntoIterator<Item = String>>(stop_words: I) -> Self {
+        StopWords(stop_words.into_iter().collect())
+    }
+}
+
+// serialized as a sorted list, so output is deterministic
+impl From<StopWords> for Vec<String> {
+    fn from(stop_words: StopWords) -> Self {
+        let mut stop_words: Vec<String> = stop_words.0.into_iter().collect();
+        stop_words.sort();
+        stop_words
+    }
+}
+
+/// English stop word lists available through [`StopWords::english`].
+///
+/// Different tools ship different lists, so the list used for published results should be named
+/// explicitly. Use [`StopWords::diff`] to see how two lists differ.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::token::{StopWordList, StopWords};
+/// 
+/// let nltk = StopWords::english(StopWordList::Nltk);
+/// let minimal = StopWords::english(StopWordList::Minimal);
+/// let diff = minimal.diff(&nltk);
+///
+/// assert!(diff.only_in_self.is_empty());
+/// assert!(diff.only_in_other.contains(&"myself".to_string()));
+/// assert!(nltk.diff(&StopWords::english(StopWordList::Nltk)).is_empty());
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum StopWordList {
+    /// The NLTK English stop word list, as returned by [`get_stop_words`]
+    #[default]
+    Nltk,
+    /// The SMART information retrieval system's English stop word list
+    Smart,
+    /// A minimal list of very frequent function words
+    Minimal,
+}
+
+impl StopWordList {
+    /// Gets the words in the list.
+    pub fn words(&self) -> &'static [&'static str] {
+        match self {
+            StopWordList::Nltk => &NLTK_STOP_WORDS,
+            StopWordList::Smart => &SMART_STOP_WORDS,
+            StopWordList::Minimal => &MINIMAL_STOP_WORDS,
+        }
+    }
+}
+
+/// Words found in only one of two [`StopWords`], from [`StopWords::diff`]. Both lists are sorted.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct StopWordsDiff {
+    pub only_in_self: Vec<String>,
+    pub only_in_other: Vec<String>,
+}
+
+impl StopWordsDiff {
+    /// Checks if both stop word sets contain the same words.
+    pub fn is_empty(&self) -> bool {
+        self.only_in_self.is_empty() && self.only_in_other.is_empty()
+    }
+}
+
+/// How [`TokenConfig`] tokenizes contractions such as "don't".
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::token::{self, Contractions, TokenConfig};
+/// 
+/// let text = "They don't know what it's worth.";
+/// let expand = TokenConfig { stem: false, remove_stop_words: false, contractions: Contractions::Expand, ..TokenConfig::default() };
+/// let split = TokenConfig { contractions: Contractions::Split, ..expand.clone() };
+///
+/// assert_eq!(token::tokenize_sentence_configurable(text, expand), vec!["they", "do", "not", "know", "what", "it", "is", "worth"]);
+/// assert_eq!(token::tokenize_sentence_configurable(text, split), vec!["they", "do", "n't", "know", "what", "it", "'s", "worth"]);
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Contractions {
+    /// Removes the apostrophe like any other punctuation, so "don't" becomes "dont"
+    #[default]
+    Strip,
+    /// Expands contractions into full words, so "don't" becomes "do" and "not". Possessives such as "John's" are stripped
+    Expand,
+    /// Splits contractions Penn Treebank style, so "don't" becomes "do" and "n't" and "John's" becomes "john" and "'s"
+    Split,
+    /// Keeps contractions as single tokens with their apostrophe, so "don't" stays "don't"
+    Keep,
+}
+
+/// How [`TokenConfig`] tokenizes hyphenated words such as "state-of-the-art".
+///
+/// Apart from [`Hyphens::Strip`], en dashes, em dashes, and double hyphens ("--") separate words, and the
+/// Unicode hyphen characters are treated as "-". [`tokenize_sentence_typed`] isn't affected, since it always
+/// keeps hyphens as punctuation tokens.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::token::{self, Hyphens, TokenConfig};
+///
+/// let text = "A state-of-the-art design\u{2014}cheap too";
+/// let keep = TokenConfig::builder().stem(false).hyphens(Hyphens::Keep).build();
+/// let split = TokenConfig::builder().stem(false).hyphens(Hyphens::Split).build();
+/// let both = TokenConfig::builder().stem(false).hyphens(Hyphens::Both).build();
+///
+/// assert_eq!(token::tokenize_sentence_configurable(text, TokenConfig { stem: false, ..TokenConfig::default() }), vec!["stateoftheart", "design\u{2014}cheap"]);
+/// assert_eq!(token::tokenize_sentence_configurable(text, keep), vec!["state-of-the-art", "design", "cheap"]);
+/// assert_eq!(token::tokenize_sentence_configurable(text, split), vec!["state", "art", "design", "cheap"]);
+/// assert_eq!(token::tokenize_sentence_configurable(text, both), vec!["state-of-the-art", "state", "art", "design", "cheap"]);
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Hyphens {
+    /// Removes hyphens like any other punctuation, so "state-of-the-art" becomes "stateoftheart"
+    #[default]
+    Strip,
+    /// Keeps hyphenated words as single tokens, so "state-of-the-art" stays "state-of-the-art"
+    Keep,
+    /// Splits hyphenated words into their parts, so "state-of-the-art" becomes "state", "of", "the", and "art"
+    Split,
+    /// Keeps hyphenated words as single tokens followed by their parts
+    Both,
+}
+
+/// How [`TokenConfig`] weights the counts returned by the `_configurable` term frequency functions.
+///
+/// Weights are computed per document, so with [`TermWeighting::Relative`] each document's frequencies sum to 1.
+/// Terms a document doesn't contain always weigh 0.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::token::{self, TermWeighting, TokenConfig};
+/// 
+/// let text = "hatred leads to hatred";
+/// let relative = TokenConfig::builder().stem(false).remove_stop_words(false).term_weighting(TermWeighting::Relative).build();
+/// let log = TokenConfig::builder().stem(false).remove_stop_words(false).term_weighting(TermWeighting::Log).build();
+///
+/// assert_eq!(token::get_term_frequencies_from_sentence_configurable(text, relative)["hatred"], 0.5);
+/// assert_eq!(token::get_term_frequencies_from_sentence_configurable(text, log)["hatred"], 1. + 2_f64.ln());
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TermWeighting {
+    /// Raw counts of each term
+    #[default]
+    Raw,
+    /// Counts divided by the total count of the document, so they can be used as probabilities
+    Relative,
+    /// Sublinear counts, \(1 + ln(count)\), which dampen terms repeated many times
+    Log,
+}
+
+/// How [`TokenConfig`] removes stop words when joining tokens into n-grams.
+///
+/// Removing stop words before joining gives n-grams spanning words that were never adjacent, while dropping
+/// n-grams that start or end with a stop word keeps clean phrases such as "cost of living" and leaves out
+/// "of living in".
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::token::{self, NgramStopWords, TokenConfig};
+///
+/// let text = "The cost of living in Paris";
+/// let remove = TokenConfig::builder().stem(false).ngram(3).build();
+/// let drop_edges = TokenConfig::builder().stem(false).ngram(3).ngram_stop_words(NgramStopWords::DropEdges).build();
+///
+/// assert_eq!(token::tokenize_sentence_configurable(text, remove), vec!["cost living paris"]);
+/// assert_eq!(token::tokenize_sentence_configurable(text, drop_edges), vec!["cost of living", "living in paris"]);
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NgramStopWords {
+    /// Removes stop words before tokens are joined
+    #[default]
+    Remove,
+    /// Keeps stop words inside n-grams, dropping n-grams that start or end with one
+    DropEdges,
+    /// Keeps stop words inside and at the end of n-grams, dropping n-grams that start with one
+    DropStart,
+    /// Keeps stop words inside and at the start of n-grams, dropping n-grams that end with one
+    DropEnd,
+}
+
+/// Unicode normalization forms for [`TokenConfig`] and [`normalize_unicode`].
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Normalization {
+    /// Canonical composition, so "e" followed by a combining acute accent becomes "é"
+    Nfc,
+    /// Compatibility composition, which also replaces characters such as ligatures and full-width letters,
+    /// so "ﬁ" becomes "fi"
+    Nfkc,
+}
+
+/// How words are case folded before stop words are removed, for [`TokenConfig`] and [`CaseFolding::fold`].
+///
+/// Folding maps every casing of a word to the same text, so "Über" matches the stop word "über" and
+/// "STRASSE" matches "Straße".
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::token::{self, CaseFolding, TokenConfig};
+///
+/// let text = "ÜBER die Straße IST";
+/// let turkic = TokenConfig::builder().stem(false).remove_stop_words(false).case_folding(CaseFolding::Turkic).build();
+///
+/// assert_eq!(token::tokenize_sentence(text), vec!["über", "die", "strasse", "ist"]);
+/// assert_eq!(token::tokenize_sentence_configurable(text, turkic), vec!["über", "die", "strasse", "ıst"]);
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CaseFolding {
+    /// Only lowercases ASCII letters, leaving "Ü" as it is
+    Ascii,
+    /// Locale-insensitive Unicode case folding, so "Ü" becomes "ü", "ß" becomes "ss", and "İ" becomes "i" followed
+    /// by a combining dot above
+    #[default]
+    Unicode,
+    /// Unicode case folding with the Turkish and Azerbaijani dotted and dotless i, so "I" becomes "ı" and "İ" becomes "i"
+    Turkic,
+}
+
+impl CaseFolding {
+    /// Case folds `text`, borrowing it when folding doesn't change it.
+    pub fn fold<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        if !text.chars().any(|c| c.is_uppercase() || matches!(c, 'ß' | 'ς' | 'ſ')) {
+            return Cow::Borrowed(text);
+        }
+        if *self == CaseFolding::Ascii || (*self == CaseFolding::Unicode && text.is_ascii()) {
+            return Cow::Owned(text.to_ascii_lowercase());
+        }
+        let mut folded = String::with_capacity(text.len());
+        for c in text.chars() {
+            match (self, c) {
+                (CaseFolding::Turkic, 'I') => folded.push('ı'),
+                (CaseFolding::Turkic, 'İ') => folded.push('i'),
+                // full case folds that differ from lowercasing
+                (_, 'ß' | 'ẞ') => folded.push_str("ss"),
+                (_, 'ς') => folded.push('σ'),
+                (_, 'ſ') => folded.push('s'),
+                _ => folded.extend(c.to_lowercase()),
+            }
+        }
+        Cow::Owned(folded)
+    }
+}
+
+/// How [`TokenConfig`] tokenizes emoji and emoticons.
+///
+/// Emoji are single pictographs or sequences joined into one, such as skin tone variants and flags.
+/// Emoticons such as ":)" or "<3" are only recognized when they stand apart from words, so the ":/" in a URL isn't one.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::token::{self, Emoji, TokenConfig};
+/// 
+/// let text = "Loved it😍 :) 🤷🏽";
+/// let keep = TokenConfig::builder().stem(false).emoji(Emoji::Keep).build();
+/// let alias = TokenConfig::builder().stem(false).emoji(Emoji::Alias).build();
+///
+/// assert_eq!(token::tokenize_sentence_configurable(text, TokenConfig { stem: false, ..TokenConfig::default() }), vec!["loved", "it😍", "🤷🏽"]);
+/// assert_eq!(token::tokenize_sentence_configurable(text, keep), vec!["loved", "😍", ":)", "🤷🏽"]);
+/// assert_eq!(token::tokenize_sentence_configurable(text, alias), vec!["loved", ":heart_eyes:", ":slightly_smiling_face:", ":shrug:"]);
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Emoji {
+    /// Leaves emoji to the word splitter, which keeps them attached to adjacent letters and strips emoticons as punctuation
+    #[default]
+    Ignore,
+    /// Keeps every emoji and emoticon as its own token
+    Keep,
+    /// Keeps every emoji and emoticon as its own token, replaced with its [`emoji_alias`] if it has one
+    Alias,
+}
+
+/// Stemmers [`TokenConfig`] can stem tokens with.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::token::{self, Stemmer, TokenConfig};
+///
+/// let text = "The bees are making honey";
+/// let plural = TokenConfig::builder().stemmer(Stemmer::Plural).build();
+///
+/// assert_eq!(token::tokenize_sentence_configurable(text, TokenConfig::default()), vec!["bee", "make", "honei"]);
+/// assert_eq!(token::tokenize_sentence_configurable(text, plural), vec!["bee", "making", "honey"]);
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Stemmer {
+    /// The Porter stemmer from [`stem::get`]
+    #[default]
+    Porter,
+    /// Harman's S stemmer, which only removes English plural endings, so "bees" becomes "bee" and "making" stays "making"
+    Plural,
+}
+
+impl Stemmer {
+    /// Gets the stem of `word`, or `None` if the stemmer can't stem it.
+    pub fn stem(&self, word: &str) -> Option<String> {
+        match self {
+            Stemmer::Porter => cached_stem(word),
+            Stemmer::Plural => Some(plural_stem(word)),
+        }
+    }
+}
+
+// removes the plural ending of `word` following Harman's S stemmer
+fn plural_stem(word: &str) -> String {
+    if let Some(stem) = word.strip_suffix("ies").filter(|stem| !stem.is_empty() && !stem.ends_with(['a', 'e'])) {
+        format!("{stem}y")
+    } else if let Some(stem) = word.strip_suffix("es").filter(|stem| !stem.is_empty() && !stem.ends_with(['a', 'e', 'o'])) {
+        format!("{stem}e")
+    } else if let Some(stem) = word.strip_suffix('s').filter(|stem| !stem.is_empty() && !stem.ends_with(['u', 's'])) {
+        stem.to_string()
+    } else {
+        word.to_string()
+    }
+}
+
+/// Words starting the scope of a negation for [`TokenConfig::negation`], besides words ending in "n't".
+/// Contractions stripped of their apostrophe are included, since [`Contractions::Strip`] is the default.
+const NEGATION_WORDS: [&str; 27] = ["never", "no", "nothing", "nowhere", "noone", "none", "not", "nobody", "neither", "nor", "cannot",
+    "havent", "hasnt", "hadnt", "cant", "couldnt", "shouldnt", "wont", "wouldnt", "dont", "doesnt", "didnt", "isnt", "arent", "aint",
+    "wasnt", "werent"];
+
+// whether `word` negates the words after it
+fn is_negation(word: &str) -> bool {
+    NEGATION_WORDS.contains(&word) || word.ends_with("n't")
+}
+
+/// Configuration for the `_configurable` tokenization and term frequency functions.
+///
+/// Besides stop words, tokens can be filtered by length, by being purely numeric, and by how often they occur.
+/// Length and numeric filters apply before stemming, and n-grams and the frequency filter after it.
+/// Every other tokenization and term frequency function in this module is a fixed configuration of the
+/// `_configurable` functions. Configurations can also be built with [`TokenConfig::builder`].
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::token::{self, TokenConfig};
+/// 
+/// let config = TokenConfig { stem: false, min_token_length: Some(3), remove_numeric: true, min_frequency: Some(2), ..TokenConfig::default() };
+/// let text = "In 1999 the band played, and in 2001 the band played again on tv.";
+///
+/// assert_eq!(token::tokenize_sentence_configurable(text, config), vec!["band", "played", "band", "played"]);
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TokenConfig {
+    pub stem: bool,
+    pub remove_stop_words: bool,
+    pub stop_words: StopWords,
+    #[serde(default)]
+    pub contractions: Contractions,
+    /// Unicode normalization applied to each word before it is case folded
+    #[serde(default)]
+    pub normalization: Option<Normalization>,
+    #[serde(default)]
+    pub case_folding: CaseFolding,
+    /// Whether words are split on whitespace only, as with [`tokenize_whitespace`], keeping their punctuation and
+    /// case. Contractions, hyphens, emoji, normalization, accents, and case folding are then left untouched
+    #[serde(default)]
+    pub whitespace_only: bool,
+    /// Whether diacritics are removed from each word, as with [`strip_accents`]
+    #[serde(default)]
+    pub strip_accents: bool,
+    /// Tokens with fewer characters than this are dropped
+    #[serde(default)]
+    pub min_token_length: Option<usize>,
+    /// Tokens with more characters than this are dropped
+    #[serde(default)]
+    pub max_token_length: Option<usize>,
+    /// Whether tokens made only of digits, such as "1999", are dropped
+    #[serde(default)]
+    pub remove_numeric: bool,
+    /// Tokens occurring fewer times than this are dropped. Occurrences are counted across everything
+    /// passed to a single call, so across all sentences for the batch functions
+    #[serde(default)]
+    pub min_frequency: Option<usize>,
+    /// Number of consecutive tokens joined with a space into each term, after stemming.
+    /// Not used by [`tokenize_sentence_with_metadata`]
+    #[serde(default = "default_ngram")]
+    pub ngram: usize,
+    /// How stop words are removed from n-grams when `ngram` is above 1 and `remove_stop_words` is set
+    #[serde(default)]
+    pub ngram_stop_words: NgramStopWords,
+    #[serde(default)]
+    pub emoji: Emoji,
+    #[serde(default)]
+    pub hyphens: Hyphens,
+    /// How term frequencies are weighted. Only used by the term frequency functions returning `f64`s
+    #[serde(default)]
+    pub term_weighting: TermWeighting,
+    /// Tokens that are never stemmed nor dropped as stop words or by the length and numeric filters, such as
+    /// product names, tickers, or gene symbols. They are matched against tokens after case folding, so they
+    /// should be case folded too, as [`TokenConfigBuilder::protected_tokens`] does
+    #[serde(default)]
+    pub protected_tokens: BTreeSet<String>,
+    /// Stemmer used when `stem` is set
+    #[serde(default)]
+    pub stemmer: Stemmer,
+    /// Whether tokens following a negation word such as "not" or "never", up to the end of the sentence, get a
+    /// `_NEG` suffix as with NLTK's `mark_negation`, so "good" in "not good" is a different term than in "very good".
+    /// Negation words are found before stop words are removed, and the suffix is added after stemming.
+    /// Not used by [`tokenize_sentence_with_metadata`]
+    #[serde(default)]
+    pub negation: bool,
+}
+
+fn default_ngram() -> usize {
+    1
+}
+
+impl TokenConfig {
+    /// Creates a [`TokenConfigBuilder`] starting from the default configuration.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rnltk::token::{self, TokenConfig};
+    /// 
+    /// let config = TokenConfig::builder()
+    ///     .stem(false)
+    ///     .stop_words(vec!["the".to_string()])
+    ///     .ngram(2)
+    ///     .build();
+    ///
+    /// assert_eq!(token::tokenize_sentence_configurable("The Jedi fear the dark side", config), vec!["jedi fear", "fear dark", "dark side"]);
+    /// ```
+    pub fn builder() -> TokenConfigBuilder {
+        TokenConfigBuilder::default()
+    }
+
+    // configuration that only splits words, the base of the non-configurable functions
+    fn plain() -> Self {
+        Self {
+            stem: false,
+            remove_stop_words: false,
+            ..Self::default_with_stop_words(StopWords::new())
+        }
+    }
+
+    fn default_with_stop_words(stop_words: StopWords) -> Self {
+        Self {
+            stem: true,
+            remove_stop_words: true,
+            stop_words,
+            contractions: Contractions::Strip,
+            normalization: None,
+            case_folding: CaseFolding::Unicode,
+            whitespace_only: false,
+            strip_accents: false,
+            min_token_length: None,
+            max_token_length: None,
+            remove_numeric: false,
+            min_frequency: None,
+            ngram: 1,
+            ngram_stop_words: NgramStopWords::Remove,
+            emoji: Emoji::Ignore,
+            hyphens: Hyphens::Strip,
+            term_weighting: TermWeighting::Raw,
+            protected_tokens: BTreeSet::new(),
+            stemmer: Stemmer::Porter,
+            negation: false,
+        }
+    }
+
+    // whether words are rewritten beyond what the regex-based tokenizer does
+    fn rewrites_words(&self) -> bool {
+        self.contractions != Contractions::Strip || self.normalization.is_some() || self.case_folding != CaseFolding::Unicode
+            || self.strip_accents || self.hyphens != Hyphens::Strip
+    }
+
+    // whether tokens can't come from the regex-based tokenizer
+    fn needs_spans(&self) -> bool {
+        self.rewrites_words() || self.emoji != Emoji::Ignore
+    }
+
+    // whether `token` is exempt from stemming and filtering
+    fn is_protected(&self, token: &str) -> bool {
+        self.protected_tokens.contains(token)
+    }
+
+    // whether `token` counts as a stop word
+    fn is_stop_word(&self, token: &str) -> bool {
+        self.stop_words.contains(token) && !self.is_protected(token)
+    }
+
+    // whether `token` passes the length and numeric filters, checked before stemming
+    fn keeps_token(&self, token: &str) -> bool {
+        let length = token.chars().count();
+        self.is_protected(token)
+            || (self.min_token_length.is_none_or(|min| length >= min)
+                && self.max_token_length.is_none_or(|max| length <= max)
+                && !(self.remove_numeric && token.chars().all(char::is_numeric)))
+    }
+
+    // removes stop words, filters, stems, and joins n-grams, in that order, so every tokenization
+    // and term frequency function applies the configuration the same way
+    fn apply<T: Term>(&self, terms: Vec<T>) -> Vec<T> {
+        // stop words are kept until n-grams are joined, so n-grams can be dropped by their first and last words
+        let trims_ngrams = self.remove_stop_words && self.ngram > 1 && self.ngram_stop_words != NgramStopWords::Remove;
+        // negation words are often stop words, so the scope of each is found before any are removed
+        let mut negating = false;
+        let (mut terms, negated): (Vec<T>, Vec<bool>) = terms.into_iter()
+            .map(|term| {
+                let negated = negating;
+                negating |= self.negation && is_negation(term.text());
+                (term, negated)
+            })
+            .filter(|(term, _)| !(self.remove_stop_words && !trims_ngrams && self.is_stop_word(term.text())) && self.keeps_token(term.text()))
+            .unzip();
+        // checked before stemming changes the text of the terms
+        let stops: Vec<bool> = if trims_ngrams { terms.iter().map(|term| self.is_stop_word(term.text())).collect() } else { vec![] };
+        if self.stem {
+            // emoticons aren't words the stemmer knows
+            for term in terms.iter_mut().filter(|term| term.text().starts_with(char::is_alphanumeric) && !self.is_protected(term.text())) {
+                if let Some(stemmed) = self.stemmer.stem(term.text()) {
+                    *term.text_mut() = stemmed;
+                }
+            }
+        }
+        if self.negation {
+            for (term, _) in terms.iter_mut().zip(negated).filter(|(_, negated)| *negated) {
+                term.text_mut().push_str("_NEG");
+            }
+        }
+        if trims_ngrams {
+            let (drops_start, drops_end) = match self.ngram_stop_words {
+                NgramStopWords::DropStart => (true, false),
+                NgramStopWords::DropEnd => (false, true),
+                _ => (true, true),
+            };
+            terms = terms.windows(self.ngram).zip(stops.windows(self.ngram))
+                .filter(|(_, stops)| !((drops_start && stops[0]) || (drops_end && stops[stops.len() - 1])))
+                .map(|(terms, _)| T::join(terms))
+                .collect();
+        } else if self.ngram > 1 {
+            terms = terms.windows(self.ngram).map(T::join).collect();
+        }
+        if let Some(min_frequency) = self.min_frequency {
+            retain_frequent(&mut terms, |term| term.text(), min_frequency);
+        }
+        terms
+    }
+}
+
+/// Number of stems cached per thread before the cache is cleared, bounding its memory on open vocabularies.
+const MAX_CACHED_STEMS: usize = 1 << 16;
+
+thread_local! {
+    // stems of words already stemmed on this thread, `None` for words that can't be stemmed
+    static STEM_CACHE: RefCell<HashMap<String, Option<String>>> = RefCell::new(HashMap::new());
+}
+
+// stems `word` with stem::get, memoized since repetitive corpora stem the same words over and over
+fn cached_stem(word: &str) -> Option<String> {
+    if let Some(stem) = STEM_CACHE.with_borrow(|cache| cache.get(word).cloned()) {
+        return stem;
+    }
+    let stem = stem::get(word).ok();
+    STEM_CACHE.with_borrow_mut(|cache| {
+        if cache.len() >= MAX_CACHED_STEMS {
+            cache.clear();
+        }
+        cache.insert(word.to_string(), stem.clone());
+    });
+    stem
+}
+
+// a token or span that a TokenConfig can be applied to
+trait Term: Sized {
+    fn text(&self) -> &str;
+    fn text_mut(&mut self) -> &mut String;
+    // joins consecutive terms into an n-gram
+    fn join(terms: &[Self]) -> Self;
+}
+
+impl Term for String {
+    fn text(&self) -> &str {
+        self
+    }
+
+    fn text_mut(&mut self) -> &mut String {
+        self
+    }
+
+    fn join(terms: &[Self]) -> Self {
+        terms.join(" ")
+    }
+}
+
+impl Term for TokenSpan {
+    fn text(&self) -> &str {
+        &self.token
+    }
+
+    fn text_mut(&mut self) -> &mut String {
+        &mut self.token
+    }
+
+    fn join(terms: &[Self]) -> Self {
+        let (first, last) = (&terms[0], &terms[terms.len() - 1]);
+        TokenSpan {
+            token: terms.iter().map(|term| term.token.as_str()).collect::<Vec<&str>>().join(" "),
+            start: first.start,
+            end: last.end.max(first.end),
+            char_start: first.char_start,
+            char_end: last.char_end.max(first.char_end),
+        }
+    }
+}
+
+impl Default for TokenConfig {
+    fn default() -> Self {
+        Self::default_with_stop_words(get_stop_words().into())
+    }
+}
+
+/// Builder for [`TokenConfig`], created with [`TokenConfig::builder`]. Options that aren't set
+/// keep their [`TokenConfig::default`] values.
+#[derive(Debug, Clone, Default)]
+pub struct TokenConfigBuilder {
+    config: TokenConfig,
+}
+
+impl TokenConfigBuilder {
+    /// Sets whether tokens are stemmed. Defaults to true.
+    pub fn stem(mut self, stem: bool) -> Self {
+        self.config.stem = stem;
+        self
+    }
+
+    /// Sets whether stop words are removed. Defaults to true.
+    pub fn remove_stop_words(mut self, remove_stop_words: bool) -> Self {
+        self.config.remove_stop_words = remove_stop_words;
+        self
+    }
+
+    /// Sets the stop words to remove. Defaults to [`get_stop_words`].
+    pub fn stop_words(mut self, stop_words: impl Into<StopWords>) -> Self {
+        self.config.stop_words = stop_words.into();
+        self
+    }
+
+    /// Sets how contractions are tokenized. Defaults to [`Contractions::Strip`].
+    pub fn contractions(mut self, contractions: Contractions) -> Self {
+        self.config.contractions = contractions;
+        self
+    }
+
+    /// Sets the Unicode normalization applied to each word. Defaults to none.
+    pub fn normalization(mut self, normalization: Normalization) -> Self {
+        self.config.normalization = Some(normalization);
+        self
+    }
+
+    /// Sets how words are case folded. Defaults to [`CaseFolding::Unicode`].
+    pub fn case_folding(mut self, case_folding: CaseFolding) -> Self {
+        self.config.case_folding = case_folding;
+        self
+    }
+
+    /// Sets whether words are split on whitespace only, keeping their punctuation and case. Defaults to false.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rnltk::token::{self, TokenConfig};
+    ///
+    /// let config = TokenConfig::builder().whitespace_only(true).stem(false).build();
+    ///
+    /// assert_eq!(token::tokenize_sentence_configurable("The C++ API, v2.0", config), vec!["The", "C++", "API,", "v2.0"]);
+    /// ```
+    pub fn whitespace_only(mut self, whitespace_only: bool) -> Self {
+        self.config.whitespace_only = whitespace_only;
+        self
+    }
+
+    /// Sets whether diacritics are removed from each word. Defaults to false.
+    pub fn strip_accents(mut self, strip_accents: bool) -> Self {
+        self.config.strip_accents = strip_accents;
+        self
+    }
+
+    /// Sets the minimum number of characters in a token. Defaults to none.
+    pub fn min_token_length(mut self, min_token_length: usize) -> Self {
+        self.config.min_token_length = Some(min_token_length);
+        self
+    }
+
+    /// Sets the maximum number of characters in a token. Defaults to none.
+    pub fn max_token_length(mut self, max_token_length: usize) -> Self {
+        self.config.max_token_length = Some(max_token_length);
+        self
+    }
+
+    /// Sets whether purely numeric tokens are removed. Defaults to false.
+    pub fn remove_numeric(mut self, remove_numeric: bool) -> Self {
+        self.config.remove_numeric = remove_numeric;
+        self
+    }
+
+    /// Sets the minimum number of occurrences of a token. Defaults to none.
+    pub fn min_frequency(mut self, min_frequency: usize) -> Self {
+        self.config.min_frequency = Some(min_frequency);
+        self
+    }
+
+    /// Sets the number of consecutive tokens joined into each term. Defaults to 1.
+    pub fn ngram(mut self, ngram: usize) -> Self {
+        self.config.ngram = ngram;
+        self
+    }
+
+    /// Sets how stop words are removed from n-grams. Defaults to [`NgramStopWords::Remove`].
+    pub fn ngram_stop_words(mut self, ngram_stop_words: NgramStopWords) -> Self {
+        self.config.ngram_stop_words = ngram_stop_words;
+        self
+    }
+
+    /// Sets how emoji and emoticons are tokenized. Defaults to [`Emoji::Ignore`].
+    pub fn emoji(mut self, emoji: Emoji) -> Self {
+        self.config.emoji = emoji;
+        self
+    }
+
+    /// Sets how hyphenated words are tokenized. Defaults to [`Hyphens::Strip`].
+    pub fn hyphens(mut self, hyphens: Hyphens) -> Self {
+        self.config.hyphens = hyphens;
+        self
+    }
+
+    /// Sets the tokens that are never stemmed nor filtered out, replacing any set before. Defaults to none.
+    /// The tokens are case folded by [`TokenConfigBuilder::build`] like the tokens they protect, so "AAPL"
+    /// protects the token "aapl".
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rnltk::token::{self, TokenConfig};
+    ///
+    /// let text = "Shares of IT and AAPL are rising";
+    /// let config = TokenConfig::builder().min_token_length(3).protected_tokens(["IT", "aapl"]).build();
+    ///
+    /// assert_eq!(token::tokenize_sentence_configurable(text, TokenConfig::default()), vec!["share", "aapl", "rise"]);
+    /// assert_eq!(token::tokenize_sentence_configurable(text, config), vec!["share", "it", "aapl", "rise"]);
+    /// ```
+    pub fn protected_tokens(mut self, protected_tokens: impl IntoIterator<Item = impl ToString>) -> Self {
+        self.config.protected_tokens = protected_tokens.into_iter().map(|token| token.to_string()).collect();
+        self
+    }
+
+    /// Sets how term frequencies are weighted. Defaults to [`TermWeighting::Raw`].
+    pub fn term_weighting(mut self, term_weighting: TermWeighting) -> Self {
+        self.config.term_weighting = term_weighting;
+        self
+    }
+
+    /// Sets the stemmer used when tokens are stemmed. Defaults to [`Stemmer::Porter`].
+    pub fn stemmer(mut self, stemmer: Stemmer) -> Self {
+        self.config.stemmer = stemmer;
+        self
+    }
+
+    /// Sets whether tokens following a negation word get a `_NEG` suffix. Defaults to false.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rnltk::token::{self, TokenConfig};
+    ///
+    /// let config = TokenConfig::builder().negation(true).build();
+    ///
+    /// assert_eq!(token::tokenize_sentence_configurable("The movie was not good at all", config), vec!["movi", "good_NEG"]);
+    /// ```
+    pub fn negation(mut self, negation: bool) -> Self {
+        self.config.negation = negation;
+        self
+    }
+
+    /// Creates the configured [`TokenConfig`].
+    pub fn build(mut self) -> TokenConfig {
+        // whitespace-only tokens keep their case, so protected tokens must too
+        if !self.config.whitespace_only {
+            let case_folding = self.config.case_folding;
+            self.config.protected_tokens = self.config.protected_tokens.iter().map(|token| case_folding.fold(token).into_owned()).collect();
+        }
+        self.config
+    }
+}
+
+/// Tokenizer holding precompiled regexes, so repeated tokenization doesn't recompile them.
+///
+/// The free functions in this module use a shared Tokenizer, so a Tokenizer is mainly useful
+/// to avoid the shared instance, to keep tokenization self-contained in hot loops, or to split
+/// sentences on other delimiters than ".", "!", and "?".
+///
+/// Sentences are stripped of punctuation into a per-thread scratch buffer that is reused across calls,
+/// and token vectors are sized up front, so tokenizing allocates little more than the tokens themselves.
+/// Stems are likewise cached per thread, so each distinct word is only stemmed once.
+/// Use [`Tokenizer::with_capacity`] when sentences are long, so the buffer is sized once rather than
+/// growing with the first long sentence of every thread.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::token::Tokenizer;
+/// 
+/// let tokenizer = Tokenizer::new();
+/// let sentences = tokenizer.tokenize_into_sentences("Why hello there. General Kenobi!");
+/// let tokens: Vec<Vec<String>> = sentences.iter().map(|sentence| tokenizer.tokenize_sentence(sentence)).collect();
+///
+/// assert_eq!(tokens, vec![vec!["why", "hello", "there"], vec!["general", "kenobi"]]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Tokenizer {
+    // terminal punctuation before a closing quote, which doesn't end the sentence
+    quote_regex: Option<Regex>,
+    separator: Regex,
+    // bytes reserved in the scratch buffer before use
+    scratch_capacity: usize,
+}
+
+/// Bytes of scratch buffer kept between calls, so one very long sentence doesn't hold on to its memory.
+const RETAINED_SCRATCH_BYTES: usize = 4096;
+
+thread_local! {
+    // punctuation-stripped sentence being tokenized, reused across calls on the same thread
+    static SCRATCH: RefCell<String> = const { RefCell::new(String::new()) };
+}
+
+impl Default for Tokenizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn shared_tokenizer() -> &'static Tokenizer {
+    static TOKENIZER: OnceLock<Tokenizer> = OnceLock::new();
+    TOKENIZER.get_or_init(Tokenizer::new)
+}
+
+impl Tokenizer {
+    /// Creates new instance of Tokenizer, compiling its regexes.
+    pub fn new() -> Self {
+        Tokenizer {
+            quote_regex: Some(Regex::new(r#"[\.!\?]""#).expect("Invalid regex")),
+            separator: Regex::new(r#"[\.!\?] *"#).expect("Invalid regex"),
+            scratch_capacity: 0,
+        }
+    }
+
+    /// Creates new instance of Tokenizer whose scratch buffer has room for sentences of `capacity` bytes,
+    /// and keeps that room between calls.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rnltk::token::Tokenizer;
+    ///
+    /// let tokenizer = Tokenizer::with_capacity(1 << 16);
+    /// let document = "All work and no play makes Jack a dull boy. ".repeat(1000);
+    ///
+    /// assert_eq!(tokenizer.tokenize_sentence(&document).len(), 10_000);
+    /// ```
+    pub fn with_capacity(capacity: usize) -> Self {
+        Tokenizer {
+            scratch_capacity: capacity,
+            ..Self::new()
+        }
+    }
+
+    /// Sets the characters that end a sentence, replacing ".", "!", and "?", such as "。", "！", and "？" for
+    /// Chinese, ";" for semicolon-delimited logs, or "\n" for newline-delimited subtitles. As with the
+    /// defaults, a delimiter directly before a closing `"` doesn't end the sentence. With no delimiters,
+    /// every document is a single sentence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rnltk::token::Tokenizer;
+    ///
+    /// let tokenizer = Tokenizer::new().sentence_delimiters(&['。', '！', '？']);
+    /// assert_eq!(tokenizer.tokenize_into_sentences("你好。今天天气很好！"), vec!["你好", "今天天气很好"]);
+    ///
+    /// let tokenizer = Tokenizer::new().sentence_delimiters(&['\n']);
+    /// assert_eq!(tokenizer.tokenize_into_sentences("Hello there.\nGeneral Kenobi!\n"), vec!["Hello there.", "General Kenobi!"]);
+    /// ```
+    pub fn sentence_delimiters(mut self, delimiters: &[char]) -> Self {
+        if delimiters.is_empty() {
+            // matches only in an empty document, which has no sentences either way
+            self.separator = Regex::new(r"\z\A").expect("Invalid regex");
+            self.quote_regex = None;
+            return self;
+        }
+        let class: String = delimiters.iter().map(|delimiter| regex::escape(&delimiter.to_string())).collect();
+        self.separator = Regex::new(&format!("[{class}] *")).expect("Invalid regex");
+        self.quote_regex = Some(Regex::new(&format!("[{class}]\"")).expect("Invalid regex"));
+        self
+    }
+
+    /// Sets the regex `pattern` matching the text between sentences, replacing the sentence delimiters,
+    /// for conventions a set of characters can't describe. Matches are removed from the sentences.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RnltkError::InvalidSentencePattern`] if `pattern` isn't a valid regex.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rnltk::token::Tokenizer;
+    ///
+    /// let tokenizer = Tokenizer::new().sentence_pattern(r"\s*\|\|\s*").unwrap();
+    /// assert_eq!(tokenizer.tokenize_into_sentences("Dr. Smith arrived || He left."), vec!["Dr. Smith arrived", "He left."]);
+    /// ```
+    pub fn sentence_pattern(mut self, pattern: &str) -> Result<Self, RnltkError> {
+        self.separator = Regex::new(pattern).map_err(|error| RnltkError::InvalidSentencePattern(error.to_string()))?;
+        self.quote_regex = None;
+        Ok(self)
+    }
+
+    /// Converts a `document` to sentence vector, like [`tokenize_into_sentences`].
+    pub fn tokenize_into_sentences(&self, document: &str) -> Vec<String> {
+        let updated_document: &str = &match &self.quote_regex {
+            Some(quote_regex) => quote_regex.replace_all(document, "\""),
+            None => document.into(),
+        };
+
+        split_sentences(&self.separator, updated_document).map(|sentence| sentence.to_string()).collect()
+    }
+
+    /// Converts `sentence` to token vector, like [`tokenize_sentence`].
+    pub fn tokenize_sentence(&self, sentence: &str) -> Vec<String> {
+        SCRATCH.with_borrow_mut(|scratch| {
+            scratch.clear();
+            scratch.reserve(self.scratch_capacity.max(sentence.len()));
+            ascii::strip_punctuation(sentence, scratch);
+
+            // every token is followed by a space or the end of the sentence, so this never reallocates
+            let mut tokens = Vec::with_capacity(scratch.bytes().filter(|byte| *byte == b' ').count() + 1);
+            tokens.extend(scratch.split(' ').map(str::trim).filter(|token| !token.is_empty()).map(|token| CaseFolding::Unicode.fold(token).into_owned()));
+
+            scratch.clear();
+            scratch.shrink_to(self.scratch_capacity.max(RETAINED_SCRATCH_BYTES));
+            tokens
+        })
+    }
+
+    /// Converts `sentence` to token vector without stop words, like [`tokenize_sentence_without_stop_words`].
+    pub fn tokenize_sentence_without_stop_words(&self, sentence: &str, stop_words: impl Into<StopWords>) -> Vec<String> {
+        self.tokenize_sentence_configurable(sentence, TokenConfig { remove_stop_words: true, stop_words: stop_words.into(), ..TokenConfig::plain() })
+    }
+
+    /// Converts `sentence` to stemmed token vector, like [`tokenize_stemmed_sentence`].
+    pub fn tokenize_stemmed_sentence(&self, sentence: &str) -> Vec<String> {
+        self.tokenize_sentence_configurable(sentence, TokenConfig { stem: true, ..TokenConfig::plain() })
+    }
+
+    /// Converts `sentence` to stemmed token vector without stop words, like [`tokenize_stemmed_sentence_without_stop_words`].
+    pub fn tokenize_stemmed_sentence_without_stop_words(&self, sentence: &str, stop_words: impl Into<StopWords>) -> Vec<String> {
+        self.tokenize_sentence_configurable(sentence, TokenConfig { stem: true, remove_stop_words: true, stop_words: stop_words.into(), ..TokenConfig::plain() })
+    }
+
+    /// Converts `sentence` to token vector split on whitespace only, like [`tokenize_whitespace`].
+    pub fn tokenize_whitespace(&self, sentence: &str) -> Vec<String> {
+        sentence.split_whitespace().map(String::from).collect()
+    }
+
+    /// Tokenize sentence based on a given configuration, like [`tokenize_sentence_configurable`].
+    pub fn tokenize_sentence_configurable(&self, sentence: &str, config: TokenConfig) -> Vec<String> {
+        let words = if config.whitespace_only {
+            self.tokenize_whitespace(sentence)
+        } else if config.needs_spans() {
+            rewritten_spans(sentence, &config).into_iter().map(|span| span.token).collect()
+        } else {
+            self.tokenize_sentence(sentence)
+        };
+        config.apply(words)
+    }
+
+    /// Converts `document` to tokens grouped by sentence, like [`tokenize_document`].
+    pub fn tokenize_document(&self, document: &str, config: TokenConfig) -> SentenceTokens {
+        let sentences = self.tokenize_into_sentences(document);
+        // minimum frequency is counted across the whole document rather than within each sentence
+        let sentence_config = TokenConfig { min_frequency: None, ..config.clone() };
+        let mut tokens: Vec<Vec<String>> = sentences.iter()
+            .map(|sentence| self.tokenize_sentence_configurable(sentence, sentence_config.clone()))
+            .collect();
+        if let Some(min_frequency) = config.min_frequency {
+            let totals = count_terms(tokens.iter().flatten());
+            for sentence_tokens in &mut tokens {
+                sentence_tokens.retain(|token| totals[token] >= min_frequency as u64);
+            }
+        }
+        SentenceTokens { sentences, tokens }
+    }
+
+    /// Gets a count of all words from a `sentence`, like [`get_term_frequencies_from_sentence`].
+    pub fn get_term_frequencies_from_sentence(&self, sentence: &str) -> BTreeMap<String, f64> {
+        self.get_term_frequencies_from_sentence_configurable(sentence, TokenConfig::plain())
+    }
+
+    /// Gets a count of all words from a `sentence` without stop words, like [`get_term_frequencies_from_sentence_without_stop_words`].
+    pub fn get_term_frequencies_from_sentence_without_stop_words(&self, sentence: &str, stop_words: impl Into<StopWords>) -> BTreeMap<String, f64> {
+        self.get_term_frequencies_from_sentence_configurable(sentence, TokenConfig { remove_stop_words: true, stop_words: stop_words.into(), ..TokenConfig::plain() })
+    }
+
+    /// Gets a count of all stemmed words from a `sentence`, like [`get_stemmed_term_frequencies_from_sentence`].
+    pub fn get_stemmed_term_frequencies_from_sentence(&self, sentence: &str) -> BTreeMap<String, f64> {
+        self.get_term_frequencies_from_sentence_configurable(sentence, TokenConfig { stem: true, ..TokenConfig::plain() })
+    }
+
+    /// Gets a count of all stemmed words from a `sentence` without stop words, like [`get_stemmed_term_frequencies_from_sentence_without_stop_words`].
+    pub fn get_stemmed_term_frequencies_from_sentence_without_stop_words(&self, sentence: &str, stop_words: impl Into<StopWords>) -> BTreeMap<String, f64> {
+        self.get_term_frequencies_from_sentence_configurable(sentence, TokenConfig { stem: true, remove_stop_words: true, stop_words: stop_words.into(), ..TokenConfig::plain() })
+    }
+
+    /// Gets a count of all words from a `sentence` based on a given configuration, like [`get_term_frequencies_from_sentence_configurable`].
+    pub fn get_term_frequencies_from_sentence_configurable(&self, sentence: &str, config: TokenConfig) -> BTreeMap<String, f64> {
+        let term_weighting = config.term_weighting;
+        counts_to_weighted_frequencies(&self.get_term_counts_from_sentence_configurable(sentence, config), term_weighting)
+    }
+
+    /// Gets integer counts of all words from a `sentence`, like [`get_term_counts_from_sentence`].
+    pub fn get_term_counts_from_sentence(&self, sentence: &str) -> BTreeMap<String, u64> {
+        self.get_term_counts_from_sentence_configurable(sentence, TokenConfig::plain())
+    }
+
+    /// Gets integer counts of all words from a `sentence` based on a given configuration, like [`get_term_counts_from_sentence_configurable`].
+    pub fn get_term_counts_from_sentence_configurable(&self, sentence: &str, config: TokenConfig) -> BTreeMap<String, u64> {
+        stage_span!("tokenize_document", bytes = sentence.len());
+        count_terms(self.tokenize_sentence_configurable(sentence, config))
+    }
+
+    /// Gets aligned counts of all words from `sentences`, like [`get_term_frequencies_from_sentences`].
+    pub fn get_term_frequencies_from_sentences(&self, sentences: &[&str]) -> Vec<BTreeMap<String, f64>> {
+        self.get_term_frequencies_from_sentences_configurable(sentences, TokenConfig::plain())
+    }
+
+    /// Gets aligned counts of all words from `sentences` without stop words, like [`get_term_frequencies_from_sentences_without_stop_words`].
+    pub fn get_term_frequencies_from_sentences_without_stop_words(&self, sentences: &[&str], stop_words: impl Into<StopWords>) -> Vec<BTreeMap<String, f64>> {
+        self.get_term_frequencies_from_sentences_configurable(sentences, TokenConfig { remove_stop_words: true, stop_words: stop_words.into(), ..TokenConfig::plain() })
+    }
+
+    /// Gets aligned counts of all stemmed words from `sentences`, like [`get_stemmed_term_frequencies_from_sentences`].
+    pub fn get_stemmed_term_frequencies_from_sentences(&self, sentences: &[&str]) -> Vec<BTreeMap<String, f64>> {
+        self.get_term_frequencies_from_sentences_configurable(sentences, TokenConfig { stem: true, ..TokenConfig::plain() })
+    }
+
+    /// Gets aligned counts of all stemmed words from `sentences` without stop words, like [`get_stemmed_term_frequencies_from_sentences_without_stop_words`].
+    pub fn get_stemmed_term_frequencies_from_sentences_without_stop_words(&self, sentences: &[&str], stop_words: impl Into<StopWords>) -> Vec<BTreeMap<String, f64>> {
+        self.get_term_frequencies_from_sentences_configurable(sentences, TokenConfig { stem: true, remove_stop_words: true, stop_words: stop_words.into(), ..TokenConfig::plain() })
+    }
+
+    /// Gets aligned counts of all words from `sentences` based on a given configuration, like [`get_term_frequencies_from_sentences_configurable`].
+    pub fn get_term_frequencies_from_sentences_configurable(&self, sentences: &[&str], config: TokenConfig) -> Vec<BTreeMap<String, f64>> {
+        let term_weighting = config.term_weighting;
+        self.get_term_counts_from_sentences_configurable(sentences, config).iter()
+            .map(|term_counts| counts_to_weighted_frequencies(term_counts, term_weighting))
+            .collect()
+    }
+
+    /// Gets aligned integer counts of all words from `sentences`, like [`get_term_counts_from_sentences`].
+    pub fn get_term_counts_from_sentences(&self, sentences: &[&str]) -> Vec<BTreeMap<String, u64>> {
+        self.get_term_counts_from_sentences_configurable(sentences, TokenConfig::plain())
+    }
+
+    /// Gets aligned integer counts of all words from `sentences` based on a given configuration, like [`get_term_counts_from_sentences_configurable`].
+    pub fn get_term_counts_from_sentences_configurable(&self, sentences: &[&str], config: TokenConfig) -> Vec<BTreeMap<String, u64>> {
+        align_term_counts(self.get_unaligned_term_counts(sentences, config))
+    }
+
+    /// Gets sparse counts of all words from `sentences`, like [`get_sparse_term_frequencies_from_sentences`].
+    pub fn get_sparse_term_frequencies_from_sentences(&self, sentences: &[&str]) -> SparseTermFrequencies {
+        self.get_sparse_term_frequencies_from_sentences_configurable(sentences, TokenConfig::plain())
+    }
+
+    /// Gets sparse counts of all words from `sentences` based on a given configuration, like
+    /// [`get_sparse_term_frequencies_from_sentences_configurable`].
+    pub fn get_sparse_term_frequencies_from_sentences_configurable(&self, sentences: &[&str], config: TokenConfig) -> SparseTermFrequencies {
+        let term_weighting = config.term_weighting;
+        let term_counts: Vec<BTreeMap<String, f64>> = self.get_unaligned_term_counts(sentences, config).iter()
+            .map(|term_counts| counts_to_weighted_frequencies(term_counts, term_weighting))
+            .collect();
+        SparseTermFrequencies::from_term_counts(&term_counts)
+    }
+
+    /// Gets the number of `documents` each word appears in, like [`get_document_frequencies`].
+    pub fn get_document_frequencies(&self, documents: &[&str]) -> BTreeMap<String, u64> {
+        self.get_document_frequencies_configurable(documents, TokenConfig::plain())
+    }
+
+    /// Gets the number of `documents` each word appears in based on a given configuration, like
+    /// [`get_document_frequencies_configurable`].
+    pub fn get_document_frequencies_configurable(&self, documents: &[&str], config: TokenConfig) -> BTreeMap<String, u64> {
+        let mut document_frequencies: BTreeMap<String, u64> = BTreeMap::new();
+        for term in self.get_unaligned_term_counts(documents, config).into_iter().flat_map(BTreeMap::into_keys) {
+            *document_frequencies.entry(term).or_insert(0) += 1;
+        }
+        document_frequencies
+    }
+
+    // counts of every sentence, holding only the terms of that sentence
+    fn get_unaligned_term_counts(&self, sentences: &[&str], config: TokenConfig) -> Vec<BTreeMap<String, u64>> {
+        stage_span!("tokenize_batch", sentences = sentences.len());
+        // minimum frequency is counted across all sentences rather than within each one
+        let sentence_config = TokenConfig { min_frequency: None, ..config.clone() };
+        let mut term_counts: Vec<BTreeMap<String, u64>> = sentences.iter()
+            .map(|sentence| self.get_term_counts_from_sentence_configurable(sentence, sentence_config.clone()))
+            .collect();
+        if let Some(min_frequency) = config.min_frequency {
+            let mut totals: BTreeMap<String, u64> = BTreeMap::new();
+            for (term, count) in term_counts.iter().flatten() {
+                *totals.entry(term.to_string()).or_insert(0) += count;
+            }
+            for counts in &mut term_counts {
+                counts.retain(|term, _| totals[term] >= min_frequency as u64);
+            }
+        }
+        term_counts
+    }
+}
+
+// counts every occurrence of each term
+fn count_terms<S: AsRef<str>>(terms: impl IntoIterator<Item = S>) -> BTreeMap<String, u64> {
+    let mut term_counts: BTreeMap<String, u64> = BTreeMap::new();
+    for term in terms {
+        *term_counts.entry(term.as_ref().to_string()).or_insert(0) += 1;
+    }
+    term_counts
+}
+
+// inserts a count of 0 for every term missing from a document, so every document has the same keys
+fn align_term_counts(mut term_counts: Vec<BTreeMap<String, u64>>) -> Vec<BTreeMap<String, u64>> {
+    let total_terms: BTreeSet<String> = term_counts.iter().flat_map(|counts| counts.keys().cloned()).collect();
+    for counts in &mut term_counts {
+        for term in &total_terms {
+            if !counts.contains_key(term) {
+                counts.insert(term.to_string(), 0);
+            }
+        }
+    }
+    term_counts
+}
+
+/// Converts integer `term_counts`, like those from [`get_term_counts_from_sentence`], to the floating point
+/// term frequencies used by the rest of the crate.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::BTreeMap;
+/// use rnltk::token;
+/// 
+/// let term_counts = token::get_term_counts_from_sentence("anger leads to hatred, hatred leads to conflict");
+///
+/// assert_eq!(term_counts["hatred"], 2);
+/// assert_eq!(token::counts_to_frequencies(&term_counts), token::get_term_frequencies_from_sentence("anger leads to hatred, hatred leads to conflict"));
+/// ```
+pub fn counts_to_frequencies(term_counts: &BTreeMap<String, u64>) -> BTreeMap<String, f64> {
+    term_counts.iter().map(|(term, count)| (term.to_string(), *count as f64)).collect()
+}
+
+/// Converts integer `term_counts` of a single document to term frequencies weighted by `term_weighting`.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::token::{self, TermWeighting};
+/// 
+/// let term_counts = token::get_term_counts_from_sentence("anger leads to hatred, hatred leads to conflict");
+/// let relative = token::counts_to_weighted_frequencies(&term_counts, TermWeighting::Relative);
+///
+/// assert_eq!(relative["hatred"], 0.25);
+/// assert_eq!(token::counts_to_weighted_frequencies(&term_counts, TermWeighting::Raw), token::counts_to_frequencies(&term_counts));
+/// ```
+pub fn counts_to_weighted_frequencies(term_counts: &BTreeMap<String, u64>, term_weighting: TermWeighting) -> BTreeMap<String, f64> {
+    let total: u64 = term_counts.values().sum();
+    term_counts.iter().map(|(term, count)| {
+        let count = *count as f64;
+        let weight = match term_weighting {
+            TermWeighting::Raw => count,
+            TermWeighting::Relative => if total > 0 { count / total as f64 } else { 0. },
+            TermWeighting::Log => if count > 0. { 1. + count.ln() } else { 0. },
+        };
+        (term.to_string(), weight)
+    }).collect()
+}
+
+/// Frequency distribution of tokens, like NLTK's `FreqDist`, for exploring the counts of a corpus.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::token::{self, FreqDist};
+/// 
+/// let mut freq_dist: FreqDist = token::tokenize_sentence("fear leads to anger, anger leads to hatred").into_iter().collect();
+/// freq_dist.merge(&token::tokenize_sentence("hatred leads to suffering").into_iter().collect());
+///
+/// assert_eq!((freq_dist.n(), freq_dist.b()), (12, 6));
+/// assert_eq!(freq_dist.most_common(2), vec![("leads".to_string(), 3), ("to".to_string(), 3)]);
+/// assert_eq!(freq_dist.hapaxes(), vec!["fear", "suffering"]);
+/// assert_eq!(freq_dist.freq("anger"), 2. / 12.);
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct FreqDist {
+    counts: BTreeMap<String, u64>,
+    total: u64,
+}
+
+impl FreqDist {
+    /// Creates new instance of FreqDist with no tokens.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates new instance of FreqDist from interned `symbols`, counting them by id and copying each
+    /// distinct token out of `interner` once. Symbols must come from `interner`: only those beyond its last
+    /// string are skipped, so a symbol from another interner is otherwise counted as whichever token has its index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rnltk::token::{self, FreqDist};
+    /// use rnltk::token::intern::Interner;
+    ///
+    /// let mut interner = Interner::new();
+    /// let symbols = interner.intern_tokens(token::tokenize_sentence("fear leads to anger, anger leads to hatred"));
+    /// let freq_dist = FreqDist::from_symbols(symbols, &interner);
+    ///
+    /// assert_eq!(freq_dist.count("anger"), 2);
+    /// assert_eq!(freq_dist, token::tokenize_sentence("fear leads to anger, anger leads to hatred").into_iter().collect());
+    /// ```
+    pub fn from_symbols(symbols: impl IntoIterator<Item = Symbol>, interner: &Interner) -> Self {
+        let mut counts = vec![0; interner.len()];
+        for symbol in symbols {
+            if let Some(count) = counts.get_mut(symbol.index()) {
+                *count += 1;
+            }
+        }
+        interner.iter().zip(counts).map(|((_, token), count)| (token.to_string(), count)).collect::<BTreeMap<String, u64>>().into()
+    }
+
+    /// Adds `count` occurrences of `token`.
+    pub fn add(&mut self, token: impl ToString, count: u64) {
+        if count == 0 {
+            return;
+        }
+        *self.counts.entry(token.to_string()).or_insert(0) += count;
+        self.total += count;
+    }
+
+    /// Adds the counts of `other` to this distribution.
+    pub fn merge(&mut self, other: &FreqDist) {
+        for (token, count) in &other.counts {
+            self.add(token, *count);
+        }
+    }
+
+    /// Gets the number of occurrences of `token`.
+    pub fn count(&self, token: &str) -> u64 {
+        self.counts.get(token).copied().unwrap_or_default()
+    }
+
+    /// Gets the share of all occurrences that are `token`, or 0 if the distribution is empty.
+    pub fn freq(&self, token: &str) -> f64 {
+        if self.total == 0 {
+            return 0.;
+        }
+        self.count(token) as f64 / self.total as f64
+    }
+
+    /// Gets the total number of occurrences of all tokens, like NLTK's `N()`.
+    pub fn n(&self) -> u64 {
+        self.total
+    }
+
+    /// Gets the number of distinct tokens, like NLTK's `B()`.
+    pub fn b(&self) -> usize {
+        self.counts.len()
+    }
+
+    /// Checks if the distribution has no tokens.
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// Gets the `n` most common tokens with their counts, in descending order of count.
+    /// Tokens with the same count are in alphabetical order.
+    pub fn most_common(&self, n: usize) -> Vec<(String, u64)> {
+        let mut counts: Vec<(&String, &u64)> = self.counts.iter().collect();
+        // stable sort keeps ties in alphabetical order
+        counts.sort_by(|first, second| second.1.cmp(first.1));
+        counts.into_iter().take(n).map(|(token, count)| (token.to_string(), *count)).collect()
+    }
+
+    /// Gets the tokens that occur exactly once, in alphabetical order.
+    pub fn hapaxes(&self) -> Vec<&str> {
+        self.counts.iter().filter(|(_, count)| **count == 1).map(|(token, _)| token.as_str()).collect()
+    }
+
+    /// Gets the count of every token.
+    pub fn get_counts(&self) -> &BTreeMap<String, u64> {
+        &self.counts
+    }
+}
+
+impl<S: AsRef<str>> FromIterator<S> for FreqDist {
+    fn from_iter<I: IntoIterator<Item = S>>(tokens: I) -> Self {
+        let mut freq_dist = FreqDist::new();
+        freq_dist.extend(tokens);
+        freq_dist
+    }
+}
+
+impl<S: AsRef<str>> Extend<S> for FreqDist {
+    fn extend<I: IntoIterator<Item = S>>(&mut self, tokens: I) {
+        for token in tokens {
+            self.add(token.as_ref(), 1);
+        }
+    }
+}
+
+/// Frequency distributions of tokens grouped by a condition, such as a document id, author, or genre,
+/// like NLTK's `ConditionalFreqDist`, for comparing word use between groups.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::token::{self, ConditionalFreqDist};
+/// 
+/// let mut by_genre = ConditionalFreqDist::new();
+/// by_genre.add_tokens("news", token::tokenize_sentence("the market fell as the rates rose"));
+/// by_genre.add_tokens("romance", token::tokenize_sentence("the rain fell as she rose to leave"));
+/// let table = by_genre.tabulate(&["the", "rates", "she"]);
+///
+/// assert_eq!(by_genre.get(&"news").unwrap().count("the"), 2);
+/// assert_eq!(by_genre.conditions(), vec![&"news", &"romance"]);
+/// assert_eq!(format!("{:.0}", table), "           the  rates    she\nnews         2      1      0\nromance      1      0      1\n");
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ConditionalFreqDist<C: Ord> {
+    distributions: BTreeMap<C, FreqDist>,
+}
+
+impl<C: Ord> Default for ConditionalFreqDist<C> {
+    fn default() -> Self {
+        ConditionalFreqDist { distributions: BTreeMap::new() }
+    }
+}
+
+impl<C: Ord> ConditionalFreqDist<C> {
+    /// Creates new instance of ConditionalFreqDist with no conditions.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `count` occurrences of `token` under `condition`.
+    pub fn add(&mut self, condition: C, token: impl ToString, count: u64) {
+        self.distributions.entry(condition).or_default().add(token, count);
+    }
+
+    /// Adds one occurrence of each of `tokens` under `condition`.
+    pub fn add_tokens<S: AsRef<str>>(&mut self, condition: C, tokens: impl IntoIterator<Item = S>) {
+        self.distributions.entry(condition).or_default().extend(tokens);
+    }
+
+    /// Adds the counts of every condition of `other` to this distribution.
+    pub fn merge(&mut self, other: &ConditionalFreqDist<C>) where C: Clone {
+        for (condition, freq_dist) in &other.distributions {
+            self.distributions.entry(condition.clone()).or_default().merge(freq_dist);
+        }
+    }
+
+    /// Gets the frequency distribution of `condition`, if it has any tokens.
+    pub fn get(&self, condition: &C) -> Option<&FreqDist> {
+        self.distributions.get(condition)
+    }
+
+    /// Gets the conditions, in sorted order.
+    pub fn conditions(&self) -> Vec<&C> {
+        self.distributions.keys().collect()
+    }
+
+    /// Gets the total number of occurrences of all tokens under all conditions.
+    pub fn n(&self) -> u64 {
+        self.distributions.values().map(FreqDist::n).sum()
+    }
+
+    /// Gets the frequency distribution of all conditions combined.
+    pub fn combined(&self) -> FreqDist {
+        let mut combined = FreqDist::new();
+        for freq_dist in self.distributions.values() {
+            combined.merge(freq_dist);
+        }
+        combined
+    }
+
+    /// Tabulates the counts of `samples` with a row for every condition and a column for every sample.
+    /// If `samples` is empty, every token is a column, in descending order of its combined count.
+    ///
+    /// The table prints like any [`LabeledMatrix`]; use `format!("{:.0}", table)` to print whole counts.
+    pub fn tabulate<S: AsRef<str>>(&self, samples: &[S]) -> LabeledMatrix where C: ToString {
+        let samples: Vec<String> = if samples.is_empty() {
+            self.combined().most_common(usize::MAX).into_iter().map(|(token, _)| token).collect()
+        } else {
+            samples.iter().map(|sample| sample.as_ref().to_string()).collect()
+        };
+        let freq_dists: Vec<&FreqDist> = self.distributions.values().collect();
+        let table = GenericMatrix::from_fn(freq_dists.len(), samples.len(), |row, column| freq_dists[row].count(&samples[column]) as f64);
+        let conditions: Vec<String> = self.distributions.keys().map(C::to_string).collect();
+        LabeledMatrix::new(table, conditions, samples).expect("labels match the table")
+    }
+}
+
+impl<C: Ord, S: AsRef<str>> FromIterator<(C, S)> for ConditionalFreqDist<C> {
+    fn from_iter<I: IntoIterator<Item = (C, S)>>(pairs: I) -> Self {
+        let mut conditional_freq_dist = ConditionalFreqDist::new();
+        for (condition, token) in pairs {
+            conditional_freq_dist.add(condition, token.as_ref(), 1);
+        }
+        conditional_freq_dist
+    }
+}
+
+// tokens counted 0 times, as in aligned term counts, aren't part of the distribution
+impl From<BTreeMap<String, u64>> for FreqDist {
+    fn from(mut counts: BTreeMap<String, u64>) -> Self {
+        counts.retain(|_, count| *count > 0);
+        let total = counts.values().sum();
+        FreqDist { counts, total }
+    }
+}
+
+/// Vocabulary assigning integer ids to terms, for encoding tokens as numbers.
+///
+/// Terms get ids from 0 in the order they are first added, so ids are stable while terms are added. Once
+/// frozen, the vocabulary no longer grows, and terms it doesn't know (out-of-vocabulary terms) are encoded
+/// as the unknown token, if there is one, or left out.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::token::{self, Vocabulary};
+///
+/// let mut vocabulary = Vocabulary::with_unknown_token("[UNK]");
+/// vocabulary.add_tokens(token::tokenize_sentence("fear leads to anger, anger leads to hatred"));
+/// vocabulary.prune(2);
+/// vocabulary.freeze();
+///
+/// assert_eq!(vocabulary.get_id("leads"), Some(1));
+/// assert_eq!(vocabulary.encode(token::tokenize_sentence("hatred leads to anger")), vec![0, 1, 2, 3]);
+/// assert_eq!(vocabulary.decode(&[3, 1, 0]), vec!["anger", "leads", "[UNK]"]);
+/// ```
+///
+/// Deserializing fails unless the terms are unique, every term has a count, and the unknown token's id is one of the terms.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(try_from = "UncheckedVocabulary")]
+pub struct Vocabulary {
+    // symbols of the interner are the term ids
+    terms: Interner,
+    counts: Vec<u64>,
+    unknown_id: Option<u32>,
+    frozen: bool,
+}
+
+// Vocabulary as deserialized, before its terms and counts are checked
+#[derive(Deserialize)]
+struct UncheckedVocabulary {
+    terms: Vec<String>,
+    counts: Vec<u64>,
+    unknown_id: Option<u32>,
+    frozen: bool,
+}
+
+impl TryFrom<UncheckedVocabulary> for Vocabulary {
+    type Error = RnltkError;
+
+    fn try_from(value: UncheckedVocabulary) -> Result<Self, Self::Error> {
+        let num_terms = value.terms.len();
+        // interning drops duplicate terms, which would shift the ids of the terms after them
+        let terms = Interner::from(value.terms);
+        if terms.len() != num_terms {
+            return Err(RnltkError::VocabularyFormat("terms must be unique".to_string()));
+        }
+        if value.counts.len() != num_terms {
+            return Err(RnltkError::VocabularyFormat(format!("{} terms but {} counts", num_terms, value.counts.len())));
+        }
+        if let Some(unknown_id) = value.unknown_id.filter(|unknown_id| *unknown_id as usize >= num_terms) {
+            return Err(RnltkError::VocabularyFormat(format!("unknown token id {unknown_id} is out of range for {num_terms} terms")));
+        }
+        Ok(Vocabulary {
+            terms,
+            counts: value.counts,
+            unknown_id: value.unknown_id,
+            frozen: value.frozen,
+        })
+    }
+}
+
+impl Vocabulary {
+    /// Creates new instance of Vocabulary without any terms, leaving out-of-vocabulary terms out when encoding.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates new instance of Vocabulary with `unknown_token` as id 0, used to encode out-of-vocabulary terms.
+    pub fn with_unknown_token(unknown_token: &str) -> Self {
+        let mut vocabulary = Self::new();
+        vocabulary.unknown_id = Some(vocabulary.add(unknown_token).expect("New vocabularies aren't frozen"));
+        vocabulary.counts[0] = 0;
+        vocabulary
+    }
+
+    /// Adds an occurrence of `term`, getting its id. Frozen vocabularies only count terms they already know,
+    /// getting the unknown token's id, if any, for other terms.
+    pub fn add(&mut self, term: &str) -> Option<u32> {
+        let symbol = if self.frozen { self.terms.get(term) } else { Some(self.terms.get_or_intern(term)) };
+        match symbol {
+            Some(symbol) => {
+                if symbol.index() == self.counts.len() {
+                    self.counts.push(0);
+                }
+                self.counts[symbol.index()] += 1;
+                Some(symbol.0)
+            },
+            None => self.unknown_id,
+        }
+    }
+
+    /// Adds an occurrence of every term of `tokens`, as with [`Vocabulary::add`].
+    pub fn add_tokens<S: AsRef<str>>(&mut self, tokens: impl IntoIterator<Item = S>) {
+        for token in tokens {
+            self.add(token.as_ref());
+        }
+    }
+
+    /// Freezes the vocabulary, so new terms are no longer added.
+    pub fn freeze(&mut self) {
+        self.frozen = true;
+    }
+
+    /// Checks if the vocabulary is frozen.
+    pub fn is_frozen(&self) -> bool {
+        self.frozen
+    }
+
+    /// Removes every term added fewer than `min_frequency` times, other than the unknown token. Remaining
+    /// terms are renumbered in their existing order, so ids from before pruning are no longer valid.
+    pub fn prune(&mut self, min_frequency: u64) {
+        let unknown_id = self.unknown_id;
+        let mut terms = Interner::new();
+        let mut counts = vec![];
+        for ((symbol, term), count) in self.terms.iter().zip(&self.counts) {
+            let is_unknown = Some(symbol.0) == unknown_id;
+            if is_unknown || *count >= min_frequency {
+                let new_symbol = terms.get_or_intern(term);
+                counts.push(*count);
+                if is_unknown {
+                    self.unknown_id = Some(new_symbol.0);
+                }
+            }
+        }
+        self.terms = terms;
+        self.counts = counts;
+    }
+
+    /// Gets the id of `term`, if it's in the vocabulary.
+    pub fn get_id(&self, term: &str) -> Option<u32> {
+        self.terms.get(term).map(|symbol| symbol.0)
+    }
+
+    /// Gets the term with the given `id`, if there is one.
+    pub fn get_term(&self, id: u32) -> Option<&str> {
+        self.terms.resolve(Symbol(id))
+    }
+
+    /// Gets the number of times `term` was added, or 0 if it isn't in the vocabulary.
+    pub fn get_count(&self, term: &str) -> u64 {
+        self.terms.get(term).map_or(0, |symbol| self.counts[symbol.index()])
+    }
+
+    /// Gets the id of the unknown token, if there is one.
+    pub fn get_unknown_id(&self) -> Option<u32> {
+        self.unknown_id
+    }
+
+    /// Gets the number of terms, including the unknown token.
+    pub fn len(&self) -> usize {
+        self.terms.len()
+    }
+
+    /// Checks if there are no terms.
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// Gets every term with its id, in id order.
+    pub fn iter(&self) -> impl Iterator<Item = (u32, &str)> {
+        self.terms.iter().map(|(symbol, term)| (symbol.0, term))
+    }
+
+    /// Encodes `tokens` as their ids without changing the vocabulary. Out-of-vocabulary terms become the
+    /// unknown token's id, or are left out if there is no unknown token.
+    pub fn encode<S: AsRef<str>>(&self, tokens: impl IntoIterator<Item = S>) -> Vec<u32> {
+        tokens.into_iter().filter_map(|token| self.get_id(token.as_ref()).or(self.unknown_id)).collect()
+    }
+
+    /// Decodes `ids` back to their terms, leaving out ids that aren't in the vocabulary.
+    pub fn decode(&self, ids: &[u32]) -> Vec<&str> {
+        ids.iter().filter_map(|id| self.get_term(*id)).collect()
+    }
+
+    pub(crate) fn get_interner(&self) -> &Interner {
+        &self.terms
+    }
+}
+
+/// Converts a `document` to sentence vector. Terminal punctuation is dropped; use
+/// [`tokenize_into_sentences_with_spans`] to keep it along with each sentence's offsets.
+///
+/// Periods in decimal and version numbers ("3.5", "v1.2.3") and after initials ("J. R. R. Tolkien", "U.S.")
+/// don't end a sentence, and neither does an ellipsis followed by a word that isn't capitalized, as long as the
+/// sentence goes on in the same line.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::token;
+/// 
+/// let text = "Why hello there. General Kenobi!";
+/// let tokens = vec!["Why hello there", "General Kenobi"];
+/// let tokenized_text = token::tokenize_into_sentences(text);
+///
+/// assert_eq!(tokens, tokenized_text);
+///
+/// let text = "Wait... what happened at 3.5 seconds? Nobody knows.";
+///
+/// assert_eq!(token::tokenize_into_sentences(text), vec!["Wait... what happened at 3.5 seconds", "Nobody knows"]);
+/// ```
+pub fn tokenize_into_sentences(document: &str) -> Vec<String> {
+    shared_tokenizer().tokenize_into_sentences(document)
+}
+
+/// Converts a `document` to sentence vector, keeping the terminal punctuation of every sentence.
+///
+/// Sentences are split as in [`tokenize_into_sentences`], but each keeps its original text, trimmed of
+/// surrounding whitespace, up to and including its full run of terminal punctuation such as "?!" or "...".
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::token;
+/// 
+/// let text = "Why hello there. General Kenobi! Really?! Wait...";
+/// let sentences = token::tokenize_into_sentences_with_delimiters(text);
+///
+/// assert_eq!(sentences, vec!["Why hello there.", "General Kenobi!", "Really?!", "Wait..."]);
+/// ```
+pub fn tokenize_into_sentences_with_delimiters(document: &str) -> Vec<String> {
+    tokenize_into_sentences_with_spans(document).into_iter().map(|span| span.text).collect()
+}
+
+/// A sentence together with its location in the original document.
+///
+/// `start` and `end` are byte offsets, so `&document[span.start..span.end]` is `text`. `terminator` is the
+/// run of terminal punctuation ending the sentence, such as "?" or "...", or `None` if the sentence ended
+/// without any, as the last sentence of a document can.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SentenceSpan {
+    pub text: String,
+    pub start: usize,
+    pub end: usize,
+    pub terminator: Option<String>,
+}
+
+/// Converts a `document` to a vector of [`SentenceSpan`]s, whose texts match
+/// [`tokenize_into_sentences_with_delimiters`].
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::token;
+/// 
+/// let text = "Why hello there. General Kenobi! Really?! Wait";
+/// let spans = token::tokenize_into_sentences_with_spans(text);
+/// let terminators: Vec<Option<&str>> = spans.iter().map(|span| span.terminator.as_deref()).collect();
+///
+/// assert_eq!(terminators, vec![Some("."), Some("!"), Some("?!"), None]);
+/// assert_eq!(&text[spans[1].start..spans[1].end], "General Kenobi!");
+/// ```
+pub fn tokenize_into_sentences_with_spans(document: &str) -> Vec<SentenceSpan> {
+    let bounds = sentence_bounds(document);
+    let mut spans = vec![];
+    for (index, (start, mut end)) in bounds.iter().copied().enumerate() {
+        let limit = bounds.get(index + 1).map_or(document.len(), |(next_start, _)| *next_start);
+        while end < limit && document[end..].starts_with(['.', '!', '?']) {
+            end += 1;
+        }
+        let untrimmed = &document[start..end];
+        let text = untrimmed.trim();
+        if text.is_empty() {
+            continue;
+        }
+        let start = start + (untrimmed.len() - untrimmed.trim_start().len());
+        let body = text.trim_end_matches(['.', '!', '?']);
+        spans.push(SentenceSpan {
+            text: text.to_string(),
+            start,
+            end: start + text.len(),
+            terminator: (body.len() < text.len()).then(|| text[body.len()..].to_string()),
+        });
+    }
+    spans
+}
+
+/// Converts a `document` to paragraph vector.
+///
+/// A paragraph ends at a blank line, or before a line indented further than the line above it,
+/// as with first-line indented paragraphs. The lines of a paragraph are trimmed and joined with single spaces,
+/// so every paragraph can be split further with [`tokenize_into_sentences`].
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::token;
+/// 
+/// let text = "Why hello there.\nGeneral Kenobi!\n\n  You are a bold one.\n  Kill him!\n    Back away.";
+/// let paragraphs = token::tokenize_into_paragraphs(text);
+///
+/// assert_eq!(paragraphs, vec!["Why hello there. General Kenobi!", "You are a bold one. Kill him!", "Back away."]);
+///
+/// let sentences: Vec<Vec<String>> = paragraphs.iter().map(|paragraph| token::tokenize_into_sentences(paragraph)).collect();
+///
+/// assert_eq!(sentences[0], vec!["Why hello there", "General Kenobi"]);
+/// ```
+pub fn tokenize_into_paragraphs(document: &str) -> Vec<String> {
+    let mut paragraphs = vec![];
+    let mut lines: Vec<&str> = vec![];
+    let mut previous_indent = 0;
+    for line in document.lines() {
+        let trimmed = line.trim();
+        let indent = line.len() - line.trim_start().len();
+        if (trimmed.is_empty() || indent > previous_indent) && !lines.is_empty() {
+            paragraphs.push(lines.join(" "));
+            lines.clear();
+        }
+        if !trimmed.is_empty() {
+            lines.push(trimmed);
+            previous_indent = indent;
+        }
+    }
+    if !lines.is_empty() {
+        paragraphs.push(lines.join(" "));
+    }
+    paragraphs
+}
+
+/// Iterator over the sentences of a document read lazily from `reader`, yielding the same sentences
+/// as [`tokenize_into_sentences`] without loading the whole document into memory.
+///
+/// The reader is consumed a line at a time, and only the text since the last sentence boundary is kept.
+/// Iteration stops after the first read error, such as invalid UTF-8.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Cursor;
+/// use rnltk::token::SentenceIter;
+/// 
+/// let reader = Cursor::new("Why hello there.\nGeneral Kenobi!");
+/// let sentences: Vec<String> = SentenceIter::new(reader).map(|sentence| sentence.unwrap()).collect();
+///
+/// assert_eq!(sentences, vec!["Why hello there", "\nGeneral Kenobi"]);
+/// ```
+#[derive(Debug)]
+pub struct SentenceIter<R: BufRead> {
+    reader: R,
+    buffer: String,
+    pending: VecDeque<String>,
+    quote_regex: Option<Regex>,
+    separator: Regex,
+    finished: bool,
+}
+
+impl<R: BufRead> SentenceIter<R> {
+    /// Creates new instance of SentenceIter reading from `reader`.
+    pub fn new(reader: R) -> Self {
+        Self::with_tokenizer(reader, shared_tokenizer())
+    }
+
+    /// Creates new instance of SentenceIter reading from `reader` and splitting sentences like
+    /// `tokenizer`, such as one with [`Tokenizer::sentence_delimiters`]. A [`Tokenizer::sentence_pattern`]
+    /// matching across lines only matches once both lines have been read.
+    pub fn with_tokenizer(reader: R, tokenizer: &Tokenizer) -> Self {
+        SentenceIter {
+            reader,
+            buffer: String::new(),
+            pending: VecDeque::new(),
+            quote_regex: tokenizer.quote_regex.clone(),
+            separator: tokenizer.separator.clone(),
+            finished: false,
+        }
+    }
+
+    fn split_complete_sentences(&mut self, text: &str) {
+        self.pending.extend(split_sentences(&self.separator, text).map(|sentence| sentence.to_string()));
+    }
+}
+
+impl<R: BufRead> Iterator for SentenceIter<R> {
+    type Item = io::Result<String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(sentence) = self.pending.pop_front() {
+                return Some(Ok(sentence));
+            }
+            if self.finished {
+                return None;
+            }
+
+            let mut line = String::new();
+            match self.reader.read_line(&mut line) {
+                Err(error) => {
+                    self.finished = true;
+                    return Some(Err(error));
+                },
+                Ok(0) => {
+                    self.finished = true;
+                    let remaining = std::mem::take(&mut self.buffer);
+                    self.split_complete_sentences(&remaining);
+                },
+                Ok(_) => {
+                    // lines end in a newline, so neither regex can match across them
+                    let line = match &self.quote_regex {
+                        Some(quote_regex) => quote_regex.replace_all(&line, "\"").into_owned(),
+                        None => line,
+                    };
+                    self.buffer.push_str(&line);
+                    let buffer = &self.buffer;
+                    let boundaries = self.separator.find_iter(buffer).filter(|separator| is_sentence_boundary(buffer, separator));
+                    if let Some(end) = boundaries.last().map(|separator| separator.end()) {
+                        let incomplete = self.buffer.split_off(end);
+                        let complete = std::mem::replace(&mut self.buffer, incomplete);
+                        self.split_complete_sentences(&complete);
+                    }
+                },
+            }
+        }
+    }
+}
+
+/// Iterator over the tokens of a document read lazily from `reader`, yielding the tokens of each
+/// sentence from [`SentenceIter`] in turn.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Cursor;
+/// use rnltk::token::{self, TokenIter};
+/// 
+/// let text = "Why hello there. General Kenobi!";
+/// let tokens: Vec<String> = TokenIter::new(Cursor::new(text)).map(|token| token.unwrap()).collect();
+/// assert_eq!(tokens, vec!["why", "hello", "there", "general", "kenobi"]);
+///
+/// let tokens: Vec<String> = TokenIter::with_config(Cursor::new(text), token::TokenConfig::default()).map(|token| token.unwrap()).collect();
+/// assert_eq!(tokens, vec!["hello", "gener", "kenobi"]);
+/// ```
+#[derive(Debug)]
+pub struct TokenIter<R: BufRead> {
+    sentences: SentenceIter<R>,
+    config: Option<TokenConfig>,
+    pending: VecDeque<String>,
+}
+
+impl<R: BufRead> TokenIter<R> {
+    /// Creates new instance of TokenIter reading from `reader` and tokenizing sentences with [`tokenize_sentence`].
+    pub fn new(reader: R) -> Self {
+        TokenIter {
+            sentences: SentenceIter::new(reader),
+            config: None,
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// Creates new instance of TokenIter reading from `reader` and tokenizing sentences with
+    /// [`tokenize_sentence_configurable`] using `config`.
+    pub fn with_config(reader: R, config: TokenConfig) -> Self {
+        TokenIter {
+            sentences: SentenceIter::new(reader),
+            config: Some(config),
+            pending: VecDeque::new(),
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for TokenIter<R> {
+    type Item = io::Result<String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(token) = self.pending.pop_front() {
+                return Some(Ok(token));
+            }
+            let sentence = match self.sentences.next()? {
+                Ok(sentence) => sentence,
+                Err(error) => return Some(Err(error)),
+            };
+            let tokens = match &self.config {
+                Some(config) => tokenize_sentence_configurable(&sentence, config.clone()),
+                None => tokenize_sentence(&sentence),
+            };
+            self.pending.extend(tokens);
+        }
+    }
+}
+
+/// Converts `sentence` to token vector.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::token;
+/// 
+/// let text = "Why hello there. General Kenobi!";
+/// let tokens = vec!["why", "hello", "there", "general", "kenobi"];
+/// let tokenized_text = token::tokenize_sentence(text);
+///
+/// assert_eq!(tokens, tokenized_text);
+/// ```
+pub fn tokenize_sentence(sentence: &str) -> Vec<String> {
+    shared_tokenizer().tokenize_sentence(sentence)
+}
+
+/// Converts `sentence` to token vector by splitting on whitespace only, without stripping punctuation
+/// or changing case, for text that is already clean. This is much faster than [`tokenize_sentence`].
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::token;
+/// 
+/// let text = "Why hello there. General  Kenobi!";
+/// let tokens = vec!["Why", "hello", "there.", "General", "Kenobi!"];
+///
+/// assert_eq!(token::tokenize_whitespace(text), tokens);
+/// ```
+pub fn tokenize_whitespace(sentence: &str) -> Vec<String> {
+    shared_tokenizer().tokenize_whitespace(sentence)
+}
+
+/// Converts `sentence` to token vector without stop words.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::token;
+/// 
+/// let text = "Why hello there. General Kenobi!";
+/// let tokens = vec!["hello", "general", "kenobi"];
+/// let stop_words = token::get_stop_words();
+/// let tokenized_text = token::tokenize_sentence_without_stop_words(text, stop_words);
+///
+/// assert_eq!(tokens, tokenized_text);
+/// ```
+pub fn tokenize_sentence_without_stop_words(sentence: &str, stop_words: impl Into<StopWords>) -> Vec<String> {
+    shared_tokenizer().tokenize_sentence_without_stop_words(sentence, stop_words)
+}
+
+/// Converts `sentence` to stemmed token vector.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::token;
+/// 
+/// let text = "Why hello there. General Kenobi!";
+/// let tokens = vec!["why", "hello", "there", "gener", "kenobi"];
+/// let tokenized_text = token::tokenize_stemmed_sentence(text);
+///
+/// assert_eq!(tokens, tokenized_text);
+/// ```
+pub fn tokenize_stemmed_sentence(sentence: &str) -> Vec<String> {
+    shared_tokenizer().tokenize_stemmed_sentence(sentence)
+}
+
+/// Converts `sentence` to stemmed token vector without stop words.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::token;
+/// 
+/// let text = "Why hello there. General Kenobi!";
+/// let tokens = vec!["hello", "gener", "kenobi"];
+/// let stop_words = token::get_stop_words();
+/// let tokenized_text = token::tokenize_stemmed_sentence_without_stop_words(text, stop_words);
+///
+/// assert_eq!(tokens, tokenized_text);
+/// ```
+pub fn tokenize_stemmed_sentence_without_stop_words(sentence: &str, stop_words: impl Into<StopWords>) -> Vec<String> {
+    shared_tokenizer().tokenize_stemmed_sentence_without_stop_words(sentence, stop_words)
+}
+
+/// Tokenize sentence based on a given configuration.
+/// 
+/// This function will be deprecated in the future once `rnltk` hits version 1.0
+/// and functionality will be moved to `tokenize_sentence`.
+/// 
+/// # Examples
+///
+/// ```
+/// use rnltk::token;
+/// 
+/// let token_config = token::TokenConfig::default();
+/// let text = "Why hello there. General Kenobi!";
+/// let tokens = vec!["hello", "gener", "kenobi"];
+/// let tokenized_text = token::tokenize_sentence_configurable(text, token_config);
+///
+/// assert_eq!(tokens, tokenized_text);
+/// ```
+pub fn tokenize_sentence_configurable(sentence: &str, config: TokenConfig) -> Vec<String> {
+    shared_tokenizer().tokenize_sentence_configurable(sentence, config)
+}
+
+/// Converts `document` to sentences, as with [`tokenize_into_sentences`], and each sentence to tokens based on
+/// a given configuration, keeping track of which sentence every token came from. Minimum frequency is counted
+/// across the whole document.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::token::{self, TokenConfig, TokenPosition};
+///
+/// let document = token::tokenize_document("Why hello there. General Kenobi!", TokenConfig::default());
+///
+/// assert_eq!(document.tokens, vec![vec!["hello"], vec!["gener", "kenobi"]]);
+/// let positions: Vec<TokenPosition> = document.iter().map(|(position, _)| position).collect();
+/// assert_eq!(positions[2], TokenPosition { sentence_index: 1, token_index: 1 });
+/// assert_eq!(document.get(positions[2]), Some("kenobi"));
+///
+/// // annotations of the flat tokens, projected back onto sentences
+/// let is_name: Vec<bool> = document.iter().map(|(_, token)| token == "kenobi").collect();
+/// assert_eq!(document.group_by_sentence(is_name).unwrap(), vec![vec![false], vec![false, true]]);
+/// ```
+pub fn tokenize_document(document: &str, config: TokenConfig) -> SentenceTokens {
+    shared_tokenizer().tokenize_document(document, config)
+}
+
+/// Struct for holding an occurrence of a target term with the tokens around it, from [`get_context_windows`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ContextWindow {
+    /// Position of the target in the tokens
+    pub index: usize,
+    /// Tokens before the target, in order
+    pub left: Vec<String>,
+    pub target: String,
+    /// Tokens after the target, in order
+    pub right: Vec<String>,
+}
+
+/// Gets the windows of up to `k` tokens on either side of every occurrence of `target` in `tokens`, in order,
+/// for concordances and aspect-based sentiment. Windows are shorter at the ends of `tokens`, and tokens must
+/// equal `target` exactly, so `target` should be normalized the same way as the tokens.
+///
+/// Use [`SentenceTokens::get_context_windows`] to keep windows within sentences.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::token;
+///
+/// let tokens = token::tokenize_sentence("the battery life is great but the battery is heavy");
+/// let windows = token::get_context_windows(&tokens, "battery", 2);
+///
+/// assert_eq!(windows.len(), 2);
+/// assert_eq!(windows[0].left, vec!["the"]);
+/// assert_eq!(windows[0].right, vec!["life", "is"]);
+/// assert_eq!((windows[1].index, windows[1].left.join(" ")), (7, "but the".to_string()));
+/// ```
+pub fn get_context_windows<S: AsRef<str>>(tokens: &[S], target: &str, k: usize) -> Vec<ContextWindow> {
+    let to_strings = |tokens: &[S]| tokens.iter().map(|token| token.as_ref().to_string()).collect();
+    tokens.iter().enumerate()
+        .filter(|(_, token)| token.as_ref() == target)
+        .map(|(index, _)| ContextWindow {
+            index,
+            left: to_strings(&tokens[index.saturating_sub(k)..index]),
+            target: target.to_string(),
+            right: to_strings(&tokens[index + 1..(index + 1 + k).min(tokens.len())]),
+        })
+        .collect()
+}
+
+/// Position of a token in a [`SentenceTokens`]: the index of its sentence, and its index within that sentence.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TokenPosition {
+    pub sentence_index: usize,
+    pub token_index: usize,
+}
+
+/// Struct for holding the tokens of a document grouped by sentence, returned from [`tokenize_document`].
+///
+/// `tokens[i]` holds the tokens of `sentences[i]`, which is empty if the sentence has no tokens.
+/// [`SentenceTokens::iter`] gives the flat view of every token with its [`TokenPosition`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SentenceTokens {
+    pub sentences: Vec<String>,
+    pub tokens: Vec<Vec<String>>,
+}
+
+impl SentenceTokens {
+    /// Gets every token in document order with its position.
+    pub fn iter(&self) -> impl Iterator<Item = (TokenPosition, &str)> {
+        self.tokens.iter().enumerate().flat_map(|(sentence_index, sentence_tokens)| {
+            sentence_tokens.iter().enumerate().map(move |(token_index, token)| (TokenPosition { sentence_index, token_index }, token.as_str()))
+        })
+    }
+
+    /// Gets the token at `position`, if there is one.
+    pub fn get(&self, position: TokenPosition) -> Option<&str> {
+        self.tokens.get(position.sentence_index)?.get(position.token_index).map(String::as_str)
+    }
+
+    /// Gets the total number of tokens across all sentences.
+    pub fn len(&self) -> usize {
+        self.tokens.iter().map(Vec::len).sum()
+    }
+
+    /// Checks if no sentence has any tokens.
+    pub fn is_empty(&self) -> bool {
+        self.tokens.iter().all(Vec::is_empty)
+    }
+
+    /// Gets the windows of up to `k` tokens on either side of every occurrence of `target`, as with
+    /// [`get_context_windows`]. Windows stop at the ends of the target's sentence if `within_sentences`
+    /// is set, and otherwise run across sentences. Window indices are positions in [`SentenceTokens::iter`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rnltk::token::{self, TokenConfig};
+    ///
+    /// let config = TokenConfig::builder().stem(false).remove_stop_words(false).build();
+    /// let document = token::tokenize_document("The service was slow. Service matters!", config);
+    ///
+    /// let windows = document.get_context_windows("service", 2, true);
+    /// assert_eq!((windows[0].left.len(), windows[0].right.clone()), (1, vec!["was".to_string(), "slow".to_string()]));
+    /// assert_eq!((windows[1].index, windows[1].right.clone()), (4, vec!["matters".to_string()]));
+    ///
+    /// let windows = document.get_context_windows("service", 2, false);
+    /// assert_eq!(windows[1].left, vec!["was", "slow"]);
+    /// ```
+    pub fn get_context_windows(&self, target: &str, k: usize, within_sentences: bool) -> Vec<ContextWindow> {
+        if !within_sentences {
+            return get_context_windows(&self.tokens.concat(), target, k);
+        }
+        let mut offset = 0;
+        let mut windows = vec![];
+        for sentence_tokens in &self.tokens {
+            windows.extend(get_context_windows(sentence_tokens, target, k).into_iter().map(|window| ContextWindow { index: window.index + offset, ..window }));
+            offset += sentence_tokens.len();
+        }
+        windows
+    }
+
+    /// Splits `values`, one per token in the order of [`SentenceTokens::iter`], into one group per sentence.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RnltkError::TokenCountMismatch`] if there isn't exactly one value per token.
+    pub fn group_by_sentence<T>(&self, values: Vec<T>) -> Result<Vec<Vec<T>>, RnltkError> {
+        if values.len() != self.len() {
+            return Err(RnltkError::TokenCountMismatch);
+        }
+        let mut values = values.into_iter();
+        Ok(self.tokens.iter().map(|sentence_tokens| values.by_ref().take(sentence_tokens.len()).collect()).collect())
+    }
+}
+
+/// A token together with its location in the original string.
+///
+/// `start` and `end` are byte offsets, so `&sentence[span.start..span.end]` is the original text of the token,
+/// and `char_start` and `char_end` are the equivalent character offsets. Leading and trailing punctuation
+/// isn't part of the span, while punctuation inside a word (as in "don't") is.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct TokenSpan {
+    pub token: String,
+    pub start: usize,
+    pub end: usize,
+    pub char_start: usize,
+    pub char_end: usize,
+}
+
+// the characters removed by tokenize_sentence, which leaves backslashes in place
+fn is_removed_punctuation(c: char) -> bool {
+    c.is_ascii_punctuation() && c != '\\'
+}
+
+/// Converts `text` to the Unicode normalization `form`, so that equivalent text is made of the same characters.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::token::{self, Normalization};
+/// 
+/// let decomposed = "cafe\u{301}";
+///
+/// assert_eq!(token::normalize_unicode(decomposed, Normalization::Nfc), "café");
+/// assert_eq!(token::normalize_unicode("ﬁne", Normalization::Nfkc), "fine");
+/// ```
+pub fn normalize_unicode(text: &str, form: Normalization) -> String {
+    match form {
+        Normalization::Nfc => text.nfc().collect(),
+        Normalization::Nfkc => text.nfkc().collect(),
+    }
+}
+
+/// Removes diacritics from `text`, so "café" becomes "cafe". Letters that aren't written with a
+/// combining accent, such as "ß" or "ø", are kept.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::token;
+/// 
+/// assert_eq!(token::strip_accents("Crème brûlée"), "Creme brulee");
+/// ```
+pub fn strip_accents(text: &str) -> String {
+    text.nfd().filter(|c| !is_combining_mark(*c)).nfc().collect()
+}
+
+fn normalize_word(word: &str, normalization: Option<Normalization>, accents_stripped: bool) -> String {
+    let word = match normalization {
+        Some(form) => normalize_unicode(word, form),
+        None => word.to_string(),
+    };
+    if accents_stripped {
+        strip_accents(&word)
+    } else {
+        word
+    }
+}
+
+// contractions whose expansion can't be made from their pieces
+const IRREGULAR_CONTRACTIONS: [(&str, [&str; 2]); 6] = [
+    ("can't", ["can", "not"]),
+    ("won't", ["will", "not"]),
+    ("shan't", ["shall", "not"]),
+    ("ain't", ["is", "not"]),
+    ("let's", ["let", "us"]),
+    ("y'all", ["you", "all"]),
+];
+// words whose "'s" means "is" rather than a possessive
+const IS_CONTRACTION_WORDS: [&str; 11] = ["he", "she", "it", "that", "this", "there", "here", "what", "where", "who", "how"];
+
+// case folds `word` and handles its contraction according to `contractions`, removing any other punctuation
+// except for inner hyphens when `keep_hyphens` is set
+fn split_contraction(word: &str, contractions: Contractions, case_folding: CaseFolding, keep_hyphens: bool) -> Vec<String> {
+    let is_removed = |c: char| is_removed_punctuation(c) && !(keep_hyphens && c == '-');
+    let strip = |word: &str| word.chars().filter(|c| !is_removed(*c)).collect::<String>().trim_matches('-').to_string();
+    let word = case_folding.fold(word).replace('\u{2019}', "'");
+    let kept: String = word.chars().filter(|c| *c == '\'' || !is_removed(*c)).collect();
+    let kept = kept.trim_matches(['\'', '-']);
+
+    let split_at = if kept.len() > 3 && kept.ends_with("n't") {
+        Some(kept.len() - 3)
+    } else {
+        kept.rfind('\'').filter(|index| *index > 0 && ["'s", "'m", "'d", "'ll", "'re", "'ve"].contains(&&kept[*index..]))
+    };
+    match contractions {
+        Contractions::Strip => vec![strip(&word)],
+        Contractions::Keep => vec![kept.to_string()],
+        Contractions::Split => match split_at {
+            Some(index) => vec![kept[..index].to_string(), kept[index..].to_string()],
+            None => vec![kept.to_string()],
+        },
+        Contractions::Expand => {
+            if let Some((_, expansion)) = IRREGULAR_CONTRACTIONS.iter().find(|(contraction, _)| *contraction == kept) {
+                return expansion.iter().map(|word| word.to_string()).collect();
+            }
+            let Some(index) = split_at else {
+                return vec![strip(kept)];
+            };
+            let (base, clitic) = kept.split_at(index);
+            let expansion = match clitic {
+                "n't" => "not",
+                "'m" => "am",
+                "'d" => "would",
+                "'ll" => "will",
+                "'re" => "are",
+                "'ve" => "have",
+                _ if IS_CONTRACTION_WORDS.contains(&base) => "is",
+                _ => return vec![strip(kept)],
+            };
+            vec![base.to_string(), expansion.to_string()]
+        }
+    }
+}
+
+const EMOTICON_PATTERN: &str = r"[<>]?[:;=8][\-o*']?[)\](\[dDpP/:}{@|\\]|[)\](\[dDpP/:}{@|\\][\-o*']?[:;=8][<>]?|</?3";
+
+const EMOJI_ALIASES: [(&str, &str); 82] = [
+    ("😀", ":grinning:"), ("😃", ":smiley:"), ("😄", ":smile:"), ("😁", ":grin:"), ("😆", ":laughing:"),
+    ("😅", ":sweat_smile:"), ("🤣", ":rofl:"), ("😂", ":joy:"), ("🙂", ":slightly_smiling_face:"), ("🙃", ":upside_down_face:"),
+    ("😉", ":wink:"), ("😊", ":blush:"), ("😇", ":innocent:"), ("😍", ":heart_eyes:"), ("🥰", ":smiling_face_with_three_hearts:"),
+    ("😘", ":kissing_heart:"), ("😋", ":yum:"), ("😛", ":stuck_out_tongue:"), ("😜", ":stuck_out_tongue_winking_eye:"), ("🤔", ":thinking:"),
+    ("😐", ":neutral_face:"), ("😑", ":expressionless:"), ("😶", ":no_mouth:"), ("😏", ":smirk:"), ("😒", ":unamused:"),
+    ("🙄", ":roll_eyes:"), ("😬", ":grimacing:"), ("😌", ":relieved:"), ("😔", ":pensive:"), ("😴", ":sleeping:"),
+    ("😷", ":mask:"), ("🤢", ":nauseated_face:"), ("😎", ":sunglasses:"), ("😕", ":confused:"), ("😟", ":worried:"),
+    ("🙁", ":slightly_frowning_face:"), ("☹", ":frowning_face:"), ("😮", ":open_mouth:"), ("😲", ":astonished:"), ("😳", ":flushed:"),
+    ("😢", ":cry:"), ("😭", ":sob:"), ("😱", ":scream:"), ("😞", ":disappointed:"), ("😩", ":weary:"),
+    ("😫", ":tired_face:"), ("😤", ":triumph:"), ("😡", ":rage:"), ("😠", ":angry:"), ("🤬", ":cursing_face:"),
+    ("💀", ":skull:"), ("💩", ":hankey:"), ("🤡", ":clown_face:"), ("❤", ":heart:"), ("💔", ":broken_heart:"),
+    ("💕", ":two_hearts:"), ("👍", ":+1:"), ("👎", ":-1:"), ("👏", ":clap:"), ("🙏", ":pray:"),
+    ("👌", ":ok_hand:"), ("💪", ":muscle:"), ("🔥", ":fire:"), ("✨", ":sparkles:"), ("🎉", ":tada:"),
+    ("💯", ":100:"), ("⭐", ":star:"), ("🚀", ":rocket:"), ("👀", ":eyes:"), ("✅", ":white_check_mark:"),
+    ("❌", ":x:"), ("🤷", ":shrug:"), ("🤦", ":facepalm:"), ("🙌", ":raised_hands:"), ("😈", ":smiling_imp:"),
+    ("🤯", ":exploding_head:"), ("🥳", ":partying_face:"), ("🥺", ":pleading_face:"), ("😪", ":sleepy:"), ("🤗", ":hugs:"),
+    ("🤩", ":star_struck:"), ("😻", ":heart_eyes_cat:"),
+];
+
+const EMOTICON_ALIASES: [(&str, &str); 22] = [
+    (":)", ":slightly_smiling_face:"), (":-)", ":slightly_smiling_face:"), ("=)", ":slightly_smiling_face:"), (":]", ":slightly_smiling_face:"),
+    (":D", ":smiley:"), (":-D", ":smiley:"), ("=D", ":smiley:"),
+    (";)", ":wink:"), (";-)", ":wink:"),
+    (":(", ":slightly_frowning_face:"), (":-(", ":slightly_frowning_face:"), ("=(", ":slightly_frowning_face:"), (":[", ":slightly_frowning_face:"),
+    (":P", ":stuck_out_tongue:"), (":-P", ":stuck_out_tongue:"), (":p", ":stuck_out_tongue:"),
+    (":'(", ":cry:"), (":/", ":confused:"), (":-/", ":confused:"), (":|", ":neutral_face:"),
+    ("<3", ":heart:"), ("</3", ":broken_heart:"),
+];
+
+/// Gets the textual alias of an emoji or emoticon, such as ":smile:" for "😄", so it can be looked up in a lexicon.
+/// Aliases are GitHub-style shortcodes covering common emoji, and ignore skin tones and emoji presentation selectors.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::token;
+/// 
+/// assert_eq!(token::emoji_alias("👍🏽"), Some(":+1:"));
+/// assert_eq!(token::emoji_alias(":-("), Some(":slightly_frowning_face:"));
+/// assert_eq!(token::emoji_alias("🦀"), None);
+/// ```
+pub fn emoji_alias(emoji: &str) -> Option<&'static str> {
+    let base: String = emoji.chars().filter(|c| !is_emoji_modifier(*c)).collect();
+    EMOJI_ALIASES.iter().chain(EMOTICON_ALIASES.iter())
+        .find(|(emoji, _)| *emoji == base)
+        .map(|(_, alias)| *alias)
+}
+
+fn is_emoji(c: char) -> bool {
+    matches!(c as u32, 0x1F000..=0x1FAFF | 0x2300..=0x23FF | 0x2600..=0x27BF | 0x2B00..=0x2BFF | 0x3030 | 0x303D | 0x3297 | 0x3299)
+}
+
+// variation selector, keycap, skin tones, and tags, which change the emoji before them
+fn is_emoji_modifier(c: char) -> bool {
+    matches!(c, '\u{FE0F}' | '\u{20E3}' | '\u{1F3FB}'..='\u{1F3FF}' | '\u{E0020}'..='\u{E007F}')
+}
+
+fn is_regional_indicator(c: char) -> bool {
+    matches!(c, '\u{1F1E6}'..='\u{1F1FF}')
+}
+
+// byte ranges of the emoji sequences and stand-alone emoticons of `text`, in order
+fn emoji_bounds(text: &str) -> Vec<Range<usize>> {
+    let chars: Vec<(usize, char)> = text.char_indices().collect();
+    let mut bounds = vec![];
+    let mut index = 0;
+    while index < chars.len() {
+        let c = chars[index].1;
+        index += 1;
+        if !is_emoji(c) || is_emoji_modifier(c) {
+            continue;
+        }
+        let start = chars[index - 1].0;
+        if is_regional_indicator(c) {
+            // flags are pairs of regional indicators
+            if chars.get(index).is_some_and(|(_, next)| is_regional_indicator(*next)) {
+                index += 1;
+            }
+        } else {
+            loop {
+                while chars.get(index).is_some_and(|(_, next)| is_emoji_modifier(*next)) {
+                    index += 1;
+                }
+                let joins = chars.get(index).is_some_and(|(_, next)| *next == '\u{200D}');
+                if !(joins && chars.get(index + 1).is_some_and(|(_, next)| is_emoji(*next))) {
+                    break;
+                }
+                index += 2;
+            }
+        }
+        bounds.push(start..chars.get(index).map_or(text.len(), |(end, _)| *end));
+    }
+
+    static EMOTICON: OnceLock<Regex> = OnceLock::new();
+    let emoticon = EMOTICON.get_or_init(|| Regex::new(EMOTICON_PATTERN).expect("Invalid regex"));
+    let stands_apart = |c: Option<char>| c.is_none_or(|c| c.is_whitespace() || is_emoji(c));
+    for found in emoticon.find_iter(text) {
+        let (before, after) = (text[..found.start()].chars().next_back(), text[found.end()..].chars().next());
+        let ends_word = after.is_none_or(|c| c.is_whitespace() || is_emoji(c) || matches!(c, '.' | ',' | '!' | '?' | ';'));
+        if stands_apart(before) && ends_word {
+            bounds.push(found.range());
+        }
+    }
+    bounds.sort_by_key(|bound| bound.start);
+    bounds
+}
+
+/// Tokenizer for social media text that keeps URLs, emoticons, hashtags, and @mentions intact as
+/// single tokens, where [`tokenize_sentence`] would strip their punctuation.
+///
+/// By default every token except URLs and emoticons is lowercased, to match the lexicon of
+/// [`SentimentModel`](crate::sentiment::SentimentModel), and stand-alone ASCII punctuation is dropped
+/// while other symbols such as emoji are kept.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::token::TweetTokenizer;
+/// 
+/// let tokenizer = TweetTokenizer::new().reduce_len(true).strip_handles(true);
+/// let tokens = tokenizer.tokenize("@remy This is waaaaayyyy too much for you!!!!!! :D");
+///
+/// assert_eq!(tokens, vec!["this", "is", "waaayyy", "too", "much", "for", "you", ":D"]);
+/// ```
 #[derive(Debug, Clone)]
-pub struct TokenConfig {
-    pub stem: bool,
-    pub remove_stop_words: bool,
-    pub stop_words: Vec<String>,
+pub struct TweetTokenizer {
+    pattern: Regex,
+    preserve_case: bool,
+    reduce_len: bool,
+    strip_handles: bool,
 }
 
-impl Default for TokenConfig {
+impl Default for TweetTokenizer {
     fn default() -> Self {
-        Self {
-            stem: true,
-            remove_stop_words: true,
-            stop_words: get_stop_words(),
+        Self::new()
+    }
+}
+
+impl TweetTokenizer {
+    /// Creates new instance of TweetTokenizer that lowercases tokens, keeps repeated characters, and keeps @mentions.
+    pub fn new() -> Self {
+        let pattern = [
+            r#"(?P<url>(?:https?://|www\.)[^\s]*[^\s.,!?;:)\]"'>])"#,
+            &format!("(?P<emoticon>{EMOTICON_PATTERN})"),
+            r"(?P<mention>@\w+)",
+            r"(?P<hashtag>#+\w+(?:['\-]\w+)*)",
+            r"(?P<word>[^\W\d_](?:[^\W\d_]|['\-_])+[^\W\d_])",
+            r"(?P<number>[+\-]?\d+(?:[,/.:\-]\d+[+\-]?)?)",
+            r"(?P<other>\w+|\.(?:\s*\.)+|\S)",
+        ].join("|");
+        TweetTokenizer {
+            pattern: Regex::new(&pattern).expect("Invalid regex"),
+            preserve_case: false,
+            reduce_len: false,
+            strip_handles: false,
+        }
+    }
+
+    /// Sets whether the case of tokens is kept instead of lowercasing them.
+    pub fn preserve_case(mut self, preserve_case: bool) -> Self {
+        self.preserve_case = preserve_case;
+        self
+    }
+
+    /// Sets whether runs of more than 3 repeated characters are shortened to 3, e.g. "sooooo" to "sooo".
+    pub fn reduce_len(mut self, reduce_len: bool) -> Self {
+        self.reduce_len = reduce_len;
+        self
+    }
+
+    /// Sets whether @mentions are removed.
+    pub fn strip_handles(mut self, strip_handles: bool) -> Self {
+        self.strip_handles = strip_handles;
+        self
+    }
+
+    /// Converts `text` to token vector.
+    pub fn tokenize(&self, text: &str) -> Vec<String> {
+        let mut tokens = vec![];
+        for captures in self.pattern.captures_iter(text) {
+            let token = &captures[0];
+            if captures.name("url").is_some() || captures.name("emoticon").is_some() {
+                tokens.push(token.to_string());
+                continue;
+            }
+            if captures.name("mention").is_some() && self.strip_handles {
+                continue;
+            }
+            let is_punctuation = token.chars().all(|c| c.is_ascii_punctuation() || c.is_whitespace());
+            if captures.name("other").is_some() && is_punctuation {
+                continue;
+            }
+            let token = if self.reduce_len { reduce_repeated_characters(token) } else { token.to_string() };
+            tokens.push(if self.preserve_case { token } else { token.to_lowercase() });
+        }
+        tokens
+    }
+}
+
+fn reduce_repeated_characters(token: &str) -> String {
+    let mut reduced = String::with_capacity(token.len());
+    let mut previous: Option<char> = None;
+    let mut run_length = 0;
+    for c in token.chars() {
+        run_length = if previous == Some(c) { run_length + 1 } else { 1 };
+        previous = Some(c);
+        if run_length <= 3 {
+            reduced.push(c);
         }
     }
+    reduced
+}
+
+/// Converts social media `text` to token vector with a default [`TweetTokenizer`], keeping URLs,
+/// emoticons, hashtags, and @mentions as single tokens.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::token;
+/// 
+/// let tokens = token::tokenize_tweet("Loving the #RustLang meetup w/ @ferris :) https://rust-lang.org/community");
+///
+/// assert_eq!(tokens, vec!["loving", "the", "#rustlang", "meetup", "w", "@ferris", ":)", "https://rust-lang.org/community"]);
+/// ```
+pub fn tokenize_tweet(text: &str) -> Vec<String> {
+    static TOKENIZER: OnceLock<TweetTokenizer> = OnceLock::new();
+    TOKENIZER.get_or_init(TweetTokenizer::new).tokenize(text)
 }
 
-/// Converts a `document` to sentence vector.
+/// Converts `sentence` to a vector of [`TokenSpan`]s whose tokens match [`tokenize_sentence`].
 ///
 /// # Examples
 ///
@@ -37,23 +2719,61 @@ impl Default for TokenConfig {
 /// use rnltk::token;
 /// 
 /// let text = "Why hello there. General Kenobi!";
-/// let tokens = vec!["Why hello there", "General Kenobi"];
-/// let tokenized_text = token::tokenize_into_sentences(text);
+/// let spans = token::tokenize_sentence_with_spans(text);
+/// let tokens: Vec<&str> = spans.iter().map(|span| span.token.as_str()).collect();
 ///
-/// assert_eq!(tokens, tokenized_text);
+/// assert_eq!(tokens, vec!["why", "hello", "there", "general", "kenobi"]);
+/// assert_eq!(&text[spans[4].start..spans[4].end], "Kenobi");
 /// ```
-pub fn tokenize_into_sentences(document: &str) -> Vec<String> {
-    let quote_regex = Regex::new(r#"[\.!\?]""#).expect("Invalid regex");
-    let updated_document: &str = &quote_regex.replace_all(document, "\"");
+pub fn tokenize_sentence_with_spans(sentence: &str) -> Vec<TokenSpan> {
+    let mut spans: Vec<TokenSpan> = vec![];
+    let mut byte_offset = 0;
+    let mut char_offset = 0;
+    for segment in sentence.split(' ') {
+        let is_token_char = |c: char| !is_removed_punctuation(c) && !c.is_whitespace();
+        if let (Some(first), Some(last)) = (segment.find(is_token_char), segment.rfind(is_token_char)) {
+            let end = last + segment[last..].chars().next().map_or(0, char::len_utf8);
+            let char_start = char_offset + segment[..first].chars().count();
+            spans.push(TokenSpan {
+                token: CaseFolding::Unicode.fold(segment.chars().filter(|c| !is_removed_punctuation(*c)).collect::<String>().trim()).into_owned(),
+                start: byte_offset + first,
+                end: byte_offset + end,
+                char_start,
+                char_end: char_start + segment[first..end].chars().count(),
+            });
+        }
+        byte_offset += segment.len() + 1;
+        char_offset += segment.chars().count() + 1;
+    }
+
+    spans
+}
 
-    let separator = Regex::new(r#"[\.!\?] *"#).expect("Invalid regex");
-    let mut full_sentences: Vec<String> = separator.split(updated_document).map(|s| s.to_string()).collect();
-    full_sentences.retain(|sentence| !sentence.is_empty());
+/// Converts `sentence` to a vector of [`TokenSpan`]s without stop words. The remaining spans
+/// keep their offsets into the original `sentence`.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::token;
+/// 
+/// let text = "Why hello there. General Kenobi!";
+/// let stop_words = token::get_stop_words();
+/// let spans = token::tokenize_sentence_with_spans_without_stop_words(text, stop_words);
+///
+/// assert_eq!(spans[0].token, "hello");
+/// assert_eq!((spans[0].start, spans[0].end), (4, 9));
+/// ```
+pub fn tokenize_sentence_with_spans_without_stop_words(sentence: &str, stop_words: impl Into<StopWords>) -> Vec<TokenSpan> {
+    let stop_words = stop_words.into();
+    let mut spans = tokenize_sentence_with_spans(sentence);
+    spans.retain(|span| !stop_words.contains(&span.token));
 
-    full_sentences
+    spans
 }
 
-/// Converts `sentence` to token vector.
+/// Converts `sentence` to a vector of [`TokenSpan`]s based on a given configuration, matching
+/// [`tokenize_sentence_configurable`]. Stemmed tokens keep the span of the unstemmed word.
 ///
 /// # Examples
 ///
@@ -61,130 +2781,598 @@ pub fn tokenize_into_sentences(document: &str) -> Vec<String> {
 /// use rnltk::token;
 /// 
 /// let text = "Why hello there. General Kenobi!";
-/// let tokens = vec!["why", "hello", "there", "general", "kenobi"];
-/// let tokenized_text = token::tokenize_sentence(text);
+/// let spans = token::tokenize_sentence_with_spans_configurable(text, token::TokenConfig::default());
 ///
-/// assert_eq!(tokens, tokenized_text);
+/// assert_eq!(spans[1].token, "gener");
+/// assert_eq!(&text[spans[1].start..spans[1].end], "General");
 /// ```
-pub fn tokenize_sentence(sentence: &str) -> Vec<String> {
-    let punctuation = Regex::new(r#"[!"\#$%&'()*+,-./:;<=>?@\[\]^_`{|}~]+"#).expect("Invalid regex");
-    let updated_sentence: &str = &punctuation.replace_all(sentence, "");
+pub fn tokenize_sentence_with_spans_configurable(sentence: &str, config: TokenConfig) -> Vec<TokenSpan> {
+    config.apply(rewritten_spans(sentence, &config))
+}
+
+// drops items whose key occurs fewer than `min_frequency` times among `items`
+fn retain_frequent<T>(items: &mut Vec<T>, key: impl Fn(&T) -> &str, min_frequency: usize) {
+    let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+    for item in items.iter() {
+        *counts.entry(key(item).to_string()).or_insert(0) += 1;
+    }
+    items.retain(|item| counts[key(item)] >= min_frequency);
+}
+
+// spans of `sentence` with emoji, contractions, and normalization from `config` applied, before stop words and stemming
+fn rewritten_spans(sentence: &str, config: &TokenConfig) -> Vec<TokenSpan> {
+    if config.whitespace_only {
+        return whitespace_spans(sentence);
+    }
+    if config.emoji == Emoji::Ignore {
+        return rewritten_word_spans(sentence, config);
+    }
+    let mut spans = vec![];
+    let mut start = 0;
+    for emoji in emoji_bounds(sentence) {
+        spans.extend(offset_spans(rewritten_word_spans(&sentence[start..emoji.start], config), sentence, start));
+        let original = &sentence[emoji.clone()];
+        let char_start = sentence[..emoji.start].chars().count();
+        let alias = (config.emoji == Emoji::Alias).then(|| emoji_alias(original)).flatten();
+        spans.push(TokenSpan {
+            token: alias.unwrap_or(original).to_string(),
+            start: emoji.start,
+            end: emoji.end,
+            char_start,
+            char_end: char_start + original.chars().count(),
+        });
+        start = emoji.end;
+    }
+    spans.extend(offset_spans(rewritten_word_spans(&sentence[start..], config), sentence, start));
+    spans
+}
+
+// spans of the whitespace-separated words of `sentence`, kept as written
+fn whitespace_spans(sentence: &str) -> Vec<TokenSpan> {
+    let mut spans = vec![];
+    // byte and character offsets of the word being read
+    let mut word_start: Option<(usize, usize)> = None;
+    let mut char_count = 0;
+    for (char_index, (byte_index, c)) in sentence.char_indices().enumerate() {
+        match (c.is_whitespace(), word_start) {
+            (true, Some((start, char_start))) => {
+                spans.push(TokenSpan { token: sentence[start..byte_index].to_string(), start, end: byte_index, char_start, char_end: char_index });
+                word_start = None;
+            },
+            (false, None) => word_start = Some((byte_index, char_index)),
+            _ => {},
+        }
+        char_count = char_index + 1;
+    }
+    if let Some((start, char_start)) = word_start {
+        spans.push(TokenSpan { token: sentence[start..].to_string(), start, end: sentence.len(), char_start, char_end: char_count });
+    }
+    spans
+}
+
+// moves spans of the text starting at byte `start` of `sentence` to offsets into `sentence`
+fn offset_spans(spans: Vec<TokenSpan>, sentence: &str, start: usize) -> impl Iterator<Item = TokenSpan> {
+    let char_offset = sentence[..start].chars().count();
+    spans.into_iter().map(move |span| TokenSpan {
+        start: span.start + start,
+        end: span.end + start,
+        char_start: span.char_start + char_offset,
+        char_end: span.char_end + char_offset,
+        ..span
+    })
+}
+
+// pieces of `word` to tokenize under the `hyphens` policy, with whether each keeps its hyphens
+fn hyphenated_pieces(word: &str, hyphens: Hyphens) -> Vec<(String, bool)> {
+    if hyphens == Hyphens::Strip {
+        return vec![(word.to_string(), false)];
+    }
+    let word = word.replace(['\u{2010}', '\u{2011}'], "-").replace("--", " ").replace(['\u{2013}', '\u{2014}'], " ");
+    let mut pieces = vec![];
+    for part in word.split_whitespace() {
+        if !part.trim_matches(is_removed_punctuation).contains('-') {
+            pieces.push((part.to_string(), false));
+            continue;
+        }
+        if matches!(hyphens, Hyphens::Keep | Hyphens::Both) {
+            pieces.push((part.to_string(), true));
+        }
+        if matches!(hyphens, Hyphens::Split | Hyphens::Both) {
+            pieces.extend(part.split('-').map(|piece| (piece.to_string(), false)));
+        }
+    }
+    pieces
+}
+
+fn rewritten_word_spans(sentence: &str, config: &TokenConfig) -> Vec<TokenSpan> {
+    let spans = tokenize_sentence_with_spans(sentence);
+    if !config.rewrites_words() {
+        return spans;
+    }
+    spans.into_iter()
+        .flat_map(|span| {
+            let word = normalize_word(&sentence[span.start..span.end], config.normalization, config.strip_accents);
+            hyphenated_pieces(&word, config.hyphens).into_iter()
+                .flat_map(|(piece, keep_hyphens)| split_contraction(&piece, config.contractions, config.case_folding, keep_hyphens))
+                .filter(|token| !token.is_empty())
+                .map(move |token| TokenSpan { token, ..span.clone() })
+        })
+        .collect()
+}
+
+/// The kind of a [`Token`]. [`tokenize_sentence_with_metadata`] only tells words, numbers, alphanumeric tokens,
+/// and symbols apart, while [`tokenize_sentence_typed`] uses every kind.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TokenKind {
+    /// Letters only, possibly with apostrophes as in "n't" or hyphens as in "state-of-the-art"
+    Word,
+    /// Digits only, or with [`tokenize_sentence_typed`] digits with separators, such as "3.14" or "2024-01-05"
+    Number,
+    /// A mix of letters and digits, such as "mp3"
+    Alphanumeric,
+    /// Anything else, such as "©", or emoji with [`tokenize_sentence_with_metadata`]
+    Symbol,
+    /// A web address, such as "https://example.com"
+    Url,
+    /// An email address, such as "jane@example.com"
+    Email,
+    /// A hashtag, such as "#rustlang"
+    Hashtag,
+    /// A mention of a user, such as "@ferris"
+    Mention,
+    /// Punctuation, such as "," or "..."
+    Punctuation,
+    /// An emoji sequence or emoticon, such as "👍🏽" or ":-)"
+    Emoji,
+}
+
+impl TokenKind {
+    fn of(text: &str) -> Self {
+        if text.chars().all(|c| c.is_alphabetic() || c == '\'' || c == '-') {
+            TokenKind::Word
+        } else if text.chars().all(char::is_numeric) {
+            TokenKind::Number
+        } else if text.chars().all(|c| c.is_alphanumeric() || c == '\'' || c == '-') {
+            TokenKind::Alphanumeric
+        } else {
+            TokenKind::Symbol
+        }
+    }
+}
+
+/// A token with the metadata downstream analysis usually needs, from [`tokenize_sentence_with_metadata`].
+///
+/// `text` is the normalized, lowercased token, while `original` keeps the surface form of the word
+/// it came from, so results can be shown with their original casing. `span` holds the byte offsets
+/// of that word, so `&sentence[token.span.clone()]` is `original`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    pub text: String,
+    /// The word as written in the sentence, without surrounding punctuation
+    #[serde(default)]
+    pub original: String,
+    pub span: Range<usize>,
+    pub kind: TokenKind,
+    /// The stem of the token, if stemming is configured and the token is a word that can be stemmed
+    pub stem: Option<String>,
+    /// Whether the token is one of the configured stop words
+    pub is_stop: bool,
+}
+
+/// Converts `sentence` to a vector of [`Token`]s based on a given configuration.
+///
+/// Unlike [`tokenize_sentence_configurable`], tokens keep their unstemmed text and original casing alongside
+/// their stem, and stop words are flagged with `is_stop`. Stop words are only removed if `remove_stop_words` is set.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::token::{self, TokenConfig, TokenKind};
+/// 
+/// let text = "General Kenobi won 3 duels!";
+/// let config = TokenConfig { remove_stop_words: false, ..TokenConfig::default() };
+/// let tokens = token::tokenize_sentence_with_metadata(text, config);
+///
+/// assert_eq!(tokens[0].text, "general");
+/// assert_eq!(tokens[0].stem.as_deref(), Some("gener"));
+/// assert_eq!(&text[tokens[1].span.clone()], "Kenobi");
+/// assert_eq!(tokens[1].original, "Kenobi");
+/// assert!(tokens[2].is_stop);
+/// assert_eq!((tokens[3].kind, tokens[3].stem.as_deref()), (TokenKind::Number, None));
+/// ```
+pub fn tokenize_sentence_with_metadata(sentence: &str, config: TokenConfig) -> Vec<Token> {
+    let mut tokens: Vec<Token> = rewritten_spans(sentence, &config).into_iter()
+        .map(|span| {
+            let kind = TokenKind::of(&span.token);
+            let stem = (config.stem && kind == TokenKind::Word && !config.is_protected(&span.token))
+                .then(|| config.stemmer.stem(&span.token))
+                .flatten();
+            Token {
+                is_stop: config.is_stop_word(&span.token),
+                original: sentence[span.start..span.end].to_string(),
+                kind,
+                stem,
+                span: span.start..span.end,
+                text: span.token,
+            }
+        })
+        .filter(|token| !(config.remove_stop_words && token.is_stop) && config.keeps_token(&token.text))
+        .collect();
+    if let Some(min_frequency) = config.min_frequency {
+        retain_frequent(&mut tokens, |token| token.text.as_str(), min_frequency);
+    }
+
+    tokens
+}
+
+/// Converts `sentence` to a vector of [`Token`]s tagged with their [`TokenKind`], based on a given configuration.
+///
+/// Unlike [`tokenize_sentence_with_metadata`], URLs, email addresses, hashtags, mentions, punctuation, and emoji
+/// are kept as tokens of their own kind instead of having their punctuation stripped, so they can be filtered
+/// or routed by kind. Words are rewritten, stemmed, and flagged as stop words as with [`tokenize_sentence_with_metadata`],
+/// while URLs, numbers, punctuation, and emoji keep their original text and the rest are lowercased. `emoji` only
+/// decides whether emoji are replaced by their aliases, since emoji tokens are always kept.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::token::{self, TokenConfig, TokenKind};
+///
+/// let text = "Email jane@example.com or see https://example.com, #RustLang @ferris 3.5 😀";
+/// let config = TokenConfig { stem: false, ..TokenConfig::default() };
+/// let tokens = token::tokenize_sentence_typed(text, config);
+/// let kinds: Vec<TokenKind> = tokens.iter().map(|token| token.kind).collect();
+///
+/// assert_eq!(kinds, vec![
+///     TokenKind::Word, TokenKind::Email, TokenKind::Word, TokenKind::Url, TokenKind::Punctuation,
+///     TokenKind::Hashtag, TokenKind::Mention, TokenKind::Number, TokenKind::Emoji,
+/// ]);
+/// assert_eq!(tokens[5].text, "#rustlang");
+/// assert_eq!(tokens[5].original, "#RustLang");
+/// ```
+pub fn tokenize_sentence_typed(sentence: &str, config: TokenConfig) -> Vec<Token> {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    let pattern = PATTERN.get_or_init(|| {
+        let pattern = [
+            r#"(?P<url>(?:https?://|www\.)[^\s]*[^\s.,!?;:)\]"'>])"#,
+            r"(?P<email>[\w.+\-]+@[\w\-]+(?:\.[\w\-]+)+)",
+            r"(?P<mention>@\w+)",
+            r"(?P<hashtag>#\w+)",
+            r"(?P<number>\d+(?:[.,:/\-]\d+)*\b)",
+            r"(?P<word>\w+(?:['\u{2019}]\w+)*)",
+            r"(?P<punctuation>\.{2,}|[!?]+|[^\w\s])",
+        ].join("|");
+        Regex::new(&pattern).expect("Invalid regex")
+    });
+
+    let mut pieces: Vec<(Range<usize>, TokenKind)> = vec![];
+    let mut start = 0;
+    // the text before each emoji, and after the last one, is split by the pattern
+    for emoji in emoji_bounds(sentence).into_iter().map(Some).chain([None]) {
+        let gap_end = emoji.as_ref().map_or(sentence.len(), |bound| bound.start);
+        for captures in pattern.captures_iter(&sentence[start..gap_end]) {
+            let found = captures.get(0).expect("Every match has a whole capture");
+            let kind = if captures.name("url").is_some() {
+                TokenKind::Url
+            } else if captures.name("email").is_some() {
+                TokenKind::Email
+            } else if captures.name("mention").is_some() {
+                TokenKind::Mention
+            } else if captures.name("hashtag").is_some() {
+                TokenKind::Hashtag
+            } else if captures.name("number").is_some() {
+                TokenKind::Number
+            } else if captures.name("word").is_some() {
+                TokenKind::Word
+            } else if found.as_str().chars().all(is_punctuation) {
+                TokenKind::Punctuation
+            } else {
+                TokenKind::Symbol
+            };
+            pieces.push((start + found.start()..start + found.end(), kind));
+        }
+        if let Some(bound) = emoji {
+            start = bound.end;
+            pieces.push((bound, TokenKind::Emoji));
+        }
+    }
 
-    let mut tokens: Vec<String> = updated_sentence
-        .split(' ')
-        .map(|s| s.trim().to_ascii_lowercase())
+    let mut tokens: Vec<Token> = pieces.into_iter()
+        .flat_map(|(span, kind)| {
+            let original = &sentence[span.clone()];
+            let texts: Vec<(String, TokenKind)> = match kind {
+                TokenKind::Word => {
+                    let word = normalize_word(original, config.normalization, config.strip_accents);
+                    split_contraction(&word, config.contractions, config.case_folding, false).into_iter()
+                        .filter(|text| !text.is_empty())
+                        .map(|text| {
+                            let kind = TokenKind::of(&text);
+                            (text, kind)
+                        })
+                        .collect()
+                },
+                TokenKind::Email | TokenKind::Mention | TokenKind::Hashtag => vec![(config.case_folding.fold(original).into_owned(), kind)],
+                TokenKind::Emoji if config.emoji == Emoji::Alias => vec![(emoji_alias(original).unwrap_or(original).to_string(), kind)],
+                _ => vec![(original.to_string(), kind)],
+            };
+            let config = &config;
+            texts.into_iter().map(move |(text, kind)| Token {
+                is_stop: config.is_stop_word(&text),
+                original: original.to_string(),
+                stem: (config.stem && kind == TokenKind::Word && !config.is_protected(&text))
+                    .then(|| config.stemmer.stem(&text))
+                    .flatten(),
+                kind,
+                span: span.clone(),
+                text,
+            })
+        })
+        .filter(|token| !(config.remove_stop_words && token.is_stop) && config.keeps_token(&token.text))
         .collect();
-    tokens.retain(|token| !token.is_empty());
+    if let Some(min_frequency) = config.min_frequency {
+        retain_frequent(&mut tokens, |token| token.text.as_str(), min_frequency);
+    }
 
     tokens
 }
 
-/// Converts `sentence` to token vector without stop words.
+// ASCII punctuation and the common Unicode dashes, quotes, ellipses, and CJK punctuation
+fn is_punctuation(c: char) -> bool {
+    c.is_ascii_punctuation() || matches!(c, '\u{2010}'..='\u{2027}' | '\u{3001}'..='\u{3003}' | '\u{FF01}' | '\u{FF0C}' | '\u{FF1F}' | '¡' | '¿' | '«' | '»')
+}
+
+/// A chunk of a document produced by [`split_into_chunks`].
+///
+/// `text` is the original text from the first sentence of the chunk up to the end of its last one,
+/// at byte offsets `start` and `end` of the document.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct TextChunk {
+    pub text: String,
+    pub start: usize,
+    pub end: usize,
+    pub num_tokens: usize,
+}
+
+/// Splits `text` into chunks of at most `max_tokens` tokens (as counted by [`tokenize_sentence`]),
+/// packing whole sentences into each chunk. Consecutive chunks share trailing sentences of up to
+/// `overlap` tokens, and sentences longer than `max_tokens` are split between words.
+///
+/// Sentences are split as in [`tokenize_into_sentences`], but chunks keep the original text,
+/// including punctuation. Text without any tokens gives no chunks.
+///
+/// # Errors
+///
+/// Returns [`RnltkError::InvalidChunkSize`] if `max_tokens` is 0 or `overlap` isn't smaller than `max_tokens`.
 ///
 /// # Examples
 ///
 /// ```
 /// use rnltk::token;
 /// 
-/// let text = "Why hello there. General Kenobi!";
-/// let tokens = vec!["hello", "general", "kenobi"];
-/// let stop_words = token::get_stop_words();
-/// let tokenized_text = token::tokenize_sentence_without_stop_words(text, stop_words);
+/// let text = "Why hello there. General Kenobi! You are bold.";
+/// let chunks = token::split_into_chunks(text, 5, 2).unwrap();
+/// let chunk_texts: Vec<&str> = chunks.iter().map(|chunk| chunk.text.as_str()).collect();
 ///
-/// assert_eq!(tokens, tokenized_text);
+/// assert_eq!(chunk_texts, vec!["Why hello there. General Kenobi!", "General Kenobi! You are bold."]);
 /// ```
-pub fn tokenize_sentence_without_stop_words(sentence: &str, stop_words: Vec<String>) -> Vec<String> {
-    let punctuation = Regex::new(r#"[!"\#$%&'()*+,-./:;<=>?@\[\]^_`{|}~]+"#).expect("Invalid regex");
-    let updated_sentence: &str = &punctuation.replace_all(sentence, "");
+pub fn split_into_chunks(text: &str, max_tokens: usize, overlap: usize) -> Result<Vec<TextChunk>, RnltkError> {
+    if max_tokens == 0 || overlap >= max_tokens {
+        return Err(RnltkError::InvalidChunkSize);
+    }
 
-    let mut tokens: Vec<String> = tokenize_sentence(updated_sentence);
-    tokens.retain(|token| !stop_words.contains(token));
+    // (start, end, number of tokens) of every sentence, or sentence piece when a sentence is too long
+    let mut units: Vec<(usize, usize, usize)> = vec![];
+    for (start, end) in sentence_bounds(text) {
+        let spans = tokenize_sentence_with_spans(&text[start..end]);
+        if spans.len() <= max_tokens {
+            if !spans.is_empty() {
+                units.push((start, end, spans.len()));
+            }
+            continue;
+        }
+        for piece in spans.chunks(max_tokens) {
+            units.push((start + piece[0].start, start + piece[piece.len() - 1].end, piece.len()));
+        }
+    }
 
-    tokens
+    let mut chunks: Vec<TextChunk> = vec![];
+    let mut start_index = 0;
+    while start_index < units.len() {
+        let mut end_index = start_index;
+        let mut num_tokens = 0;
+        while end_index < units.len() && num_tokens + units[end_index].2 <= max_tokens {
+            num_tokens += units[end_index].2;
+            end_index += 1;
+        }
+        let (start, end) = (units[start_index].0, units[end_index - 1].1);
+        chunks.push(TextChunk {
+            text: text[start..end].to_string(),
+            start,
+            end,
+            num_tokens,
+        });
+        if end_index == units.len() {
+            break;
+        }
+
+        // carry trailing sentences over, as long as the next new sentence still fits
+        let mut next_start_index = end_index;
+        let mut carried_tokens = 0;
+        while next_start_index > start_index + 1 {
+            let carried_with_previous = carried_tokens + units[next_start_index - 1].2;
+            if carried_with_previous > overlap || carried_with_previous + units[end_index].2 > max_tokens {
+                break;
+            }
+            carried_tokens = carried_with_previous;
+            next_start_index -= 1;
+        }
+        start_index = next_start_index;
+    }
+
+    Ok(chunks)
 }
 
-/// Converts `sentence` to stemmed token vector.
+// whether the `separator` match in `text` ends a sentence. A period doesn't when it is part of a decimal or
+// version number ("3.5", "v1.2.3"), follows an initial of a name ("J. R. R. Tolkien", "George W. Bush", "U.S."),
+// or is part of an ellipsis followed by a word that isn't capitalized ("Wait... what"), as long as the sentence
+// goes on in the same line, so that SentenceIter can decide every boundary from the lines read so far
+fn is_sentence_boundary(text: &str, separator: &regex::Match) -> bool {
+    let continues_line = |rest: &str| rest.trim_start_matches(' ').starts_with(|c: char| !c.is_whitespace());
+    if !separator.as_str().starts_with('.') || !continues_line(&text[separator.end()..]) {
+        return true;
+    }
+    let before = &text[..separator.start()];
+    let after = &text[separator.start() + 1..];
+    let run_start = before.trim_end_matches('.').len();
+    let run_end = text.len() - after.trim_start_matches('.').len();
+    if run_end - run_start > 1 {
+        return !text[run_end..].trim_start_matches(' ').starts_with(char::is_lowercase);
+    }
+    let mut previous = before.chars().rev();
+    match (previous.next(), previous.next()) {
+        (Some(digit), _) if digit.is_ascii_digit() => !after.starts_with(|c: char| c.is_ascii_digit()),
+        (Some(letter), before_letter) if letter.is_uppercase() && before_letter.is_none_or(|c| c.is_whitespace() || c == '.') => {
+            !is_initial(&before[..before.len() - letter.len_utf8()], letter, after)
+        },
+        _ => true,
+    }
+}
+
+// whether the single uppercase `letter` between `head` and `tail`, followed by a period, is an initial of a name:
+// next to another initial ("J. R. R.", "U.S."), or between capitalized words ("George W. Bush"). The pronoun "I"
+// is only an initial next to an earlier one, and a letter after a word ending in punctuation ("Grade: A.") never is
+fn is_initial(head: &str, letter: char, tail: &str) -> bool {
+    let is_initial_at = |text: &str| {
+        let mut chars = text.chars();
+        matches!((chars.next(), chars.next()), (Some(initial), Some('.')) if initial.is_uppercase())
+            && chars.next().is_none_or(|c| c.is_whitespace() || c.is_uppercase())
+    };
+    let head = head.trim_end_matches(' ');
+    let follows_initial = head.ends_with('.') && {
+        let mut previous = head[..head.len() - 1].chars().rev();
+        matches!((previous.next(), previous.next()), (Some(initial), before) if initial.is_uppercase() && before.is_none_or(|c| c.is_whitespace() || c == '.'))
+    };
+    if follows_initial {
+        return true;
+    }
+    if letter == 'I' {
+        return false;
+    }
+    let tail = tail.trim_start_matches(' ');
+    let previous_word = head.rsplit(char::is_whitespace).next().unwrap_or_default();
+    let after_name = previous_word.starts_with(char::is_uppercase) && previous_word.chars().all(char::is_alphabetic);
+    is_initial_at(tail) || (after_name && tail.starts_with(char::is_uppercase))
+}
+
+// the non-empty pieces of `text` between the separators that end sentences
+fn split_sentences<'a>(separator: &Regex, text: &'a str) -> impl Iterator<Item = &'a str> {
+    let mut start = 0;
+    let mut pieces = vec![];
+    for found in separator.find_iter(text).filter(|found| is_sentence_boundary(text, found)) {
+        pieces.push(&text[start..found.start()]);
+        start = found.end();
+    }
+    pieces.push(&text[start..]);
+    pieces.into_iter().filter(|piece| !piece.is_empty())
+}
+
+// byte ranges of the sentences of `text`, matching `tokenize_into_sentences` but including terminal punctuation
+pub(crate) fn sentence_bounds(text: &str) -> Vec<(usize, usize)> {
+    let mut bounds = vec![];
+    let mut start = 0;
+    for separator in shared_tokenizer().separator.find_iter(text).filter(|separator| is_sentence_boundary(text, separator)) {
+        // terminal punctuation followed by a quote doesn't end the sentence
+        if text[separator.end()..].starts_with('"') && separator.len() == 1 {
+            continue;
+        }
+        let end = separator.start() + 1;
+        if start < separator.start() {
+            bounds.push((start, end));
+        }
+        start = separator.end();
+    }
+    if start < text.len() {
+        bounds.push((start, text.len()));
+    }
+    bounds
+}
+
+/// Converts `tokens` back to a sentence by joining them with single spaces, so that
+/// tokenizing the result with [`tokenize_sentence`] gives back the same tokens.
+///
+/// Punctuation and casing removed during tokenization aren't restored; use [`tokenize_sentence_with_spans`]
+/// to recover the original text of each token.
 ///
 /// # Examples
 ///
 /// ```
 /// use rnltk::token;
 /// 
-/// let text = "Why hello there. General Kenobi!";
-/// let tokens = vec!["why", "hello", "there", "gener", "kenobi"];
-/// let tokenized_text = token::tokenize_stemmed_sentence(text);
+/// let tokens = token::tokenize_sentence("Why hello there. General Kenobi!");
+/// let text = token::detokenize(&tokens);
 ///
-/// assert_eq!(tokens, tokenized_text);
+/// assert_eq!(text, "why hello there general kenobi");
+/// assert_eq!(token::tokenize_sentence(&text), tokens);
 /// ```
-pub fn tokenize_stemmed_sentence(sentence: &str) -> Vec<String> {
-    let punctuation = Regex::new(r#"[!"\#$%&'()*+,-./:;<=>?@\[\]^_`{|}~]+"#).expect("Invalid regex");
-    let updated_sentence: &str = &punctuation.replace_all(sentence, "");
-
-    let tokens: Vec<String> = updated_sentence
-        .split(' ')
-        .map(|s| s.trim())
-        .filter(|s| !s.is_empty())
-        .map(|s| stem::get(s).unwrap_or_else(|_| s.to_string()))
-        .collect();
-    
-    tokens
+pub fn detokenize<T: AsRef<str>>(tokens: &[T]) -> String {
+    tokens.iter().map(|token| token.as_ref()).collect::<Vec<&str>>().join(" ")
 }
 
-/// Converts `sentence` to stemmed token vector without stop words.
+/// Gets a count of all phrases in `text`, using [`get_stop_words`] as phrase boundaries.
+/// See [`get_phrase_frequencies_with_stop_words`].
 ///
 /// # Examples
 ///
 /// ```
 /// use rnltk::token;
 /// 
-/// let text = "Why hello there. General Kenobi!";
-/// let tokens = vec!["hello", "gener", "kenobi"];
-/// let stop_words = token::get_stop_words();
-/// let tokenized_text = token::tokenize_stemmed_sentence_without_stop_words(text, stop_words);
+/// let text = "The machine learning team and the data science team. Machine learning is fun!";
+/// let phrase_frequencies = token::get_phrase_frequencies(text);
 ///
-/// assert_eq!(tokens, tokenized_text);
+/// assert_eq!(phrase_frequencies["machine learning team"], 1.);
+/// assert_eq!(phrase_frequencies["machine learning"], 1.);
+/// assert_eq!(phrase_frequencies["data science team"], 1.);
 /// ```
-pub fn tokenize_stemmed_sentence_without_stop_words(sentence: &str, stop_words: Vec<String>) -> Vec<String> {
-    let punctuation = Regex::new(r#"[!"\#$%&'()*+,-./:;<=>?@\[\]^_`{|}~]+"#).expect("Invalid regex");
-    let updated_sentence: &str = &punctuation.replace_all(sentence, "");
-
-    let tokens: Vec<String> = updated_sentence
-        .split(' ')
-        .map(|token| token.trim().to_ascii_lowercase())
-        .filter(|token| !token.is_empty() && !stop_words.contains(&token.to_string()))
-        .map(|token| stem::get(&token).unwrap_or_else(|_| token.to_string()))
-        .collect();
-
-    tokens
+pub fn get_phrase_frequencies(text: &str) -> BTreeMap<String, f64> {
+    get_phrase_frequencies_with_stop_words(text, get_stop_words())
 }
 
-/// Tokenize sentence based on a given configuration.
-/// 
-/// This function will be deprecated in the future once `rnltk` hits version 1.0
-/// and functionality will be moved to `tokenize_sentence`.
-/// 
+/// Gets a count of all phrases in `text`, where a phrase is a run of consecutive words that aren't
+/// `stop_words`, within a sentence and not crossing clause punctuation such as commas.
+///
+/// Without a part-of-speech tagger this approximates noun-phrase chunks ("the *data science team*
+/// met ..."), giving more readable "top terms" than single tokens. Phrases are lowercased and their
+/// words joined by single spaces.
+///
 /// # Examples
 ///
 /// ```
 /// use rnltk::token;
 /// 
-/// let token_config = token::TokenConfig::default();
-/// let text = "Why hello there. General Kenobi!";
-/// let tokens = vec!["hello", "gener", "kenobi"];
-/// let tokenized_text = token::tokenize_sentence_configurable(text, token_config);
+/// let stop_words = vec!["of".to_string(), "the".to_string()];
+/// let phrase_frequencies = token::get_phrase_frequencies_with_stop_words("Return of the Jedi, Jedi Knights", stop_words);
 ///
-/// assert_eq!(tokens, tokenized_text);
+/// assert_eq!(phrase_frequencies.keys().collect::<Vec<&String>>(), vec!["jedi", "jedi knights", "return"]);
 /// ```
-pub fn tokenize_sentence_configurable(sentence: &str, config: TokenConfig) -> Vec<String> {
-    if config.remove_stop_words && config.stem {
-        tokenize_stemmed_sentence_without_stop_words(sentence, config.stop_words)
-    } else if config.remove_stop_words {
-        tokenize_sentence_without_stop_words(sentence, config.stop_words)
-    } else if config.stem {
-        tokenize_stemmed_sentence(sentence)
-    } else {
-        tokenize_sentence(sentence)
+pub fn get_phrase_frequencies_with_stop_words(text: &str, stop_words: impl Into<StopWords>) -> BTreeMap<String, f64> {
+    let mut phrase_frequencies: BTreeMap<String, f64> = BTreeMap::new();
+    for phrase in get_phrases(text, &stop_words.into()) {
+        *phrase_frequencies.entry(phrase.join(" ")).or_insert(0.) += 1.;
+    }
+    phrase_frequencies
+}
+
+// every phrase of `text` in order of appearance, as split by `get_phrase_frequencies_with_stop_words`
+pub(crate) fn get_phrases(text: &str, stop_words: &StopWords) -> Vec<Vec<String>> {
+    let clause_separator = Regex::new(r#"[,;:()\[\]{}"]"#).expect("Invalid regex");
+    let mut phrases = vec![];
+    for sentence in tokenize_into_sentences(text) {
+        for clause in clause_separator.split(&sentence) {
+            let tokens = tokenize_sentence(clause);
+            phrases.extend(tokens.split(|token| stop_words.contains(token)).filter(|phrase| !phrase.is_empty()).map(<[String]>::to_vec));
+        }
     }
+    phrases
 }
 
 /// Gets a count of all words from a vector of `word_tokens`.
@@ -202,12 +3390,7 @@ pub fn tokenize_sentence_configurable(sentence: &str, config: TokenConfig) -> Ve
 /// assert_eq!(word_counts, term_frequencies);
 /// ```
 pub fn get_term_frequencies_from_word_vector(word_tokens: Vec<&str>) -> BTreeMap<String, f64> {
-    let mut word_counts: BTreeMap<String, f64> = BTreeMap::new();
-    for word in word_tokens {
-        let count = word_counts.entry(word.to_string()).or_insert(0.);
-        *count += 1.;
-    }
-    word_counts
+    get_term_frequencies_from_word_vector_configurable(word_tokens, TokenConfig::plain())
 }
 
 /// Gets a count of all words from a vector of `word_tokens` without stop words.
@@ -225,15 +3408,8 @@ pub fn get_term_frequencies_from_word_vector(word_tokens: Vec<&str>) -> BTreeMap
 ///
 /// assert_eq!(word_counts, term_frequencies);
 /// ```
-pub fn get_term_frequencies_from_word_vector_without_stop_words(word_tokens: Vec<&str>, stop_words: Vec<String>) -> BTreeMap<String, f64> {
-    let mut word_counts: BTreeMap<String, f64> = BTreeMap::new();
-    for word in word_tokens {
-        if !stop_words.contains(&word.to_string()) {
-            let count = word_counts.entry(word.to_string()).or_insert(0.);
-            *count += 1.;
-        }
-    }
-    word_counts
+pub fn get_term_frequencies_from_word_vector_without_stop_words(word_tokens: Vec<&str>, stop_words: impl Into<StopWords>) -> BTreeMap<String, f64> {
+    get_term_frequencies_from_word_vector_configurable(word_tokens, TokenConfig { remove_stop_words: true, stop_words: stop_words.into(), ..TokenConfig::plain() })
 }
 
 /// Gets a count of all stemmed words from a vector of `word_tokens`.
@@ -253,12 +3429,7 @@ pub fn get_term_frequencies_from_word_vector_without_stop_words(word_tokens: Vec
 /// assert_eq!(word_counts, term_frequencies);
 /// ```
 pub fn get_stemmed_term_frequencies_from_word_vector(word_tokens: Vec<&str>) -> BTreeMap<String, f64> {
-    let mut word_counts: BTreeMap<String, f64> = BTreeMap::new();
-    for word in word_tokens {
-        let count = word_counts.entry(stem::get(word).unwrap_or_else(|_| word.to_string())).or_insert(0.);
-        *count += 1.;
-    }
-    word_counts
+    get_term_frequencies_from_word_vector_configurable(word_tokens, TokenConfig { stem: true, ..TokenConfig::plain() })
 }
 
 /// Gets a count of all stemmed words from a vector of `word_tokens` without stop words.
@@ -278,15 +3449,8 @@ pub fn get_stemmed_term_frequencies_from_word_vector(word_tokens: Vec<&str>) ->
 ///
 /// assert_eq!(word_counts, term_frequencies);
 /// ```
-pub fn get_stemmed_term_frequencies_from_word_vector_without_stop_words(word_tokens: Vec<&str>, stop_words: Vec<String>) -> BTreeMap<String, f64> {
-    let mut word_counts: BTreeMap<String, f64> = BTreeMap::new();
-    for word in word_tokens {
-        if !stop_words.contains(&word.to_string()) {
-            let count = word_counts.entry(stem::get(word).unwrap_or_else(|_| word.to_string())).or_insert(0.);
-            *count += 1.;
-        }
-    }
-    word_counts
+pub fn get_stemmed_term_frequencies_from_word_vector_without_stop_words(word_tokens: Vec<&str>, stop_words: impl Into<StopWords>) -> BTreeMap<String, f64> {
+    get_term_frequencies_from_word_vector_configurable(word_tokens, TokenConfig { stem: true, remove_stop_words: true, stop_words: stop_words.into(), ..TokenConfig::plain() })
 }
 
 /// Gets a count of all words from a vector of `word_tokens` based on a given configuration.
@@ -308,15 +3472,21 @@ pub fn get_stemmed_term_frequencies_from_word_vector_without_stop_words(word_tok
 /// assert_eq!(word_counts, term_frequencies);
 /// ```
 pub fn get_term_frequencies_from_word_vector_configurable(word_tokens: Vec<&str>, config: TokenConfig) -> BTreeMap<String, f64> {
-    if config.remove_stop_words && config.stem {
-        get_stemmed_term_frequencies_from_word_vector_without_stop_words(word_tokens, config.stop_words)
-    } else if config.remove_stop_words {
-        get_term_frequencies_from_word_vector_without_stop_words(word_tokens, config.stop_words)
-    } else if config.stem {
-        get_stemmed_term_frequencies_from_word_vector(word_tokens)
+    let splits_contractions = matches!(config.contractions, Contractions::Expand | Contractions::Split);
+    let words: Vec<String> = if splits_contractions || config.normalization.is_some() || config.strip_accents || config.hyphens != Hyphens::Strip {
+        word_tokens.iter()
+            .flat_map(|word| {
+                let word = normalize_word(word, config.normalization, config.strip_accents);
+                hyphenated_pieces(&word, config.hyphens).into_iter().map(|(piece, _)| piece).collect::<Vec<String>>()
+            })
+            .flat_map(|word| {
+                if splits_contractions && word.contains(['\'', '\u{2019}']) { split_contraction(&word, config.contractions, config.case_folding, false) } else { vec![word] }
+            })
+            .collect()
     } else {
-        get_term_frequencies_from_word_vector(word_tokens)
-    }
+        word_tokens.into_iter().map(String::from).collect()
+    };
+    counts_to_weighted_frequencies(&count_terms(config.apply(words)), config.term_weighting)
 }
 
 /// Gets a count of all words from a `sentence`.
@@ -334,9 +3504,7 @@ pub fn get_term_frequencies_from_word_vector_configurable(word_tokens: Vec<&str>
 /// assert_eq!(word_counts, term_frequencies);
 /// ```
 pub fn get_term_frequencies_from_sentence(sentence: &str) -> BTreeMap<String, f64> {
-    let sentence_tokens = tokenize_sentence(sentence);
-    let sentence_tokens: Vec<&str> = sentence_tokens.iter().map(|s| s.as_str()).collect();
-    get_term_frequencies_from_word_vector(sentence_tokens)
+    shared_tokenizer().get_term_frequencies_from_sentence(sentence)
 }
 
 /// Gets a count of all words from a `sentence` without `stop_words`.
@@ -354,10 +3522,8 @@ pub fn get_term_frequencies_from_sentence(sentence: &str) -> BTreeMap<String, f6
 ///
 /// assert_eq!(word_counts, term_frequencies);
 /// ```
-pub fn get_term_frequencies_from_sentence_without_stop_words(sentence: &str, stop_words: Vec<String>) -> BTreeMap<String, f64> {
-    let sentence_tokens = tokenize_sentence(sentence);
-    let sentence_tokens: Vec<&str> = sentence_tokens.iter().map(|s| s.as_str()).collect();
-    get_term_frequencies_from_word_vector_without_stop_words(sentence_tokens, stop_words)
+pub fn get_term_frequencies_from_sentence_without_stop_words(sentence: &str, stop_words: impl Into<StopWords>) -> BTreeMap<String, f64> {
+    shared_tokenizer().get_term_frequencies_from_sentence_without_stop_words(sentence, stop_words)
 }
 
 /// Gets a count of all stemmed words from a `sentence`.
@@ -375,9 +3541,7 @@ pub fn get_term_frequencies_from_sentence_without_stop_words(sentence: &str, sto
 /// assert_eq!(word_counts, term_frequencies);
 /// ```
 pub fn get_stemmed_term_frequencies_from_sentence(sentence: &str) -> BTreeMap<String, f64> {
-    let sentence_tokens = tokenize_sentence(sentence);
-    let sentence_tokens: Vec<&str> = sentence_tokens.iter().map(|s| s.as_str()).collect();
-    get_stemmed_term_frequencies_from_word_vector(sentence_tokens)
+    shared_tokenizer().get_stemmed_term_frequencies_from_sentence(sentence)
 }
 
 /// Gets a count of all stemmed words from a `sentence` without `stop_words`.
@@ -395,10 +3559,8 @@ pub fn get_stemmed_term_frequencies_from_sentence(sentence: &str) -> BTreeMap<St
 ///
 /// assert_eq!(word_counts, term_frequencies);
 /// ```
-pub fn get_stemmed_term_frequencies_from_sentence_without_stop_words(sentence: &str, stop_words: Vec<String>) -> BTreeMap<String, f64> {
-    let sentence_tokens = tokenize_sentence(sentence);
-    let sentence_tokens: Vec<&str> = sentence_tokens.iter().map(|s| s.as_str()).collect();
-    get_stemmed_term_frequencies_from_word_vector_without_stop_words(sentence_tokens, stop_words)
+pub fn get_stemmed_term_frequencies_from_sentence_without_stop_words(sentence: &str, stop_words: impl Into<StopWords>) -> BTreeMap<String, f64> {
+    shared_tokenizer().get_stemmed_term_frequencies_from_sentence_without_stop_words(sentence, stop_words)
 }
 
 /// Gets a count of all words from a `sentence` based on a given configuration.
@@ -420,18 +3582,11 @@ pub fn get_stemmed_term_frequencies_from_sentence_without_stop_words(sentence: &
 /// assert_eq!(word_counts, term_frequencies);
 /// ```
 pub fn get_term_frequencies_from_sentence_configurable(sentence: &str, config: TokenConfig) -> BTreeMap<String, f64> {
-    if config.remove_stop_words && config.stem {
-        get_stemmed_term_frequencies_from_sentence_without_stop_words(sentence, config.stop_words)
-    } else if config.remove_stop_words {
-        get_term_frequencies_from_sentence_without_stop_words(sentence, config.stop_words)
-    } else if config.stem {
-        get_stemmed_term_frequencies_from_sentence(sentence)
-    } else {
-        get_term_frequencies_from_sentence(sentence)
-    }
+    shared_tokenizer().get_term_frequencies_from_sentence_configurable(sentence, config)
 }
 
-/// Gets a count of all words from a vector of `sentence`s.
+/// Gets a count of all words from a vector of `sentence`s. Every sentence holds every word of all sentences,
+/// with a count of 0 for words it doesn't contain; for large corpora use [`get_sparse_term_frequencies_from_sentences`].
 ///
 /// # Examples
 ///
@@ -457,20 +3612,7 @@ pub fn get_term_frequencies_from_sentence_configurable(sentence: &str, config: T
 /// assert_eq!(vec![word_counts1, word_counts2, word_counts3, word_counts4], term_frequencies);
 /// ```
 pub fn get_term_frequencies_from_sentences(sentences: &[&str]) -> Vec<BTreeMap<String, f64>> {
-    let mut total_terms: Vec<String> = vec![];
-    let mut term_frequencies: Vec<BTreeMap<String, f64>> = sentences.iter().map(|sentence| {
-        let frequencies = get_term_frequencies_from_sentence(sentence);
-        total_terms.extend(frequencies.keys().cloned().collect::<Vec<String>>());
-        frequencies
-    }).collect();
-    for frequency_counts in &mut term_frequencies {
-        for term in &total_terms {
-            if !frequency_counts.contains_key(term) {
-                frequency_counts.insert(term.to_string(), 0.);
-            }
-        }
-    }
-    term_frequencies
+    shared_tokenizer().get_term_frequencies_from_sentences(sentences)
 }
 
 /// Gets a count of all words from a vector of `sentence`s without `stop_words`.
@@ -499,21 +3641,8 @@ pub fn get_term_frequencies_from_sentences(sentences: &[&str]) -> Vec<BTreeMap<S
 ///
 /// assert_eq!(vec![word_counts1, word_counts2, word_counts3, word_counts4], term_frequencies);
 /// ```
-pub fn get_term_frequencies_from_sentences_without_stop_words(sentences: &[&str], stop_words: Vec<String>) -> Vec<BTreeMap<String, f64>> {
-    let mut total_terms: Vec<String> = vec![];
-    let mut term_frequencies: Vec<BTreeMap<String, f64>> = sentences.iter().map(|sentence| {
-        let frequencies = get_term_frequencies_from_sentence_without_stop_words(sentence, stop_words.clone());
-        total_terms.extend(frequencies.keys().cloned().collect::<Vec<String>>());
-        frequencies
-    }).collect();
-    for frequency_counts in &mut term_frequencies {
-        for term in &total_terms {
-            if !frequency_counts.contains_key(term) {
-                frequency_counts.insert(term.to_string(), 0.);
-            }
-        }
-    }
-    term_frequencies
+pub fn get_term_frequencies_from_sentences_without_stop_words(sentences: &[&str], stop_words: impl Into<StopWords>) -> Vec<BTreeMap<String, f64>> {
+    shared_tokenizer().get_term_frequencies_from_sentences_without_stop_words(sentences, stop_words)
 }
 
 /// Gets a count of all stemmed words from a vector of `sentence`s.
@@ -539,108 +3668,316 @@ pub fn get_term_frequencies_from_sentences_without_stop_words(sentences: &[&str]
 /// ]);
 /// let term_frequencies = token::get_stemmed_term_frequencies_from_sentences(&sentences);
 ///
-/// assert_eq!(vec![word_counts1, word_counts2, word_counts3, word_counts4], term_frequencies);
+/// assert_eq!(vec![word_counts1, word_counts2, word_counts3, word_counts4], term_frequencies);
+/// ```
+pub fn get_stemmed_term_frequencies_from_sentences(sentences: &[&str]) -> Vec<BTreeMap<String, f64>> {
+    shared_tokenizer().get_stemmed_term_frequencies_from_sentences(sentences)
+}
+
+
+/// Gets a count of all stemmed words from a vector of `sentence`s without `stop_words`.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::BTreeMap;
+/// use rnltk::token;
+/// 
+/// let sentences = vec!["fear leads to anger", "anger leads to hatred", "hatred leads to conflict", "conflict leads to suffering."];
+/// let stop_words = token::get_stop_words();
+/// let word_counts1 = BTreeMap::from([
+///     ("fear".to_string(), 1.), ("lead".to_string(), 1.), ("to".to_string(), 1.), ("anger".to_string(), 1.), ("hatr".to_string(), 0.), ("conflict".to_string(), 0.), ("suffer".to_string(), 0.)
+/// ]);
+/// let word_counts2 = BTreeMap::from([
+///     ("fear".to_string(), 0.), ("lead".to_string(), 1.), ("to".to_string(), 1.), ("anger".to_string(), 1.), ("hatr".to_string(), 1.), ("conflict".to_string(), 0.), ("suffer".to_string(), 0.)
+/// ]);
+/// let word_counts3 = BTreeMap::from([
+///     ("fear".to_string(), 0.), ("lead".to_string(), 1.), ("to".to_string(), 1.), ("anger".to_string(), 0.), ("hatr".to_string(), 1.), ("conflict".to_string(),1.), ("suffer".to_string(), 0.)
+/// ]);
+/// let word_counts4 = BTreeMap::from([
+///     ("fear".to_string(), 0.), ("lead".to_string(), 1.), ("to".to_string(), 1.), ("anger".to_string(), 0.), ("hatr".to_string(), 0.), ("conflict".to_string(), 1.), ("suffer".to_string(), 1.)
+/// ]);
+/// let term_frequencies = token::get_stemmed_term_frequencies_from_sentences(&sentences);
+///
+/// assert_eq!(vec![word_counts1, word_counts2, word_counts3, word_counts4], term_frequencies);
+/// ```
+pub fn get_stemmed_term_frequencies_from_sentences_without_stop_words(sentences: &[&str], stop_words: impl Into<StopWords>) -> Vec<BTreeMap<String, f64>> {
+    shared_tokenizer().get_stemmed_term_frequencies_from_sentences_without_stop_words(sentences, stop_words)
+}
+
+/// Gets a count of all words from a vector of `word_tokens` based on a given configuration.
+/// 
+/// This function will be deprecated in the future once `rnltk` hits version 1.0
+/// and functionality will be moved to `get_term_frequencies_from_word_vector`.
+/// 
+/// # Examples
+///
+/// ```
+/// use std::collections::BTreeMap;
+/// use rnltk::token;
+/// 
+/// let token_config = token::TokenConfig::default();
+/// let sentences = vec!["fear leads to anger", "anger leads to hatred", "hatred leads to conflict", "conflict leads to suffering."];
+/// let word_counts1 = BTreeMap::from([
+///     ("fear".to_string(), 1.), ("lead".to_string(), 1.), ("anger".to_string(), 1.), ("hatr".to_string(), 0.), ("conflict".to_string(), 0.), ("suffer".to_string(), 0.)
+/// ]);
+/// let word_counts2 = BTreeMap::from([
+///     ("fear".to_string(), 0.), ("lead".to_string(), 1.), ("anger".to_string(), 1.), ("hatr".to_string(), 1.), ("conflict".to_string(), 0.), ("suffer".to_string(), 0.)
+/// ]);
+/// let word_counts3 = BTreeMap::from([
+///     ("fear".to_string(), 0.), ("lead".to_string(), 1.), ("anger".to_string(), 0.), ("hatr".to_string(), 1.), ("conflict".to_string(),1.), ("suffer".to_string(), 0.)
+/// ]);
+/// let word_counts4 = BTreeMap::from([
+///     ("fear".to_string(), 0.), ("lead".to_string(), 1.), ("anger".to_string(), 0.), ("hatr".to_string(), 0.), ("conflict".to_string(), 1.), ("suffer".to_string(), 1.)
+/// ]);
+/// let term_frequencies = token::get_term_frequencies_from_sentences_configurable(&sentences, token_config);
+///
+/// assert_eq!(vec![word_counts1, word_counts2, word_counts3, word_counts4], term_frequencies);
+/// ```
+pub fn get_term_frequencies_from_sentences_configurable(sentences: &[&str], config: TokenConfig) -> Vec<BTreeMap<String, f64>> {
+    shared_tokenizer().get_term_frequencies_from_sentences_configurable(sentences, config)
+}
+
+/// Gets the number of `documents` each word appears in, tokenizing each document with [`tokenize_sentence`].
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::token;
+/// 
+/// let document_frequencies = token::get_document_frequencies(&["fear leads to anger", "anger leads to hatred. Hatred!"]);
+///
+/// assert_eq!(document_frequencies["anger"], 2);
+/// assert_eq!(document_frequencies["hatred"], 1);
+/// ```
+pub fn get_document_frequencies(documents: &[&str]) -> BTreeMap<String, u64> {
+    shared_tokenizer().get_document_frequencies(documents)
+}
+
+/// Gets the number of `documents` each word appears in based on a given configuration, like [`get_document_frequencies`].
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::token;
+/// 
+/// let documents = ["fear leads to anger", "anger leads to hatred"];
+/// let document_frequencies = token::get_document_frequencies_configurable(&documents, token::TokenConfig::default());
+///
+/// assert_eq!(document_frequencies["lead"], 2);
+/// assert!(!document_frequencies.contains_key("to"));
+/// ```
+pub fn get_document_frequencies_configurable(documents: &[&str], config: TokenConfig) -> BTreeMap<String, u64> {
+    shared_tokenizer().get_document_frequencies_configurable(documents, config)
+}
+
+/// Gets the inverse document frequency of every term of `document_frequencies` over `num_documents` documents,
+/// \(idf_i = ln(n / n_i)\), as used by
+/// [`DocumentTermFrequencies::get_tfidf_from_term_frequencies`](crate::document::DocumentTermFrequencies::get_tfidf_from_term_frequencies).
+/// Terms that appear in no documents are left out.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::token;
+/// 
+/// let documents = ["fear leads to anger", "anger leads to hatred", "hatred leads to conflict"];
+/// let document_frequencies = token::get_document_frequencies(&documents);
+/// let inverse_document_frequencies = token::get_inverse_document_frequencies(&document_frequencies, documents.len());
+///
+/// assert_eq!(inverse_document_frequencies["leads"], 0.);
+/// assert_eq!(inverse_document_frequencies["fear"], 3_f64.ln());
+/// ```
+pub fn get_inverse_document_frequencies(document_frequencies: &BTreeMap<String, u64>, num_documents: usize) -> BTreeMap<String, f64> {
+    document_frequencies.iter()
+        .filter(|(_, document_frequency)| **document_frequency > 0)
+        .map(|(term, document_frequency)| (term.clone(), (num_documents as f64 / *document_frequency as f64).ln()))
+        .collect()
+}
+
+/// Struct for holding the counts of terms in each of a set of documents, storing only the terms each document
+/// contains. Aligned counts such as [`get_term_frequencies_from_sentences`] hold every term of every document,
+/// so they grow with documents × vocabulary; sparse counts grow with the number of distinct terms per document.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct SparseTermFrequencies {
+    /// The vocabulary shared by all documents, in sorted order
+    pub terms: Vec<String>,
+    /// Non-zero counts of each document as `(term index, count)` pairs, sorted by term index
+    pub documents: Vec<Vec<(usize, f64)>>,
+}
+
+impl SparseTermFrequencies {
+    // builds the shared vocabulary once from each document's counts, which hold only the terms it contains
+    fn from_term_counts(term_counts: &[BTreeMap<String, f64>]) -> Self {
+        let vocabulary: BTreeSet<&str> = term_counts.iter().flat_map(|counts| counts.keys().map(String::as_str)).collect();
+        let term_indices: HashMap<&str, usize> = vocabulary.iter().enumerate().map(|(index, term)| (*term, index)).collect();
+        // counts are sorted by term, and so by term index
+        let documents = term_counts.iter().map(|counts| {
+            counts.iter().map(|(term, count)| (term_indices[term.as_str()], *count)).collect()
+        }).collect();
+        let terms = vocabulary.into_iter().map(str::to_string).collect();
+        SparseTermFrequencies { terms, documents }
+    }
+
+    /// Gets the number of terms in the shared vocabulary.
+    pub fn num_terms(&self) -> usize {
+        self.terms.len()
+    }
+
+    /// Gets the number of documents.
+    pub fn num_documents(&self) -> usize {
+        self.documents.len()
+    }
+
+    /// Gets the count of `term` in the document at `document_index`, which is 0 if the term or document doesn't exist.
+    pub fn get(&self, document_index: usize, term: &str) -> f64 {
+        let Ok(term_index) = self.terms.binary_search_by(|candidate| candidate.as_str().cmp(term)) else {
+            return 0.;
+        };
+        self.documents.get(document_index)
+            .and_then(|counts| counts.binary_search_by_key(&term_index, |(index, _)| *index).ok().map(|position| counts[position].1))
+            .unwrap_or(0.)
+    }
+
+    /// Gets the counts of the document at `document_index` by term, holding only the terms it contains.
+    pub fn get_document(&self, document_index: usize) -> BTreeMap<String, f64> {
+        self.documents.get(document_index).into_iter().flatten()
+            .map(|(term_index, count)| (self.terms[*term_index].clone(), *count))
+            .collect()
+    }
+}
+
+/// Gets a sparse count of all words from `sentences`: a shared vocabulary and the non-zero counts of each sentence.
+/// The counts match [`get_term_frequencies_from_sentences`] without the zeros.
+/// [`DocumentTermFrequencies::from_sparse`](crate::document::DocumentTermFrequencies::from_sparse)
+/// turns them into a term frequency matrix.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::token;
+/// 
+/// let term_frequencies = token::get_sparse_term_frequencies_from_sentences(&["fear leads to anger", "anger leads to hatred"]);
+///
+/// assert_eq!(term_frequencies.terms, vec!["anger", "fear", "hatred", "leads", "to"]);
+/// assert_eq!(term_frequencies.documents[1], vec![(0, 1.), (2, 1.), (3, 1.), (4, 1.)]);
+/// assert_eq!(term_frequencies.get(1, "fear"), 0.);
+/// ```
+pub fn get_sparse_term_frequencies_from_sentences(sentences: &[&str]) -> SparseTermFrequencies {
+    shared_tokenizer().get_sparse_term_frequencies_from_sentences(sentences)
+}
+
+/// Gets a sparse count of all words from `sentences` based on a given configuration, like
+/// [`get_sparse_term_frequencies_from_sentences`].
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::token;
+/// 
+/// let sentences = ["fear leads to anger", "anger leads to hatred"];
+/// let term_frequencies = token::get_sparse_term_frequencies_from_sentences_configurable(&sentences, token::TokenConfig::default());
+///
+/// assert_eq!(term_frequencies.terms, vec!["anger", "fear", "hatr", "lead"]);
+/// assert_eq!(term_frequencies.get(0, "lead"), 1.);
+/// ```
+pub fn get_sparse_term_frequencies_from_sentences_configurable(sentences: &[&str], config: TokenConfig) -> SparseTermFrequencies {
+    shared_tokenizer().get_sparse_term_frequencies_from_sentences_configurable(sentences, config)
+}
+
+/// Gets integer counts of all words from a `sentence`. Counts are exact, unlike the `f64` counts of
+/// [`get_term_frequencies_from_sentence`], which [`counts_to_frequencies`] converts them to.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::BTreeMap;
+/// use rnltk::token;
+/// 
+/// let sentence = "fear leads to anger, anger leads to hatred.";
+/// let word_counts = BTreeMap::from([("fear".to_string(), 1), ("leads".to_string(), 2), ("to".to_string(), 2), ("anger".to_string(), 2), ("hatred".to_string(), 1)]);
+///
+/// assert_eq!(token::get_term_counts_from_sentence(sentence), word_counts);
+/// ```
+pub fn get_term_counts_from_sentence(sentence: &str) -> BTreeMap<String, u64> {
+    shared_tokenizer().get_term_counts_from_sentence(sentence)
+}
+
+/// Gets integer counts of all words from a `sentence` based on a given configuration, like
+/// [`get_term_frequencies_from_sentence_configurable`].
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::token;
+/// 
+/// let sentence = "fear leads to anger, anger leads to hatred.";
+/// let term_counts = token::get_term_counts_from_sentence_configurable(sentence, token::TokenConfig::default());
+///
+/// assert_eq!(term_counts["lead"], 2);
+/// assert!(!term_counts.contains_key("to"));
+/// ```
+pub fn get_term_counts_from_sentence_configurable(sentence: &str, config: TokenConfig) -> BTreeMap<String, u64> {
+    shared_tokenizer().get_term_counts_from_sentence_configurable(sentence, config)
+}
+
+/// Gets aligned integer counts of all words from `sentences`, where every map contains every term,
+/// like [`get_term_frequencies_from_sentences`].
+///
+/// # Examples
+///
 /// ```
-pub fn get_stemmed_term_frequencies_from_sentences(sentences: &[&str]) -> Vec<BTreeMap<String, f64>> {
-    let mut total_terms: Vec<String> = vec![];
-    let mut term_frequencies: Vec<BTreeMap<String, f64>> = sentences.iter().map(|sentence| {
-        let frequencies = get_stemmed_term_frequencies_from_sentence(sentence);
-        total_terms.extend(frequencies.keys().cloned().collect::<Vec<String>>());
-        frequencies
-    }).collect();
-    for frequency_counts in &mut term_frequencies {
-        for term in &total_terms {
-            if !frequency_counts.contains_key(term) {
-                frequency_counts.insert(term.to_string(), 0.);
-            }
-        }
-    }
-    term_frequencies
+/// use rnltk::token;
+/// 
+/// let term_counts = token::get_term_counts_from_sentences(&["fear leads to anger", "anger leads to hatred"]);
+///
+/// assert_eq!((term_counts[0]["fear"], term_counts[1]["fear"]), (1, 0));
+/// assert_eq!(term_counts[1]["hatred"], 1);
+/// ```
+pub fn get_term_counts_from_sentences(sentences: &[&str]) -> Vec<BTreeMap<String, u64>> {
+    shared_tokenizer().get_term_counts_from_sentences(sentences)
 }
 
-
-/// Gets a count of all stemmed words from a vector of `sentence`s without `stop_words`.
+/// Gets aligned integer counts of all words from `sentences` based on a given configuration,
+/// like [`get_term_frequencies_from_sentences_configurable`].
+/// [`DocumentTermFrequencies::from_term_counts`](crate::document::DocumentTermFrequencies::from_term_counts)
+/// turns them into a term frequency matrix.
 ///
 /// # Examples
 ///
 /// ```
-/// use std::collections::BTreeMap;
 /// use rnltk::token;
 /// 
-/// let sentences = vec!["fear leads to anger", "anger leads to hatred", "hatred leads to conflict", "conflict leads to suffering."];
-/// let stop_words = token::get_stop_words();
-/// let word_counts1 = BTreeMap::from([
-///     ("fear".to_string(), 1.), ("lead".to_string(), 1.), ("to".to_string(), 1.), ("anger".to_string(), 1.), ("hatr".to_string(), 0.), ("conflict".to_string(), 0.), ("suffer".to_string(), 0.)
-/// ]);
-/// let word_counts2 = BTreeMap::from([
-///     ("fear".to_string(), 0.), ("lead".to_string(), 1.), ("to".to_string(), 1.), ("anger".to_string(), 1.), ("hatr".to_string(), 1.), ("conflict".to_string(), 0.), ("suffer".to_string(), 0.)
-/// ]);
-/// let word_counts3 = BTreeMap::from([
-///     ("fear".to_string(), 0.), ("lead".to_string(), 1.), ("to".to_string(), 1.), ("anger".to_string(), 0.), ("hatr".to_string(), 1.), ("conflict".to_string(),1.), ("suffer".to_string(), 0.)
-/// ]);
-/// let word_counts4 = BTreeMap::from([
-///     ("fear".to_string(), 0.), ("lead".to_string(), 1.), ("to".to_string(), 1.), ("anger".to_string(), 0.), ("hatr".to_string(), 0.), ("conflict".to_string(), 1.), ("suffer".to_string(), 1.)
-/// ]);
-/// let term_frequencies = token::get_stemmed_term_frequencies_from_sentences(&sentences);
+/// let sentences = ["fear leads to anger", "anger leads to hatred"];
+/// let term_counts = token::get_term_counts_from_sentences_configurable(&sentences, token::TokenConfig::default());
 ///
-/// assert_eq!(vec![word_counts1, word_counts2, word_counts3, word_counts4], term_frequencies);
+/// assert_eq!(term_counts[1].keys().collect::<Vec<&String>>(), vec!["anger", "fear", "hatr", "lead"]);
 /// ```
-pub fn get_stemmed_term_frequencies_from_sentences_without_stop_words(sentences: &[&str], stop_words: Vec<String>) -> Vec<BTreeMap<String, f64>> {
-    let mut total_terms: Vec<String> = vec![];
-    let mut term_frequencies: Vec<BTreeMap<String, f64>> = sentences.iter().map(|sentence| {
-        let frequencies = get_stemmed_term_frequencies_from_sentence_without_stop_words(sentence, stop_words.clone());
-        total_terms.extend(frequencies.keys().cloned().collect::<Vec<String>>());
-        frequencies
-    }).collect();
-    for frequency_counts in &mut term_frequencies {
-        for term in &total_terms {
-            if !frequency_counts.contains_key(term) {
-                frequency_counts.insert(term.to_string(), 0.);
-            }
-        }
-    }
-    term_frequencies
+pub fn get_term_counts_from_sentences_configurable(sentences: &[&str], config: TokenConfig) -> Vec<BTreeMap<String, u64>> {
+    shared_tokenizer().get_term_counts_from_sentences_configurable(sentences, config)
 }
 
-/// Gets a count of all words from a vector of `word_tokens` based on a given configuration.
-/// 
-/// This function will be deprecated in the future once `rnltk` hits version 1.0
-/// and functionality will be moved to `get_term_frequencies_from_word_vector`.
-/// 
+/// Gets a sparse count of all words from already tokenized `documents`, skipping internal tokenization
+/// so tokens from custom tokenizers or external taggers are counted exactly as given.
+/// Like [`get_sparse_term_frequencies_from_sentences`], only the terms each document contains are stored.
+///
 /// # Examples
 ///
 /// ```
-/// use std::collections::BTreeMap;
 /// use rnltk::token;
 /// 
-/// let token_config = token::TokenConfig::default();
-/// let sentences = vec!["fear leads to anger", "anger leads to hatred", "hatred leads to conflict", "conflict leads to suffering."];
-/// let word_counts1 = BTreeMap::from([
-///     ("fear".to_string(), 1.), ("lead".to_string(), 1.), ("anger".to_string(), 1.), ("hatr".to_string(), 0.), ("conflict".to_string(), 0.), ("suffer".to_string(), 0.)
-/// ]);
-/// let word_counts2 = BTreeMap::from([
-///     ("fear".to_string(), 0.), ("lead".to_string(), 1.), ("anger".to_string(), 1.), ("hatr".to_string(), 1.), ("conflict".to_string(), 0.), ("suffer".to_string(), 0.)
-/// ]);
-/// let word_counts3 = BTreeMap::from([
-///     ("fear".to_string(), 0.), ("lead".to_string(), 1.), ("anger".to_string(), 0.), ("hatr".to_string(), 1.), ("conflict".to_string(),1.), ("suffer".to_string(), 0.)
-/// ]);
-/// let word_counts4 = BTreeMap::from([
-///     ("fear".to_string(), 0.), ("lead".to_string(), 1.), ("anger".to_string(), 0.), ("hatr".to_string(), 0.), ("conflict".to_string(), 1.), ("suffer".to_string(), 1.)
-/// ]);
-/// let term_frequencies = token::get_term_frequencies_from_sentences_configurable(&sentences, token_config);
+/// let documents = vec![vec!["New York", "is", "big"], vec!["is", "it", "?", "is"]];
+/// let term_frequencies = token::get_term_frequencies_from_tokenized_documents(&documents);
 ///
-/// assert_eq!(vec![word_counts1, word_counts2, word_counts3, word_counts4], term_frequencies);
+/// assert_eq!(term_frequencies.terms, vec!["?", "New York", "big", "is", "it"]);
+/// assert_eq!(term_frequencies.documents[1], vec![(0, 1.), (3, 2.), (4, 1.)]);
+/// assert_eq!(term_frequencies.get(0, "it"), 0.);
 /// ```
-pub fn get_term_frequencies_from_sentences_configurable(sentences: &[&str], config: TokenConfig) -> Vec<BTreeMap<String, f64>> {
-    if config.remove_stop_words && config.stem {
-        get_stemmed_term_frequencies_from_sentences_without_stop_words(sentences, config.stop_words)
-    } else if config.remove_stop_words {
-        get_term_frequencies_from_sentences_without_stop_words(sentences, config.stop_words)
-    } else if config.stem {
-        get_stemmed_term_frequencies_from_sentences(sentences)
-    } else {
-        get_term_frequencies_from_sentences(sentences)
-    }
+pub fn get_term_frequencies_from_tokenized_documents<T: AsRef<str>>(documents: &[Vec<T>]) -> SparseTermFrequencies {
+    let term_counts: Vec<BTreeMap<String, f64>> = documents.iter()
+        .map(|tokens| count_terms(tokens).into_iter().map(|(term, count)| (term, count as f64)).collect())
+        .collect();
+    SparseTermFrequencies::from_term_counts(&term_counts)
 }
 
 #[cfg(test)]
@@ -655,6 +3992,503 @@ mod tests {
         assert_eq!(tokens, tokenized_text);
     }
 
+    #[test]
+    fn test_streaming_sentences_match_batch() {
+        let text = "He said \"stop!\" and left.\nThen   nothing...   Why?\n\nThe end";
+        let reader = io::BufReader::with_capacity(4, text.as_bytes());
+        let sentences: Vec<String> = SentenceIter::new(reader).map(|sentence| sentence.unwrap()).collect();
+        assert_eq!(sentences, tokenize_into_sentences(text));
+    }
+
+    #[test]
+    fn test_streaming_stops_on_invalid_utf8() {
+        let bytes: &[u8] = b"Fine.\n\xff\xfe\nNever read.";
+        let results: Vec<io::Result<String>> = SentenceIter::new(bytes).collect();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].as_ref().unwrap(), "Fine");
+        assert_eq!(results[1].as_ref().unwrap_err().kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_tokenizer_matches_free_functions() {
+        let tokenizer = Tokenizer::new();
+        let sentences = ["Why hello there.", "General Kenobi! You are a bold one."];
+        for (stem, remove_stop_words) in [(true, true), (true, false), (false, true), (false, false)] {
+            let config = TokenConfig { stem, remove_stop_words, stop_words: get_stop_words().into(), ..TokenConfig::default() };
+            assert_eq!(tokenizer.tokenize_sentence_configurable(sentences[1], config.clone()), tokenize_sentence_configurable(sentences[1], config.clone()));
+            assert_eq!(tokenizer.get_term_frequencies_from_sentences_configurable(&sentences, config.clone()), get_term_frequencies_from_sentences_configurable(&sentences, config));
+        }
+    }
+
+    #[test]
+    fn test_emoji_tokens() {
+        let config = TokenConfig::builder().stem(true).remove_stop_words(false).emoji(Emoji::Keep).build();
+        let text = "Runners 🏃‍♀️🏃 love 🇳🇿! See https://x.io :-D";
+        let spans = tokenize_sentence_with_spans_configurable(text, config.clone());
+        let tokens: Vec<&str> = spans.iter().map(|span| span.token.as_str()).collect();
+        assert_eq!(tokens, vec!["runner", "🏃‍♀️", "🏃", "love", "🇳🇿", "see", "httpsxio", ":-D"]);
+        assert!(spans.iter().all(|span| text[span.start..span.end].chars().count() == span.char_end - span.char_start));
+        assert_eq!(&text[spans[4].start..spans[4].end], "🇳🇿");
+        assert_eq!(text.chars().skip(spans[7].char_start).collect::<String>(), ":-D");
+
+        let alias = TokenConfig { emoji: Emoji::Alias, contractions: Contractions::Keep, ..config };
+        let tokens = tokenize_sentence_with_metadata("Don't 👎🏻<3", alias);
+        assert_eq!(tokens.iter().map(|token| token.text.as_str()).collect::<Vec<&str>>(), vec!["don't", ":-1:", ":heart:"]);
+        assert_eq!(tokens[1].original, "👎🏻");
+        assert_eq!(tokens[1].kind, TokenKind::Symbol);
+    }
+
+    #[test]
+    fn test_tokenizer_scratch_buffer() {
+        let sentence = "  Hello, there!  GENERAL...Kenobi -- you're  ";
+        let tokens = vec!["hello", "there", "generalkenobi", "youre"];
+        assert_eq!(Tokenizer::new().tokenize_sentence(sentence), tokens);
+        assert_eq!(Tokenizer::with_capacity(8).tokenize_sentence(sentence), tokens);
+
+        let long_sentence = "word ".repeat(RETAINED_SCRATCH_BYTES);
+        assert_eq!(Tokenizer::new().tokenize_sentence(&long_sentence).len(), RETAINED_SCRATCH_BYTES);
+        assert!(SCRATCH.with_borrow(String::capacity) <= RETAINED_SCRATCH_BYTES);
+        Tokenizer::with_capacity(1 << 16).tokenize_sentence("");
+        assert!(SCRATCH.with_borrow(String::capacity) >= 1 << 16);
+        assert!(Tokenizer::new().tokenize_sentence(" ,, ").is_empty());
+    }
+
+    #[test]
+    fn test_stem_cache() {
+        let stemmed = tokenize_stemmed_sentence("Running runners ran, running again");
+        assert_eq!(stemmed, vec!["run", "runner", "ran", "run", "again"]);
+        assert_eq!(STEM_CACHE.with_borrow(|cache| cache.get("running").cloned()), Some(Some("run".to_string())));
+        // words the stemmer rejects are cached as such
+        assert_eq!(cached_stem("café"), None);
+        assert_eq!(STEM_CACHE.with_borrow(|cache| cache.get("café").cloned()), Some(None));
+        assert_eq!(tokenize_stemmed_sentence("running"), vec!["run"]);
+    }
+
+    #[test]
+    fn test_term_weighting() {
+        let sentences = ["the cat saw the other cat", "a dog", ""];
+        let config = |term_weighting| TokenConfig { term_weighting, ..TokenConfig::plain() };
+        let relative = get_term_frequencies_from_sentences_configurable(&sentences, config(TermWeighting::Relative));
+        assert_eq!(relative[0]["cat"], 2. / 6.);
+        assert_eq!(relative[0]["dog"], 0.);
+        assert_eq!(relative[1].values().sum::<f64>(), 1.);
+        assert!(relative[2].values().all(|weight| *weight == 0.));
+
+        let log = get_term_frequencies_from_sentences_configurable(&sentences, config(TermWeighting::Log));
+        assert_eq!(log[0]["cat"], 1. + 2_f64.ln());
+        assert_eq!(log[0]["saw"], 1.);
+        assert_eq!(log[1]["cat"], 0.);
+
+        let sparse = get_sparse_term_frequencies_from_sentences_configurable(&sentences, config(TermWeighting::Relative));
+        assert_eq!(sparse.get(0, "cat"), 2. / 6.);
+        let words = get_term_frequencies_from_word_vector_configurable(vec!["cat", "cat", "dog", "owl"], config(TermWeighting::Relative));
+        assert_eq!(words["cat"], 0.5);
+    }
+
+    #[test]
+    fn test_case_folding() {
+        assert_eq!(CaseFolding::Unicode.fold("ΟΔΥΣΣΕΥΣ"), "οδυσσευσ");
+        assert_eq!(CaseFolding::Unicode.fold("İstanbul"), "i\u{307}stanbul");
+        assert_eq!(CaseFolding::Turkic.fold("İSTANBUL"), "istanbul");
+        assert_eq!(CaseFolding::Ascii.fold("ÜBER Alles"), "Über alles");
+        assert!(matches!(CaseFolding::Unicode.fold("already folded"), Cow::Borrowed(_)));
+
+        let stop_words = vec!["über".to_string()];
+        assert_eq!(tokenize_sentence_without_stop_words("Über den Fluss", stop_words.clone()), vec!["den", "fluss"]);
+        let config = TokenConfig { case_folding: CaseFolding::Ascii, remove_stop_words: true, stop_words: stop_words.into(), ..TokenConfig::plain() };
+        assert_eq!(tokenize_sentence_configurable("Über den Fluss", config.clone()), vec!["Über", "den", "fluss"]);
+        assert_eq!(get_term_counts_from_sentence_configurable("Über über", config)["Über"], 1);
+    }
+
+    #[test]
+    fn test_whitespace_only() {
+        let text = "Don't  STOP\tme-now. 🙂";
+        let config = TokenConfig { whitespace_only: true, contractions: Contractions::Expand, ..TokenConfig::plain() };
+        assert_eq!(tokenize_sentence_configurable(text, config.clone()), vec!["Don't", "STOP", "me-now.", "🙂"]);
+
+        let spans = tokenize_sentence_with_spans_configurable(text, config.clone());
+        assert_eq!(spans.iter().map(|span| &text[span.start..span.end]).collect::<Vec<&str>>(), tokenize_whitespace(text));
+        assert_eq!((spans[3].char_start, spans[3].char_end), (20, 21));
+        assert_eq!(get_term_counts_from_sentence_configurable("a A a", config)["a"], 2);
+    }
+
+    #[test]
+    fn test_sentence_boundary_exceptions() {
+        let cases = [
+            ("Wait... what happened at 3.5 seconds?", vec!["Wait... what happened at 3.5 seconds"]),
+            ("I waited... Nobody came.", vec!["I waited", "Nobody came"]),
+            ("Upgrade to v1.2.3 today. It is faster.", vec!["Upgrade to v1.2.3 today", "It is faster"]),
+            ("Sales grew in 2024. Costs fell.", vec!["Sales grew in 2024", "Costs fell"]),
+            ("J. R. R. Tolkien moved to the U.S. in 1925.", vec!["J. R. R. Tolkien moved to the U.S. in 1925"]),
+            ("She works for NASA. He doesn't.", vec!["She works for NASA", "He doesn't"]),
+            ("So did I. Then we left.", vec!["So did I", "Then we left"]),
+            ("Grade: A. Next item.", vec!["Grade: A", "Next item"]),
+            ("We voted for George W. Bush twice.", vec!["We voted for George W. Bush twice"]),
+            ("It was a plan b. Then it failed.", vec!["It was a plan b", "Then it failed"]),
+            ("Wait...\nwhat? Ask J.\nTolkien.", vec!["Wait", "\nwhat", "Ask J", "\nTolkien"]),
+        ];
+        for (text, sentences) in cases {
+            assert_eq!(tokenize_into_sentences(text), sentences, "{text}");
+            let read: Vec<String> = SentenceIter::new(text.as_bytes()).map(|sentence| sentence.unwrap()).collect();
+            assert_eq!(read, sentences, "{text}");
+        }
+
+        let spans = tokenize_into_sentences_with_spans("Wait... what? Version 2.0. Done...");
+        let texts: Vec<&str> = spans.iter().map(|span| span.text.as_str()).collect();
+        assert_eq!(texts, vec!["Wait... what?", "Version 2.0.", "Done..."]);
+    }
+
+    #[test]
+    fn test_protected_tokens() {
+        let config = TokenConfig::builder().remove_numeric(true).protected_tokens(["US", "Running", "2024"]).build();
+        let text = "Running for the US in 2024 and running again in 2028";
+        assert_eq!(tokenize_sentence_configurable(text, config.clone()), vec!["running", "us", "2024", "running"]);
+
+        let counts = get_term_counts_from_sentences_configurable(&[text, "The runners ran"], config.clone());
+        assert_eq!((counts[0]["running"], counts[0]["us"], counts[1]["runner"]), (2, 1, 1));
+        let words = get_term_frequencies_from_word_vector_configurable(vec!["running", "us", "races"], config.clone());
+        assert_eq!(words.keys().collect::<Vec<&String>>(), vec!["race", "running", "us"]);
+
+        let tokens = tokenize_sentence_with_metadata("Running for us", TokenConfig { remove_stop_words: false, ..config });
+        assert_eq!((tokens[0].stem.as_deref(), tokens[2].is_stop), (None, false));
+        assert!(tokens[1].is_stop);
+
+        let whitespace = TokenConfig::builder().whitespace_only(true).stem(false).protected_tokens(["The"]).build();
+        assert_eq!(tokenize_sentence_configurable("The the", whitespace), vec!["The"]);
+        let ascii = TokenConfig::builder().protected_tokens(["ÜBER"]).case_folding(CaseFolding::Ascii).build();
+        assert!(ascii.protected_tokens.contains("Über"));
+    }
+
+    #[test]
+    fn test_ngram_stop_words() {
+        let text = "the machine learning model of the year";
+        let config = |ngram_stop_words| TokenConfig { ngram: 3, ngram_stop_words, ..TokenConfig::default() };
+        assert_eq!(tokenize_sentence_configurable(text, config(NgramStopWords::DropEdges)), vec!["machin learn model"]);
+        assert_eq!(tokenize_sentence_configurable(text, config(NgramStopWords::DropStart)), vec!["machin learn model", "learn model of", "model of the"]);
+        assert_eq!(tokenize_sentence_configurable(text, config(NgramStopWords::DropEnd)), vec!["the machin learn", "machin learn model", "of the year"]);
+
+        let spans = tokenize_sentence_with_spans_configurable(text, config(NgramStopWords::DropEdges));
+        assert_eq!(&text[spans[0].start..spans[0].end], "machine learning model");
+        let unfiltered = TokenConfig { remove_stop_words: false, ..config(NgramStopWords::DropEdges) };
+        assert_eq!(tokenize_sentence_configurable(text, unfiltered).len(), 5);
+    }
+
+    #[test]
+    fn test_document_frequencies() {
+        let documents = ["the cat sat", "the cat", "a dog", ""];
+        let document_frequencies = get_document_frequencies(&documents);
+        assert_eq!(document_frequencies, BTreeMap::from([
+            ("a".to_string(), 1), ("cat".to_string(), 2), ("dog".to_string(), 1), ("sat".to_string(), 1), ("the".to_string(), 2),
+        ]));
+
+        let inverse_document_frequencies = get_inverse_document_frequencies(&document_frequencies, documents.len());
+        assert_eq!(inverse_document_frequencies["cat"], 2_f64.ln());
+        assert_eq!(inverse_document_frequencies["dog"], 4_f64.ln());
+        let zero = BTreeMap::from([("unseen".to_string(), 0)]);
+        assert!(get_inverse_document_frequencies(&zero, 4).is_empty());
+    }
+
+    #[test]
+    fn test_sparse_term_frequencies() {
+        let sentences = ["the cat sat", "the dog", "", "cat cat"];
+        let sparse = get_sparse_term_frequencies_from_sentences(&sentences);
+        let dense = get_term_frequencies_from_sentences(&sentences);
+        assert_eq!(sparse.num_terms(), 4);
+        assert_eq!(sparse.num_documents(), 4);
+        assert!(sparse.documents[2].is_empty());
+        for (index, counts) in dense.iter().enumerate() {
+            let non_zero: BTreeMap<String, f64> = counts.iter().filter(|(_, count)| **count > 0.).map(|(term, count)| (term.clone(), *count)).collect();
+            assert_eq!(sparse.get_document(index), non_zero);
+            for (term, count) in counts {
+                assert_eq!(sparse.get(index, term), *count);
+            }
+        }
+        assert_eq!(sparse.get(0, "bird"), 0.);
+        assert_eq!(sparse.get(9, "cat"), 0.);
+
+        let config = TokenConfig::builder().stem(false).min_frequency(2).build();
+        let frequent = get_sparse_term_frequencies_from_sentences_configurable(&sentences, config);
+        assert_eq!(frequent.terms, vec!["cat"]);
+        assert_eq!(frequent.documents[3], vec![(0, 2.)]);
+
+        let tokenized: Vec<Vec<String>> = sentences.iter().map(|sentence| tokenize_sentence(sentence)).collect();
+        assert_eq!(get_term_frequencies_from_tokenized_documents(&tokenized), sparse);
+    }
+
+    #[test]
+    fn test_sentence_spans() {
+        let document = "  Is it over?  Yes... \"Finally!\" she said. Done";
+        let spans = tokenize_into_sentences_with_spans(document);
+        for span in &spans {
+            assert_eq!(&document[span.start..span.end], span.text);
+        }
+        let texts: Vec<&str> = spans.iter().map(|span| span.text.as_str()).collect();
+        assert_eq!(texts, vec!["Is it over?", "Yes...", "\"Finally!\" she said.", "Done"]);
+        let terminators: Vec<Option<&str>> = spans.iter().map(|span| span.terminator.as_deref()).collect();
+        assert_eq!(terminators, vec![Some("?"), Some("..."), Some("."), None]);
+        assert!(tokenize_into_sentences_with_spans("   ").is_empty());
+    }
+
+    #[test]
+    fn test_tokenize_document() {
+        let config = TokenConfig::builder().stem(false).remove_stop_words(false).min_frequency(2).build();
+        let document = tokenize_document("The cat sat. Nothing! The cat ran.", config);
+        assert_eq!(document.sentences, vec!["The cat sat", "Nothing", "The cat ran"]);
+        // sentences left without tokens keep their place
+        assert_eq!(document.tokens, vec![vec!["the", "cat"], vec![], vec!["the", "cat"]]);
+        assert_eq!(document.len(), 4);
+        let positions: Vec<(usize, usize)> = document.iter().map(|(position, _)| (position.sentence_index, position.token_index)).collect();
+        assert_eq!(positions, vec![(0, 0), (0, 1), (2, 0), (2, 1)]);
+        assert_eq!(document.get(TokenPosition { sentence_index: 1, token_index: 0 }), None);
+        assert_eq!(document.group_by_sentence(vec![1, 2, 3, 4]).unwrap(), vec![vec![1, 2], vec![], vec![3, 4]]);
+        assert!(matches!(document.group_by_sentence(vec![1]), Err(RnltkError::TokenCountMismatch)));
+        assert!(tokenize_document("", TokenConfig::default()).is_empty());
+    }
+
+    #[test]
+    fn test_vocabulary() {
+        let mut vocabulary = Vocabulary::new();
+        vocabulary.add_tokens(["b", "a", "b", "c", "b", "a"]);
+        assert_eq!(vocabulary.iter().collect::<Vec<_>>(), vec![(0, "b"), (1, "a"), (2, "c")]);
+        assert_eq!(vocabulary.get_count("b"), 3);
+        // without an unknown token, out-of-vocabulary terms are left out
+        assert_eq!(vocabulary.encode(["c", "d", "a"]), vec![2, 1]);
+
+        vocabulary.freeze();
+        assert_eq!(vocabulary.add("d"), None);
+        assert_eq!(vocabulary.add("c"), Some(2));
+        assert_eq!((vocabulary.len(), vocabulary.get_count("c")), (3, 2));
+
+        vocabulary.prune(3);
+        assert_eq!(vocabulary.iter().collect::<Vec<_>>(), vec![(0, "b")]);
+        assert!(vocabulary.is_frozen());
+
+        let mut vocabulary = Vocabulary::with_unknown_token("<unk>");
+        vocabulary.add_tokens(["x", "y", "y"]);
+        vocabulary.prune(2);
+        assert_eq!(vocabulary.iter().collect::<Vec<_>>(), vec![(0, "<unk>"), (1, "y")]);
+        assert_eq!((vocabulary.get_unknown_id(), vocabulary.get_count("<unk>")), (Some(0), 0));
+        assert_eq!(vocabulary.encode(["x", "y"]), vec![0, 1]);
+
+        let json = serde_json::to_string(&vocabulary).unwrap();
+        assert_eq!(serde_json::from_str::<Vocabulary>(&json).unwrap(), vocabulary);
+        for json in [
+            r#"{"terms":["a","b","c"],"counts":[1],"unknown_id":null,"frozen":false}"#,
+            r#"{"terms":["a","b","a"],"counts":[1,1,1],"unknown_id":null,"frozen":false}"#,
+            r#"{"terms":["a"],"counts":[1],"unknown_id":1,"frozen":true}"#,
+        ] {
+            assert!(serde_json::from_str::<Vocabulary>(json).unwrap_err().to_string().contains("Invalid vocabulary"));
+        }
+    }
+
+    #[test]
+    fn test_hyphens() {
+        let config = TokenConfig::builder().stem(false).remove_stop_words(false).hyphens(Hyphens::Keep).contractions(Contractions::Split).build();
+        let tokens = tokenize_sentence_configurable("My mother-in-law's x-ray, yes--no -well- e\u{2011}mail", config.clone());
+        assert_eq!(tokens, vec!["my", "mother-in-law", "'s", "x-ray", "yes", "no", "well", "e-mail"]);
+
+        let spans = tokenize_sentence_with_spans_configurable("well-known", TokenConfig { hyphens: Hyphens::Both, ..config.clone() });
+        let tokens: Vec<(&str, usize, usize)> = spans.iter().map(|span| (span.token.as_str(), span.start, span.end)).collect();
+        assert_eq!(tokens, vec![("well-known", 0, 10), ("well", 0, 10), ("known", 0, 10)]);
+
+        let metadata = tokenize_sentence_with_metadata("well-known", config.clone());
+        assert_eq!((metadata[0].text.as_str(), metadata[0].kind), ("well-known", TokenKind::Word));
+
+        let split = TokenConfig { hyphens: Hyphens::Split, ..config };
+        let frequencies = get_term_frequencies_from_word_vector_configurable(vec!["well-known", "well"], split);
+        assert_eq!(frequencies, BTreeMap::from([("known".to_string(), 1.), ("well".to_string(), 2.)]));
+    }
+
+    #[test]
+    fn test_context_windows() {
+        let tokens = ["a", "x", "b", "x"];
+        let windows = get_context_windows(&tokens, "x", 1);
+        assert_eq!(windows.iter().map(|window| (window.index, window.left.clone(), window.right.clone())).collect::<Vec<_>>(), vec![
+            (1, vec!["a".to_string()], vec!["b".to_string()]),
+            (3, vec!["b".to_string()], vec![]),
+        ]);
+        assert_eq!(get_context_windows(&tokens, "x", 0)[0].left, Vec::<String>::new());
+        assert!(get_context_windows(&tokens, "y", 3).is_empty());
+
+        let document = SentenceTokens {
+            sentences: vec!["a x".to_string(), "".to_string(), "x b".to_string()],
+            tokens: vec![vec!["a".to_string(), "x".to_string()], vec![], vec!["x".to_string(), "b".to_string()]],
+        };
+        let within = document.get_context_windows("x", 5, true);
+        assert_eq!((within[1].index, within[1].left.len(), within[1].right.len()), (2, 0, 1));
+        let across = document.get_context_windows("x", 5, false);
+        assert_eq!((across[1].index, across[1].left.len(), across[0].right.len()), (2, 2, 2));
+    }
+
+    #[test]
+    fn test_sentence_delimiters() {
+        let tokenizer = Tokenizer::new().sentence_delimiters(&[';']);
+        assert_eq!(tokenizer.tokenize_into_sentences("start=1; stop=2;; \"done;\" said the log"), vec!["start=1", "stop=2", "\"done\" said the log"]);
+        assert_eq!(Tokenizer::new().sentence_delimiters(&[]).tokenize_into_sentences("One. Two!"), vec!["One. Two!"]);
+        assert!(matches!(Tokenizer::new().sentence_pattern("(").unwrap_err(), RnltkError::InvalidSentencePattern(_)));
+
+        let tokenizer = Tokenizer::new().sentence_delimiters(&['\n']);
+        let sentences: Vec<String> = SentenceIter::with_tokenizer(io::Cursor::new("1\n00:01 Hi.\n\nBye."), &tokenizer).map(|sentence| sentence.unwrap()).collect();
+        assert_eq!(sentences, vec!["1", "00:01 Hi.", "Bye."]);
+    }
+
+    #[test]
+    fn test_typed_tokens() {
+        let config = TokenConfig::builder().remove_stop_words(false).contractions(Contractions::Split).emoji(Emoji::Alias).build();
+        let text = "Don't stop!!! It's 4th... \u{a9}2024 www.rust-lang.org \u{1f1f3}\u{1f1ff} :)";
+        let tokens = tokenize_sentence_typed(text, config);
+        let texts: Vec<&str> = tokens.iter().map(|token| token.text.as_str()).collect();
+        assert_eq!(texts, vec!["do", "n't", "stop", "!!!", "it", "'s", "4th", "...", "\u{a9}", "2024", "www.rust-lang.org", "\u{1f1f3}\u{1f1ff}", ":slightly_smiling_face:"]);
+        let kinds: Vec<TokenKind> = tokens.iter().map(|token| token.kind).collect();
+        assert_eq!(kinds, vec![
+            TokenKind::Word, TokenKind::Word, TokenKind::Word, TokenKind::Punctuation, TokenKind::Word, TokenKind::Word, TokenKind::Alphanumeric,
+            TokenKind::Punctuation, TokenKind::Symbol, TokenKind::Number, TokenKind::Url, TokenKind::Emoji, TokenKind::Emoji,
+        ]);
+        assert_eq!(tokens[1].original, "Don't");
+        assert_eq!((tokens[1].stem.as_deref(), tokens[2].stem.as_deref()), (Some("n't"), Some("stop")));
+        assert!(tokens.iter().all(|token| text[token.span.clone()] == token.original));
+
+        let config = TokenConfig { min_token_length: Some(2), ..TokenConfig::default() };
+        let texts: Vec<String> = tokenize_sentence_typed("the cats, @Bob!", config).into_iter().map(|token| token.text).collect();
+        assert_eq!(texts, vec!["cats", "@bob"]);
+    }
+
+    #[test]
+    fn test_term_counts_match_frequencies() {
+        let sentences = ["fear leads to anger", "anger leads to hatred", "hatred leads to conflict"];
+        let config = TokenConfig { min_frequency: Some(2), ..TokenConfig::default() };
+        let term_counts = get_term_counts_from_sentences_configurable(&sentences, config.clone());
+        assert_eq!(term_counts[0], BTreeMap::from([("anger".to_string(), 1), ("hatr".to_string(), 0), ("lead".to_string(), 1)]));
+        let converted: Vec<BTreeMap<String, f64>> = term_counts.iter().map(counts_to_frequencies).collect();
+        assert_eq!(converted, get_term_frequencies_from_sentences_configurable(&sentences, config));
+    }
+
+    #[test]
+    fn test_freq_dist() {
+        let mut freq_dist = FreqDist::from(get_term_counts_from_sentence("a b b c c c"));
+        freq_dist.extend(["d", "b"]);
+        freq_dist.add("e", 0);
+        assert_eq!(freq_dist.most_common(10), vec![("b".to_string(), 3), ("c".to_string(), 3), ("a".to_string(), 1), ("d".to_string(), 1)]);
+        assert_eq!((freq_dist.n(), freq_dist.b(), freq_dist.count("z")), (8, 4, 0));
+        assert_eq!(FreqDist::from(BTreeMap::from([("a".to_string(), 0)])).b(), 0);
+        assert_eq!(FreqDist::new().freq("a"), 0.);
+        assert!(FreqDist::new().is_empty());
+    }
+
+    #[test]
+    fn test_conditional_freq_dist() {
+        let mut by_author: ConditionalFreqDist<String> = [("b", "x"), ("a", "y"), ("a", "x"), ("a", "x")].into_iter()
+            .map(|(author, token)| (author.to_string(), token))
+            .collect();
+        by_author.merge(&by_author.clone());
+        assert_eq!(by_author.n(), 8);
+        assert_eq!(by_author.get(&"a".to_string()).unwrap().count("x"), 4);
+        let table = by_author.tabulate::<&str>(&[]);
+        assert_eq!(table.get_column_labels(), ["x", "y"]);
+        assert_eq!(table.get_row_labels(), ["a", "b"]);
+        assert_eq!(table.get_matrix()[(1, 1)], 0.);
+        assert!(by_author.get(&"c".to_string()).is_none());
+    }
+
+    #[test]
+    fn test_paragraphs() {
+        let text = "\n\n  First line\nstill first.\r\n   \t\n\tSecond one.\n\tThird, same indent? No.\n";
+        assert_eq!(tokenize_into_paragraphs(text), vec!["First line still first.", "Second one. Third, same indent? No."]);
+        assert_eq!(tokenize_into_paragraphs("One.\n  Two.\n    Three."), vec!["One.", "Two.", "Three."]);
+        assert!(tokenize_into_paragraphs(" \n\n").is_empty());
+    }
+
+    #[test]
+    fn test_metadata_keeps_original_forms() {
+        let text = "The Jedi Council met in CORUSCANT's tower.";
+        let tokens = tokenize_sentence_with_metadata(text, TokenConfig::default());
+        let forms: Vec<(&str, &str)> = tokens.iter().map(|token| (token.text.as_str(), token.original.as_str())).collect();
+        assert_eq!(forms, vec![("jedi", "Jedi"), ("council", "Council"), ("met", "met"), ("coruscants", "CORUSCANT's"), ("tower", "tower")]);
+    }
+
+    #[test]
+    fn test_builder_matches_struct_config() {
+        let built = TokenConfig::builder().stem(false).remove_numeric(true).min_token_length(2).build();
+        let literal = TokenConfig { stem: false, remove_numeric: true, min_token_length: Some(2), ..TokenConfig::default() };
+        let text = "R2 and 3PO walked 40 miles, C3PO said.";
+        assert_eq!(tokenize_sentence_configurable(text, built.clone()), tokenize_sentence_configurable(text, literal));
+        assert_eq!(get_term_frequencies_from_word_vector_configurable(vec!["r2", "7", "walked", "a"], built), BTreeMap::from([("r2".to_string(), 1.), ("walked".to_string(), 1.)]));
+    }
+
+    #[test]
+    fn test_ngram_spans_cover_their_words() {
+        let text = "Fear leads to anger.";
+        let config = TokenConfig::builder().remove_stop_words(false).stem(false).ngram(2).build();
+        let spans = tokenize_sentence_with_spans_configurable(text, config.clone());
+        let ngrams: Vec<(&str, &str)> = spans.iter().map(|span| (span.token.as_str(), &text[span.start..span.end])).collect();
+        assert_eq!(ngrams, vec![("fear leads", "Fear leads"), ("leads to", "leads to"), ("to anger", "to anger")]);
+        assert!(tokenize_sentence_configurable("Fear", config).is_empty());
+    }
+
+    #[test]
+    fn test_token_filters() {
+        let text = "Ok so 42 droids and 7 x-wings, droids everywhere";
+        let config = TokenConfig { stem: false, remove_stop_words: false, min_token_length: Some(2), max_token_length: Some(6), remove_numeric: true, ..TokenConfig::default() };
+        assert_eq!(tokenize_sentence_configurable(text, config.clone()), vec!["ok", "so", "droids", "and", "xwings", "droids"]);
+        let tokens: Vec<String> = tokenize_sentence_with_metadata(text, config).into_iter().map(|token| token.text).collect();
+        assert_eq!(tokens, vec!["ok", "so", "droids", "and", "xwings", "droids"]);
+    }
+
+    #[test]
+    fn test_min_frequency_counts_across_sentences() {
+        let sentences = ["droids droids everywhere", "more droids", "no wookiees"];
+        let config = TokenConfig { stem: false, remove_stop_words: false, min_frequency: Some(2), ..TokenConfig::default() };
+        let term_frequencies = get_term_frequencies_from_sentences_configurable(&sentences, config.clone());
+        assert_eq!(term_frequencies[1], BTreeMap::from([("droids".to_string(), 1.)]));
+        assert_eq!(term_frequencies[2], BTreeMap::from([("droids".to_string(), 0.)]));
+        assert_eq!(tokenize_sentence_configurable(sentences[1], config), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_chunks_respect_budget_and_overlap() {
+        let text = "One two three. Four five. Six seven eight nine ten eleven twelve. Thirteen! \"Fourteen?\" he said.";
+        let chunks = split_into_chunks(text, 4, 2).unwrap();
+        for chunk in &chunks {
+            assert!(chunk.num_tokens <= 4);
+            assert_eq!(&text[chunk.start..chunk.end], chunk.text);
+            assert_eq!(tokenize_sentence(&chunk.text).len(), chunk.num_tokens);
+        }
+        let chunk_texts: Vec<&str> = chunks.iter().map(|chunk| chunk.text.as_str()).collect();
+        assert_eq!(chunk_texts, vec!["One two three.", "Four five.", "Six seven eight nine", "ten eleven twelve. Thirteen!", "Thirteen! \"Fourteen?\" he said."]);
+    }
+
+    #[test]
+    fn test_chunks_cover_every_sentence() {
+        let text = "Why hello there. General Kenobi! You are a bold one.";
+        let chunks = split_into_chunks(text, 3, 0).unwrap();
+        let tokens: Vec<String> = chunks.iter().flat_map(|chunk| tokenize_sentence(&chunk.text)).collect();
+        assert_eq!(tokens, tokenize_sentence(text));
+        assert!(split_into_chunks("...", 3, 0).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_invalid_chunk_size() {
+        assert_eq!(split_into_chunks("text", 0, 0).unwrap_err(), RnltkError::InvalidChunkSize);
+        assert_eq!(split_into_chunks("text", 2, 2).unwrap_err(), RnltkError::InvalidChunkSize);
+    }
+
+    #[test]
+    fn test_phrases_stop_at_sentences_and_clauses() {
+        let phrase_frequencies = get_phrase_frequencies("Red apples, green pears. Pears grow; apples fall");
+        let phrases: Vec<&str> = phrase_frequencies.keys().map(|phrase| phrase.as_str()).collect();
+        assert_eq!(phrases, vec!["apples fall", "green pears", "pears grow", "red apples"]);
+        assert!(get_phrase_frequencies("The, and of.").is_empty());
+    }
+
+    #[test]
+    fn test_tweet_tokens() {
+        let tokens = tokenize_tweet("Can't wait!!! <3 #summer-vibes 🌞 (see www.example.com/a?b=c.) 12:30 ;-)");
+        assert_eq!(tokens, vec!["can't", "wait", "<3", "#summer-vibes", "🌞", "see", "www.example.com/a?b=c", "12:30", ";-)"]);
+        let tokenizer = TweetTokenizer::new().preserve_case(true);
+        assert_eq!(tokenizer.tokenize("GREAT Job :P"), vec!["GREAT", "Job", ":P"]);
+        assert_eq!(reduce_repeated_characters("yessss!!!!"), "yesss!!!");
+    }
+
     #[test]
     fn test_sentence_tokenization() {
         let text = "Why hello there. General Kenobi!";
@@ -663,6 +4497,144 @@ mod tests {
         assert_eq!(tokens, tokenized_text);
     }
 
+    #[test]
+    fn test_spans_match_tokens() {
+        let text = "  Don't   panic, the café's \"towels\" -- are here!\n";
+        let spans = tokenize_sentence_with_spans(text);
+        let tokens: Vec<String> = spans.iter().map(|span| span.token.clone()).collect();
+        assert_eq!(tokens, tokenize_sentence(text));
+        for span in &spans {
+            let by_bytes = &text[span.start..span.end];
+            let by_chars: String = text.chars().skip(span.char_start).take(span.char_end - span.char_start).collect();
+            assert_eq!(by_bytes, by_chars);
+        }
+        assert_eq!(&text[spans[0].start..spans[0].end], "Don't");
+        assert_eq!(&text[spans[3].start..spans[3].end], "café's");
+        assert_eq!((spans[3].end - spans[3].start, spans[3].char_end - spans[3].char_start), (7, 6));
+        assert_eq!(&text[spans[4].start..spans[4].end], "towels");
+    }
+
+    #[test]
+    fn test_accents_stripped_for_stemming() {
+        let config = TokenConfig { remove_stop_words: false, strip_accents: true, ..TokenConfig::default() };
+        assert_eq!(tokenize_sentence_configurable("Café CRÈME, naïvely", config.clone()), vec!["cafe", "creme", "naiv"]);
+        let spans = tokenize_sentence_with_spans_configurable("Café CRÈME", config);
+        assert_eq!((spans[1].token.as_str(), spans[1].start, spans[1].end), ("creme", 6, 12));
+    }
+
+    #[test]
+    fn test_normalization_unifies_forms() {
+        let config = TokenConfig { stem: false, remove_stop_words: false, normalization: Some(Normalization::Nfkc), ..TokenConfig::default() };
+        let frequencies = get_term_frequencies_from_sentence_configurable("caf\u{e9} cafe\u{301} \u{ff21}\u{ff22} \u{ff01}", config.clone());
+        assert_eq!(frequencies, BTreeMap::from([("ab".to_string(), 1.), ("caf\u{e9}".to_string(), 2.)]));
+        let frequencies = get_term_frequencies_from_word_vector_configurable(vec!["cafe\u{301}"], config);
+        assert_eq!(frequencies, BTreeMap::from([("caf\u{e9}".to_string(), 1.)]));
+    }
+
+    #[test]
+    fn test_metadata_matches_configurable_tokens() {
+        let text = "Why hello there. General Kenobi! I can't pay 20 bucks for an mp3 \u{1f600}";
+        for (stem, remove_stop_words) in [(true, true), (true, false), (false, true), (false, false)] {
+            let config = TokenConfig { stem, remove_stop_words, contractions: Contractions::Split, ..TokenConfig::default() };
+            let tokens: Vec<String> = tokenize_sentence_with_metadata(text, config.clone()).into_iter()
+                .map(|token| token.stem.unwrap_or(token.text))
+                .collect();
+            assert_eq!(tokens, tokenize_sentence_configurable(text, config));
+        }
+    }
+
+    #[test]
+    fn test_metadata_kinds() {
+        let config = TokenConfig { stem: false, remove_stop_words: false, contractions: Contractions::Split, ..TokenConfig::default() };
+        let kinds: Vec<TokenKind> = tokenize_sentence_with_metadata("can't pay 20 for mp3 \u{1f600}", config).into_iter().map(|token| token.kind).collect();
+        assert_eq!(kinds, vec![TokenKind::Word, TokenKind::Word, TokenKind::Word, TokenKind::Number, TokenKind::Word, TokenKind::Alphanumeric, TokenKind::Symbol]);
+    }
+
+    #[test]
+    fn test_stop_word_lists() {
+        assert_eq!(StopWords::english(StopWordList::default()), StopWords::from(get_stop_words()));
+        for list in [StopWordList::Nltk, StopWordList::Smart, StopWordList::Minimal] {
+            assert_eq!(StopWords::english(list).len(), list.words().len());
+        }
+        let diff = StopWords::english(StopWordList::Nltk).diff(&StopWords::english(StopWordList::Smart));
+        assert!(diff.only_in_self.contains(&"mustn't".to_string()));
+        assert!(diff.only_in_other.contains(&"afterwards".to_string()));
+        assert!(diff.only_in_other.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn test_stop_words_serialize_sorted() {
+        let stop_words = StopWords::from(vec!["the".to_string(), "a".to_string(), "the".to_string()]);
+        assert_eq!(stop_words.len(), 2);
+        let json = serde_json::to_string(&stop_words).unwrap();
+        assert_eq!(json, r#"["a","the"]"#);
+        assert_eq!(serde_json::from_str::<StopWords>(&json).unwrap(), stop_words);
+    }
+
+    #[test]
+    fn test_stop_words_from_reader() {
+        let stop_words = StopWords::from_reader("  the \r\n#the comment\n\na#b\n".as_bytes()).unwrap();
+        assert_eq!(stop_words, StopWords::from(vec!["the".to_string(), "a".to_string()]));
+        let stop_words = StopWords::from_reader("\n# list\n[\"the\", # trailing comment\n \" \", \"a\"]".as_bytes()).unwrap();
+        assert_eq!(stop_words, StopWords::from(vec!["the".to_string(), "a".to_string()]));
+        assert!(matches!(StopWords::from_reader("[1, 2]".as_bytes()), Err(RnltkError::StopWordsFormat(_))));
+        assert!(matches!(StopWords::from_path("does/not/exist.txt"), Err(RnltkError::StopWordsIo(_))));
+    }
+
+    #[test]
+    fn test_token_config_stop_words_deserialize_from_list() {
+        let config: TokenConfig = serde_json::from_str(r#"{"stem": false, "remove_stop_words": true, "stop_words": ["why", "there"]}"#).unwrap();
+        assert_eq!(tokenize_sentence_configurable("Why hello there", config), vec!["hello"]);
+    }
+
+    #[test]
+    fn test_contraction_handling() {
+        let config = |contractions| TokenConfig { stem: false, remove_stop_words: false, contractions, ..TokenConfig::default() };
+        let text = "I can't say John's won\u{2019}t, 'kay?";
+        assert_eq!(tokenize_sentence_configurable(text, config(Contractions::Strip)), vec!["i", "cant", "say", "johns", "won\u{2019}t", "kay"]);
+        assert_eq!(tokenize_sentence_configurable(text, config(Contractions::Keep)), vec!["i", "can't", "say", "john's", "won't", "kay"]);
+        assert_eq!(tokenize_sentence_configurable(text, config(Contractions::Split)), vec!["i", "ca", "n't", "say", "john", "'s", "wo", "n't", "kay"]);
+        assert_eq!(tokenize_sentence_configurable(text, config(Contractions::Expand)), vec!["i", "can", "not", "say", "johns", "will", "not", "kay"]);
+    }
+
+    #[test]
+    fn test_words_with_apostrophes_are_stemmed() {
+        assert_eq!(get_stemmed_term_frequencies_from_word_vector(vec!["it's", "it's"]), BTreeMap::from([("it'".to_string(), 2.)]));
+        let config = TokenConfig { remove_stop_words: false, contractions: Contractions::Keep, ..TokenConfig::default() };
+        assert_eq!(tokenize_sentence_configurable("It's raining", config), vec!["it'", "rain"]);
+    }
+
+    #[test]
+    fn test_kept_contractions_match_stop_words() {
+        let config = TokenConfig { contractions: Contractions::Keep, ..TokenConfig::default() };
+        assert_eq!(tokenize_sentence_configurable("You shouldn't have waited", config), vec!["wait"]);
+    }
+
+    #[test]
+    fn test_contraction_spans_share_word_span() {
+        let config = TokenConfig { stem: false, remove_stop_words: false, contractions: Contractions::Expand, ..TokenConfig::default() };
+        let spans = tokenize_sentence_with_spans_configurable("We'll see", config);
+        assert_eq!((spans[0].token.as_str(), spans[1].token.as_str()), ("we", "will"));
+        assert_eq!((spans[1].start, spans[1].end), (0, 5));
+    }
+
+    #[test]
+    fn test_word_vector_contractions() {
+        let config = TokenConfig { stem: false, remove_stop_words: false, contractions: Contractions::Split, ..TokenConfig::default() };
+        let frequencies = get_term_frequencies_from_word_vector_configurable(vec!["don't", "do"], config);
+        assert_eq!(frequencies, BTreeMap::from([("do".to_string(), 2.), ("n't".to_string(), 1.)]));
+    }
+
+    #[test]
+    fn test_spans_configurable_match_tokens() {
+        let text = "Why hello there. General Kenobi!";
+        for (stem, remove_stop_words) in [(true, true), (true, false), (false, true), (false, false)] {
+            let config = TokenConfig { stem, remove_stop_words, stop_words: get_stop_words().into(), ..TokenConfig::default() };
+            let tokens: Vec<String> = tokenize_sentence_with_spans_configurable(text, config.clone()).into_iter().map(|span| span.token).collect();
+            assert_eq!(tokens, tokenize_sentence_configurable(text, config));
+        }
+    }
+
     #[test]
     fn test_sentence_tokenization_without_stop_words() {
         let stop_words = get_stop_words();
@@ -843,4 +4815,30 @@ mod tests {
         
         assert_eq!(vec![word_counts1, word_counts2, word_counts3, word_counts4], term_frequencies);
     }
+
+    #[test]
+    fn test_plural_stemmer() {
+        let cases = [("ponies", "pony"), ("bees", "bee"), ("classes", "classe"), ("cats", "cat"), ("glass", "glass"), ("corpus", "corpus"), ("toes", "toe"), ("making", "making")];
+        for (word, stem) in cases {
+            assert_eq!(Stemmer::Plural.stem(word).as_deref(), Some(stem));
+        }
+        let config = TokenConfig::builder().stemmer(Stemmer::Plural).build();
+        assert_eq!(config.stemmer, Stemmer::Plural);
+        assert_eq!(tokenize_sentence_with_metadata("Bees sting", config)[0].stem.as_deref(), Some("bee"));
+    }
+
+    #[test]
+    fn test_negation() {
+        let config = |contractions| TokenConfig::builder().stem(false).remove_stop_words(false).contractions(contractions).negation(true).build();
+        assert_eq!(tokenize_sentence_configurable("I don't like it", config(Contractions::Strip)), vec!["i", "dont", "like_NEG", "it_NEG"]);
+        assert_eq!(tokenize_sentence_configurable("I don't like it", config(Contractions::Split)), vec!["i", "do", "n't", "like_NEG", "it_NEG"]);
+        assert_eq!(tokenize_sentence_configurable("Never again", config(Contractions::Keep)), vec!["never", "again_NEG"]);
+        let unmarked = TokenConfig::builder().stem(false).remove_stop_words(false).build();
+        assert_eq!(tokenize_sentence_configurable("I don't like it", unmarked), vec!["i", "dont", "like", "it"]);
+
+        let stemmed = TokenConfig::builder().negation(true).ngram(2).build();
+        let sentences = tokenize_into_sentences("Nobody liked the endings. The endings were fine.");
+        let tokens: Vec<Vec<String>> = sentences.iter().map(|sentence| tokenize_sentence_configurable(sentence, stemmed.clone())).collect();
+        assert_eq!(tokens, vec![vec!["nobodi like_NEG", "like_NEG end_NEG"], vec!["end fine"]]);
+    }
 }
\ No newline at end of file
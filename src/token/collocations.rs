@@ -0,0 +1,190 @@
+//! Module containing finders of collocations, word sequences such as "new york" or "strong tea" that occur
+//! together more often than their words' frequencies suggest, like NLTK's `BigramCollocationFinder`.
+//!
+//! Every n-gram is scored from its contingency table: the counts of windows where each of its positions
+//! does or doesn't hold its word, compared with the counts expected if the words occurred independently.
+
+use std::collections::HashMap;
+
+use crate::token::FreqDist;
+
+/// Association measure used to score collocations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AssociationMeasure {
+    /// Number of times the n-gram occurs
+    RawFrequency,
+    /// Pointwise mutual information, \\(log_2 \frac{P(w_1 \dots w_n)}{P(w_1) \dots P(w_n)}\\), which favors rare n-grams
+    Pmi,
+    /// Pearson's chi-square statistic of the contingency table
+    ChiSquare,
+    /// Dunning's log-likelihood ratio of the contingency table, which is reliable for rare n-grams too
+    LikelihoodRatio,
+}
+
+/// Finder of collocations of `N` words. Use [`BigramCollocationFinder`] or [`TrigramCollocationFinder`].
+#[derive(Debug, Clone)]
+pub struct CollocationFinder<const N: usize> {
+    word_fd: FreqDist,
+    // counts of the words at every subset of two or more positions of the n-gram windows,
+    // keyed by the bitmask of the positions and the words at them
+    pattern_counts: HashMap<(usize, Vec<String>), u64>,
+    ngram_counts: HashMap<Vec<String>, u64>,
+}
+
+/// Finder of two-word collocations.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::token;
+/// use rnltk::token::collocations::{AssociationMeasure, BigramCollocationFinder};
+///
+/// let text = "I live in new york. New york is big. The new car is in york. I like new york pizza.";
+/// let mut finder = BigramCollocationFinder::from_words(&token::tokenize_sentence(text));
+/// finder.apply_freq_filter(2);
+///
+/// assert_eq!(finder.nbest(AssociationMeasure::LikelihoodRatio, 1), vec![vec!["new", "york"]]);
+/// ```
+pub type BigramCollocationFinder = CollocationFinder<2>;
+
+/// Finder of three-word collocations.
+pub type TrigramCollocationFinder = CollocationFinder<3>;
+
+impl<const N: usize> CollocationFinder<N> {
+    /// Creates new instance of CollocationFinder from the n-grams of one stream of `words`.
+    pub fn from_words<T: AsRef<str>>(words: &[T]) -> Self {
+        Self::from_documents(&[words])
+    }
+
+    /// Creates new instance of CollocationFinder from the n-grams of `documents`, so that no n-gram
+    /// spans two documents.
+    pub fn from_documents<D: AsRef<[T]>, T: AsRef<str>>(documents: &[D]) -> Self {
+        let mut finder = CollocationFinder {
+            word_fd: FreqDist::new(),
+            pattern_counts: HashMap::new(),
+            ngram_counts: HashMap::new(),
+        };
+        for document in documents {
+            let words: Vec<&str> = document.as_ref().iter().map(AsRef::as_ref).collect();
+            finder.word_fd.extend(&words);
+            for window in words.windows(N) {
+                for mask in (1..1 << N).filter(|mask: &usize| mask.count_ones() >= 2) {
+                    let pattern: Vec<String> = (0..N).filter(|position| mask & (1 << position) != 0).map(|position| window[position].to_string()).collect();
+                    *finder.pattern_counts.entry((mask, pattern)).or_insert(0) += 1;
+                }
+                *finder.ngram_counts.entry(window.iter().map(|word| word.to_string()).collect()).or_insert(0) += 1;
+            }
+        }
+        finder
+    }
+
+    /// Removes n-grams occurring fewer than `min_frequency` times.
+    pub fn apply_freq_filter(&mut self, min_frequency: u64) {
+        self.ngram_counts.retain(|_, count| *count >= min_frequency);
+    }
+
+    /// Removes n-grams containing any word for which `is_filtered` is true, such as stop words.
+    pub fn apply_word_filter(&mut self, is_filtered: impl Fn(&str) -> bool) {
+        self.ngram_counts.retain(|ngram, _| !ngram.iter().any(|word| is_filtered(word)));
+    }
+
+    /// Gets the word frequency distribution the n-grams are scored against.
+    pub fn get_word_fd(&self) -> &FreqDist {
+        &self.word_fd
+    }
+
+    // number of windows whose words at the positions of `mask` are those of `ngram`
+    fn marginal(&self, ngram: &[String], mask: usize) -> f64 {
+        match mask.count_ones() {
+            0 => self.word_fd.n() as f64,
+            1 => self.word_fd.count(&ngram[mask.trailing_zeros() as usize]) as f64,
+            _ => {
+                let pattern: Vec<String> = (0..N).filter(|position| mask & (1 << position) != 0).map(|position| ngram[position].to_string()).collect();
+                self.pattern_counts.get(&(mask, pattern)).copied().unwrap_or_default() as f64
+            }
+        }
+    }
+
+    fn score(&self, ngram: &[String], count: u64, measure: AssociationMeasure) -> f64 {
+        let total = self.word_fd.n() as f64;
+        let word_probabilities: Vec<f64> = (0..N).map(|position| self.marginal(ngram, 1 << position) / total).collect();
+        match measure {
+            AssociationMeasure::RawFrequency => count as f64,
+            AssociationMeasure::Pmi => (count as f64 / total / word_probabilities.iter().product::<f64>()).log2(),
+            AssociationMeasure::ChiSquare | AssociationMeasure::LikelihoodRatio => {
+                let full = (1 << N) - 1;
+                let mut statistic = 0.;
+                for cell in 0..=full {
+                    // inclusion-exclusion over the positions outside `cell` gives windows matching exactly its positions
+                    let outside = full & !cell;
+                    let observed: f64 = (0..=outside)
+                        .filter(|extra| extra & !outside == 0)
+                        .map(|extra: usize| if extra.count_ones().is_multiple_of(2) { 1. } else { -1. } * self.marginal(ngram, cell | extra))
+                        .sum::<f64>()
+                        .max(0.);
+                    let expected = total * (0..N)
+                        .map(|position| if cell & (1 << position) != 0 { word_probabilities[position] } else { 1. - word_probabilities[position] })
+                        .product::<f64>();
+                    statistic += match measure {
+                        AssociationMeasure::ChiSquare if expected > 0. => (observed - expected).powi(2) / expected,
+                        AssociationMeasure::LikelihoodRatio if observed > 0. && expected > 0. => 2. * observed * (observed / expected).ln(),
+                        _ => 0.,
+                    };
+                }
+                statistic
+            }
+        }
+    }
+
+    /// Gets every n-gram with its score by `measure`, in descending order of score.
+    /// N-grams with the same score are in alphabetical order.
+    pub fn score_ngrams(&self, measure: AssociationMeasure) -> Vec<(Vec<String>, f64)> {
+        let mut scored: Vec<(Vec<String>, f64)> = self.ngram_counts.iter()
+            .map(|(ngram, count)| (ngram.clone(), self.score(ngram, *count, measure)))
+            .collect();
+        scored.sort_by(|first, second| second.1.total_cmp(&first.1).then_with(|| first.0.cmp(&second.0)));
+        scored
+    }
+
+    /// Gets the `n` highest scoring n-grams by `measure`.
+    pub fn nbest(&self, measure: AssociationMeasure, n: usize) -> Vec<Vec<String>> {
+        self.score_ngrams(measure).into_iter().take(n).map(|(ngram, _)| ngram).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words() -> Vec<&'static str> {
+        "a b a b c d a b e f c d g a h".split(' ').collect()
+    }
+
+    #[test]
+    fn bigram_scores() {
+        let finder = BigramCollocationFinder::from_words(&words());
+        let scores: HashMap<Vec<String>, f64> = finder.score_ngrams(AssociationMeasure::Pmi).into_iter().collect();
+        // "a b" occurs 3 times among 15 words, "a" 4 times and "b" 3 times
+        assert!((scores[&vec!["a".to_string(), "b".to_string()]] - (3_f64 * 15. / (4. * 3.)).log2()).abs() < 1e-12);
+        assert_eq!(finder.nbest(AssociationMeasure::RawFrequency, 1), vec![vec!["a", "b"]]);
+
+        // contingency table of "c d": 2 together, 0 "c" without "d", 0 "d" without "c", 13 neither
+        let chi_square: HashMap<Vec<String>, f64> = finder.score_ngrams(AssociationMeasure::ChiSquare).into_iter().collect();
+        let expected_together: f64 = 2. * 2. / 15.;
+        let expected_apart = 2. * 13. / 15.;
+        let expected_neither: f64 = 13. * 13. / 15.;
+        let manual = (2. - expected_together).powi(2) / expected_together + 2. * expected_apart + (13. - expected_neither).powi(2) / expected_neither;
+        assert!((chi_square[&vec!["c".to_string(), "d".to_string()]] - manual).abs() < 1e-9);
+    }
+
+    #[test]
+    fn filters_and_trigrams() {
+        let mut finder = TrigramCollocationFinder::from_documents(&[words(), vec!["a", "b", "c"]]);
+        finder.apply_freq_filter(2);
+        finder.apply_word_filter(|word| word == "e");
+        assert_eq!(finder.score_ngrams(AssociationMeasure::LikelihoodRatio).len(), 1);
+        assert_eq!(finder.nbest(AssociationMeasure::Pmi, 5), vec![vec!["a", "b", "c"]]);
+        assert_eq!(finder.get_word_fd().n(), 18);
+        assert!(finder.score_ngrams(AssociationMeasure::ChiSquare)[0].1 > 0.);
+    }
+}
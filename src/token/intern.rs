@@ -0,0 +1,187 @@
+//! Module containing a string interner, which stores every distinct word once and hands out compact
+//! [`Symbol`] ids in its place.
+//!
+//! Tokenizing a large corpus into `Vec<String>`s allocates every occurrence of every word. Interning the
+//! tokens instead keeps one copy of each word's bytes in a single buffer, and [`Symbol`]s are 4-byte `Copy`
+//! values that are cheap to store, compare, and count. [`FreqDist::from_symbols`](crate::token::FreqDist::from_symbols)
+//! and [`DocumentTermFrequencies::from_symbol_documents`](crate::document::DocumentTermFrequencies::from_symbol_documents)
+//! build their outputs from symbols directly.
+
+use std::collections::hash_map::RandomState;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::hash::BuildHasher;
+
+use serde::{Serialize, Deserialize};
+
+/// Id of a string in an [`Interner`]. Symbols are numbered from 0 in the order their strings were interned.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Symbol(pub(crate) u32);
+
+impl Symbol {
+    /// Gets the position of the symbol, the number of strings interned before it.
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// Interner mapping strings to [`Symbol`]s and back, storing each distinct string once.
+///
+/// Interners serialize as the list of their strings in symbol order, so symbols stay valid across a round trip.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::token;
+/// use rnltk::token::intern::Interner;
+///
+/// let mut interner = Interner::new();
+/// let symbols = interner.intern_tokens(token::tokenize_sentence("The cat saw the other cat"));
+///
+/// assert_eq!(symbols[1], symbols[5]);
+/// assert_eq!(interner.len(), 4);
+/// assert_eq!(interner.resolve(symbols[2]), Some("saw"));
+/// assert_eq!(interner.get("dog"), None);
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(from = "Vec<String>", into = "Vec<String>")]
+pub struct Interner {
+    // every string, back to back
+    text: String,
+    // end of each string in `text`, where each string starts at the end of the one before it
+    ends: Vec<usize>,
+    // symbols by the hash of their string, with any strings whose hash was already taken in `collisions`
+    symbols: HashMap<u64, Symbol>,
+    collisions: HashMap<u64, Vec<Symbol>>,
+    hasher: RandomState,
+}
+
+impl Interner {
+    /// Creates new instance of Interner without any strings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Gets the symbol of `word`, interning it first if it's new.
+    ///
+    /// # Panics
+    ///
+    /// Panics if more than `u32::MAX` strings are interned.
+    pub fn get_or_intern(&mut self, word: &str) -> Symbol {
+        let hash = self.hasher.hash_one(word);
+        if let Some(symbol) = self.find(hash, word) {
+            return symbol;
+        }
+        let symbol = Symbol(u32::try_from(self.ends.len()).expect("Too many interned strings"));
+        self.text.push_str(word);
+        self.ends.push(self.text.len());
+        if let Entry::Vacant(entry) = self.symbols.entry(hash) {
+            entry.insert(symbol);
+        } else {
+            self.collisions.entry(hash).or_default().push(symbol);
+        }
+        symbol
+    }
+
+    /// Gets the symbol of `word`, or `None` if it hasn't been interned.
+    pub fn get(&self, word: &str) -> Option<Symbol> {
+        self.find(self.hasher.hash_one(word), word)
+    }
+
+    fn find(&self, hash: u64, word: &str) -> Option<Symbol> {
+        let first = *self.symbols.get(&hash)?;
+        std::iter::once(first)
+            .chain(self.collisions.get(&hash).into_iter().flatten().copied())
+            .find(|symbol| self.resolve(*symbol) == Some(word))
+    }
+
+    /// Gets the string of `symbol`, or `None` if it doesn't belong to this interner.
+    pub fn resolve(&self, symbol: Symbol) -> Option<&str> {
+        let end = *self.ends.get(symbol.index())?;
+        let start = symbol.index().checked_sub(1).map_or(0, |previous| self.ends[previous]);
+        Some(&self.text[start..end])
+    }
+
+    /// Interns every token of `tokens`, getting their symbols in order.
+    pub fn intern_tokens<S: AsRef<str>>(&mut self, tokens: impl IntoIterator<Item = S>) -> Vec<Symbol> {
+        tokens.into_iter().map(|token| self.get_or_intern(token.as_ref())).collect()
+    }
+
+    /// Gets the number of interned strings.
+    pub fn len(&self) -> usize {
+        self.ends.len()
+    }
+
+    /// Checks if no strings have been interned.
+    pub fn is_empty(&self) -> bool {
+        self.ends.is_empty()
+    }
+
+    /// Gets every symbol with its string, in symbol order.
+    pub fn iter(&self) -> impl Iterator<Item = (Symbol, &str)> {
+        (0..self.ends.len()).map(|index| {
+            let symbol = Symbol(index as u32);
+            (symbol, self.resolve(symbol).unwrap_or_default())
+        })
+    }
+}
+
+impl PartialEq for Interner {
+    fn eq(&self, other: &Self) -> bool {
+        self.text == other.text && self.ends == other.ends
+    }
+}
+
+impl<S: AsRef<str>> FromIterator<S> for Interner {
+    fn from_iter<I: IntoIterator<Item = S>>(words: I) -> Self {
+        let mut interner = Interner::new();
+        interner.intern_tokens(words);
+        interner
+    }
+}
+
+impl From<Vec<String>> for Interner {
+    fn from(words: Vec<String>) -> Self {
+        words.into_iter().collect()
+    }
+}
+
+impl From<Interner> for Vec<String> {
+    fn from(interner: Interner) -> Self {
+        interner.iter().map(|(_, word)| word.to_string()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interns_each_word_once() {
+        let mut interner: Interner = ["b", "a", "b", ""].into_iter().collect();
+        assert_eq!(interner.len(), 3);
+        assert_eq!(interner.text, "ba");
+        assert_eq!(interner.get(""), Some(Symbol(2)));
+        assert_eq!(interner.get_or_intern("a"), Symbol(1));
+        assert_eq!(interner.resolve(Symbol(3)), None);
+
+        let json = serde_json::to_string(&interner).unwrap();
+        assert_eq!(json, r#"["b","a",""]"#);
+        let round_trip: Interner = serde_json::from_str(&json).unwrap();
+        assert_eq!(round_trip, interner);
+        assert_eq!(round_trip.get("a"), Some(Symbol(1)));
+    }
+
+    #[test]
+    fn hash_collisions_keep_words_apart() {
+        let mut interner = Interner::new();
+        let (first, second) = (interner.get_or_intern("first"), interner.get_or_intern("second"));
+        // file "second" under the hash of "first", as a colliding hash would
+        let hash = interner.hasher.hash_one("first");
+        interner.symbols.retain(|_, symbol| *symbol == first);
+        interner.collisions.insert(hash, vec![second]);
+        assert_eq!(interner.find(hash, "second"), Some(second));
+        assert_eq!(interner.find(hash, "first"), Some(first));
+        assert_eq!(interner.find(hash, "third"), None);
+    }
+}
@@ -0,0 +1,737 @@
+//! Module containing topic models, which describe documents as mixtures of topics and topics as
+//! weighted sets of terms.
+//!
+//! Topics can be anchored with seed words, so that a topic lines up with a known category (such as
+//! "billing" or "shipping") instead of whichever latent axis the factorization would otherwise find.
+//! Topics, or any clusters described by term weights, can be arranged into a labeled tree for browsing
+//! with [`build_topic_hierarchy`].
+
+use std::collections::HashMap;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Serialize, Deserialize};
+
+use crate::cancel::CancellationToken;
+use crate::corpus::{Corpus, DocId};
+use crate::document::{self, DocumentTermFrequencies, GenericMatrix};
+use crate::error::RnltkError;
+use crate::{similarity, token};
+use crate::trace::stage_span;
+
+/// Prior on the topics of each document, used by [`TopicMethod::Lda`].
+const LDA_ALPHA: f64 = 0.1;
+/// Prior on the terms of each topic, used by [`TopicMethod::Lda`].
+const LDA_BETA: f64 = 0.01;
+/// Added to denominators of the NMF updates so they never divide by zero.
+const NMF_EPSILON: f64 = 1e-10;
+
+/// Method used by [`TopicModel::new`] to find topics.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TopicMethod {
+    /// Non-negative matrix factorization of the term frequencies with multiplicative updates
+    Nmf,
+    /// Latent Dirichlet allocation fitted with collapsed Gibbs sampling
+    Lda,
+}
+
+/// Struct for configuring [`TopicModel::new`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TopicConfig {
+    pub method: TopicMethod,
+    pub num_topics: usize,
+    /// Number of NMF updates or Gibbs sampling sweeps
+    pub iterations: usize,
+    /// Seed of the random number generator, so the same seed fits the same topics
+    pub seed: u64,
+    /// Seed words of the first `seed_words.len()` topics, where `seed_words[i]` anchors topic `i`.
+    /// Seed words must be terms of the model, so they should be stemmed if the documents were.
+    pub seed_words: Vec<Vec<String>>,
+    /// How strongly seed words pull their topic towards them. For [`TopicMethod::Lda`] this is the
+    /// pseudo-count added to the prior of a seed word in its topic.
+    pub seed_weight: f64,
+}
+
+impl Default for TopicConfig {
+    fn default() -> Self {
+        TopicConfig {
+            method: TopicMethod::Nmf,
+            num_topics: 10,
+            iterations: 200,
+            seed: 0,
+            seed_words: vec![],
+            seed_weight: 1.,
+        }
+    }
+}
+
+/// Struct for holding a node of the tree built by [`build_topic_hierarchy`], grouping similar topics.
+/// Leaves hold a single topic and inner nodes hold the topics of all their descendants.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TopicNode {
+    /// The top terms of the node joined by " / ", for display
+    pub label: String,
+    /// Terms with the highest weight across the node's topics, from highest to lowest
+    pub top_terms: Vec<String>,
+    /// Indices of the topics under this node, in ascending order
+    pub topics: Vec<usize>,
+    /// Average cosine similarity between the topics of the two children when they were merged, or 1 for leaves
+    pub similarity: f64,
+    pub children: Vec<TopicNode>,
+}
+
+/// Struct for holding a fitted topic model, created with [`TopicModel::new`].
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TopicModel {
+    method: TopicMethod,
+    terms: Vec<String>,
+    term_indices: HashMap<String, usize>,
+    // terms × topics, the NMF factor W or the LDA topic-term distributions
+    topic_terms: GenericMatrix,
+    // topics × documents, each column a distribution over topics
+    document_topics: GenericMatrix,
+    doc_ids: Vec<DocId>,
+    // iterations and seed of the fit, reused by inference
+    iterations: usize,
+    seed: u64,
+}
+
+impl TopicModel {
+    /// Creates new instance of TopicModel by fitting `config.num_topics` topics to `term_frequencies`.
+    /// `terms` labels the rows of `term_frequencies`.
+    ///
+    /// Seed words are anchored to their topic. With [`TopicMethod::Nmf`] a seed word starts out weighted
+    /// towards its topic and can't load on any other topic, since multiplicative updates keep zero weights at zero.
+    /// With [`TopicMethod::Lda`] every occurrence of a seed word starts in its topic, and the word's prior in that topic
+    /// is raised by `seed_weight`. Topics without seed words are found as usual.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RnltkError::LabelCountMismatch`] if the number of `terms` doesn't match the number of rows.
+    ///
+    /// Returns [`RnltkError::TermFrequenciesEmpty`] if there are no terms or no documents.
+    ///
+    /// Returns [`RnltkError::TermFrequenciesInvalid`] if a frequency is negative or not finite, or isn't a whole number
+    /// with [`TopicMethod::Lda`], which samples a topic for every occurrence of a term.
+    ///
+    /// Returns [`RnltkError::InvalidTopicConfig`] if there are no topics, more seeded topics than topics,
+    /// a seed word that isn't one of the `terms`, or a `seed_weight` that is negative or not finite.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rnltk::document::DocumentTermFrequencies;
+    /// use rnltk::topic::{TopicConfig, TopicMethod, TopicModel};
+    /// use nalgebra::DMatrix;
+    ///
+    /// let terms = vec!["bees", "honey", "hive", "rocket", "orbit"];
+    /// let term_frequencies = DMatrix::from_row_slice(5, 4, &[2., 1., 0., 0.,
+    ///     1., 2., 0., 0.,
+    ///     1., 1., 0., 0.,
+    ///     0., 0., 2., 1.,
+    ///     0., 0., 1., 2.,]);
+    /// let config = TopicConfig {
+    ///     method: TopicMethod::Nmf,
+    ///     num_topics: 2,
+    ///     seed_words: vec![vec!["rocket".to_string()], vec!["honey".to_string()]],
+    ///     ..TopicConfig::default()
+    /// };
+    /// let topic_model = TopicModel::new(terms, &DocumentTermFrequencies::new(term_frequencies).unwrap(), &config).unwrap();
+    ///
+    /// let space_terms: Vec<String> = topic_model.get_topic_terms(0, 2).into_iter().map(|(term, _)| term).collect();
+    ///
+    /// assert!(space_terms.contains(&"rocket".to_string()) && space_terms.contains(&"orbit".to_string()));
+    /// assert!(topic_model.get_document_topics()[(1, 0)] > 0.9);
+    /// ```
+    pub fn new<T: ToString>(terms: Vec<T>, term_frequencies: &DocumentTermFrequencies, config: &TopicConfig) -> Result<Self, RnltkError> {
+        Self::new_cancellable(terms, term_frequencies, config, &CancellationToken::new())
+    }
+
+    /// Creates new instance of TopicModel like [`TopicModel::new`], checking `cancellation_token`
+    /// before every NMF update or Gibbs sampling sweep.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error of [`TopicModel::new`].
+    ///
+    /// Returns [`RnltkError::Cancelled`] if `cancellation_token` was cancelled.
+    pub fn new_cancellable<T: ToString>(terms: Vec<T>, term_frequencies: &DocumentTermFrequencies, config: &TopicConfig, cancellation_token: &CancellationToken) -> Result<Self, RnltkError> {
+        let frequencies = &term_frequencies.document_term_frequencies;
+        if terms.len() != frequencies.nrows() {
+            return Err(RnltkError::LabelCountMismatch);
+        }
+        if frequencies.nrows() == 0 || frequencies.ncols() == 0 {
+            return Err(RnltkError::TermFrequenciesEmpty);
+        }
+        let is_count = |frequency: &f64| config.method != TopicMethod::Lda || frequency.fract() == 0.;
+        if frequencies.iter().any(|frequency| !frequency.is_finite() || *frequency < 0. || !is_count(frequency)) {
+            return Err(RnltkError::TermFrequenciesInvalid);
+        }
+        if config.num_topics == 0 {
+            return Err(RnltkError::InvalidTopicConfig("number of topics must be at least 1".to_string()));
+        }
+        if config.seed_words.len() > config.num_topics {
+            return Err(RnltkError::InvalidTopicConfig(format!("{} seeded topics but only {} topics", config.seed_words.len(), config.num_topics)));
+        }
+        if !config.seed_weight.is_finite() || config.seed_weight < 0. {
+            return Err(RnltkError::InvalidTopicConfig("seed weight must be finite and non-negative".to_string()));
+        }
+        stage_span!("topic_model", topics = config.num_topics, terms = terms.len(), documents = frequencies.ncols());
+
+        let terms: Vec<String> = terms.iter().map(|term| term.to_string()).collect();
+        let term_indices: HashMap<String, usize> = terms.iter().enumerate().map(|(index, term)| (term.clone(), index)).collect();
+        let mut seeds: Vec<(usize, usize)> = vec![];
+        for (topic, words) in config.seed_words.iter().enumerate() {
+            for word in words {
+                let index = term_indices.get(word)
+                    .ok_or_else(|| RnltkError::InvalidTopicConfig(format!("seed word '{word}' is not a term")))?;
+                seeds.push((*index, topic));
+            }
+        }
+
+        let (topic_terms, document_topics) = match config.method {
+            TopicMethod::Nmf => fit_nmf(frequencies, &seeds, config, cancellation_token)?,
+            TopicMethod::Lda => fit_lda(frequencies, &seeds, config, cancellation_token)?,
+        };
+        let doc_ids = (0..frequencies.ncols() as u64).map(DocId).collect();
+        Ok(TopicModel {
+            method: config.method,
+            terms,
+            term_indices,
+            topic_terms,
+            document_topics,
+            doc_ids,
+            iterations: config.iterations,
+            seed: config.seed,
+        })
+    }
+
+    /// Creates new instance of TopicModel from a [`Corpus`], using its terms as the vocabulary and
+    /// its [`DocId`]s to identify documents.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error of [`TopicModel::new`].
+    pub fn from_corpus(corpus: &Corpus, config: &TopicConfig) -> Result<Self, RnltkError> {
+        let mut topic_model = TopicModel::new(corpus.get_terms(), &corpus.get_document_term_frequencies()?, config)?;
+        topic_model.doc_ids = corpus.get_doc_ids();
+        Ok(topic_model)
+    }
+
+    /// Gets the method the model was fitted with.
+    pub fn get_method(&self) -> TopicMethod {
+        self.method
+    }
+
+    /// Gets the number of topics.
+    pub fn num_topics(&self) -> usize {
+        self.topic_terms.ncols()
+    }
+
+    /// Gets the vocabulary of the model.
+    pub fn get_terms(&self) -> &[String] {
+        &self.terms
+    }
+
+    /// Gets the ids of the documents, one per column of [`TopicModel::get_document_topics`].
+    /// Models created with [`TopicModel::new`] number documents by column.
+    pub fn get_doc_ids(&self) -> &[DocId] {
+        &self.doc_ids
+    }
+
+    /// Gets the topic distribution of every document as the columns of a topics × documents matrix.
+    /// Each column sums to 1, except for documents without any terms, whose columns are all 0.
+    pub fn get_document_topics(&self) -> &GenericMatrix {
+        &self.document_topics
+    }
+
+    /// Gets the `n` terms with the highest weight in `topic`, with their share of the topic's total weight,
+    /// from highest to lowest. Topics out of range have no terms.
+    pub fn get_topic_terms(&self, topic: usize, n: usize) -> Vec<(String, f64)> {
+        if topic >= self.num_topics() {
+            return vec![];
+        }
+        let column = self.topic_terms.column(topic);
+        let total = column.sum();
+        let mut terms: Vec<(String, f64)> = self.terms.iter().zip(column.iter())
+            .map(|(term, weight)| (term.clone(), if total > 0. { weight / total } else { 0. }))
+            .collect();
+        // stable sort keeps ties in vocabulary order
+        terms.sort_by(|(_, first), (_, second)| second.total_cmp(first));
+        terms.truncate(n);
+        terms
+    }
+
+    /// Gets the index of `term` in the vocabulary, if it's a term of the model.
+    pub fn get_term_index(&self, term: &str) -> Option<usize> {
+        self.term_indices.get(term).copied()
+    }
+
+    /// Gets the topic distribution of unseen `document`, tokenized with [`token::tokenize_sentence`], while
+    /// keeping the topics fixed. See [`TopicModel::infer_tokens`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rnltk::corpus::CorpusReader;
+    /// use rnltk::topic::{TopicConfig, TopicMethod, TopicModel};
+    ///
+    /// let corpus = CorpusReader::new().read_texts(vec![
+    ///     "bees make honey in the hive",
+    ///     "honey from the hive of bees",
+    ///     "the rocket reached orbit",
+    ///     "a rocket launch to orbit",
+    /// ]).unwrap();
+    /// let config = TopicConfig {
+    ///     method: TopicMethod::Lda,
+    ///     num_topics: 2,
+    ///     seed_words: vec![vec!["bees".to_string()], vec!["rocket".to_string()]],
+    ///     ..TopicConfig::default()
+    /// };
+    /// let topic_model = TopicModel::from_corpus(&corpus, &config).unwrap();
+    /// let topics = topic_model.infer("Honey bees buzz around the hive");
+    ///
+    /// assert!(topics[0] > topics[1]);
+    /// assert!(topic_model.infer("unknown words").iter().all(|weight| *weight == 0.));
+    /// ```
+    pub fn infer(&self, document: &str) -> Vec<f64> {
+        self.infer_tokens(&token::tokenize_sentence(document))
+    }
+
+    /// Gets the topic distribution of a document made of `tokens`, keeping the topics fixed. The distribution
+    /// sums to 1, or is all 0 if none of the tokens are terms of the model.
+    ///
+    /// NMF models fold the document in by fitting its topic weights with the same multiplicative updates used
+    /// for the training documents. LDA models run Gibbs sampling on the document alone with the topic-term
+    /// distributions fixed, averaging over the second half of the sweeps, and always give the same result
+    /// for the same document. LSA models fold documents in with [`LsaModel::embed_sentence`](crate::document::LsaModel::embed_sentence).
+    pub fn infer_tokens<S: AsRef<str>>(&self, tokens: &[S]) -> Vec<f64> {
+        let term_indices: Vec<usize> = tokens.iter().filter_map(|token| self.get_term_index(token.as_ref())).collect();
+        if term_indices.is_empty() {
+            return vec![0.; self.num_topics()];
+        }
+        match self.method {
+            TopicMethod::Nmf => self.infer_nmf(&term_indices),
+            TopicMethod::Lda => self.infer_lda(&term_indices),
+        }
+    }
+
+    // checks that the vocabulary, topics, and documents of a loaded model agree
+    pub(crate) fn check_consistency(&self) -> Result<(), RnltkError> {
+        let problem = if self.topic_terms.nrows() != self.terms.len() {
+            Some(format!("{} terms but {} rows of topic-term weights", self.terms.len(), self.topic_terms.nrows()))
+        } else if !document::term_indices_match(&self.terms, &self.term_indices) {
+            Some("term indices don't match the terms".to_string())
+        } else if self.num_topics() == 0 || self.document_topics.nrows() != self.num_topics() {
+            Some(format!("{} topics but {} rows of document-topic weights", self.num_topics(), self.document_topics.nrows()))
+        } else if self.doc_ids.len() != self.document_topics.ncols() {
+            Some(format!("{} document ids but {} documents", self.doc_ids.len(), self.document_topics.ncols()))
+        } else {
+            None
+        };
+        problem.map_or(Ok(()), |problem| Err(RnltkError::ModelFormat(problem)))
+    }
+
+    /// Gets a tree of the model's topics, built by [`build_topic_hierarchy`] with nodes labeled by their `num_label_terms` top terms.
+    pub fn get_topic_hierarchy(&self, num_label_terms: usize) -> TopicNode {
+        let topic_terms: Vec<Vec<f64>> = self.topic_terms.column_iter().map(|column| column.iter().copied().collect()).collect();
+        build_topic_hierarchy(&topic_terms, &self.terms, num_label_terms).expect("Topic models have at least one topic and one weight per term")
+    }
+
+    fn infer_nmf(&self, term_indices: &[usize]) -> Vec<f64> {
+        let mut frequencies = nalgebra::DVector::zeros(self.terms.len());
+        for index in term_indices {
+            frequencies[*index] += 1.;
+        }
+        let w = &self.topic_terms;
+        let numerator = w.transpose() * frequencies;
+        let gram = w.transpose() * w;
+        let mut h = nalgebra::DVector::from_element(self.num_topics(), 1. / self.num_topics() as f64);
+        for _ in 0..self.iterations.max(1) {
+            let denominator = &gram * &h;
+            h.zip_zip_apply(&numerator, &denominator, |value, numerator, denominator| *value *= numerator / (denominator + NMF_EPSILON));
+        }
+        normalize(h.iter().copied().collect())
+    }
+
+    fn infer_lda(&self, term_indices: &[usize]) -> Vec<f64> {
+        let num_topics = self.num_topics();
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let mut topics: Vec<usize> = term_indices.iter().map(|_| rng.gen_range(0..num_topics)).collect();
+        let mut topic_counts = vec![0.; num_topics];
+        topics.iter().for_each(|topic| topic_counts[*topic] += 1.);
+
+        let iterations = self.iterations.max(1);
+        let mut totals = vec![0.; num_topics];
+        let mut weights = vec![0.; num_topics];
+        for iteration in 0..iterations {
+            for (term, topic) in term_indices.iter().zip(topics.iter_mut()) {
+                topic_counts[*topic] -= 1.;
+                let mut total = 0.;
+                for (candidate, weight) in weights.iter_mut().enumerate() {
+                    *weight = (topic_counts[candidate] + LDA_ALPHA) * self.topic_terms[(*term, candidate)];
+                    total += *weight;
+                }
+                *topic = sample(&weights, total, &mut rng);
+                topic_counts[*topic] += 1.;
+            }
+            if iteration >= iterations / 2 {
+                totals.iter_mut().zip(&topic_counts).for_each(|(sum, count)| *sum += count + LDA_ALPHA);
+            }
+        }
+        normalize(totals)
+    }
+}
+
+/// Organizes topics or clusters, given as their weights over `terms`, into a binary tree for browsing, by
+/// repeatedly merging the two groups with the highest average cosine similarity between their members
+/// (agglomerative clustering with average linkage). Each node is labeled with the `num_label_terms` terms
+/// of highest total weight, where each topic's weights are first scaled to sum to 1. The tree serializes to
+/// nested JSON objects.
+///
+/// # Errors
+///
+/// Returns [`RnltkError::TermFrequenciesEmpty`] if there are no topics.
+///
+/// Returns [`RnltkError::LabelCountMismatch`] if a topic doesn't have one weight per term.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::topic;
+///
+/// let terms = vec!["bees".to_string(), "honey".to_string(), "rocket".to_string()];
+/// let topics = vec![vec![0.8, 0.2, 0.], vec![0., 0.1, 0.9], vec![0.6, 0.4, 0.]];
+/// let root = topic::build_topic_hierarchy(&topics, &terms, 2).unwrap();
+///
+/// assert_eq!(root.topics, vec![0, 1, 2]);
+/// assert_eq!(root.children[0].topics, vec![0, 2]);
+/// assert_eq!(root.children[0].label, "bees / honey");
+/// assert_eq!(root.children[1].label, "rocket / honey");
+/// ```
+pub fn build_topic_hierarchy(topic_terms: &[Vec<f64>], terms: &[String], num_label_terms: usize) -> Result<TopicNode, RnltkError> {
+    if topic_terms.is_empty() {
+        return Err(RnltkError::TermFrequenciesEmpty);
+    }
+    if topic_terms.iter().any(|weights| weights.len() != terms.len()) {
+        return Err(RnltkError::LabelCountMismatch);
+    }
+    let distributions: Vec<Vec<f64>> = topic_terms.iter().map(|weights| normalize(weights.clone())).collect();
+    let label_node = |topics: Vec<usize>, similarity: f64, children: Vec<TopicNode>| {
+        let mut totals = vec![0.; terms.len()];
+        for topic in &topics {
+            totals.iter_mut().zip(&distributions[*topic]).for_each(|(total, weight)| *total += weight);
+        }
+        let mut ranked: Vec<usize> = (0..terms.len()).filter(|index| totals[*index] > 0.).collect();
+        // stable sort keeps ties in term order
+        ranked.sort_by(|first, second| totals[*second].total_cmp(&totals[*first]));
+        let top_terms: Vec<String> = ranked.into_iter().take(num_label_terms).map(|index| terms[index].clone()).collect();
+        TopicNode {
+            label: top_terms.join(" / "),
+            top_terms,
+            topics,
+            similarity,
+            children,
+        }
+    };
+
+    let mut nodes: Vec<TopicNode> = (0..topic_terms.len()).map(|topic| label_node(vec![topic], 1., vec![])).collect();
+    while nodes.len() > 1 {
+        let mut best = (0, 1, f64::NEG_INFINITY);
+        for first in 0..nodes.len() {
+            for second in (first + 1)..nodes.len() {
+                let pairs = (nodes[first].topics.len() * nodes[second].topics.len()) as f64;
+                let similarity = nodes[first].topics.iter()
+                    .flat_map(|a| nodes[second].topics.iter().map(move |b| (a, b)))
+                    .map(|(a, b)| similarity::cosine(&distributions[*a], &distributions[*b]))
+                    .sum::<f64>() / pairs;
+                if similarity > best.2 {
+                    best = (first, second, similarity);
+                }
+            }
+        }
+        let (first, second, similarity) = best;
+        let second_node = nodes.remove(second);
+        let first_node = nodes.remove(first);
+        let mut topics: Vec<usize> = first_node.topics.iter().chain(&second_node.topics).copied().collect();
+        topics.sort_unstable();
+        // the merged node takes the place of its first child, keeping nodes in order of their lowest topic
+        nodes.insert(first, label_node(topics, similarity, vec![first_node, second_node]));
+    }
+    Ok(nodes.remove(0))
+}
+
+// scales `weights` to sum to 1, unless they're all 0
+fn normalize(mut weights: Vec<f64>) -> Vec<f64> {
+    let total: f64 = weights.iter().sum();
+    if total > 0. {
+        weights.iter_mut().for_each(|weight| *weight /= total);
+    }
+    weights
+}
+
+// index drawn with probability proportional to its weight, where `total` is the sum of `weights`
+fn sample(weights: &[f64], total: f64, rng: &mut StdRng) -> usize {
+    let mut target = rng.gen_range(0. ..total);
+    weights.iter().position(|weight| {
+        target -= weight;
+        target < 0.
+    }).unwrap_or(weights.len() - 1)
+}
+
+// W (terms × topics) and normalized H (topics × documents) of V ≈ WH
+fn fit_nmf(frequencies: &GenericMatrix, seeds: &[(usize, usize)], config: &TopicConfig, cancellation_token: &CancellationToken) -> Result<(GenericMatrix, GenericMatrix), RnltkError> {
+    let (num_terms, num_documents) = frequencies.shape();
+    let mut rng = StdRng::seed_from_u64(config.seed);
+    let scale = (frequencies.mean().max(NMF_EPSILON) / config.num_topics as f64).sqrt();
+    let mut w = GenericMatrix::from_fn(num_terms, config.num_topics, |_, _| scale * rng.gen_range(0.01..1.));
+    let mut h = GenericMatrix::from_fn(config.num_topics, num_documents, |_, _| scale * rng.gen_range(0.01..1.));
+    // a seed word of several topics is anchored to each of them
+    let anchors: Vec<f64> = seeds.iter()
+        .map(|(term, topic)| w[(*term, *topic)] + config.seed_weight * frequencies.row(*term).max().max(scale))
+        .collect();
+    for (term, _) in seeds {
+        w.row_mut(*term).fill(0.);
+    }
+    for ((term, topic), anchored) in seeds.iter().zip(anchors) {
+        w[(*term, *topic)] = anchored;
+    }
+
+    for _ in 0..config.iterations {
+        cancellation_token.check()?;
+        let numerator = w.transpose() * frequencies;
+        let denominator = w.transpose() * &w * &h;
+        h.zip_zip_apply(&numerator, &denominator, |value, numerator, denominator| *value *= numerator / (denominator + NMF_EPSILON));
+        let numerator = frequencies * h.transpose();
+        let denominator = &w * &h * h.transpose();
+        w.zip_zip_apply(&numerator, &denominator, |value, numerator, denominator| *value *= numerator / (denominator + NMF_EPSILON));
+    }
+
+    for mut column in h.column_iter_mut() {
+        let total = column.sum();
+        if total > 0. {
+            column /= total;
+        }
+    }
+    Ok((w, h))
+}
+
+// topic-term distributions (terms × topics) and document-topic distributions (topics × documents)
+fn fit_lda(frequencies: &GenericMatrix, seeds: &[(usize, usize)], config: &TopicConfig, cancellation_token: &CancellationToken) -> Result<(GenericMatrix, GenericMatrix), RnltkError> {
+    let (num_terms, num_documents) = frequencies.shape();
+    let num_topics = config.num_topics;
+    let mut rng = StdRng::seed_from_u64(config.seed);
+
+    let mut priors = GenericMatrix::from_element(num_terms, num_topics, LDA_BETA);
+    let mut seed_topics: HashMap<usize, usize> = HashMap::new();
+    for (term, topic) in seeds {
+        priors[(*term, *topic)] += config.seed_weight;
+        seed_topics.entry(*term).or_insert(*topic);
+    }
+    let prior_totals: Vec<f64> = priors.column_iter().map(|column| column.sum()).collect();
+
+    // every occurrence of a term in a document, with its topic
+    let mut occurrences: Vec<Vec<(usize, usize)>> = vec![vec![]; num_documents];
+    let mut document_counts = GenericMatrix::zeros(num_topics, num_documents);
+    let mut term_counts = GenericMatrix::zeros(num_terms, num_topics);
+    let mut topic_counts = vec![0.; num_topics];
+    for (document, column) in frequencies.column_iter().enumerate() {
+        for (term, frequency) in column.iter().enumerate() {
+            for _ in 0..*frequency as usize {
+                let topic = seed_topics.get(&term).copied().unwrap_or_else(|| rng.gen_range(0..num_topics));
+                occurrences[document].push((term, topic));
+                document_counts[(topic, document)] += 1.;
+                term_counts[(term, topic)] += 1.;
+                topic_counts[topic] += 1.;
+            }
+        }
+    }
+
+    let mut weights = vec![0.; num_topics];
+    for _ in 0..config.iterations {
+        cancellation_token.check()?;
+        for (document, document_occurrences) in occurrences.iter_mut().enumerate() {
+            for (term, topic) in document_occurrences.iter_mut() {
+                document_counts[(*topic, document)] -= 1.;
+                term_counts[(*term, *topic)] -= 1.;
+                topic_counts[*topic] -= 1.;
+
+                let mut total = 0.;
+                for (candidate, weight) in weights.iter_mut().enumerate() {
+                    *weight = (document_counts[(candidate, document)] + LDA_ALPHA)
+                        * (term_counts[(*term, candidate)] + priors[(*term, candidate)])
+                        / (topic_counts[candidate] + prior_totals[candidate]);
+                    total += *weight;
+                }
+                *topic = sample(&weights, total, &mut rng);
+
+                document_counts[(*topic, document)] += 1.;
+                term_counts[(*term, *topic)] += 1.;
+                topic_counts[*topic] += 1.;
+            }
+        }
+    }
+
+    let topic_terms = GenericMatrix::from_fn(num_terms, num_topics, |term, topic| {
+        (term_counts[(term, topic)] + priors[(term, topic)]) / (topic_counts[topic] + prior_totals[topic])
+    });
+    let document_topics = GenericMatrix::from_fn(num_topics, num_documents, |topic, document| {
+        let length = occurrences[document].len() as f64;
+        if length == 0. {
+            0.
+        } else {
+            (document_counts[(topic, document)] + LDA_ALPHA) / (length + num_topics as f64 * LDA_ALPHA)
+        }
+    });
+    Ok((topic_terms, document_topics))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::corpus::CorpusReader;
+
+    fn corpus() -> Corpus {
+        CorpusReader::new().read_texts(vec![
+            "refund charged invoice billing refund",
+            "invoice payment charged twice refund",
+            "package delivery late courier tracking",
+            "tracking package courier delivery lost",
+            "billing invoice payment refund",
+            "courier delivery package tracking late",
+        ]).unwrap()
+    }
+
+    fn seeded_config(method: TopicMethod) -> TopicConfig {
+        TopicConfig {
+            method,
+            num_topics: 2,
+            seed_words: vec![vec!["delivery".to_string(), "courier".to_string()], vec!["refund".to_string()]],
+            ..TopicConfig::default()
+        }
+    }
+
+    #[test]
+    fn seeded_topics_follow_their_seed_words() {
+        for method in [TopicMethod::Nmf, TopicMethod::Lda] {
+            let topic_model = TopicModel::from_corpus(&corpus(), &seeded_config(method)).unwrap();
+            let shipping: Vec<String> = topic_model.get_topic_terms(0, 4).into_iter().map(|(term, _)| term).collect();
+            let billing: Vec<String> = topic_model.get_topic_terms(1, 4).into_iter().map(|(term, _)| term).collect();
+            assert!(shipping.contains(&"courier".to_string()) && shipping.contains(&"package".to_string()), "{method:?} {shipping:?}");
+            assert!(billing.contains(&"refund".to_string()) && billing.contains(&"invoice".to_string()), "{method:?} {billing:?}");
+
+            let document_topics = topic_model.get_document_topics();
+            assert!(document_topics[(0, 2)] > document_topics[(1, 2)], "{method:?}");
+            assert!(document_topics[(1, 0)] > document_topics[(0, 0)], "{method:?}");
+            for column in document_topics.column_iter() {
+                assert!((column.sum() - 1.).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn fitting_is_reproducible() {
+        let config = TopicConfig { method: TopicMethod::Lda, num_topics: 3, iterations: 20, ..TopicConfig::default() };
+        let first = TopicModel::from_corpus(&corpus(), &config).unwrap();
+        let second = TopicModel::from_corpus(&corpus(), &config).unwrap();
+        assert_eq!(first.get_document_topics(), second.get_document_topics());
+        assert_eq!(first.num_topics(), 3);
+        assert!(first.get_topic_terms(3, 5).is_empty());
+    }
+
+    #[test]
+    fn inference_matches_the_training_documents() {
+        for method in [TopicMethod::Nmf, TopicMethod::Lda] {
+            let corpus = corpus();
+            let topic_model = TopicModel::from_corpus(&corpus, &seeded_config(method)).unwrap();
+            let shipping = topic_model.infer("The courier lost my package");
+            let billing = topic_model.infer_tokens(&["invoice", "refund", "unknown"]);
+            assert!(shipping[0] > shipping[1], "{method:?} {shipping:?}");
+            assert!(billing[1] > billing[0], "{method:?} {billing:?}");
+            assert!((shipping.iter().sum::<f64>() - 1.).abs() < 1e-9);
+            assert_eq!(topic_model.infer("The courier lost my package"), shipping);
+            assert_eq!(topic_model.infer(""), vec![0., 0.]);
+        }
+    }
+
+    #[test]
+    fn invalid_frequencies() {
+        let fractional = DocumentTermFrequencies::new(GenericMatrix::from_row_slice(2, 2, &[1.5, 0., 0., 2.])).unwrap();
+        let lda = TopicConfig { method: TopicMethod::Lda, num_topics: 2, ..TopicConfig::default() };
+        let nmf = TopicConfig { method: TopicMethod::Nmf, ..lda.clone() };
+        assert_eq!(TopicModel::new(vec!["bees", "honey"], &fractional, &lda).unwrap_err(), RnltkError::TermFrequenciesInvalid);
+        assert!(TopicModel::new(vec!["bees", "honey"], &fractional, &nmf).is_ok());
+
+        let negative = DocumentTermFrequencies { document_term_frequencies: GenericMatrix::from_row_slice(2, 2, &[1., -1., 0., 2.]) };
+        assert_eq!(TopicModel::new(vec!["bees", "honey"], &negative, &nmf).unwrap_err(), RnltkError::TermFrequenciesInvalid);
+    }
+
+    #[test]
+    fn seed_word_of_several_topics() {
+        let config = TopicConfig {
+            num_topics: 3,
+            seed_words: vec![vec!["refund".to_string()], vec!["refund".to_string(), "courier".to_string()]],
+            iterations: 0,
+            ..TopicConfig::default()
+        };
+        let topic_model = TopicModel::from_corpus(&corpus(), &config).unwrap();
+        let refund = topic_model.get_term_index("refund").unwrap();
+        let courier = topic_model.get_term_index("courier").unwrap();
+        let anchored = |term: usize, topic: usize| topic_model.topic_terms[(term, topic)];
+        assert!(anchored(refund, 0) > 1. && anchored(refund, 1) > 1.);
+        assert_eq!(anchored(refund, 2), 0.);
+        assert!(anchored(courier, 1) > 1.);
+        assert_eq!((anchored(courier, 0), anchored(courier, 2)), (0., 0.));
+    }
+
+    #[test]
+    fn cancelled_fit() {
+        let corpus = corpus();
+        let term_frequencies = corpus.get_document_term_frequencies().unwrap();
+        for method in [TopicMethod::Nmf, TopicMethod::Lda] {
+            let config = TopicConfig { iterations: usize::MAX, ..seeded_config(method) };
+            let token = CancellationToken::new();
+            let worker_token = token.clone();
+            let canceller = std::thread::spawn(move || {
+                std::thread::sleep(std::time::Duration::from_millis(20));
+                worker_token.cancel();
+            });
+            let fit = TopicModel::new_cancellable(corpus.get_terms(), &term_frequencies, &config, &token);
+            canceller.join().unwrap();
+            assert_eq!(fit.unwrap_err(), RnltkError::Cancelled, "{method:?}");
+        }
+    }
+
+    #[test]
+    fn topic_hierarchy() {
+        let topic_model = TopicModel::from_corpus(&corpus(), &TopicConfig { num_topics: 3, ..seeded_config(TopicMethod::Nmf) }).unwrap();
+        let root = topic_model.get_topic_hierarchy(3);
+        assert_eq!(root.topics, vec![0, 1, 2]);
+        assert_eq!(root.children.len(), 2);
+        assert_eq!(root.top_terms.len(), 3);
+        fn count_leaves(node: &TopicNode) -> usize {
+            if node.children.is_empty() { 1 } else { node.children.iter().map(count_leaves).sum() }
+        }
+        assert_eq!(count_leaves(&root), 3);
+
+        let json = serde_json::to_string(&root).unwrap();
+        assert_eq!(serde_json::from_str::<TopicNode>(&json).unwrap(), root);
+
+        let terms = vec!["a".to_string()];
+        assert!(matches!(build_topic_hierarchy(&[], &terms, 1), Err(RnltkError::TermFrequenciesEmpty)));
+        assert!(matches!(build_topic_hierarchy(&[vec![1., 2.]], &terms, 1), Err(RnltkError::LabelCountMismatch)));
+        let leaf = build_topic_hierarchy(&[vec![1.]], &terms, 1).unwrap();
+        assert_eq!((leaf.label.as_str(), leaf.children.len(), leaf.similarity), ("a", 0, 1.));
+    }
+
+    #[test]
+    fn invalid_configs() {
+        let corpus = corpus();
+        let unknown = TopicConfig { seed_words: vec![vec!["weather".to_string()]], ..seeded_config(TopicMethod::Nmf) };
+        assert!(matches!(TopicModel::from_corpus(&corpus, &unknown), Err(RnltkError::InvalidTopicConfig(message)) if message.contains("weather")));
+        let too_many = TopicConfig { num_topics: 1, ..seeded_config(TopicMethod::Nmf) };
+        assert!(matches!(TopicModel::from_corpus(&corpus, &too_many), Err(RnltkError::InvalidTopicConfig(_))));
+        let no_topics = TopicConfig { num_topics: 0, ..TopicConfig::default() };
+        assert!(matches!(TopicModel::from_corpus(&corpus, &no_topics), Err(RnltkError::InvalidTopicConfig(_))));
+    }
+}
@@ -0,0 +1,15 @@
+//! Crate-internal instrumentation helpers.
+//!
+//! With the `tracing` feature enabled, [`stage_span!`] enters a [`tracing`] span that lasts until the
+//! end of the enclosing block, so services embedding RNLTK can see where time is spent. Without the
+//! feature the macro expands to nothing.
+
+/// Enters an info-level span named `$name` with optional `field = value` pairs until the end of the enclosing block.
+macro_rules! stage_span {
+    ($name:literal $(, $field:ident = $value:expr)* $(,)?) => {
+        #[cfg(feature = "tracing")]
+        let _stage_span = tracing::info_span!($name $(, $field = $value)*).entered();
+    };
+}
+
+pub(crate) use stage_span;
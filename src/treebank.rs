@@ -0,0 +1,138 @@
+//! Module containing a Penn Treebank style word tokenizer, an alternative to [`token::tokenize_sentence`](crate::token::tokenize_sentence)
+//! for downstream tools and gold corpora that expect PTB conventions.
+//!
+//! Unlike the default tokenizer, casing and punctuation are kept: punctuation is split into its own tokens,
+//! contractions are split ("don't" → "do", "n't"), and double quotes become \`\` and ''. The rules follow
+//! NLTK's `TreebankWordTokenizer`.
+
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+/// Penn Treebank tokenizer holding its precompiled rules.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::treebank::TreebankTokenizer;
+///
+/// let tokenizer = TreebankTokenizer::new();
+///
+/// assert_eq!(tokenizer.tokenize("They'll save and invest more."), vec!["They", "'ll", "save", "and", "invest", "more", "."]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct TreebankTokenizer {
+    // applied in order before the text is padded with spaces
+    unpadded_rules: Vec<(Regex, &'static str)>,
+    // applied in order after the text is padded, as they rely on tokens being followed by a space
+    padded_rules: Vec<(Regex, &'static str)>,
+}
+
+impl Default for TreebankTokenizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn rule(pattern: &str, replacement: &'static str) -> (Regex, &'static str) {
+    (Regex::new(pattern).expect("Invalid regex"), replacement)
+}
+
+impl TreebankTokenizer {
+    /// Creates new instance of TreebankTokenizer, compiling its rules.
+    pub fn new() -> Self {
+        let unpadded_rules = vec![
+            // starting quotes
+            rule(r#"^""#, "``"),
+            rule(r"(``)", " ${1} "),
+            rule(r#"([ (\[{<])("|'')"#, "${1} `` "),
+            // punctuation
+            rule(r"([:,])([^\d])", " ${1} ${2}"),
+            rule(r"([:,])\n?$", " ${1} "),
+            rule(r"\.\.\.", " ... "),
+            rule(r"[;@#$%&]", " ${0} "),
+            rule(r#"([^.])(\.)([\])}>"']*)\s*$"#, "${1} ${2}${3} "),
+            rule(r"[?!]", " ${0} "),
+            rule(r"([^'])' ", "${1} ' "),
+            // parentheses and brackets
+            rule(r"[\]\[(){}<>]", " ${0} "),
+            // double dashes
+            rule(r"--", " -- "),
+        ];
+        let padded_rules = vec![
+            // ending quotes
+            rule(r"''", " '' "),
+            rule(r#"""#, " '' "),
+            rule(r"([^' ])('[sS]|'[mM]|'[dD]|') ", "${1} ${2} "),
+            rule(r"([^' ])('ll|'LL|'re|'RE|'ve|'VE|n't|N'T) ", "${1} ${2} "),
+            // contractions
+            rule(r"(?i)\b(can)(not)\b", " ${1} ${2} "),
+            rule(r"(?i)\b(d)('ye)\b", " ${1} ${2} "),
+            rule(r"(?i)\b(gim)(me)\b", " ${1} ${2} "),
+            rule(r"(?i)\b(gon)(na)\b", " ${1} ${2} "),
+            rule(r"(?i)\b(got)(ta)\b", " ${1} ${2} "),
+            rule(r"(?i)\b(lem)(me)\b", " ${1} ${2} "),
+            rule(r"(?i)\b(more)('n)\b", " ${1} ${2} "),
+            rule(r"(?i)\b(wan)(na)(\s)", " ${1} ${2}${3}"),
+            rule(r"(?i) ('t)(is)\b", " ${1} ${2} "),
+            rule(r"(?i) ('t)(was)\b", " ${1} ${2} "),
+        ];
+        TreebankTokenizer {
+            unpadded_rules,
+            padded_rules,
+        }
+    }
+
+    /// Converts `text` to Penn Treebank tokens. Only a period at the very end of `text` is split off, so
+    /// `text` should be a single sentence, as from [`token::tokenize_into_sentences`](crate::token::tokenize_into_sentences).
+    pub fn tokenize(&self, text: &str) -> Vec<String> {
+        let mut text = text.to_string();
+        for (regex, replacement) in &self.unpadded_rules {
+            text = regex.replace_all(&text, *replacement).into_owned();
+        }
+        text = format!(" {} ", text);
+        for (regex, replacement) in &self.padded_rules {
+            text = regex.replace_all(&text, *replacement).into_owned();
+        }
+        text.split_whitespace().map(|token| token.to_string()).collect()
+    }
+}
+
+/// Converts `text` to Penn Treebank tokens using a shared [`TreebankTokenizer`].
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::treebank;
+///
+/// let tokens = treebank::tokenize("\"Don't stop,\" she said -- twice!");
+///
+/// assert_eq!(tokens, vec!["``", "Do", "n't", "stop", ",", "''", "she", "said", "--", "twice", "!"]);
+/// ```
+pub fn tokenize(text: &str) -> Vec<String> {
+    static TOKENIZER: OnceLock<TreebankTokenizer> = OnceLock::new();
+    TOKENIZER.get_or_init(TreebankTokenizer::new).tokenize(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn contractions() {
+        assert_eq!(tokenize("I cannot wait, gonna be fun"), vec!["I", "can", "not", "wait", ",", "gon", "na", "be", "fun"]);
+        assert_eq!(tokenize("'Tis the season"), vec!["'T", "is", "the", "season"]);
+        assert_eq!(tokenize("He's here and we've left"), vec!["He", "'s", "here", "and", "we", "'ve", "left"]);
+    }
+
+    #[test]
+    fn numbers_keep_separators() {
+        assert_eq!(tokenize("It cost 1,000.50 at 3:30"), vec!["It", "cost", "1,000.50", "at", "3:30"]);
+    }
+
+    #[test]
+    fn empty_text() {
+        assert!(tokenize("").is_empty());
+        assert!(tokenize("  \n ").is_empty());
+    }
+}
@@ -0,0 +1,492 @@
+//! Module containing temporal analysis of timestamped documents: term frequencies bucketed
+//! by period, rising/falling term detection, burst detection, and sentiment over time.
+//!
+//! Timestamps are plain `i64` values (e.g. Unix seconds) and periods are fixed-length
+//! buckets of `period_length` in the same unit, so `86_400` buckets Unix timestamps by day.
+
+use std::collections::BTreeMap;
+
+use serde::{Serialize, Deserialize};
+
+use crate::error::RnltkError;
+use crate::sentiment::SentimentModel;
+use crate::token;
+
+/// z-value of a two-sided 95% confidence interval.
+const Z_95: f64 = 1.96;
+/// Most periods documents can span, counting the empty periods between them, since every term keeps
+/// a count for every period.
+pub const MAX_PERIODS: usize = 100_000;
+
+/// Struct for holding stop-word-filtered term counts for every period between the
+/// earliest and latest document, including periods without documents.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TermTimeline {
+    period_length: i64,
+    periods: Vec<i64>,
+    totals: Vec<f64>,
+    counts: BTreeMap<String, Vec<f64>>,
+}
+
+/// Struct for holding how a term's frequency in the latest period compares to its history.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TermTrend {
+    pub term: String,
+    /// Relative frequency of the term in the latest period
+    pub current: f64,
+    /// Mean relative frequency of the term over the earlier periods
+    pub mean: f64,
+    /// Standard deviation of the relative frequency over the earlier periods
+    pub standard_deviation: f64,
+    /// Number of standard deviations the latest period is above (positive) or below (negative) the mean
+    pub z_score: f64,
+}
+
+/// Struct for holding an interval of periods where a term's rate significantly exceeded its baseline,
+/// found by [`TermTimeline::get_bursts`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Burst {
+    pub term: String,
+    /// Start timestamp of the first period in the burst
+    pub start: i64,
+    /// Start timestamp of the last period in the burst
+    pub end: i64,
+    /// How much better the burst state explains the interval than the baseline state, in log-likelihood
+    pub weight: f64,
+}
+
+/// Struct for holding a mean with its 95% confidence interval.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct Estimate {
+    pub mean: f64,
+    pub standard_error: f64,
+    /// Half-width of the 95% confidence interval, `mean ± margin_of_error`
+    pub margin_of_error: f64,
+}
+
+impl Estimate {
+    pub(crate) fn from_values(values: &[f64]) -> Option<Self> {
+        if values.is_empty() {
+            return None;
+        }
+        let count = values.len() as f64;
+        let mean = values.iter().sum::<f64>() / count;
+        let standard_error = if values.len() > 1 {
+            let sample_variance = values.iter().map(|value| (value - mean).powi(2)).sum::<f64>() / (count - 1.);
+            (sample_variance / count).sqrt()
+        } else {
+            0.
+        };
+        Some(Estimate {
+            mean,
+            standard_error,
+            margin_of_error: Z_95 * standard_error,
+        })
+    }
+}
+
+/// Struct for holding the aggregate sentiment of the documents in one period.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PeriodSentiment {
+    /// Start timestamp of the period
+    pub period: i64,
+    pub num_documents: usize,
+    /// Number of documents containing at least one term from the sentiment lexicons
+    pub num_scored: usize,
+    /// Mean document valence, `None` if no document in the period could be scored
+    pub valence: Option<Estimate>,
+    /// Mean document arousal, `None` if no document in the period could be scored
+    pub arousal: Option<Estimate>,
+}
+
+/// Gets the aggregate valence and arousal of `(timestamp, text)` `documents` for every period of
+/// `period_length` between the earliest and latest document, for charting mood over time.
+///
+/// Each document is scored with [`SentimentModel::get_valence_for_term_vector`] and
+/// [`SentimentModel::get_arousal_for_term_vector`], and documents without lexicon terms are
+/// counted but not scored. Confidence is reported as the standard error of the mean and a 95% margin of error.
+///
+/// # Errors
+///
+/// Returns [`RnltkError::InvalidPeriod`] if `period_length` is not positive.
+///
+/// Returns [`RnltkError::TooManyPeriods`] if the documents span more than [`MAX_PERIODS`] periods.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::trends;
+/// use rnltk::sentiment::SentimentModel;
+/// use rnltk::sample_data;
+///
+/// let sentiment = SentimentModel::new(sample_data::get_sample_custom_word_dict());
+/// let documents = vec![(0, "I was betrayed"), (3, "The bees were nice"), (25, "Nothing to report")];
+/// let sentiment_over_time = trends::get_sentiment_over_time(&documents, &sentiment, 10).unwrap();
+///
+/// assert_eq!(sentiment_over_time.len(), 3);
+/// assert_eq!(sentiment_over_time[0].num_documents, 2);
+/// assert_eq!(sentiment_over_time[0].num_scored, 2);
+/// assert!(sentiment_over_time[0].valence.unwrap().margin_of_error > 0.);
+/// assert_eq!(sentiment_over_time[1].num_documents, 0);
+/// assert_eq!(sentiment_over_time[2].valence, None);
+/// ```
+pub fn get_sentiment_over_time(documents: &[(i64, &str)], sentiment_model: &SentimentModel, period_length: i64) -> Result<Vec<PeriodSentiment>, RnltkError> {
+    let (period_starts, document_periods) = bucket_into_periods(documents, period_length)?;
+    let num_periods = period_starts.len();
+
+    let mut num_documents = vec![0; num_periods];
+    let mut valences: Vec<Vec<f64>> = vec![vec![]; num_periods];
+    let mut arousals: Vec<Vec<f64>> = vec![vec![]; num_periods];
+    for ((_, text), period) in documents.iter().zip(document_periods) {
+        num_documents[period] += 1;
+        let tokens: Vec<String> = token::tokenize_into_sentences(text).iter().flat_map(|sentence| token::tokenize_sentence(sentence)).collect();
+        if tokens.iter().any(|token| sentiment_model.does_term_exist(token)) {
+            valences[period].push(sentiment_model.get_valence_for_term_vector(&tokens));
+            arousals[period].push(sentiment_model.get_arousal_for_term_vector(&tokens));
+        }
+    }
+
+    Ok(period_starts.into_iter().enumerate().map(|(period, period_start)| PeriodSentiment {
+        period: period_start,
+        num_documents: num_documents[period],
+        num_scored: valences[period].len(),
+        valence: Estimate::from_values(&valences[period]),
+        arousal: Estimate::from_values(&arousals[period]),
+    }).collect())
+}
+
+/// Gets the start timestamp of every period between the earliest and latest document, and the
+/// index of the period each document falls into.
+fn bucket_into_periods(documents: &[(i64, &str)], period_length: i64) -> Result<(Vec<i64>, Vec<usize>), RnltkError> {
+    if period_length <= 0 {
+        return Err(RnltkError::InvalidPeriod);
+    }
+    let period_indices: Vec<i64> = documents.iter().map(|(timestamp, _)| timestamp.div_euclid(period_length)).collect();
+    let (Some(first_period), Some(last_period)) = (period_indices.iter().min(), period_indices.iter().max()) else {
+        return Ok((vec![], vec![]));
+    };
+    let num_periods = last_period.checked_sub(*first_period)
+        .and_then(|span| usize::try_from(span).ok())
+        .and_then(|span| span.checked_add(1))
+        .filter(|num_periods| *num_periods <= MAX_PERIODS)
+        .ok_or(RnltkError::TooManyPeriods)?;
+    // the earliest period can start before i64::MIN when the earliest timestamp is close to it
+    let period_starts = (0..num_periods as i64)
+        .map(|period| (first_period + period).checked_mul(period_length).ok_or(RnltkError::TooManyPeriods))
+        .collect::<Result<_, _>>()?;
+    let document_periods = period_indices.iter().map(|period_index| (period_index - first_period) as usize).collect();
+    Ok((period_starts, document_periods))
+}
+
+impl TermTimeline {
+    /// Creates new instance of TermTimeline from `(timestamp, text)` `documents`, bucketing them
+    /// into periods of `period_length`. Stop words are removed using [`token::get_stop_words`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RnltkError::InvalidPeriod`] if `period_length` is not positive.
+    ///
+    /// Returns [`RnltkError::TooManyPeriods`] if the documents span more than [`MAX_PERIODS`] periods.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rnltk::trends::TermTimeline;
+    ///
+    /// let documents = vec![(0, "bees make honey"), (5, "bees sting"), (25, "honey is sweet")];
+    /// let timeline = TermTimeline::from_documents(&documents, 10).unwrap();
+    ///
+    /// assert_eq!(timeline.get_periods(), &[0, 10, 20]);
+    /// assert_eq!(timeline.get_counts("bees"), Some(&[2., 0., 0.][..]));
+    /// assert_eq!(timeline.get_totals(), &[5., 0., 2.]);
+    /// ```
+    pub fn from_documents(documents: &[(i64, &str)], period_length: i64) -> Result<Self, RnltkError> {
+        let (periods, document_periods) = bucket_into_periods(documents, period_length)?;
+        let num_periods = periods.len();
+
+        let stop_words = token::get_stop_words();
+        let mut totals = vec![0.; num_periods];
+        let mut counts: BTreeMap<String, Vec<f64>> = BTreeMap::new();
+        for ((_, text), period) in documents.iter().zip(document_periods) {
+            for sentence in token::tokenize_into_sentences(text) {
+                for term in token::tokenize_sentence_without_stop_words(&sentence, stop_words.clone()) {
+                    totals[period] += 1.;
+                    counts.entry(term).or_insert_with(|| vec![0.; num_periods])[period] += 1.;
+                }
+            }
+        }
+
+        Ok(TermTimeline {
+            period_length,
+            periods,
+            totals,
+            counts,
+        })
+    }
+
+    /// Gets the period length the timeline was built with.
+    pub fn get_period_length(&self) -> i64 {
+        self.period_length
+    }
+
+    /// Gets the start timestamp of every period, in order.
+    pub fn get_periods(&self) -> &[i64] {
+        &self.periods
+    }
+
+    /// Gets the total number of terms in every period.
+    pub fn get_totals(&self) -> &[f64] {
+        &self.totals
+    }
+
+    /// Gets the count of `term` in every period, or `None` if the term never appears.
+    pub fn get_counts(&self, term: &str) -> Option<&[f64]> {
+        self.counts.get(term).map(|counts| counts.as_slice())
+    }
+
+    /// Gets every term in the timeline, in sorted order.
+    pub fn get_terms(&self) -> impl Iterator<Item = &str> {
+        self.counts.keys().map(|term| term.as_str())
+    }
+
+    /// Gets the [`TermTrend`] of every term in the latest period compared to the earlier periods,
+    /// sorted from most rising to most falling. Frequencies are relative to each period's total so
+    /// busy periods don't inflate every term. Periods without terms are left out of the history.
+    ///
+    /// The standard deviation is floored at one occurrence in the latest period, so a term that was
+    /// perfectly steady doesn't get an infinite z-score. Returns an empty vector if there is no
+    /// history to compare against or the latest period has no terms.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rnltk::trends::TermTimeline;
+    ///
+    /// let documents = vec![
+    ///     (0, "weather report sunny"),
+    ///     (1, "weather report cloudy"),
+    ///     (2, "weather report sunny"),
+    ///     (3, "earthquake earthquake weather"),
+    /// ];
+    /// let timeline = TermTimeline::from_documents(&documents, 1).unwrap();
+    /// let trends = timeline.get_trends();
+    ///
+    /// assert_eq!(trends.first().unwrap().term, "earthquake");
+    /// assert_eq!(trends.last().unwrap().term, "report");
+    /// ```
+    pub fn get_trends(&self) -> Vec<TermTrend> {
+        let Some(latest) = self.totals.len().checked_sub(1) else { return vec![] };
+        let history: Vec<usize> = (0..latest).filter(|period| self.totals[*period] > 0.).collect();
+        if history.is_empty() || self.totals[latest] == 0. {
+            return vec![];
+        }
+        let minimum_deviation = 1. / self.totals[latest];
+
+        let mut trends: Vec<TermTrend> = self.counts.iter().map(|(term, counts)| {
+            let frequencies: Vec<f64> = history.iter().map(|period| counts[*period] / self.totals[*period]).collect();
+            let mean = frequencies.iter().sum::<f64>() / frequencies.len() as f64;
+            let variance = frequencies.iter().map(|frequency| (frequency - mean).powi(2)).sum::<f64>() / frequencies.len() as f64;
+            let standard_deviation = variance.sqrt();
+            let current = counts[latest] / self.totals[latest];
+            TermTrend {
+                term: term.to_string(),
+                current,
+                mean,
+                standard_deviation,
+                z_score: (current - mean) / standard_deviation.max(minimum_deviation),
+            }
+        }).collect();
+        // stable sort keeps ties in alphabetical order
+        trends.sort_by(|first, second| second.z_score.total_cmp(&first.z_score));
+        trends
+    }
+
+    /// Gets the bursts of `term` using Kleinberg's two-state burst detection for batched documents.
+    ///
+    /// The baseline state emits the term at its overall rate \\(p_0\\) and the burst state at
+    /// \\(p_1 = s \cdot p_0\\), where `scaling` is \\(s\\). Entering the burst state costs
+    /// \\(\gamma \ln n\\) for \\(n\\) periods, where `gamma` is \\(\gamma\\), so larger values only
+    /// report stronger bursts. The most likely state sequence is found with the Viterbi algorithm and
+    /// every run of burst periods is returned, in time order. Kleinberg's defaults are `scaling` = 2 and `gamma` = 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rnltk::trends::TermTimeline;
+    ///
+    /// let mut documents = vec![];
+    /// for day in 0..10 {
+    ///     documents.push((day, "markets open trading volume steady"));
+    /// }
+    /// documents.push((4, "earthquake earthquake earthquake earthquake"));
+    /// documents.push((5, "earthquake earthquake earthquake aftershock"));
+    /// documents.push((8, "earthquake"));
+    /// let timeline = TermTimeline::from_documents(&documents, 1).unwrap();
+    /// let bursts = timeline.get_bursts("earthquake", 2., 1.);
+    ///
+    /// assert_eq!(bursts.len(), 1);
+    /// assert_eq!((bursts[0].start, bursts[0].end), (4, 5));
+    /// assert!(timeline.get_bursts("markets", 2., 1.).is_empty());
+    /// ```
+    pub fn get_bursts(&self, term: &str, scaling: f64, gamma: f64) -> Vec<Burst> {
+        let Some(counts) = self.counts.get(term) else { return vec![] };
+        let total_count: f64 = counts.iter().sum();
+        let total: f64 = self.totals.iter().sum();
+        let baseline_rate = total_count / total;
+        if baseline_rate <= 0. || baseline_rate >= 1. {
+            return vec![];
+        }
+        let burst_rate = (scaling * baseline_rate).min(1. - f64::EPSILON);
+        let transition_cost = gamma * (self.totals.len() as f64).ln();
+
+        // negative log-likelihood of each period's count under a rate, without the shared binomial coefficient
+        let emission_cost = |period: usize, rate: f64| -> f64 {
+            let (count, period_total) = (counts[period], self.totals[period]);
+            -(count * rate.ln() + (period_total - count) * (1. - rate).ln())
+        };
+
+        // Viterbi over the baseline (0) and burst (1) states
+        let num_periods = self.totals.len();
+        let mut costs = [0., f64::INFINITY];
+        let mut came_from_baseline: Vec<[bool; 2]> = Vec::with_capacity(num_periods);
+        for period in 0..num_periods {
+            let baseline_cost = costs[0].min(costs[1]);
+            let burst_from_baseline = costs[0] + transition_cost;
+            let burst_cost = burst_from_baseline.min(costs[1]);
+            came_from_baseline.push([costs[0] <= costs[1], burst_from_baseline <= costs[1]]);
+            costs = [
+                baseline_cost + emission_cost(period, baseline_rate),
+                burst_cost + emission_cost(period, burst_rate),
+            ];
+        }
+
+        let mut states = vec![0; num_periods];
+        let mut state = if costs[1] < costs[0] { 1 } else { 0 };
+        for period in (0..num_periods).rev() {
+            states[period] = state;
+            state = if came_from_baseline[period][state] { 0 } else { 1 };
+        }
+
+        let mut bursts: Vec<Burst> = vec![];
+        let mut period = 0;
+        while period < num_periods {
+            if states[period] == 0 {
+                period += 1;
+                continue;
+            }
+            let start = period;
+            let mut weight = 0.;
+            while period < num_periods && states[period] == 1 {
+                weight += emission_cost(period, baseline_rate) - emission_cost(period, burst_rate);
+                period += 1;
+            }
+            bursts.push(Burst {
+                term: term.to_string(),
+                start: self.periods[start],
+                end: self.periods[period - 1],
+                weight,
+            });
+        }
+        bursts
+    }
+
+    /// Gets the bursts of every term with [`TermTimeline::get_bursts`], from strongest to weakest.
+    pub fn get_all_bursts(&self, scaling: f64, gamma: f64) -> Vec<Burst> {
+        let mut bursts: Vec<Burst> = self.counts.keys().flat_map(|term| self.get_bursts(term, scaling, gamma)).collect();
+        bursts.sort_by(|first, second| second.weight.total_cmp(&first.weight));
+        bursts
+    }
+
+    /// Gets the terms from [`TermTimeline::get_trends`] with a z-score of at least `threshold`.
+    pub fn get_rising_terms(&self, threshold: f64) -> Vec<TermTrend> {
+        self.get_trends().into_iter().filter(|trend| trend.z_score >= threshold).collect()
+    }
+
+    /// Gets the terms from [`TermTimeline::get_trends`] with a z-score of at most `-threshold`,
+    /// from most to least falling.
+    pub fn get_falling_terms(&self, threshold: f64) -> Vec<TermTrend> {
+        self.get_trends().into_iter().rev().filter(|trend| trend.z_score <= -threshold).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invalid_period() {
+        assert_eq!(TermTimeline::from_documents(&[(0, "bees")], 0).unwrap_err(), RnltkError::InvalidPeriod);
+    }
+
+    #[test]
+    fn negative_timestamps_bucket_downwards() {
+        let timeline = TermTimeline::from_documents(&[(-1, "bees"), (0, "honey")], 10).unwrap();
+        assert_eq!(timeline.get_periods(), &[-10, 0]);
+    }
+
+    #[test]
+    fn rising_and_falling_terms() {
+        let documents = vec![(0, "rain rain sun"), (1, "rain rain sun"), (2, "sun sun sun")];
+        let timeline = TermTimeline::from_documents(&documents, 1).unwrap();
+        let rising: Vec<String> = timeline.get_rising_terms(2.).into_iter().map(|trend| trend.term).collect();
+        let falling: Vec<String> = timeline.get_falling_terms(2.).into_iter().map(|trend| trend.term).collect();
+        assert_eq!(rising, vec!["sun"]);
+        assert_eq!(falling, vec!["rain"]);
+    }
+
+    #[test]
+    fn single_document_estimate_has_no_error() {
+        let estimate = Estimate::from_values(&[4.]).unwrap();
+        assert_eq!(estimate.mean, 4.);
+        assert_eq!(estimate.margin_of_error, 0.);
+        assert_eq!(Estimate::from_values(&[]), None);
+    }
+
+    #[test]
+    fn sentiment_over_time_invalid_period() {
+        let sentiment = SentimentModel::new(crate::sample_data::get_sample_custom_word_dict());
+        assert_eq!(get_sentiment_over_time(&[(0, "bees")], &sentiment, -5).unwrap_err(), RnltkError::InvalidPeriod);
+    }
+
+    #[test]
+    fn steady_terms_have_no_bursts() {
+        let documents: Vec<(i64, &str)> = (0..20).map(|day| (day, "bees honey")).collect();
+        let timeline = TermTimeline::from_documents(&documents, 1).unwrap();
+        assert!(timeline.get_all_bursts(2., 1.).is_empty());
+        assert!(timeline.get_bursts("wasps", 2., 1.).is_empty());
+    }
+
+    #[test]
+    fn strongest_burst_first() {
+        let mut documents: Vec<(i64, &str)> = (0..10).map(|day| (day, "news report")).collect();
+        documents.extend([(3, "storm storm storm storm storm"), (7, "parade parade")]);
+        let timeline = TermTimeline::from_documents(&documents, 1).unwrap();
+        let bursts = timeline.get_all_bursts(2., 0.5);
+        assert_eq!(bursts[0].term, "storm");
+        assert_eq!((bursts[0].start, bursts[0].end), (3, 3));
+    }
+
+    #[test]
+    fn too_many_periods() {
+        let far_apart = [(0, "bees"), (MAX_PERIODS as i64 * 10, "honey")];
+        assert_eq!(TermTimeline::from_documents(&far_apart, 10).unwrap_err(), RnltkError::TooManyPeriods);
+        let timeline = TermTimeline::from_documents(&[(0, "bees"), ((MAX_PERIODS as i64 - 1) * 10, "honey")], 10).unwrap();
+        assert_eq!(timeline.get_periods().len(), MAX_PERIODS);
+
+        let extremes = [(i64::MIN, "bees"), (i64::MAX, "honey")];
+        assert_eq!(TermTimeline::from_documents(&extremes, 1).unwrap_err(), RnltkError::TooManyPeriods);
+        let sentiment = SentimentModel::new(crate::sample_data::get_sample_custom_word_dict());
+        assert_eq!(get_sentiment_over_time(&extremes, &sentiment, 1).unwrap_err(), RnltkError::TooManyPeriods);
+        assert_eq!(TermTimeline::from_documents(&[(i64::MIN, "bees")], 3).unwrap_err(), RnltkError::TooManyPeriods);
+        assert_eq!(TermTimeline::from_documents(&[(i64::MAX, "bees")], i64::MAX).unwrap().get_periods(), &[i64::MAX]);
+    }
+
+    #[test]
+    fn no_history() {
+        let timeline = TermTimeline::from_documents(&[(0, "bees")], 1).unwrap();
+        assert!(timeline.get_trends().is_empty());
+        let empty = TermTimeline::from_documents(&[], 1).unwrap();
+        assert!(empty.get_periods().is_empty());
+        assert!(empty.get_trends().is_empty());
+    }
+}
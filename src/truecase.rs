@@ -0,0 +1,167 @@
+//! Module containing a unigram truecasing model used to restore proper casing
+//! in lowercased or ALL-CAPS text.
+
+use std::collections::{BTreeMap, HashMap};
+
+use regex::Regex;
+use serde::{Serialize, Deserialize};
+
+/// Unigram capitalization statistics learned from correctly cased text.
+///
+/// For every word, the model counts how often each surface form (e.g. "Paris", "paris") is seen.
+/// Sentence-initial words are skipped while training since their capitalization is forced
+/// by position rather than by the word itself.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct TruecaseModel {
+    casings: HashMap<String, BTreeMap<String, u64>>,
+}
+
+impl TruecaseModel {
+    /// Creates new, untrained instance of TruecaseModel.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates new instance of TruecaseModel trained on `documents`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rnltk::truecase::TruecaseModel;
+    ///
+    /// let model = TruecaseModel::from_documents(&["I visited Paris and London.", "We love Paris in the spring."]);
+    /// assert_eq!(model.truecase("WE VISITED PARIS."), "We visited Paris.");
+    /// ```
+    pub fn from_documents(documents: &[&str]) -> Self {
+        let mut model = Self::new();
+        model.train(documents);
+        model
+    }
+
+    /// Updates the capitalization statistics with the words in `documents`.
+    pub fn train(&mut self, documents: &[&str]) {
+        let word_regex = word_regex();
+        for document in documents {
+            let mut sentence_start = true;
+            let mut last_end = 0;
+            for word in word_regex.find_iter(document) {
+                if ends_sentence(&document[last_end..word.start()]) {
+                    sentence_start = true;
+                }
+                last_end = word.end();
+                if sentence_start {
+                    sentence_start = false;
+                    continue;
+                }
+                let counts = self.casings.entry(word.as_str().to_lowercase()).or_default();
+                *counts.entry(word.as_str().to_string()).or_insert(0) += 1;
+            }
+        }
+    }
+
+    /// Gets the most frequent casing of `word`, or `None` if the word was never seen while training.
+    /// Ties are broken by the lexicographically smallest form.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rnltk::truecase::TruecaseModel;
+    ///
+    /// let model = TruecaseModel::from_documents(&["Yesterday NASA launched a rocket."]);
+    /// assert_eq!(model.get_casing("nasa"), Some("NASA"));
+    /// assert_eq!(model.get_casing("Rocket"), Some("rocket"));
+    /// assert_eq!(model.get_casing("moon"), None);
+    /// ```
+    pub fn get_casing(&self, word: &str) -> Option<&str> {
+        self.casings.get(&word.to_lowercase()).and_then(|counts| {
+            counts.iter().fold(None, |best: Option<(&String, &u64)>, (form, count)| {
+                match best {
+                    Some((_, best_count)) if best_count >= count => best,
+                    _ => Some((form, count)),
+                }
+            }).map(|(form, _)| form.as_str())
+        })
+    }
+
+    /// Restores the casing of `text`, preserving whitespace and punctuation.
+    ///
+    /// Known words take their most frequent casing, unknown words are lowercased, and the first
+    /// word of every sentence is capitalized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rnltk::truecase::TruecaseModel;
+    ///
+    /// let model = TruecaseModel::from_documents(&["Then NASA flew to the Moon. It was great."]);
+    /// assert_eq!(model.truecase("nasa flew to the moon! then what?"), "NASA flew to the Moon! Then what?");
+    /// ```
+    pub fn truecase(&self, text: &str) -> String {
+        let word_regex = word_regex();
+        let mut truecased = String::with_capacity(text.len());
+        let mut sentence_start = true;
+        let mut last_end = 0;
+        for word in word_regex.find_iter(text) {
+            let between = &text[last_end..word.start()];
+            if ends_sentence(between) {
+                sentence_start = true;
+            }
+            truecased.push_str(between);
+            last_end = word.end();
+
+            let cased = match self.get_casing(word.as_str()) {
+                Some(form) => form.to_string(),
+                None => word.as_str().to_lowercase(),
+            };
+            if sentence_start {
+                truecased.push_str(&capitalize(&cased));
+                sentence_start = false;
+            } else {
+                truecased.push_str(&cased);
+            }
+        }
+        truecased.push_str(&text[last_end..]);
+
+        truecased
+    }
+}
+
+fn word_regex() -> Regex {
+    Regex::new(r"[\p{L}\p{N}]+(?:'[\p{L}\p{N}]+)*").expect("Invalid regex")
+}
+
+fn ends_sentence(between_words: &str) -> bool {
+    between_words.contains(['.', '!', '?'])
+}
+
+fn capitalize(word: &str) -> String {
+    let mut characters = word.chars();
+    match characters.next() {
+        Some(first) => first.to_uppercase().chain(characters).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sentence_initial_words_are_not_learned() {
+        let model = TruecaseModel::from_documents(&["The cat sat. The dog ran."]);
+        assert_eq!(model.get_casing("the"), None);
+        assert_eq!(model.get_casing("cat"), Some("cat"));
+    }
+
+    #[test]
+    fn most_frequent_casing_wins() {
+        let model = TruecaseModel::from_documents(&["I like Apple. We ate an apple and an apple."]);
+        assert_eq!(model.get_casing("APPLE"), Some("apple"));
+    }
+
+    #[test]
+    fn truecase_all_caps() {
+        let model = TruecaseModel::from_documents(&["Yesterday I met Michael in Raleigh. He said hello."]);
+        assert_eq!(model.truecase("I MET MICHAEL IN RALEIGH, HE SAID HELLO."), "I met Michael in Raleigh, he said hello.");
+    }
+}
@@ -0,0 +1,265 @@
+//! Module containing a WordPiece subword tokenizer, so tokenization can line up with transformer models
+//! such as BERT.
+//!
+//! Vocabularies use the BERT `vocab.txt` format: one token per line, where a token's line number (from 0)
+//! is its id and tokens continuing a word start with `##`.
+
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+use crate::error::RnltkError;
+use crate::token;
+use crate::token::intern::{Interner, Symbol};
+
+/// WordPiece tokenizer splitting words into the longest subwords found in its vocabulary.
+///
+/// Text is first split on whitespace and punctuation, with every punctuation character as its own word.
+/// Each word is then split greedily from the left, taking the longest vocabulary token that matches
+/// at every step. Words that can't be split completely become the unknown token.
+///
+/// # Examples
+///
+/// ```
+/// use rnltk::wordpiece::WordPieceTokenizer;
+///
+/// let tokenizer = WordPieceTokenizer::from_vocab_str("[UNK]\nun\n##aff\n##able\nthe\n!").unwrap();
+///
+/// assert_eq!(tokenizer.tokenize("The unaffable!"), vec!["the", "un", "##aff", "##able", "!"]);
+/// assert_eq!(tokenizer.encode("The unaffable!"), vec![4, 1, 2, 3, 5]);
+/// assert_eq!(tokenizer.tokenize("the unknowable"), vec!["the", "[UNK]"]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct WordPieceTokenizer {
+    // symbols of the vocabulary are the token ids
+    vocab: Interner,
+    unknown_token: String,
+    continuing_prefix: String,
+    max_word_chars: usize,
+    lowercase: bool,
+}
+
+impl WordPieceTokenizer {
+    /// Creates new instance of WordPieceTokenizer from the vocabulary file at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RnltkError::VocabularyIo`] if the file couldn't be read, or any error of
+    /// [`WordPieceTokenizer::from_tokens`].
+    pub fn from_vocab_file(path: impl AsRef<Path>) -> Result<Self, RnltkError> {
+        let file = File::open(path).map_err(|error| RnltkError::VocabularyIo(error.to_string()))?;
+        Self::from_reader(BufReader::new(file))
+    }
+
+    /// Creates new instance of WordPieceTokenizer from a `reader` of a vocabulary file.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RnltkError::VocabularyIo`] if the vocabulary couldn't be read, or any error of
+    /// [`WordPieceTokenizer::from_tokens`].
+    pub fn from_reader(reader: impl BufRead) -> Result<Self, RnltkError> {
+        let tokens = reader.lines()
+            .collect::<Result<Vec<String>, _>>()
+            .map_err(|error| RnltkError::VocabularyIo(error.to_string()))?;
+        Self::from_tokens(tokens)
+    }
+
+    /// Creates new instance of WordPieceTokenizer from the contents of a vocabulary file.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error of [`WordPieceTokenizer::from_tokens`].
+    pub fn from_vocab_str(vocab: &str) -> Result<Self, RnltkError> {
+        Self::from_tokens(vocab.lines().map(String::from))
+    }
+
+    /// Creates new instance of WordPieceTokenizer from vocabulary `tokens` in id order. Trailing
+    /// whitespace (such as a `\r` line ending) is trimmed and empty trailing lines are skipped.
+    /// The unknown token defaults to `[UNK]`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RnltkError::VocabularyFormat`] if a token is empty or appears more than once, or if the vocabulary
+    /// has no `[UNK]` token.
+    pub fn from_tokens(tokens: impl IntoIterator<Item = String>) -> Result<Self, RnltkError> {
+        let mut tokens: Vec<String> = tokens.into_iter().map(|token| token.trim_end().to_string()).collect();
+        while tokens.last().is_some_and(String::is_empty) {
+            tokens.pop();
+        }
+        let mut vocab = Interner::new();
+        for (id, token) in tokens.iter().enumerate() {
+            if token.is_empty() {
+                return Err(RnltkError::VocabularyFormat(format!("empty token on line {}", id + 1)));
+            }
+            if vocab.get_or_intern(token).index() != id {
+                return Err(RnltkError::VocabularyFormat(format!("duplicate token '{token}' on line {}", id + 1)));
+            }
+        }
+        let tokenizer = WordPieceTokenizer {
+            vocab,
+            unknown_token: "[UNK]".to_string(),
+            continuing_prefix: "##".to_string(),
+            max_word_chars: 100,
+            lowercase: true,
+        };
+        tokenizer.check_unknown_token()?;
+        Ok(tokenizer)
+    }
+
+    /// Sets the token used for words that can't be split into vocabulary tokens. Defaults to `[UNK]`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RnltkError::VocabularyFormat`] if `unknown_token` isn't in the vocabulary.
+    pub fn unknown_token(mut self, unknown_token: impl ToString) -> Result<Self, RnltkError> {
+        self.unknown_token = unknown_token.to_string();
+        self.check_unknown_token()?;
+        Ok(self)
+    }
+
+    /// Sets the prefix marking tokens that continue a word. Defaults to `##`.
+    pub fn continuing_prefix(mut self, continuing_prefix: impl ToString) -> Self {
+        self.continuing_prefix = continuing_prefix.to_string();
+        self
+    }
+
+    /// Sets the longest word, in characters, that is split rather than replaced with the unknown token.
+    /// Defaults to 100.
+    pub fn max_word_chars(mut self, max_word_chars: usize) -> Self {
+        self.max_word_chars = max_word_chars;
+        self
+    }
+
+    /// Sets whether text is lowercased and stripped of accents before splitting, as for uncased models.
+    /// Defaults to true; set it to false for cased vocabularies.
+    pub fn lowercase(mut self, lowercase: bool) -> Self {
+        self.lowercase = lowercase;
+        self
+    }
+
+    fn check_unknown_token(&self) -> Result<(), RnltkError> {
+        if self.vocab.get(&self.unknown_token).is_some() {
+            Ok(())
+        } else {
+            Err(RnltkError::VocabularyFormat(format!("unknown token '{}' is not in the vocabulary", self.unknown_token)))
+        }
+    }
+
+    /// Gets the number of tokens in the vocabulary.
+    pub fn vocab_size(&self) -> usize {
+        self.vocab.len()
+    }
+
+    /// Gets the id of `token`, if it's in the vocabulary.
+    pub fn token_to_id(&self, token: &str) -> Option<u32> {
+        self.vocab.get(token).map(|symbol| symbol.0)
+    }
+
+    /// Gets the token with the given `id`, if there is one.
+    pub fn id_to_token(&self, id: u32) -> Option<&str> {
+        self.vocab.resolve(Symbol(id))
+    }
+
+    /// Splits `text` into vocabulary tokens.
+    pub fn tokenize(&self, text: &str) -> Vec<String> {
+        self.encode(text).into_iter().filter_map(|id| self.id_to_token(id)).map(String::from).collect()
+    }
+
+    /// Splits `text` into the ids of vocabulary tokens.
+    pub fn encode(&self, text: &str) -> Vec<u32> {
+        let text = if self.lowercase { token::strip_accents(&text.to_lowercase()) } else { text.to_string() };
+        let unknown_id = self.token_to_id(&self.unknown_token).expect("The unknown token is checked to be in the vocabulary");
+        let mut ids = vec![];
+        for word in split_words(&text) {
+            match self.split_word(word) {
+                Some(word_ids) => ids.extend(word_ids),
+                None => ids.push(unknown_id),
+            }
+        }
+        ids
+    }
+
+    // greedy longest-match-first split of `word`, or None if some part of it isn't in the vocabulary
+    fn split_word(&self, word: &str) -> Option<Vec<u32>> {
+        if word.chars().count() > self.max_word_chars {
+            return None;
+        }
+        let boundaries: Vec<usize> = word.char_indices().map(|(index, _)| index).chain([word.len()]).collect();
+        let mut ids = vec![];
+        let mut start = 0;
+        while start < boundaries.len() - 1 {
+            let (end, id) = (start + 1..boundaries.len()).rev().find_map(|end| {
+                let piece = &word[boundaries[start]..boundaries[end]];
+                let id = if start == 0 {
+                    self.token_to_id(piece)
+                } else {
+                    self.token_to_id(&format!("{}{piece}", self.continuing_prefix))
+                };
+                id.map(|id| (end, id))
+            })?;
+            ids.push(id);
+            start = end;
+        }
+        Some(ids)
+    }
+}
+
+// splits on whitespace, keeping every punctuation or symbol character as its own word
+fn split_words(text: &str) -> Vec<&str> {
+    let mut words = vec![];
+    let mut word_start = None;
+    for (index, c) in text.char_indices() {
+        if c.is_whitespace() || !c.is_alphanumeric() {
+            if let Some(start) = word_start.take() {
+                words.push(&text[start..index]);
+            }
+            if !c.is_whitespace() {
+                words.push(&text[index..index + c.len_utf8()]);
+            }
+        } else if word_start.is_none() {
+            word_start = Some(index);
+        }
+    }
+    if let Some(start) = word_start {
+        words.push(&text[start..]);
+    }
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokenizer() -> WordPieceTokenizer {
+        WordPieceTokenizer::from_vocab_str("[PAD]\n[UNK]\nplay\n##ing\n##s\nplays\ncafe\n,\n##é\ncaf\n").unwrap()
+    }
+
+    #[test]
+    fn longest_match_wins() {
+        assert_eq!(tokenizer().tokenize("plays playing"), vec!["plays", "play", "##ing"]);
+    }
+
+    #[test]
+    fn punctuation_and_accents() {
+        assert_eq!(tokenizer().tokenize("Café,PLAYS"), vec!["cafe", ",", "plays"]);
+        let cased = tokenizer().lowercase(false);
+        assert_eq!(cased.tokenize("café Plays"), vec!["caf", "##é", "[UNK]"]);
+    }
+
+    #[test]
+    fn long_words_are_unknown() {
+        let tokenizer = tokenizer().max_word_chars(4);
+        assert_eq!(tokenizer.tokenize("play playing"), vec!["play", "[UNK]"]);
+    }
+
+    #[test]
+    fn invalid_vocabularies() {
+        assert!(matches!(WordPieceTokenizer::from_vocab_str("a\nb\n"), Err(RnltkError::VocabularyFormat(_))));
+        assert!(matches!(WordPieceTokenizer::from_vocab_str("[UNK]\na\na"), Err(RnltkError::VocabularyFormat(message)) if message.contains("line 3")));
+        assert!(matches!(tokenizer().unknown_token("<unk>"), Err(RnltkError::VocabularyFormat(_))));
+        assert!(matches!(WordPieceTokenizer::from_vocab_file("does/not/exist.txt"), Err(RnltkError::VocabularyIo(_))));
+        assert_eq!(tokenizer().vocab_size(), 10);
+        assert_eq!(tokenizer().token_to_id("##ing"), Some(3));
+        assert_eq!(tokenizer().id_to_token(5), Some("plays"));
+    }
+}
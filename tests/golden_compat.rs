@@ -0,0 +1,59 @@
+//! Golden-file tests comparing rnltk against reference outputs from NLTK and scikit-learn,
+//! stored in `test_data/golden`. They are opt-in, so changes to the reference outputs don't break
+//! `cargo test`; run them with `cargo test --features golden_tests --test golden_compat`.
+
+use nalgebra::DMatrix;
+use rnltk::compat;
+use rnltk::document::DocumentTermFrequencies;
+use rnltk::treebank;
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct TokenFixture {
+    text: String,
+    tokens: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct TfidfFixture {
+    counts: Vec<Vec<f64>>,
+    expected: Vec<TfidfExpectation>,
+}
+
+#[derive(Deserialize)]
+struct TfidfExpectation {
+    smooth_idf: bool,
+    tfidf: Vec<Vec<f64>>,
+}
+
+// fixtures are documents by terms, while rnltk matrices are terms by documents
+fn to_term_document_matrix(rows: &[Vec<f64>]) -> DMatrix<f64> {
+    DMatrix::from_fn(rows[0].len(), rows.len(), |term, document| rows[document][term])
+}
+
+#[test]
+fn porter_stems_match_nltk() {
+    let cases = compat::parse_tab_separated_cases(include_str!("../test_data/golden/porter_stems.tsv")).unwrap();
+    assert!(!cases.is_empty());
+    let mismatches = compat::diff_stems(&cases);
+    assert!(mismatches.is_empty(), "{:#?}", mismatches);
+}
+
+#[test]
+fn treebank_tokens_match_nltk() {
+    let fixtures: Vec<TokenFixture> = serde_json::from_str(include_str!("../test_data/golden/treebank_tokens.json")).unwrap();
+    let cases: Vec<(String, Vec<String>)> = fixtures.into_iter().map(|fixture| (fixture.text, fixture.tokens)).collect();
+    let mismatches = compat::diff_tokens(&cases, treebank::tokenize);
+    assert!(mismatches.is_empty(), "{:#?}", mismatches);
+}
+
+#[test]
+fn tfidf_matches_scikit_learn() {
+    let fixture: TfidfFixture = serde_json::from_str(include_str!("../test_data/golden/sklearn_tfidf.json")).unwrap();
+    let document_term_frequencies = DocumentTermFrequencies::new(to_term_document_matrix(&fixture.counts)).unwrap();
+    for expectation in fixture.expected {
+        let tfidf_matrix = document_term_frequencies.get_tfidf_from_term_frequencies_sklearn(expectation.smooth_idf);
+        let mismatches = compat::diff_matrices(&to_term_document_matrix(&expectation.tfidf), tfidf_matrix.get_tfidf_matrix(), 1e-8).unwrap();
+        assert!(mismatches.is_empty(), "smooth_idf = {}: {:#?}", expectation.smooth_idf, mismatches);
+    }
+}
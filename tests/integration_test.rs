@@ -23,8 +23,7 @@ fn sentiment_from_tokenized_sentence() {
     let sentiment = SentimentModel::new(setup.custom_words);
 
     let text = "I betrayed the bees!";
-    let tokenized_text = token::tokenize_sentence(text);
-    let tokens: Vec<&str> = tokenized_text.iter().map(|token| &**token).collect();
+    let tokens = token::tokenize_sentence(text);
 
     let sentiment_description = sentiment.get_term_vector_description(&tokens);
     let description = "stressed";
@@ -0,0 +1,74 @@
+use proptest::prelude::*;
+use rnltk::{stem, token, treebank};
+
+proptest! {
+    #[test]
+    fn tokenizing_arbitrary_text_does_not_panic(text in any::<String>()) {
+        token::tokenize_into_sentences(&text);
+        token::tokenize_sentence_configurable(&text, token::TokenConfig::default());
+        token::get_term_frequencies_from_sentence(&text);
+        treebank::tokenize(&text);
+        token::tokenize_tweet(&text);
+        for word in text.split(' ') {
+            let _ = stem::get(word);
+        }
+    }
+
+    #[test]
+    fn detokenize_preserves_tokens(text in any::<String>()) {
+        let tokens = token::tokenize_sentence(&text);
+        prop_assert_eq!(token::tokenize_sentence(&token::detokenize(&tokens)), tokens);
+    }
+
+    #[test]
+    fn detokenize_preserves_words(words in prop::collection::vec("[a-z]{1,12}", 0..20)) {
+        let text = words.join(" ");
+        prop_assert_eq!(token::detokenize(&token::tokenize_sentence(&text)), text);
+    }
+
+    #[test]
+    fn spans_point_into_original_text(text in any::<String>()) {
+        let spans = token::tokenize_sentence_with_spans(&text);
+        let tokens: Vec<String> = spans.iter().map(|span| span.token.clone()).collect();
+        prop_assert_eq!(tokens, token::tokenize_sentence(&text));
+        for span in spans {
+            prop_assert!(text.is_char_boundary(span.start) && text.is_char_boundary(span.end));
+            let by_chars: String = text.chars().skip(span.char_start).take(span.char_end - span.char_start).collect();
+            prop_assert_eq!(&text[span.start..span.end], by_chars.as_str());
+        }
+    }
+
+    #[test]
+    fn streaming_matches_batch_tokenization(text in prop_oneof![any::<String>(), "[a-z .!?\"\n]{0,80}"]) {
+        let sentences: Vec<String> = token::SentenceIter::new(text.as_bytes()).map(|sentence| sentence.unwrap()).collect();
+        prop_assert_eq!(sentences, token::tokenize_into_sentences(&text));
+        let tokens: Vec<String> = token::TokenIter::new(text.as_bytes()).map(|token| token.unwrap()).collect();
+        let batch_tokens: Vec<String> = token::tokenize_into_sentences(&text).iter().flat_map(|sentence| token::tokenize_sentence(sentence)).collect();
+        prop_assert_eq!(tokens, batch_tokens);
+    }
+
+    #[test]
+    fn chunks_fit_budget(text in prop_oneof![any::<String>(), "[a-z .!?\"\n]{0,200}"], max_tokens in 1usize..8, overlap in 0usize..8) {
+        prop_assume!(overlap < max_tokens);
+        let chunks = token::split_into_chunks(&text, max_tokens, overlap).unwrap();
+        for chunk in &chunks {
+            prop_assert!(chunk.num_tokens >= 1 && chunk.num_tokens <= max_tokens);
+            prop_assert_eq!(&text[chunk.start..chunk.end], chunk.text.as_str());
+        }
+        if overlap == 0 {
+            let tokens: Vec<String> = chunks.iter()
+                .flat_map(|chunk| token::tokenize_into_sentences(&chunk.text))
+                .flat_map(|sentence| token::tokenize_sentence(&sentence))
+                .collect();
+            let batch_tokens: Vec<String> = token::tokenize_into_sentences(&text).iter().flat_map(|sentence| token::tokenize_sentence(sentence)).collect();
+            prop_assert_eq!(tokens, batch_tokens);
+        }
+    }
+
+    #[test]
+    fn stop_word_filtering_keeps_spans(text in "[A-Za-z ,.!']{0,80}") {
+        let spans = token::tokenize_sentence_with_spans(&text);
+        let filtered = token::tokenize_sentence_with_spans_without_stop_words(&text, token::get_stop_words());
+        prop_assert!(filtered.iter().all(|span| spans.contains(span)));
+    }
+}